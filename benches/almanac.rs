@@ -0,0 +1,62 @@
+use std::hint::black_box;
+use criterion::{criterion_group, criterion_main, Criterion};
+use sofars::almanac::{
+    Body, Horizon, eqtime, moonillum, moonphases, night, riset, solarnoon, sunpos, topo,
+};
+use sofars::eph::{Epv00, Moon98, Target};
+use sofars::site::{Eop, Observer, Weather};
+
+fn bench_almanac(c: &mut Criterion) {
+    let mut group = c.benchmark_group("almanac");
+
+    let obs = Observer::new(0.0, 0.8984518, 46.0);
+    let eop = Eop::default();
+
+    group.bench_function("topo", |b| {
+        b.iter(|| topo(&Moon98, &Body::Solar(Target::Moon), &obs, &eop, black_box(2451544.5), black_box(0.25)))
+    });
+
+    group.bench_function("riset", |b| {
+        b.iter(|| {
+            riset(
+                &Epv00,
+                &Body::Solar(Target::Sun),
+                &obs,
+                &eop,
+                &Horizon::standard(),
+                black_box(2451544.5),
+                black_box(0.0),
+            )
+        })
+    });
+
+    group.bench_function("night", |b| {
+        b.iter(|| night(&obs, &eop, black_box(2000), black_box(6), black_box(21), black_box(1.0)))
+    });
+
+    group.bench_function("moonillum", |b| {
+        b.iter(|| moonillum(black_box(2451549.5), black_box(0.0)))
+    });
+
+    group.bench_function("moonphases", |b| {
+        b.iter(|| moonphases(black_box(2451544.5), black_box(0.0), black_box(31.0)))
+    });
+
+    group.bench_function("sunpos", |b| {
+        let wx = Weather::new(1000.0, 10.0, 0.5, 0.55);
+        b.iter(|| sunpos(&obs, &eop, &wx, black_box(2451544.5), black_box(0.5)))
+    });
+
+    group.bench_function("eqtime", |b| {
+        b.iter(|| eqtime(&eop, black_box(2451585.5), black_box(0.0)))
+    });
+
+    group.bench_function("solarnoon", |b| {
+        b.iter(|| solarnoon(&obs, &eop, black_box(2000), black_box(1), black_box(1), 0.0))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_almanac);
+criterion_main!(benches);
@@ -1,6 +1,7 @@
 use std::hint::black_box;
 use criterion::{criterion_group, criterion_main, Criterion};
 use sofars::astro::*;
+use sofars::eph::{Moon98, Target};
 
 fn bench_astro(c: &mut Criterion) {
     let mut group = c.benchmark_group("astro");
@@ -203,6 +204,24 @@ fn bench_astro(c: &mut Criterion) {
         b.iter(|| aticqn(black_box(rc), black_box(dc), &mut astrom, black_box(3), black_box(&bodies)))
     });
 
+    // atciqb
+    group.bench_function("atciqb", |b| {
+        let mut astrom = IauAstrom::default();
+        let mut eo = 0.0;
+        apci13(tt1, tt2, &mut astrom, &mut eo);
+        b.iter(|| atciqb(&Moon98, Target::Moon, black_box(tt1), black_box(tt2), &astrom))
+    });
+
+    // atci13b
+    group.bench_function("atci13b", |b| {
+        b.iter(|| atci13b(&Moon98, Target::Moon, black_box(tt1), black_box(tt2)))
+    });
+
+    // atco13b
+    group.bench_function("atco13b", |b| {
+        b.iter(|| atco13b(&Moon98, Target::Moon, black_box(utc1), black_box(utc2), black_box(dut1), black_box(elong), black_box(phi), black_box(hm), black_box(xp), black_box(yp), black_box(phpa), black_box(tc), black_box(rh), black_box(wl)))
+    });
+
     // atio13
     group.bench_function("atio13", |b| {
         b.iter(|| atio13(black_box(rc), black_box(dc), black_box(utc1), black_box(utc2), black_box(dut1), black_box(elong), black_box(phi), black_box(hm), black_box(xp), black_box(yp), black_box(phpa), black_box(tc), black_box(rh), black_box(wl)))
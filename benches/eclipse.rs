@@ -0,0 +1,38 @@
+use std::hint::black_box;
+use criterion::{criterion_group, criterion_main, Criterion};
+use sofars::eclipse::{Shadow, bessel, besselpoly, lunecl, solarlocal};
+use sofars::eph::Moon98;
+use sofars::site::Observer;
+
+fn bench_eclipse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("eclipse");
+
+    group.bench_function("bessel", |b| {
+        b.iter(|| bessel(black_box(2457987.25), black_box(0.0), black_box(70.3), &Moon98))
+    });
+
+    group.bench_function("besselpoly", |b| {
+        b.iter(|| besselpoly(black_box(2457987.25), black_box(0.0), black_box(70.3), &Moon98))
+    });
+
+    let p = besselpoly(2457987.25, 0.0, 70.3, &Moon98).unwrap();
+    let obs = Observer::new(-1.5571876, 0.6583481, 130.0);
+    group.bench_function("solarlocal", |b| b.iter(|| solarlocal(black_box(&p), &obs)));
+
+    group.bench_function("lunecl", |b| {
+        b.iter(|| {
+            lunecl(
+                black_box(2451544.5),
+                black_box(0.0),
+                black_box(31.0),
+                Shadow::Danjon,
+                &Moon98,
+            )
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_eclipse);
+criterion_main!(benches);
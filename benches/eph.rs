@@ -1,6 +1,7 @@
 use std::hint::black_box;
 use criterion::{criterion_group, criterion_main, Criterion};
 use sofars::eph;
+use sofars::eph::{Ephemeris, Target};
 
 fn bench_eph(c: &mut Criterion) {
     let mut group = c.benchmark_group("eph");
@@ -17,6 +18,18 @@ fn bench_eph(c: &mut Criterion) {
         b.iter(|| eph::plan94(black_box(2400000.5), black_box(43999.9), black_box(1)))
     });
 
+    group.bench_function("Plan94::pvb", |b| {
+        b.iter(|| eph::Plan94.pvb(black_box(Target::Mars), black_box(2400000.5), black_box(43999.9)))
+    });
+
+    group.bench_function("Moon98::pvb", |b| {
+        b.iter(|| eph::Moon98.pvb(black_box(Target::Moon), black_box(2400000.5), black_box(43999.9)))
+    });
+
+    group.bench_function("eulrm", |b| {
+        b.iter(|| eph::eulrm(black_box(0.05), black_box(0.4), black_box(1.2)))
+    });
+
     group.finish();
 }
 
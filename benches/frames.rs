@@ -0,0 +1,37 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use sofars::frames::{Frame, SkyCoord};
+use sofars::site::{Eop, Observer, Weather};
+use std::hint::black_box;
+
+fn bench_frames(c: &mut Criterion) {
+    let mut group = c.benchmark_group("frames");
+
+    let obstime = (2456384.5, 0.969254051);
+    let altaz = Frame::AltAz {
+        obstime,
+        eop: Eop::new(0.1550675, 2.47230737e-7, 1.82640464e-6),
+        observer: Observer::new(-0.527800806, -1.2345856, 2738.0),
+        weather: Weather::new(731.0, 12.8, 0.59, 0.55),
+    };
+    let sc = SkyCoord::new(Frame::Icrs, 2.71, 0.174);
+
+    group.bench_function("icrs_galactic", |b| {
+        b.iter(|| black_box(sc).transform_to(&Frame::Galactic))
+    });
+
+    group.bench_function("icrs_altaz", |b| {
+        b.iter(|| black_box(sc).transform_to(&altaz))
+    });
+
+    group.bench_function("fk4_icrs", |b| {
+        b.iter(|| {
+            SkyCoord::new(Frame::FK4_B1950, black_box(0.076), black_box(-1.137))
+                .transform_to(&Frame::Icrs)
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_frames);
+criterion_main!(benches);
@@ -0,0 +1,82 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use sofars::consts::{DJ00, GK};
+use sofars::orbit::{self, Elements};
+use std::hint::black_box;
+
+fn bench_orbit(c: &mut Criterion) {
+    let mut group = c.benchmark_group("orbit");
+
+    let gm = GK * GK;
+    let el = Elements {
+        q: 0.7,
+        e: 0.6,
+        incl: 0.3,
+        node: 5.9,
+        argp: 0.4,
+        tp: DJ00 - 100.0,
+    };
+    let pv = orbit::el2pv(&el, gm, DJ00, 0.0).unwrap();
+
+    group.bench_function("kepler", |b| {
+        b.iter(|| orbit::kepler(black_box(2.5), black_box(0.9)))
+    });
+
+    group.bench_function("pvprop", |b| {
+        b.iter(|| orbit::pvprop(black_box(&pv), black_box(gm), black_box(1000.0)))
+    });
+
+    group.bench_function("el2pv", |b| {
+        b.iter(|| {
+            orbit::el2pv(
+                black_box(&el),
+                black_box(gm),
+                black_box(DJ00),
+                black_box(0.0),
+            )
+        })
+    });
+
+    group.bench_function("pv2el", |b| {
+        b.iter(|| {
+            orbit::pv2el(
+                black_box(&pv),
+                black_box(gm),
+                black_box(DJ00),
+                black_box(0.0),
+            )
+        })
+    });
+
+    let sights: Vec<orbit::Sight> = [-6.0, 0.0, 7.0]
+        .iter()
+        .map(|&d| {
+            let (eh, _) = sofars::eph::epv00(DJ00, d).unwrap();
+            let mut s = orbit::Sight {
+                tdb: [DJ00, d],
+                obs: eh,
+                sigma: [1e-6; 2],
+                ..Default::default()
+            };
+            (s.ra, s.dec, _) = s.predict(&pv, DJ00, 0.0, gm).unwrap();
+            s
+        })
+        .collect();
+    let s3 = [sights[0], sights[1], sights[2]];
+
+    group.bench_function("gauss", |b| {
+        b.iter(|| orbit::gauss(black_box(&s3), black_box(gm)))
+    });
+
+    group.bench_function("unpackdes", |b| {
+        b.iter(|| orbit::unpackdes(black_box("K07Tf8A")))
+    });
+
+    group.bench_function("packdes", |b| {
+        b.iter(|| orbit::packdes(black_box("2007 TA418")))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_orbit);
+criterion_main!(benches);
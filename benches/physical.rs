@@ -0,0 +1,72 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use sofars::astro::{IauAstrom, apcg13};
+use sofars::eph::{Moon98, Plan94, Target};
+use sofars::physical::{bodyfix, hg1g2mag, hgmag, moonlibr, physeph, planmage, selciq, wgccre};
+use std::hint::black_box;
+
+fn bench_physical(c: &mut Criterion) {
+    let mut group = c.benchmark_group("physical");
+
+    group.bench_function("wgccre", |b| {
+        b.iter(|| wgccre(Target::Moon, black_box(2451545.0), black_box(0.0)))
+    });
+
+    group.bench_function("bodyfix", |b| {
+        b.iter(|| bodyfix(Target::Mars, black_box(2451545.0), black_box(0.0)))
+    });
+
+    group.bench_function("physeph", |b| {
+        b.iter(|| {
+            physeph(
+                &Plan94,
+                Target::Jupiter,
+                black_box(2448972.5),
+                black_box(0.0),
+            )
+        })
+    });
+
+    group.bench_function("moonlibr", |b| {
+        b.iter(|| moonlibr(&Moon98, black_box(2448724.5), black_box(0.0)))
+    });
+
+    let mut astrom = IauAstrom::default();
+    apcg13(2448724.5, 0.0, &mut astrom);
+    group.bench_function("selciq", |b| {
+        b.iter(|| {
+            selciq(
+                &Moon98,
+                black_box(-0.5),
+                black_box(0.3),
+                0.0,
+                2448724.5,
+                0.0,
+                &astrom,
+            )
+        })
+    });
+
+    group.bench_function("planmage", |b| {
+        b.iter(|| {
+            planmage(
+                &Plan94,
+                Target::Saturn,
+                black_box(2460561.5),
+                black_box(0.0),
+            )
+        })
+    });
+
+    group.bench_function("hgmag", |b| {
+        b.iter(|| hgmag(black_box(10.0), 0.15, 2.0, 1.5, black_box(0.3)))
+    });
+
+    group.bench_function("hg1g2mag", |b| {
+        b.iter(|| hg1g2mag(black_box(10.0), 0.26, 0.38, 2.0, 1.5, black_box(0.3)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_physical);
+criterion_main!(benches);
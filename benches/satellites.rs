@@ -0,0 +1,29 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use sofars::eph::Plan94;
+use sofars::satellites::{Satellite, galsat, satevents, satxy, titan};
+use std::hint::black_box;
+
+fn bench_satellites(c: &mut Criterion) {
+    let mut group = c.benchmark_group("satellites");
+
+    group.bench_function("galsat", |b| {
+        b.iter(|| galsat(black_box(2448972.5), black_box(0.0)))
+    });
+
+    group.bench_function("titan", |b| {
+        b.iter(|| titan(black_box(2448972.5), black_box(0.0)))
+    });
+
+    group.bench_function("satxy", |b| {
+        b.iter(|| satxy(&Plan94, Satellite::Io, black_box(2448972.5), black_box(0.0)))
+    });
+
+    group.bench_function("satevents", |b| {
+        b.iter(|| satevents(&Plan94, &[Satellite::Io], black_box(2460600.5), 0.0, 2.0))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_satellites);
+criterion_main!(benches);
@@ -0,0 +1,37 @@
+use std::hint::black_box;
+use criterion::{criterion_group, criterion_main, Criterion};
+use sofars::site::{Eop, Observer, Weather};
+
+fn bench_site(c: &mut Criterion) {
+    let mut group = c.benchmark_group("site");
+
+    let obs = Observer::new(-0.527800806, -1.2345856, 2738.0);
+    let eop = Eop::new(0.1550675, 2.47230737e-7, 1.82640464e-6);
+    let wx = Weather::new(731.0, 12.8, 0.59, 0.55);
+
+    group.bench_function("apco13", |b| {
+        b.iter(|| obs.apco13(black_box(2456384.5), black_box(0.969254051), &eop, &wx))
+    });
+
+    group.bench_function("atco13", |b| {
+        b.iter(|| {
+            obs.atco13(
+                black_box(2.71),
+                black_box(0.174),
+                black_box(1e-5),
+                black_box(5e-6),
+                black_box(0.1),
+                black_box(55.0),
+                black_box(2456384.5),
+                black_box(0.969254051),
+                &eop,
+                &wx,
+            )
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_site);
+criterion_main!(benches);
@@ -0,0 +1,29 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use sofars::eph::Plan94;
+use sofars::solar::{carrstart, hgs2hpc, hpc2hgs, sunpbl};
+use std::hint::black_box;
+
+fn bench_solar(c: &mut Criterion) {
+    let mut group = c.benchmark_group("solar");
+
+    group.bench_function("sunpbl", |b| {
+        b.iter(|| sunpbl(&Plan94, black_box(2448908.5), black_box(0.0)))
+    });
+
+    group.bench_function("carrstart", |b| {
+        b.iter(|| carrstart(&Plan94, black_box(1699)))
+    });
+
+    group.bench_function("hgs2hpc", |b| {
+        b.iter(|| hgs2hpc(black_box(-0.7), black_box(0.4), 1.0, 0.09, 215.0))
+    });
+
+    group.bench_function("hpc2hgs", |b| {
+        b.iter(|| hpc2hgs(black_box(-0.002), black_box(0.003), 0.09, 215.0, 1.0))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_solar);
+criterion_main!(benches);
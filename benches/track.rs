@@ -0,0 +1,76 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use sofars::track;
+use std::hint::black_box;
+
+fn sc(d1: f64, d2: f64) -> [[f64; 3]; 2] {
+    let t = (d1 - 2456384.5) + d2;
+    [
+        [0.4 + 0.01 * t, -1.6 - 0.002 * t, -0.3 + 0.001 * t],
+        [0.01, -0.002, 0.001],
+    ]
+}
+
+fn bench_track(c: &mut Criterion) {
+    let mut group = c.benchmark_group("track");
+
+    let utc1 = 2456384.5;
+    let utc2 = 0.969254051;
+    let dut1 = 0.1550675;
+    let elong = -0.527800806;
+    let phi = -1.2345856;
+    let hm = 2738.0;
+    let xp = 2.47230737e-7;
+    let yp = 1.82640464e-6;
+
+    group.bench_function("stapvb", |b| {
+        b.iter(|| {
+            track::stapvb(
+                black_box(utc1),
+                black_box(utc2),
+                black_box(dut1),
+                black_box(elong),
+                black_box(phi),
+                black_box(hm),
+                black_box(xp),
+                black_box(yp),
+            )
+        })
+    });
+
+    group.bench_function("lt1w", |b| {
+        b.iter(|| {
+            track::lt1w(
+                black_box(utc1),
+                black_box(utc2),
+                black_box(dut1),
+                black_box(elong),
+                black_box(phi),
+                black_box(hm),
+                black_box(xp),
+                black_box(yp),
+                sc,
+            )
+        })
+    });
+
+    group.bench_function("lt2w", |b| {
+        b.iter(|| {
+            track::lt2w(
+                black_box(utc1),
+                black_box(utc2),
+                black_box(dut1),
+                black_box(elong),
+                black_box(phi),
+                black_box(hm),
+                black_box(xp),
+                black_box(yp),
+                sc,
+            )
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_track);
+criterion_main!(benches);
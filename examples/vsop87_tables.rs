@@ -0,0 +1,63 @@
+//! Write VSOP87 series files as Rust const tables, for Vsop87::insert.
+//!
+//! cargo run --example vsop87_tables -- A VSOP87A.mer VSOP87A.ven ... > vsop87a.rs
+
+use sofars::eph::{Target, Vsop87, Vsop87Version};
+use std::env;
+use std::process::exit;
+
+const BODIES: [(Target, &str); 10] = [
+    (Target::Sun, "SUN"),
+    (Target::Mercury, "MERCURY"),
+    (Target::Venus, "VENUS"),
+    (Target::Earth, "EARTH"),
+    (Target::Emb, "EMB"),
+    (Target::Mars, "MARS"),
+    (Target::Jupiter, "JUPITER"),
+    (Target::Saturn, "SATURN"),
+    (Target::Uranus, "URANUS"),
+    (Target::Neptune, "NEPTUNE"),
+];
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let version = match args.first().map(|s| s.as_str()) {
+        Some("A") => Vsop87Version::A,
+        Some("B") => Vsop87Version::B,
+        Some("C") => Vsop87Version::C,
+        Some("D") => Vsop87Version::D,
+        Some("E") => Vsop87Version::E,
+        _ => {
+            eprintln!("usage: vsop87_tables A|B|C|D|E FILE...");
+            exit(1);
+        }
+    };
+    let vsop = Vsop87::load(version, &args[1..]).unwrap_or_else(|e| {
+        eprintln!("vsop87_tables: {e}");
+        exit(1);
+    });
+
+    println!(
+        "// VSOP87{:?} series (Bretagnon & Francou 1988, IMCCE).",
+        version
+    );
+    println!("use sofars::eph::Vsop87Tables;");
+    for (target, name) in BODIES.into_iter().filter(|(t, _)| vsop.has(*t)) {
+        println!();
+        println!("pub const {name}: Vsop87Tables = [");
+        for ic in 0..3 {
+            println!("    &[");
+            let mut it = 0;
+            while let Some(terms) = vsop.terms(target, ic, it) {
+                println!("        &[");
+                for [a, b, c] in terms {
+                    println!("            [{a:?}, {b:?}, {c:?}],");
+                }
+                println!("        ],");
+                it += 1;
+            }
+            println!("    ],");
+        }
+        println!("];");
+    }
+}
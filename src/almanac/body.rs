@@ -0,0 +1,48 @@
+use crate::eph::Target;
+use crate::physical::bodyshape;
+
+/// Celestial body for almanac computations
+///
+/// Solar system bodies are identified by Target and taken from the
+/// ephemeris passed to topo and riset.  Stars are described by their
+/// ICRS catalog data, in the form taken by iauAtco13.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Body {
+    Solar(Target),
+    Star {
+        rc: f64, // ICRS right ascension at J2000.0 (radians)
+        dc: f64, // ICRS declination at J2000.0 (radians)
+        pr: f64, // RA proper motion (radians/year)
+        pd: f64, // Dec proper motion (radians/year)
+        px: f64, // parallax (arcsec)
+        rv: f64, // radial velocity (km/s, +ve if receding)
+    },
+}
+
+impl From<Target> for Body {
+    fn from(target: Target) -> Self {
+        Body::Solar(target)
+    }
+}
+
+impl Body {
+    /// Star with the given ICRS catalog data (see iauAtco13).
+    pub fn star(rc: f64, dc: f64, pr: f64, pd: f64, px: f64, rv: f64) -> Self {
+        Body::Star {
+            rc,
+            dc,
+            pr,
+            pd,
+            px,
+            rv,
+        }
+    }
+
+    /// Equatorial radius (km, see bodyshape), zero for a star.
+    pub fn radius(&self) -> f64 {
+        match *self {
+            Body::Solar(target) => bodyshape(target).map_or(0.0, |(a, _)| a),
+            Body::Star { .. } => 0.0,
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use super::sunpos::sunradec;
+use crate::consts::DPI;
+use crate::erst::gst06;
+use crate::pnp::pnm06a;
+use crate::site::Eop;
+use crate::ts::{taitt, utctai, utcut1};
+use crate::vm::anpm;
+
+///  Equation of time
+///
+///  Given:
+///  ```text
+///     eop    Eop     UT1-UTC (polar motion is not used)
+///     utc1   f64     UTC as a 2-part...
+///     utc2   f64     ...quasi Julian Date
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(eot)  f64   equation of time (radians, Notes 1,2)
+///     Err(j)   i32   -1 = unacceptable date
+///  ```
+///  Notes:
+///
+///  1) The equation of time is apparent minus mean solar time, that
+///     is the Greenwich hour angle of the apparent Sun (GAST minus the
+///     Sun's apparent RA, see `sunapp`) minus the Greenwich hour angle
+///     of the mean Sun (UT1 - 12h).  It is positive when a sundial is
+///     ahead of the clock.
+///
+///  2) The result is in the range +/- pi; divide by DS2R for seconds
+///     of time.  The extremes are about -14.2 minutes in February and
+///     +16.4 minutes in November.
+///
+///  Called:
+///  ```text
+///     iauUtctai    UTC to TAI
+///     iauTaitt     TAI to TT
+///     iauUtcut1    UTC to UT1
+///     iauPnm06a    classical NPB matrix, IAU 2006/2000A
+///     iauGst06     Greenwich apparent sidereal time, IAU 2006
+///     iauAnpm      normalize angle into range +/- pi
+///  ```
+pub fn eqtime(eop: &Eop, utc1: f64, utc2: f64) -> Result<f64, i32> {
+    let (tai1, tai2) = utctai(utc1, utc2)?;
+    let (tt1, tt2) = taitt(tai1, tai2)?;
+    let (ut11, ut12) = utcut1(utc1, utc2, eop.dut1)?;
+
+    /* Greenwich hour angle of the apparent Sun. */
+    let rnpb = pnm06a(tt1, tt2);
+    let (ra, _, _) = sunradec(tt1, tt2, &rnpb)?;
+    let gha = gst06(ut11, ut12, tt1, tt2, &rnpb) - ra;
+
+    /* Greenwich hour angle of the mean Sun (UT1 - 12h). */
+    let f = (ut11 - 0.5).rem_euclid(1.0) + ut12.rem_euclid(1.0);
+    let ghm = 2.0 * DPI * f - DPI;
+
+    Ok(anpm(gha - ghm))
+}
@@ -0,0 +1,114 @@
+use crate::consts::DAS2R;
+use crate::site::Weather;
+
+/// Equatorial radius of the Earth (m, WGS84).
+const AE: f64 = 6378137.0;
+
+/// Standard refraction at the horizon (radians, 34 arcminutes).
+const REFH: f64 = 34.0 * 60.0 * DAS2R;
+
+/// Definition of the horizon for rising and setting
+///
+/// The event occurs when the body's topocentric, unrefracted altitude
+/// (see `topo`) is equal to the value returned by the `h0` method.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Horizon {
+    pub alt: f64,   // altitude of the visible horizon (radians)
+    pub refr: f64,  // refraction at the horizon (radians)
+    pub limb: bool, // true = upper limb, false = center of the disk
+    pub dip: bool,  // allow for the dip of the horizon (observer height)
+}
+
+impl Default for Horizon {
+    fn default() -> Self {
+        Horizon::standard()
+    }
+}
+
+impl Horizon {
+    pub fn new(alt: f64, refr: f64, limb: bool, dip: bool) -> Self {
+        Horizon {
+            alt,
+            refr,
+            limb,
+            dip,
+        }
+    }
+
+    /// The almanac convention:  upper limb on a sea-level horizon, with
+    /// 34 arcminutes of refraction.
+    pub fn standard() -> Self {
+        Horizon::new(0.0, REFH, true, false)
+    }
+
+    /// Center of the disk on the astronomical horizon, no refraction.
+    pub fn geometric() -> Self {
+        Horizon::new(0.0, 0.0, false, false)
+    }
+
+    ///  Horizon with the refraction scaled for the ambient conditions
+    ///
+    ///  Given:
+    ///  ```text
+    ///     wx     Weather    pressure and temperature at the observer
+    ///  ```
+    ///  Notes:
+    ///
+    ///  1) The standard 34 arcminutes is scaled by the usual factor
+    ///     (P/1010)*(283/(273+T)), with P in hPa and T in deg C.  A zero
+    ///     pressure gives zero refraction.
+    ///
+    ///  2) Refraction close to the horizon is very uncertain, varying by
+    ///     several arcminutes with the temperature gradient near the
+    ///     ground, and so rising and setting times cannot be predicted
+    ///     to better than a few seconds of time even in principle.
+    pub fn with_weather(wx: &Weather) -> Self {
+        let refr = REFH * (wx.phpa / 1010.0) * (283.0 / (273.0 + wx.tc));
+        Horizon::new(0.0, refr, true, false)
+    }
+
+    ///  Dip of the horizon
+    ///
+    ///  Given:
+    ///  ```text
+    ///     hm     f64     height of the observer above the horizon (m)
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///            f64     dip (radians, zero if hm is not positive)
+    ///  ```
+    ///  Note:
+    ///
+    ///     The geometric dip acos(a/(a+hm)) for a spherical Earth of
+    ///     radius a = 6378137 m.  Terrestrial refraction reduces the
+    ///     true dip by some 10%.
+    pub fn dip(hm: f64) -> f64 {
+        if hm > 0.0 {
+            (AE / (AE + hm)).acos()
+        } else {
+            0.0
+        }
+    }
+
+    ///  Altitude of the body's center at rising and setting
+    ///
+    ///  Given:
+    ///  ```text
+    ///     sd     f64     apparent semidiameter of the body (radians)
+    ///     hm     f64     height of the observer (m)
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///            f64     topocentric unrefracted altitude (radians)
+    ///  ```
+    pub fn h0(&self, sd: f64, hm: f64) -> f64 {
+        let mut h0 = self.alt - self.refr;
+        if self.limb {
+            h0 -= sd;
+        }
+        if self.dip {
+            h0 -= Horizon::dip(hm);
+        }
+        h0
+    }
+}
@@ -0,0 +1,36 @@
+//! Almanac (rising and setting, twilight, lunar phases and solar time)
+
+mod body;
+pub use body::*;
+
+mod eqtime;
+pub use eqtime::*;
+
+mod horizon;
+pub use horizon::*;
+
+mod moonillum;
+pub use moonillum::*;
+
+mod moonphase;
+pub use moonphase::*;
+
+mod night;
+pub use night::*;
+
+mod riset;
+pub use riset::*;
+
+pub(crate) mod search;
+
+mod solarnoon;
+pub use solarnoon::*;
+
+mod solartime;
+pub use solartime::*;
+
+mod sunpos;
+pub use sunpos::*;
+
+mod topo;
+pub use topo::*;
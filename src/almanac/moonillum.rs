@@ -0,0 +1,92 @@
+use super::topo::bdapp;
+use crate::astro::{IauAstrom, apcg13};
+use crate::eph::{Moon98, Target};
+use crate::pnp::pnm06a;
+use crate::vm::{anp, c2s, pmp, rxp, sepp, sxp};
+
+/// Illumination of the Moon, as seen from the geocenter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoonIllum {
+    pub elong: f64, // elongation of the Moon from the Sun (radians)
+    pub phase: f64, // phase angle, Sun-Moon-Earth (radians)
+    pub k: f64,     // illuminated fraction of the disk
+    pub chi: f64,   // position angle of the bright limb (radians)
+}
+
+///  Illuminated fraction, phase angle and bright-limb position angle
+///  of the Moon
+///
+///  Given:
+///  ```text
+///     tt1    f64     TT as a 2-part...
+///     tt2    f64     ...Julian Date (Note 1)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(MoonIllum)
+///            elong   f64   elongation of the Moon from the Sun (radians)
+///            phase   f64   phase angle (radians, Note 2)
+///            k       f64   illuminated fraction (0 = new, 1 = full)
+///            chi     f64   position angle of the midpoint of the bright
+///                          limb (radians, 0-2pi, Note 3)
+///     Err(j) j       i32   -1 = date outside the range of iauEpv00
+///  ```
+///  Notes:
+///
+///  1) The Sun and Moon are from iauEpv00 and iauMoon98, with TT used
+///     in place of TDB.  Both are corrected for light time, light
+///     deflection and aberration as seen from the geocenter.
+///
+///  2) The phase angle is the angle at the Moon between the directions
+///     to the Sun and to the Earth, formed from the light-time
+///     corrected geometric vectors, and the illuminated fraction is
+///     (1 + cos(phase))/2.
+///
+///  3) The position angle is measured from the north point of the
+///     disk towards the east, with respect to the true equator of
+///     date.  The bright limb faces the Sun, so chi is near 270 deg
+///     (west) while the Moon is waxing and near 90 deg (east) while it
+///     is waning.
+///
+///  Reference:
+///
+///     Meeus, J., Astronomical Algorithms, 2nd edition,
+///     Willmann-Bell (1998), Chapter 48.
+///
+///  Called:
+///  ```text
+///     iauApcg13    astrometry parameters, ICRS-GCRS, geocenter (Note 1)
+///     iauPnm06a    classical NPB matrix, IAU 2006/2000A
+///     iauSepp      angular separation between two p-vectors
+///     iauRxp       product of r-matrix and p-vector
+///     iauC2s       p-vector to spherical
+///  ```
+pub fn moonillum(tt1: f64, tt2: f64) -> Result<MoonIllum, i32> {
+    let mut astrom = IauAstrom::default();
+    apcg13(tt1, tt2, &mut astrom);
+
+    /* Geocentric Moon and Sun. */
+    let (pmo, umo) = bdapp(&Moon98, Target::Moon, tt1, tt2, &astrom)?;
+    let (psu, usu) = bdapp(&Moon98, Target::Sun, tt1, tt2, &astrom)?;
+
+    /* Elongation and phase angle. */
+    let elong = sepp(&umo, &usu);
+    let phase = sepp(&pmp(&psu, &pmo), &sxp(-1.0, &pmo));
+
+    /* Position angle of the bright limb, true equator of date. */
+    let r = pnm06a(tt1, tt2);
+    let (mut vm, mut vs) = ([0.0; 3], [0.0; 3]);
+    rxp(&r, &umo, &mut vm);
+    rxp(&r, &usu, &mut vs);
+    let (am, dm) = c2s(&vm);
+    let (asu, dsu) = c2s(&vs);
+    let chi = anp((dsu.cos() * (asu - am).sin())
+        .atan2(dsu.sin() * dm.cos() - dsu.cos() * dm.sin() * (asu - am).cos()));
+
+    Ok(MoonIllum {
+        elong,
+        phase,
+        k: (1.0 + phase.cos()) / 2.0,
+        chi,
+    })
+}
@@ -0,0 +1,104 @@
+use super::search::crossings;
+use super::topo::bdapp;
+use crate::astro::{IauAstrom, apcg13};
+use crate::consts::DPI;
+use crate::coords::ecm06;
+use crate::eph::{Moon98, Target};
+use crate::vm::{anpm, c2s, rxp};
+
+/// Principal phase of the Moon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    New,
+    FirstQuarter,
+    Full,
+    LastQuarter,
+}
+
+const PHASES: [Phase; 4] = [
+    Phase::New,
+    Phase::FirstQuarter,
+    Phase::Full,
+    Phase::LastQuarter,
+];
+
+///  Instants of the principal phases of the Moon
+///
+///  Given:
+///  ```text
+///     tt1    f64     TT as a 2-part...
+///     tt2    f64     ...Julian Date (start of search)
+///     days   f64     length of the search interval (days)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(phases)   Vec<(Phase, f64, f64)>   the phases in the interval,
+///                                           in order, each with its TT
+///                                           as a 2-part Julian Date
+///                                           (tt1 unchanged)
+///     Err(j) j     i32      -1 = date outside the range of iauEpv00
+///  ```
+///  Notes:
+///
+///  1) The phases are the instants when the excess of the Moon's
+///     apparent geocentric ecliptic longitude over the Sun's is 0, 90,
+///     180 and 270 degrees.  The longitudes are referred to the mean
+///     ecliptic and equinox of date (iauEcm06); nutation in longitude
+///     affects both bodies equally and so is not needed.
+///
+///  2) The Sun and Moon are from iauEpv00 and iauMoon98, corrected for
+///     light time, light deflection and aberration.  The instants are
+///     located to about 1 ms; the accuracy is limited by iauMoon98 to
+///     a few seconds of time.
+///
+///  3) To express the results in UTC, use iauTttai and iauTaiutc.
+///
+///  Called:
+///  ```text
+///     iauApcg13    astrometry parameters, ICRS-GCRS, geocenter
+///     iauEcm06     ICRS to ecliptic rotation matrix, IAU 2006
+///     iauRxp       product of r-matrix and p-vector
+///     iauC2s       p-vector to spherical
+///     iauAnpm      normalize angle into range +/- pi
+///  ```
+pub fn moonphases(tt1: f64, tt2: f64, days: f64) -> Result<Vec<(Phase, f64, f64)>, i32> {
+    /* Moon's longitude minus the Sun's. */
+    let dlon = |x: f64| -> Result<f64, i32> {
+        let mut astrom = IauAstrom::default();
+        apcg13(tt1, tt2 + x, &mut astrom);
+        let r = ecm06(tt1, tt2 + x);
+        let mut lon = [0.0; 2];
+        for (l, b) in lon.iter_mut().zip([Target::Moon, Target::Sun]) {
+            let (_, u) = bdapp(&Moon98, b, tt1, tt2 + x, &astrom)?;
+            let mut v = [0.0; 3];
+            rxp(&r, &u, &mut v);
+            *l = c2s(&v).0;
+        }
+        Ok(lon[0] - lon[1])
+    };
+
+    /* Sample daily. */
+    let n = days.max(0.0).ceil() as usize;
+    let xs: Vec<f64> = (0..=n).map(|i| days * i as f64 / n.max(1) as f64).collect();
+    let ds = xs
+        .iter()
+        .map(|&x| dlon(x))
+        .collect::<Result<Vec<f64>, i32>>()?;
+
+    /* Locate the crossings for each phase. */
+    let mut phases = Vec::new();
+    for (k, ph) in PHASES.iter().enumerate() {
+        let off = k as f64 * DPI / 2.0;
+        let fs: Vec<f64> = ds.iter().map(|d| anpm(d - off)).collect();
+        let mut f = |x: f64| dlon(x).map(|d| anpm(d - off));
+        for x in crossings(&mut f, &xs, &fs, true, usize::MAX)? {
+            phases.push((x, *ph));
+        }
+    }
+    phases.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    Ok(phases
+        .into_iter()
+        .map(|(x, ph)| (ph, tt1, tt2 + x))
+        .collect())
+}
@@ -0,0 +1,153 @@
+use super::search::crossing;
+use super::{Body, Diurnal, Horizon, topo};
+use crate::cal::cal2jd;
+use crate::consts::DD2R;
+use crate::eph::{Epv00, Target};
+use crate::site::{Eop, Observer};
+
+/// Number of samples per day when searching for events.
+const NSTEP: usize = 24;
+
+/// Time of an event in UTC and in local (zone) time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EventTime {
+    pub utc: (f64, f64),   // UTC as a 2-part quasi Julian Date
+    pub local: (f64, f64), // zone time as a 2-part quasi Julian Date
+}
+
+/// Interval during which the Sun is below a given altitude
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Period {
+    pub start: Option<EventTime>, // Sun passes below the altitude (evening)
+    pub end: Option<EventTime>,   // Sun passes above the altitude (morning)
+    pub state: Diurnal,           // Sun's behavior relative to the altitude
+    pub length: f64,              // length of the period (hours, Note 4)
+}
+
+/// Sunset, twilight and sunrise for one night
+///
+/// Each Period runs from the evening event to the morning event:
+/// sunset to sunrise, and the end to the start of civil, nautical and
+/// astronomical twilight respectively.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Night {
+    pub sun: Period,          // sunset to sunrise (standard horizon)
+    pub civil: Period,        // Sun below -6 deg
+    pub nautical: Period,     // Sun below -12 deg
+    pub astronomical: Period, // Sun below -18 deg
+}
+
+///  Sunset, sunrise and twilight for a given night and site
+///
+///  Given:
+///  ```text
+///     obs    Observer   observing site
+///     eop    Eop        UT1-UTC and polar motion
+///     iy     i32        year      } local date on which
+///     im     i32        month     } the night begins
+///     id     i32        day       }
+///     tz     f64        zone time minus UTC (hours, east +ve)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(Night)   events and periods for the night (Notes 1-4)
+///     Err(j) j    i32    -1 = bad year or unacceptable date
+///                        -2 = bad month
+///                        -3 = bad day
+///  ```
+///  Notes:
+///
+///  1) The search runs from local noon on the given date to local
+///     noon the next day, local time being UTC+tz.  All the event times
+///     are given in both UTC and local time.
+///
+///  2) Sunset and sunrise are for the upper limb with the standard
+///     34 arcminutes of refraction (see Horizon::standard).  The
+///     twilight limits are the unrefracted altitudes -6, -12 and -18
+///     degrees of the center of the Sun.  The positions come from the
+///     same topocentric chain as `riset` (iauEpv00, iauApco13,
+///     iauAtioq).
+///
+///  3) Close to the poles some of the events may not occur.  If the
+///     Sun stays above a given altitude all night the period is empty
+///     and its state is Circumpolar (polar day, or twilight lasting all
+///     night).  If it stays below, the state is NeverRises (polar
+///     night) and the period covers the whole 24 hours.
+///
+///  4) The length of each period is from its start to its end.  If
+///     only one of the two events occurs, the other end of the period
+///     is taken to be the start or end of the search interval.  In
+///     polar night the length is 24h and in polar day it is zero.
+///
+///  5) The times are for a sea-level horizon; no allowance is made
+///     for the dip of the horizon.
+///
+///  Called:
+///  ```text
+///     iauCal2jd    Gregorian calendar to JD
+///     topo         topocentric place of a star or solar system body
+///  ```
+pub fn night(obs: &Observer, eop: &Eop, iy: i32, im: i32, id: i32, tz: f64) -> Result<Night, i32> {
+    /* Altitudes for the twilight limits. */
+    const HTW: [f64; 3] = [-6.0 * DD2R, -12.0 * DD2R, -18.0 * DD2R];
+
+    /* UTC of local noon. */
+    let (utc1, d) = cal2jd(iy, im, id)?;
+    let utc2 = d + 0.5 - tz / 24.0;
+
+    /* Sun's altitude relative to each limit (sunset, twilights). */
+    let hz = Horizon::standard();
+    let alt = |x: f64| -> Result<[f64; 4], i32> {
+        let t = topo(&Epv00, &Body::Solar(Target::Sun), obs, eop, utc1, utc2 + x)?;
+        let h = t.alt();
+        Ok([h - hz.h0(t.sd, 0.0), h - HTW[0], h - HTW[1], h - HTW[2]])
+    };
+
+    /* Sample the night. */
+    let mut xs = [0.0; NSTEP + 1];
+    let mut fs = [[0.0; NSTEP + 1]; 4];
+    for (i, x) in xs.iter_mut().enumerate() {
+        *x = i as f64 / NSTEP as f64;
+        for (f, a) in fs.iter_mut().zip(alt(*x)?) {
+            f[i] = a;
+        }
+    }
+
+    /* Refine the events for each limit. */
+    let mut periods = [Period {
+        start: None,
+        end: None,
+        state: Diurnal::RisesSets,
+        length: 0.0,
+    }; 4];
+    for (k, (p, f)) in periods.iter_mut().zip(&fs).enumerate() {
+        let mut fk = |x: f64| alt(x).map(|a| a[k]);
+        let event = |x: Option<f64>| {
+            x.map(|x| EventTime {
+                utc: (utc1, utc2 + x),
+                local: (utc1, utc2 + x + tz / 24.0),
+            })
+        };
+        let start = crossing(&mut fk, &xs, f, false)?;
+        let end = crossing(&mut fk, &xs, f, true)?;
+        p.start = event(start);
+        p.end = event(end);
+        if start.is_none() && end.is_none() {
+            if f[0] >= 0.0 {
+                p.state = Diurnal::Circumpolar;
+            } else {
+                p.state = Diurnal::NeverRises;
+                p.length = 24.0;
+            }
+        } else {
+            p.length = ((end.unwrap_or(1.0) - start.unwrap_or(0.0)) * 24.0).max(0.0);
+        }
+    }
+
+    Ok(Night {
+        sun: periods[0],
+        civil: periods[1],
+        nautical: periods[2],
+        astronomical: periods[3],
+    })
+}
@@ -0,0 +1,133 @@
+use super::search::crossing;
+use super::{Body, Horizon, Topo, topo};
+use crate::consts::DPI;
+use crate::eph::Ephemeris;
+use crate::site::{Eop, Observer};
+use crate::vm::anpm;
+
+/// Number of samples per day when searching for events.
+const NSTEP: usize = 24;
+
+/// Behavior of a body over the search interval
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Diurnal {
+    RisesSets,   // at least one rising or setting
+    Circumpolar, // above the horizon throughout
+    NeverRises,  // below the horizon throughout
+}
+
+/// Rising, setting and transit times over one day
+///
+/// Each time is a UTC 2-part quasi Julian Date, or None if the event
+/// does not occur within the day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RiseSet {
+    pub rise: Option<(f64, f64)>,    // rising
+    pub set: Option<(f64, f64)>,     // setting
+    pub transit: Option<(f64, f64)>, // upper transit (hour angle zero)
+    pub lower: Option<(f64, f64)>,   // lower transit (hour angle 12h)
+    pub state: Diurnal,              // circumpolar/never-rises indicator
+}
+
+///  Rising, setting and transit of a star or solar system body
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris  source of the Earth, Sun and body (see topo)
+///     body   Body       the star or solar system body
+///     obs    Observer   observing site
+///     eop    Eop        UT1-UTC and polar motion
+///     hz     Horizon    horizon definition (altitude, refraction, limb, dip)
+///     utc1   f64        UTC as a 2-part...
+///     utc2   f64        ...quasi Julian Date (start of search, Note 1)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(RiseSet)   first rising, setting, upper and lower transit in
+///                   the 24 hours from utc1+utc2 (Notes 2-4)
+///     Err(j) j   i32    -1 = unacceptable date
+///                       -2 = not a valid body
+///                       else status from eph
+///  ```
+///  Notes:
+///
+///  1) The search covers one day starting at the given UTC, so for
+///     "tonight" the start would normally be local noon.  The times
+///     returned keep utc1 unchanged, adding the offset to utc2.
+///
+///  2) Rising and setting are when the topocentric unrefracted
+///     altitude (see `topo`) passes through the value given by
+///     Horizon::h0, which allows for the horizon altitude, refraction,
+///     the body's semidiameter and the dip of the horizon.  The
+///     semidiameter is recomputed at each step, which matters for the
+///     Moon.
+///
+///  3) Transits are when the topocentric hour angle is zero (upper)
+///     or 12h (lower).  They are reported whether or not the body is
+///     above the horizon at the time.
+///
+///  4) If there is neither rising nor setting in the day, the state
+///     reports whether the body stayed above (Circumpolar) or below
+///     (NeverRises) the horizon.  The search samples the altitude
+///     hourly, so a body that just grazes the horizon between samples
+///     may be missed.
+///
+///  5) The events are refined to about 1 ms.  The accuracy is in
+///     practice limited by the refraction at the horizon and, for the
+///     Moon and planets, by eph.
+///
+///  Called:
+///  ```text
+///     topo         topocentric place of a star or solar system body
+///     iauAnpm      normalize angle into range +/- pi
+///  ```
+pub fn riset<E: Ephemeris>(
+    eph: &E,
+    body: &Body,
+    obs: &Observer,
+    eop: &Eop,
+    hz: &Horizon,
+    utc1: f64,
+    utc2: f64,
+) -> Result<RiseSet, i32> {
+    let alt = |t: &Topo| t.alt() - hz.h0(t.sd, obs.hm);
+
+    /* Sample the day. */
+    let mut xs = [0.0; NSTEP + 1];
+    let mut fa = [0.0; NSTEP + 1];
+    let mut fu = [0.0; NSTEP + 1];
+    let mut fl = [0.0; NSTEP + 1];
+    for (i, x) in xs.iter_mut().enumerate() {
+        *x = i as f64 / NSTEP as f64;
+        let t = topo(eph, body, obs, eop, utc1, utc2 + *x)?;
+        fa[i] = alt(&t);
+        fu[i] = anpm(t.hob);
+        fl[i] = anpm(t.hob - DPI);
+    }
+
+    /* Refine the events. */
+    let mut falt = |x: f64| topo(eph, body, obs, eop, utc1, utc2 + x).map(|t| alt(&t));
+    let rise = crossing(&mut falt, &xs, &fa, true)?;
+    let set = crossing(&mut falt, &xs, &fa, false)?;
+    let mut fup = |x: f64| topo(eph, body, obs, eop, utc1, utc2 + x).map(|t| anpm(t.hob));
+    let transit = crossing(&mut fup, &xs, &fu, true)?;
+    let mut flow = |x: f64| topo(eph, body, obs, eop, utc1, utc2 + x).map(|t| anpm(t.hob - DPI));
+    let lower = crossing(&mut flow, &xs, &fl, true)?;
+
+    let state = if rise.is_some() || set.is_some() {
+        Diurnal::RisesSets
+    } else if fa[0] >= 0.0 {
+        Diurnal::Circumpolar
+    } else {
+        Diurnal::NeverRises
+    };
+
+    let utc = |x: Option<f64>| x.map(|x| (utc1, utc2 + x));
+    Ok(RiseSet {
+        rise: utc(rise),
+        set: utc(set),
+        transit: utc(transit),
+        lower: utc(lower),
+        state,
+    })
+}
@@ -0,0 +1,112 @@
+use crate::consts::DPI;
+
+/// Convergence tolerance for event times (days, about 1 ms).
+const TOL: f64 = 1e-8;
+
+/// Maximum number of iterations when refining an event.
+const MAXIT: usize = 60;
+
+///  Locate the first zero crossing of a sampled function
+///
+///  Given:
+///  ```text
+///     f      FnMut      the function, of time offset x (days)
+///     xs     [f64]      sample offsets, increasing
+///     fs     [f64]      f at the sample offsets
+///     rising bool       true = crossing from -ve to +ve, false = +ve to -ve
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(Some(x))   offset of the crossing
+///     Ok(None)      no crossing in the sampled interval
+///     Err(j)        status from f
+///  ```
+///  Notes:
+///
+///  1) Sign changes with a jump of more than pi between samples are
+///     taken to be discontinuities of an angle wrapped into +/- pi and
+///     are ignored.
+///
+///  2) The crossing is refined by the Illinois variant of the method
+///     of false position.
+pub(crate) fn crossing<F>(
+    f: &mut F,
+    xs: &[f64],
+    fs: &[f64],
+    rising: bool,
+) -> Result<Option<f64>, i32>
+where
+    F: FnMut(f64) -> Result<f64, i32>,
+{
+    Ok(crossings(f, xs, fs, rising, 1)?.pop())
+}
+
+///  Locate up to nmax zero crossings of a sampled function
+///
+///  As `crossing`, but returning the offsets of the first nmax
+///  crossings in the given direction, in increasing order.
+pub(crate) fn crossings<F>(
+    f: &mut F,
+    xs: &[f64],
+    fs: &[f64],
+    rising: bool,
+    nmax: usize,
+) -> Result<Vec<f64>, i32>
+where
+    F: FnMut(f64) -> Result<f64, i32>,
+{
+    let mut roots = Vec::new();
+    for i in 1..xs.len() {
+        if roots.len() >= nmax {
+            break;
+        }
+        let (f0, f1) = if rising {
+            (fs[i - 1], fs[i])
+        } else {
+            (-fs[i - 1], -fs[i])
+        };
+        if f0 < 0.0 && f1 >= 0.0 && f1 - f0 < DPI {
+            roots.push(refine(f, xs[i - 1], fs[i - 1], xs[i], fs[i])?);
+        }
+    }
+    Ok(roots)
+}
+
+/// Refine a root of f bracketed by a and b.
+fn refine<F>(f: &mut F, mut a: f64, mut fa: f64, mut b: f64, mut fb: f64) -> Result<f64, i32>
+where
+    F: FnMut(f64) -> Result<f64, i32>,
+{
+    if fb == 0.0 {
+        return Ok(b);
+    }
+    let mut c = b;
+    let mut side = 0;
+    for _ in 0..MAXIT {
+        let cp = c;
+        c = (a * fb - b * fa) / (fb - fa);
+        if (c - cp).abs() < TOL {
+            break;
+        }
+        let fc = f(c)?;
+        if fc == 0.0 {
+            break;
+        }
+        if fc * fb > 0.0 {
+            b = c;
+            fb = fc;
+            if side == -1 {
+                fa /= 2.0;
+            }
+            side = -1;
+        } else {
+            a = c;
+            fa = fc;
+            if side == 1 {
+                fb /= 2.0;
+            }
+            side = 1;
+        }
+    }
+    Ok(c)
+}
@@ -0,0 +1,71 @@
+use super::{Body, EventTime, topo};
+use crate::cal::cal2jd;
+use crate::consts::D2PI;
+use crate::eph::{Epv00, Target};
+use crate::site::{Eop, Observer};
+use crate::vm::anpm;
+
+/// Maximum number of iterations.
+const MAXIT: usize = 10;
+
+///  Time of local apparent noon
+///
+///  Given:
+///  ```text
+///     obs    Observer   observing site
+///     eop    Eop        UT1-UTC and polar motion
+///     iy     i32        year      }
+///     im     i32        month     } local date
+///     id     i32        day       }
+///     tz     f64        zone time minus UTC (hours, east +ve)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(EventTime)   time of solar noon, UTC and local (Note 1)
+///     Err(j) j   i32  -1 = bad year or unacceptable date
+///                     -2 = bad month
+///                     -3 = bad day
+///  ```
+///  Notes:
+///
+///  1) Solar noon is the upper transit of the Sun, when its topocentric
+///     hour angle (see `topo`) is zero.  It is found by iteration from
+///     local mean noon, and is located to better than 1 ms.
+///
+///  2) The local date is in the zone time UTC+tz.  For sites far from
+///     the zone meridian, solar noon may fall on the adjacent local
+///     date.
+///
+///  Called:
+///  ```text
+///     iauCal2jd    Gregorian calendar to JD
+///     topo         topocentric place of a star or solar system body
+///     iauAnpm      normalize angle into range +/- pi
+///  ```
+pub fn solarnoon(
+    obs: &Observer,
+    eop: &Eop,
+    iy: i32,
+    im: i32,
+    id: i32,
+    tz: f64,
+) -> Result<EventTime, i32> {
+    /* Local mean noon (UTC). */
+    let (utc1, d) = cal2jd(iy, im, id)?;
+    let mut utc2 = d + 0.5 - obs.elong / D2PI;
+
+    /* Iterate on the hour angle. */
+    for _ in 0..MAXIT {
+        let t = topo(&Epv00, &Body::Solar(Target::Sun), obs, eop, utc1, utc2)?;
+        let dt = anpm(t.hob) / D2PI;
+        utc2 -= dt;
+        if dt.abs() < 1e-9 {
+            break;
+        }
+    }
+
+    Ok(EventTime {
+        utc: (utc1, utc2),
+        local: (utc1, utc2 + tz / 24.0),
+    })
+}
@@ -0,0 +1,52 @@
+use super::eqtime;
+use crate::consts::D2PI;
+use crate::site::{Eop, Observer};
+use crate::ts::utcut1;
+use crate::vm::anp;
+
+///  Local apparent and mean solar time
+///
+///  Given:
+///  ```text
+///     obs    Observer   observing site (only the longitude is used)
+///     eop    Eop        UT1-UTC (polar motion is not used)
+///     utc1   f64        UTC as a 2-part...
+///     utc2   f64        ...quasi Julian Date
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok((last, lmst))
+///            last   f64   local apparent solar time (radians, Note 1)
+///            lmst   f64   local mean solar time (radians, Note 1)
+///     Err(j) j      i32   -1 = unacceptable date
+///  ```
+///  Notes:
+///
+///  1) Both times are expressed as angles in the range 0-2pi, zero
+///     being midnight and pi noon; divide by DS2R for seconds.
+///     Apparent solar time is what a sundial shows:  the local hour
+///     angle of the apparent Sun plus 12h.  Mean solar time is UT1 plus
+///     the longitude, and the two differ by the equation of time.
+///
+///  2) The Sun's place is geocentric.  The topocentric hour angle
+///     differs from the geocentric one by at most a fraction of a
+///     second of time.
+///
+///  Called:
+///  ```text
+///     iauUtcut1    UTC to UT1
+///     eqtime       equation of time
+///     iauAnp       normalize angle into range 0 to 2pi
+///  ```
+pub fn solartime(obs: &Observer, eop: &Eop, utc1: f64, utc2: f64) -> Result<(f64, f64), i32> {
+    let (ut11, ut12) = utcut1(utc1, utc2, eop.dut1)?;
+    let eot = eqtime(eop, utc1, utc2)?;
+
+    /* Local mean solar time. */
+    let f = (ut11 - 0.5).rem_euclid(1.0) + ut12.rem_euclid(1.0);
+    let lmst = anp(D2PI * f + obs.elong);
+
+    /* Add the equation of time to obtain apparent solar time. */
+    let last = anp(lmst + eot);
+    Ok((last, lmst))
+}
@@ -0,0 +1,145 @@
+use super::topo::bdapp;
+use crate::astro::{IauAstrom, apcg13, atioq};
+use crate::eph::{Epv00, Target};
+use crate::pnp::pnm06a;
+use crate::site::{Eop, Observer, Weather};
+use crate::ts::{taitt, utctai};
+use crate::vm::{anp, c2s, pm, rxp};
+
+/// Apparent and observed place of the Sun
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SunPos {
+    pub ra: f64,    // geocentric apparent RA (radians, Note 2)
+    pub dec: f64,   // geocentric apparent Dec (radians)
+    pub dist: f64,  // geocentric distance (au)
+    pub tra: f64,   // topocentric apparent RA (radians, Note 2)
+    pub tdec: f64,  // topocentric apparent Dec (radians)
+    pub tdist: f64, // topocentric distance (au)
+    pub ha: f64,    // topocentric hour angle (radians, -pi to +pi)
+    pub az: f64,    // observed azimuth (radians, N=0,E=90)
+    pub el: f64,    // observed elevation (radians, Note 3)
+}
+
+///  Apparent geocentric place of the Sun
+///
+///  Given:
+///  ```text
+///     tt1    f64     TT as a 2-part...
+///     tt2    f64     ...Julian Date
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok((ra, dec, dist))
+///            ra     f64   apparent RA (true equinox of date, radians)
+///            dec    f64   apparent Dec (true equator of date, radians)
+///            dist   f64   distance (au)
+///     Err(j) j      i32   -1 = date outside the range of iauEpv00
+///  ```
+///  Notes:
+///
+///  1) The heliocentric Earth comes from iauEpv00, with TT used in
+///     place of TDB.  The place is corrected for light time and annual
+///     aberration (iauAb) and referred to the true equator and equinox
+///     of date using the IAU 2006/2000A precession-nutation.
+///
+///  2) The distance is the geometric distance at the retarded time.
+///
+///  Called:
+///  ```text
+///     iauApcg13    astrometry parameters, ICRS-GCRS, geocenter
+///     iauPnm06a    classical NPB matrix, IAU 2006/2000A
+///  ```
+pub fn sunapp(tt1: f64, tt2: f64) -> Result<(f64, f64, f64), i32> {
+    sunradec(tt1, tt2, &pnm06a(tt1, tt2))
+}
+
+/// Apparent geocentric place of the Sun, given the NPB matrix.
+pub(crate) fn sunradec(tt1: f64, tt2: f64, rnpb: &[[f64; 3]; 3]) -> Result<(f64, f64, f64), i32> {
+    let mut astrom = IauAstrom::default();
+    apcg13(tt1, tt2, &mut astrom);
+    let (p, u) = bdapp(&Epv00, Target::Sun, tt1, tt2, &astrom)?;
+    let mut v = [0.0; 3];
+    rxp(rnpb, &u, &mut v);
+    let (w, dec) = c2s(&v);
+    Ok((anp(w), dec, pm(p)))
+}
+
+///  Geocentric, topocentric and observed place of the Sun
+///
+///  Given:
+///  ```text
+///     obs    Observer   observing site
+///     eop    Eop        UT1-UTC and polar motion
+///     wx     Weather    ambient conditions (refraction, Note 3)
+///     utc1   f64        UTC as a 2-part...
+///     utc2   f64        ...quasi Julian Date
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(SunPos)   the Sun's place (see the field comments)
+///     Err(j) j     i32    -1 = unacceptable date
+///  ```
+///  Notes:
+///
+///  1) The geocentric place is as for `sunapp`.  The topocentric
+///     place also includes diurnal parallax, and is obtained from the
+///     same chain as iauAtco13 (iauApco13, aberration via iauAb), with
+///     the observer's barycentric position and velocity.
+///
+///  2) The topocentric RA is converted from the CIO-based RA given by
+///     the chain to the equinox-based RA by subtracting the equation
+///     of the origins, so both RAs refer to the true equinox of date.
+///
+///  3) The azimuth and elevation come from iauAtioq, and so include
+///     refraction unless the pressure in wx is zero.  The hour angle is
+///     unrefracted.
+///
+///  Called:
+///  ```text
+///     iauUtctai    UTC to TAI
+///     iauTaitt     TAI to TT
+///     sunapp       apparent geocentric place of the Sun
+///     iauApco13    astrometry parameters, ICRS-observed, 2013
+///     iauAtioq     quick CIRS to observed
+///  ```
+pub fn sunpos(
+    obs: &Observer,
+    eop: &Eop,
+    wx: &Weather,
+    utc1: f64,
+    utc2: f64,
+) -> Result<SunPos, i32> {
+    let (tai1, tai2) = utctai(utc1, utc2)?;
+    let (tt1, tt2) = taitt(tai1, tai2)?;
+
+    /* Geocentric. */
+    let (ra, dec, dist) = sunapp(tt1, tt2)?;
+
+    /* Topocentric, CIRS. */
+    let (mut astrom, eo) = obs.apco13(utc1, utc2, eop, wx)?;
+    let (p, u) = bdapp(&Epv00, Target::Sun, tt1, tt2, &astrom)?;
+    let mut v = [0.0; 3];
+    rxp(&astrom.bpn, &u, &mut v);
+    let (w, tdec) = c2s(&v);
+    let ri = anp(w);
+
+    /* Observed azimuth and elevation. */
+    let (az, zd, _, _, _) = atioq(ri, tdec, &astrom);
+
+    /* Unrefracted hour angle. */
+    astrom.refa = 0.0;
+    astrom.refb = 0.0;
+    let (_, _, ha, _, _) = atioq(ri, tdec, &astrom);
+
+    Ok(SunPos {
+        ra,
+        dec,
+        dist,
+        tra: anp(ri - eo),
+        tdec,
+        tdist: pm(p),
+        ha,
+        az,
+        el: std::f64::consts::FRAC_PI_2 - zd,
+    })
+}
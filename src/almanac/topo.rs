@@ -0,0 +1,165 @@
+use super::Body;
+use crate::astro::{IauAstrom, atciq, atioq, bodyapp};
+use crate::consts::DAU;
+use crate::eph::{Ephemeris, Target};
+use crate::site::{Eop, Observer, Weather};
+use crate::ts::{taitt, utctai};
+use crate::vm::{anp, c2s, pm, rxp};
+
+/// Topocentric place of a body, as seen by a terrestrial observer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Topo {
+    pub aob: f64,  // azimuth (radians, N=0,E=90)
+    pub zob: f64,  // zenith distance (radians)
+    pub hob: f64,  // hour angle (radians)
+    pub dob: f64,  // declination (radians)
+    pub rob: f64,  // right ascension (CIO-based, radians)
+    pub dist: f64, // topocentric distance (au, zero for a star)
+    pub sd: f64,   // apparent semidiameter (radians)
+}
+
+impl Topo {
+    /// Altitude above the horizon (radians).
+    pub fn alt(&self) -> f64 {
+        std::f64::consts::FRAC_PI_2 - self.zob
+    }
+}
+
+///  Topocentric place of a star or solar system body
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris  source of the Earth, Sun and body (Note 2)
+///     body   Body       the star or solar system body
+///     obs    Observer   observing site
+///     eop    Eop        UT1-UTC and polar motion
+///     utc1   f64        UTC as a 2-part...
+///     utc2   f64        ...quasi Julian Date
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(Topo)   observed place, without refraction (Note 1)
+///     Err(j) j   i32    -1 = unacceptable date
+///                       -2 = not a valid body (Note 3)
+///                       else status from eph
+///  ```
+///  Notes:
+///
+///  1) The place is the one iauAtco13 would return with the pressure
+///     set to zero:  it includes light deflection, annual and diurnal
+///     aberration, precession-nutation, Earth rotation, polar motion and
+///     (for solar system bodies) topocentric parallax, but not
+///     atmospheric refraction.  Refraction at the horizon is handled by
+///     the Horizon type.
+///
+///  2) The astrometry parameters are computed by apco13e, so that the
+///     observer's barycentric position and velocity and the Sun used
+///     for light deflection come from eph, as does the body.  For a
+///     star the result is therefore that of iauAtco13 only when eph
+///     gives the Earth as iauEpv00 does.  For a solar system body the
+///     observer-to-body vector is formed from the barycentric site
+///     position and the body's barycentric position at the retarded
+///     time, iterating for the light time.  Light deflection by the
+///     Sun is computed with the body's heliocentric direction (as for
+///     iauLdn) and is omitted for the Sun itself.  TT is used in place
+///     of TDB when calling eph.  Suitable ephemerides are Epv00 for the
+///     Sun, Moon98 (or Elpmpp02) for the Moon, Plan94 or Vsop87 for the
+///     planets, or Spk or JplEph for any of them.
+///
+///  3) The Earth and the barycenters (Ssb, Emb) are not valid bodies.
+///
+///  Called:
+///  ```text
+///     iauUtctai    UTC to TAI
+///     iauTaitt     TAI to TT
+///     apco13e      astrometry parameters, ICRS-observed, given eph
+///     iauAtciq     quick ICRS to CIRS
+///     iauAtioq     quick CIRS to observed
+///  ```
+pub fn topo<E: Ephemeris>(
+    eph: &E,
+    body: &Body,
+    obs: &Observer,
+    eop: &Eop,
+    utc1: f64,
+    utc2: f64,
+) -> Result<Topo, i32> {
+    let (mut astrom, _) = obs.apco13e(eph, utc1, utc2, eop, &Weather::vacuum())?;
+
+    let (ri, di, dist) = match *body {
+        Body::Star {
+            rc,
+            dc,
+            pr,
+            pd,
+            px,
+            rv,
+        } => {
+            let (ri, di) = atciq(rc, dc, pr, pd, px, rv, &mut astrom);
+            (ri, di, 0.0)
+        }
+        Body::Solar(Target::Earth | Target::Ssb | Target::Emb) => return Err(-2),
+        Body::Solar(target) => {
+            let (tai1, tai2) = utctai(utc1, utc2)?;
+            let (tt1, tt2) = taitt(tai1, tai2)?;
+
+            let (p, ppr) = bdapp(eph, target, tt1, tt2, &astrom)?;
+
+            /* Bias-precession-nutation, giving CIRS. */
+            let mut pi = [0.0; 3];
+            rxp(&astrom.bpn, &ppr, &mut pi);
+            let (w, di) = c2s(&pi);
+            (anp(w), di, pm(p))
+        }
+    };
+
+    let (aob, zob, hob, dob, rob) = atioq(ri, di, &astrom);
+    let sd = if dist > 0.0 {
+        (body.radius() * 1e3 / DAU / dist).min(1.0).asin()
+    } else {
+        0.0
+    };
+    Ok(Topo {
+        aob,
+        zob,
+        hob,
+        dob,
+        rob,
+        dist,
+        sd,
+    })
+}
+
+///  Astrometric vector and proper direction of a solar system body
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris  source of the Earth, Sun and body
+///     target Target     solar system body
+///     tt1    f64        TT as a 2-part...
+///     tt2    f64        ...Julian Date
+///     astrom IauAstrom  star-independent astrometry parameters
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok((p, ppr))
+///            p     [f64; 3]  observer to body at the retarded time (au)
+///            ppr   [f64; 3]  proper direction (unit vector, BCRS)
+///     Err(j) j     i32       status from eph
+///  ```
+///  Note:
+///
+///     The light time is iterated, and the direction corrected for
+///     light deflection by the Sun (except for the Sun itself) and for
+///     aberration, using the observer data in astrom (see atciqb).
+pub(crate) fn bdapp<E: Ephemeris>(
+    eph: &E,
+    target: Target,
+    tt1: f64,
+    tt2: f64,
+    astrom: &IauAstrom,
+) -> Result<([f64; 3], [f64; 3]), i32> {
+    let pvb = |d1, d2| eph.pvb(target, d1, d2);
+    let (p, ppr, _) = bodyapp(&pvb, tt1, tt2, astrom)?;
+    Ok((p, ppr))
+}
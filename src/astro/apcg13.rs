@@ -1,6 +1,5 @@
-use crate::eph::epv00;
-
-use super::{IauAstrom, apcg};
+use super::{IauAstrom, apcg13e};
+use crate::eph::Epv00;
 
 ///  Prepare for ICRS <−> GCRS, geocentric
 ///
@@ -111,9 +110,6 @@ use super::{IauAstrom, apcg};
 ///     iauApcg      astrometry parameters, ICRS-GCRS, geocenter
 ///  ```
 pub fn apcg13(date1: f64, date2: f64, astrom: &mut IauAstrom) {
-    /* Earth barycentric & heliocentric position/velocity (au, au/d). */
-    let (ehpv, ebpv) = epv00(date1, date2).unwrap();
-
-    /* Compute the star-independent astrometry parameters. */
-    apcg(date1, date2, &ebpv, &ehpv[0], astrom);
+    /* Earth ephemeris from iauEpv00. */
+    apcg13e(&Epv00, date1, date2, astrom).unwrap();
 }
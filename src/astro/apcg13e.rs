@@ -0,0 +1,45 @@
+use super::{IauAstrom, apcg};
+use crate::eph::Ephemeris;
+
+///  Prepare for ICRS <−> GCRS, geocentric, given an ephemeris
+///
+///  As iauApcg13, but with the Earth ephemeris supplied by the caller.
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris  source of the Earth ephemeris
+///     date1  f64        TDB as a 2-part...
+///     date2  f64        ...Julian Date
+///  ```
+///  Returned:
+///  ```text
+///     astrom IauAstrom  star-independent astrometry parameters
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(())
+///     Err(j) j   i32    status from eph
+///  ```
+///  Note:
+///
+///     apcg13 is this function with the iauEpv00 ephemeris (Epv00).
+///
+///  Called:
+///  ```text
+///     Ephemeris::earth   Earth position and velocity
+///     iauApcg            astrometry parameters, ICRS-GCRS, geocenter
+///  ```
+pub fn apcg13e<E: Ephemeris>(
+    eph: &E,
+    date1: f64,
+    date2: f64,
+    astrom: &mut IauAstrom,
+) -> Result<(), i32> {
+    /* Earth barycentric & heliocentric position/velocity (au, au/d). */
+    let (ehpv, ebpv) = eph.earth(date1, date2)?;
+
+    /* Compute the star-independent astrometry parameters. */
+    apcg(date1, date2, &ebpv, &ehpv[0], astrom);
+
+    Ok(())
+}
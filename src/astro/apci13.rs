@@ -1,8 +1,5 @@
-use super::{IauAstrom, apci};
-use crate::{
-    eph::epv00,
-    pnp::{bpn2xy, eors, pnm06a, s06},
-};
+use super::{IauAstrom, apci13e};
+use crate::eph::Epv00;
 
 ///  Prepare for ICRS <−> CIRS, terrestrial
 ///
@@ -118,21 +115,6 @@ use crate::{
 ///     iauEors      equation of the origins, given NPB matrix and s
 ///  ```
 pub fn apci13(date1: f64, date2: f64, astrom: &mut IauAstrom, eo: &mut f64) {
-    /* Earth barycentric & heliocentric position/velocity (au, au/d). */
-    let (ehpv, ebpv) = epv00(date1, date2).unwrap();
-
-    /* Form the equinox based BPN matrix, IAU 2006/2000A. */
-    let r = pnm06a(date1, date2);
-
-    /* Extract CIP X,Y. */
-    let (x, y) = bpn2xy(&r);
-
-    /* Obtain CIO locator s. */
-    let s = s06(date1, date2, x, y);
-
-    /* Compute the star-independent astrometry parameters. */
-    apci(date1, date2, &ebpv, &ehpv[0], x, y, s, astrom);
-
-    /* Equation of the origins. */
-    *eo = eors(&r, s);
+    /* Earth ephemeris from iauEpv00. */
+    apci13e(&Epv00, date1, date2, astrom, eo).unwrap();
 }
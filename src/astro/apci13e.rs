@@ -0,0 +1,64 @@
+use super::{IauAstrom, apci};
+use crate::eph::Ephemeris;
+use crate::pnp::{bpn2xy, eors, pnm06a, s06};
+
+///  Prepare for ICRS <−> CIRS, terrestrial, given an ephemeris
+///
+///  As iauApci13, but with the Earth ephemeris supplied by the caller.
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris  source of the Earth ephemeris
+///     date1  f64        TDB as a 2-part...
+///     date2  f64        ...Julian Date
+///  ```
+///  Returned:
+///  ```text
+///     astrom IauAstrom  star-independent astrometry parameters
+///     eo     f64        equation of the origins (ERA-GST, radians)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(())
+///     Err(j) j   i32    status from eph
+///  ```
+///  Note:
+///
+///     apci13 is this function with the iauEpv00 ephemeris (Epv00).
+///
+///  Called:
+///  ```text
+///     Ephemeris::earth   Earth position and velocity
+///     iauPnm06a    classical NPB matrix, IAU 2006/2000A
+///     iauBpn2xy    extract CIP X,Y coordinates from NPB matrix
+///     iauS06       the CIO locator s, given X,Y, IAU 2006
+///     iauApci      astrometry parameters, ICRS-CIRS
+///     iauEors      equation of the origins, given NPB matrix and s
+///  ```
+pub fn apci13e<E: Ephemeris>(
+    eph: &E,
+    date1: f64,
+    date2: f64,
+    astrom: &mut IauAstrom,
+    eo: &mut f64,
+) -> Result<(), i32> {
+    /* Earth barycentric & heliocentric position/velocity (au, au/d). */
+    let (ehpv, ebpv) = eph.earth(date1, date2)?;
+
+    /* Form the equinox based BPN matrix, IAU 2006/2000A. */
+    let r = pnm06a(date1, date2);
+
+    /* Extract CIP X,Y. */
+    let (x, y) = bpn2xy(&r);
+
+    /* Obtain CIO locator s. */
+    let s = s06(date1, date2, x, y);
+
+    /* Compute the star-independent astrometry parameters. */
+    apci(date1, date2, &ebpv, &ehpv[0], x, y, s, astrom);
+
+    /* Equation of the origins. */
+    *eo = eors(&r, s);
+
+    Ok(())
+}
@@ -1,8 +1,5 @@
-use super::{IauAstrom, apco, refco};
-use crate::eph::epv00;
-use crate::erst::era00;
-use crate::pnp::{bpn2xy, eors, pnm06a, s06, sp00};
-use crate::ts::{taitt, utctai, utcut1};
+use super::{IauAstrom, apco13e};
+use crate::eph::Epv00;
 
 ///  Prepare for ICRS <−> observed, terrestrial
 ///
@@ -195,53 +192,8 @@ pub fn apco13(
     astrom: &mut IauAstrom,
     eo: &mut f64,
 ) -> Result<i32, i32> {
-    let s: f64;
-    let theta: f64;
-    let sp: f64;
-
-    /* UTC to other time scales. */
-    let (tai1, tai2) = match utctai(utc1, utc2) {
-        Ok(v) => v,
-        Err(j) => return Err(j),
-    };
-
-    let (tt1, tt2) = match taitt(tai1, tai2) {
-        Ok(v) => v,
-        Err(j) => return Err(j),
-    };
-
-    let (ut11, ut12) = match utcut1(utc1, utc2, dut1) {
-        Ok(v) => v,
-        Err(j) => return Err(j),
-    };
-
-    let (ehpv, ebpv) = epv00(tt1, tt2).unwrap();
-
-    /* Form the equinox based BPN matrix, IAU 2006/2000A. */
-    let r = pnm06a(tt1, tt2);
-
-    /* Extract CIP X,Y. */
-    let (x, y) = bpn2xy(&r);
-
-    /* Obtain CIO locator s. */
-    s = s06(tt1, tt2, x, y);
-
-    /* Earth rotation angle. */
-    theta = era00(ut11, ut12);
-
-    /* TIO locator s'. */
-    sp = sp00(tt1, tt2);
-
-    /* Refraction constants A and B. */
-    let (refa, refb) = refco(phpa, tc, rh, wl);
-
-    /* Compute the star-independent astrometry parameters. */
-    apco(
-        tt1, tt2, &ebpv, &ehpv[0], x, y, s, theta, elong, phi, hm, xp, yp, sp, refa, refb, astrom,
-    );
-
-    /* Equation of the origins. */
-    *eo = eors(&r, s);
-
-    Ok(0)
+    /* Earth ephemeris from iauEpv00. */
+    apco13e(
+        &Epv00, utc1, utc2, dut1, elong, phi, hm, xp, yp, phpa, tc, rh, wl, astrom, eo,
+    )
 }
@@ -0,0 +1,110 @@
+use super::{IauAstrom, apco, refco};
+use crate::eph::Ephemeris;
+use crate::erst::era00;
+use crate::pnp::{bpn2xy, eors, pnm06a, s06, sp00};
+use crate::ts::{taitt, utctai, utcut1};
+
+///  Prepare for ICRS <−> observed, terrestrial, given an ephemeris
+///
+///  As iauApco13, but with the Earth ephemeris supplied by the caller.
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris  source of the Earth ephemeris
+///     utc1   f64        UTC as a 2-part...
+///     utc2   f64        ...quasi Julian Date
+///     dut1   f64        UT1-UTC (seconds)
+///     elong  f64        longitude (radians, east +ve)
+///     phi    f64        latitude (geodetic, radians)
+///     hm     f64        height above ellipsoid (m, geodetic)
+///     xp,yp  f64        polar motion coordinates (radians)
+///     phpa   f64        pressure at the observer (hPa = mB)
+///     tc     f64        ambient temperature at the observer (deg C)
+///     rh     f64        relative humidity at the observer (range 0-1)
+///     wl     f64        wavelength (micrometers)
+///  ```
+///  Returned:
+///  ```text
+///     astrom IauAstrom  star-independent astrometry parameters
+///     eo     f64        equation of the origins (ERA-GST, radians)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(0)
+///     Err(j) j   i32    -1 = unacceptable date, or status from eph
+///  ```
+///  Notes:
+///
+///  1) apco13 is this function with the iauEpv00 ephemeris (Epv00).
+///
+///  2) The ephemeris is called with TT in place of TDB.
+///
+///  Called:
+///  ```text
+///     iauUtctai    UTC to TAI
+///     iauTaitt     TAI to TT
+///     iauUtcut1    UTC to UT1
+///     Ephemeris::earth   Earth position and velocity
+///     iauPnm06a    classical NPB matrix, IAU 2006/2000A
+///     iauBpn2xy    extract CIP X,Y coordinates from NPB matrix
+///     iauS06       the CIO locator s, given X,Y, IAU 2006
+///     iauEra00     Earth rotation angle, IAU 2000
+///     iauSp00      the TIO locator s', IERS 2000
+///     iauRefco     refraction constants for given ambient conditions
+///     iauApco      astrometry parameters, ICRS-observed
+///     iauEors      equation of the origins, given NPB matrix and s
+///  ```
+#[allow(clippy::too_many_arguments)]
+pub fn apco13e<E: Ephemeris>(
+    eph: &E,
+    utc1: f64,
+    utc2: f64,
+    dut1: f64,
+    elong: f64,
+    phi: f64,
+    hm: f64,
+    xp: f64,
+    yp: f64,
+    phpa: f64,
+    tc: f64,
+    rh: f64,
+    wl: f64,
+    astrom: &mut IauAstrom,
+    eo: &mut f64,
+) -> Result<i32, i32> {
+    /* UTC to other time scales. */
+    let (tai1, tai2) = utctai(utc1, utc2)?;
+    let (tt1, tt2) = taitt(tai1, tai2)?;
+    let (ut11, ut12) = utcut1(utc1, utc2, dut1)?;
+
+    /* Earth barycentric & heliocentric position/velocity (au, au/d). */
+    let (ehpv, ebpv) = eph.earth(tt1, tt2)?;
+
+    /* Form the equinox based BPN matrix, IAU 2006/2000A. */
+    let r = pnm06a(tt1, tt2);
+
+    /* Extract CIP X,Y. */
+    let (x, y) = bpn2xy(&r);
+
+    /* Obtain CIO locator s. */
+    let s = s06(tt1, tt2, x, y);
+
+    /* Earth rotation angle. */
+    let theta = era00(ut11, ut12);
+
+    /* TIO locator s'. */
+    let sp = sp00(tt1, tt2);
+
+    /* Refraction constants A and B. */
+    let (refa, refb) = refco(phpa, tc, rh, wl);
+
+    /* Compute the star-independent astrometry parameters. */
+    apco(
+        tt1, tt2, &ebpv, &ehpv[0], x, y, s, theta, elong, phi, hm, xp, yp, sp, refa, refb, astrom,
+    );
+
+    /* Equation of the origins. */
+    *eo = eors(&r, s);
+
+    Ok(0)
+}
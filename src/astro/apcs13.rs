@@ -1,6 +1,5 @@
-use crate::eph::epv00;
-
-use super::{IauAstrom, apcs};
+use super::{IauAstrom, apcs13e};
+use crate::eph::Epv00;
 
 ///  Prepare for ICRS <−> CIRS, space
 ///
@@ -117,9 +116,6 @@ use super::{IauAstrom, apcs};
 ///     iauApcs      astrometry parameters, ICRS-GCRS, space observer
 ///  ```
 pub fn apcs13(date1: f64, date2: f64, pv: &[[f64; 3]; 2], astrom: &mut IauAstrom) {
-    /* Earth barycentric & heliocentric position/velocity (au, au/d). */
-    let (ehpv, ebpv) = epv00(date1, date2).unwrap();
-
-    /* Compute the star-independent astrometry parameters. */
-    apcs(date1, date2, pv, &ebpv, &ehpv[0], astrom);
+    /* Earth ephemeris from iauEpv00. */
+    apcs13e(&Epv00, date1, date2, pv, astrom).unwrap();
 }
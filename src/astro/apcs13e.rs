@@ -0,0 +1,47 @@
+use super::{IauAstrom, apcs};
+use crate::eph::Ephemeris;
+
+///  Prepare for ICRS <−> GCRS, space observer, given an ephemeris
+///
+///  As iauApcs13, but with the Earth ephemeris supplied by the caller.
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris      source of the Earth ephemeris
+///     date1  f64            TDB as a 2-part...
+///     date2  f64            ...Julian Date
+///     pv     [[f64; 3]; 2]  observer's geocentric pos/vel (m, m/s)
+///  ```
+///  Returned:
+///  ```text
+///     astrom IauAstrom  star-independent astrometry parameters
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(())
+///     Err(j) j   i32    status from eph
+///  ```
+///  Note:
+///
+///     apcs13 is this function with the iauEpv00 ephemeris (Epv00).
+///
+///  Called:
+///  ```text
+///     Ephemeris::earth   Earth position and velocity
+///     iauApcs            astrometry parameters, ICRS-GCRS, space observer
+///  ```
+pub fn apcs13e<E: Ephemeris>(
+    eph: &E,
+    date1: f64,
+    date2: f64,
+    pv: &[[f64; 3]; 2],
+    astrom: &mut IauAstrom,
+) -> Result<(), i32> {
+    /* Earth barycentric & heliocentric position/velocity (au, au/d). */
+    let (ehpv, ebpv) = eph.earth(date1, date2)?;
+
+    /* Compute the star-independent astrometry parameters. */
+    apcs(date1, date2, pv, &ebpv, &ehpv[0], astrom);
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use super::{IauAstrom, IauBodyPlace, apci13e, atciqb};
+use crate::eph::{Ephemeris, Target};
+
+///  Solar-system body −> CIRS
+///
+///  Astrometric and CIRS geocentric place of a solar-system body.  The
+///  counterpart of iauAtci13 for bodies.
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris  source of the Earth and body ephemerides
+///     target Target  the body
+///     date1  f64     TDB as a 2-part...
+///     date2  f64     ...Julian Date
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(IauBodyPlace)   rca,dca   astrometric RA,Dec (ICRS, radians)
+///                        ri,di     CIRS geocentric RA,Dec (radians)
+///                        eo        equation of the origins (ERA-GST)
+///                        dist      geocentric distance (au)
+///                        lt        light time (days)
+///                        (the observed-place fields are zero)
+///     Err(j) j   i32     status from eph
+///  ```
+///  Notes:
+///
+///  1) The precession-nutation is as for iauAtci13, with the Earth
+///     taken from eph (see apci13e), and the body's place is computed
+///     by `atciqb`:  light time, light deflection by the Sun and
+///     aberration are included.
+///
+///  2) Should the equinox based apparent place be required rather
+///     than the CIO based intermediate place, subtract the equation of
+///     the origins from the returned RA:  RA = RI - EO.
+///
+///  Called:
+///  ```text
+///     apci13e      astrometry parameters, ICRS-CIRS, given ephemeris
+///     atciqb       quick ICRS to CIRS, solar-system body
+///  ```
+pub fn atci13b<E: Ephemeris>(
+    eph: &E,
+    target: Target,
+    date1: f64,
+    date2: f64,
+) -> Result<IauBodyPlace, i32> {
+    let eo = &mut 0.0;
+    let astrom = &mut IauAstrom::default();
+
+    /* The transformation parameters. */
+    apci13e(eph, date1, date2, astrom, eo)?;
+
+    /* Body to CIRS. */
+    let (rca, dca, ri, di, dist, lt) = atciqb(eph, target, date1, date2, astrom)?;
+
+    Ok(IauBodyPlace {
+        rca,
+        dca,
+        ri,
+        di,
+        eo: *eo,
+        dist,
+        lt,
+        ..Default::default()
+    })
+}
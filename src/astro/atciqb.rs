@@ -0,0 +1,125 @@
+use super::{IauAstrom, ab, ld};
+use crate::consts::{AULT, DAYSEC};
+use crate::eph::{Ephemeris, Target};
+use crate::vm::{anp, c2s, pm, pmp, pn, rxp, sxp};
+
+/// Number of light-time iterations.
+const NITER: usize = 3;
+
+/// Heliocentric distance (au) inside which no deflection is applied.
+const RSUN: f64 = 0.005;
+
+///  Quick ICRS, epoch J2000.0, to CIRS transformation, for a solar-system
+///  body
+///
+///  Use of this function is appropriate when efficiency is important and
+///  where many body positions are to be transformed for one date.  The
+///  star-independent parameters can be obtained by calling one of the
+///  functions iauApci[13], iauApcg[13], iauApco[13] or iauApcs[13].
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris  source of the body's ephemeris (Note 1)
+///     target Target     the body
+///     date1  f64        TDB as a 2-part...
+///     date2  f64        ...Julian Date
+///     astrom IauAstrom  star-independent astrometry parameters
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok((rca, dca, ri, di, dist, lt))
+///            rca,dca  f64   astrometric RA,Dec (ICRS, radians, Note 2)
+///            ri,di    f64   CIRS RA,Dec (radians)
+///            dist     f64   distance from the observer (au, Note 2)
+///            lt       f64   light time (days)
+///     Err(j) j        i32   status from eph (-2 = body not supplied)
+///  ```
+///  Notes:
+///
+///  1) The body's barycentric position is obtained from the ephemeris
+///     at the date and at successively retarded dates, the light time
+///     being iterated three times.  The barycentric positions of the
+///     observer and the Sun are taken from astrom, which will normally
+///     have been prepared using the same ephemeris (for example by
+///     apci13e or apco13e).
+///
+///  2) The astrometric place and distance are those of the
+///     observer-to-body vector at the retarded time, without light
+///     deflection or aberration.
+///
+///  3) The CIRS place includes light deflection by the Sun, using the
+///     body's heliocentric direction (as in iauLdn), except for bodies
+///     within 0.005 au of the Sun (so not for the Sun itself), and
+///     aberration.
+///
+///  Called:
+///  ```text
+///     iauLd        light deflection by a solar-system body
+///     iauAb        stellar aberration
+///     iauRxp       product of r-matrix and pv-vector
+///     iauC2s       p-vector to spherical
+///     iauAnp       normalize angle into range 0 to 2pi
+///  ```
+pub fn atciqb<E: Ephemeris>(
+    eph: &E,
+    target: Target,
+    date1: f64,
+    date2: f64,
+    astrom: &IauAstrom,
+) -> Result<(f64, f64, f64, f64, f64, f64), i32> {
+    let pvb = |d1, d2| eph.pvb(target, d1, d2);
+    let (p, ppr, lt) = bodyapp(&pvb, date1, date2, astrom)?;
+
+    /* Astrometric place. */
+    let (w, dca) = c2s(&p);
+
+    /* Bias-precession-nutation, giving CIRS proper direction. */
+    let mut pi = [0.0; 3];
+    rxp(&astrom.bpn, &ppr, &mut pi);
+    let (w2, di) = c2s(&pi);
+
+    Ok((anp(w), dca, anp(w2), di, pm(p), lt))
+}
+
+///  Observer-to-body vector, proper direction and light time
+///
+///  Returns the astrometric vector p (au), the proper direction (unit
+///  vector, BCRS, after light deflection and aberration) and the light
+///  time (days).  The ephemeris eph gives the body's barycentric p,v
+///  (au, au/d) as a function of TDB.
+pub(crate) fn bodyapp<F>(
+    eph: &F,
+    date1: f64,
+    date2: f64,
+    astrom: &IauAstrom,
+) -> Result<([f64; 3], [f64; 3], f64), i32>
+where
+    F: Fn(f64, f64) -> Result<[[f64; 3]; 2], i32>,
+{
+    /* Barycentric Sun. */
+    let sb = pmp(&astrom.eb, &sxp(astrom.em, &astrom.eh));
+
+    /* Observer to body, allowing for light time. */
+    let mut lt = 0.0;
+    let mut p = [0.0; 3];
+    let mut q = [0.0; 3];
+    for _ in 0..NITER {
+        let pv = eph(date1, date2 - lt)?;
+        p = pmp(&pv[0], &astrom.eb);
+        q = pmp(&pv[0], &sb);
+        lt = pm(p) * AULT / DAYSEC;
+    }
+    let (_, u) = pn(&p);
+    let (qm, qu) = pn(&q);
+
+    /* Light deflection by the Sun. */
+    let pnat = if qm > RSUN {
+        let dlim = 1e-6 / (astrom.em * astrom.em).max(1.0);
+        ld(1.0, u, qu, astrom.eh, astrom.em, dlim)
+    } else {
+        u
+    };
+
+    /* Aberration. */
+    Ok((p, ab(&pnat, &astrom.v, astrom.em, astrom.bm1), lt))
+}
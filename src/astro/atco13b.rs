@@ -0,0 +1,112 @@
+use super::{IauAstrom, IauBodyPlace, apco13e, atciqb, atioq};
+use crate::eph::{Ephemeris, Target};
+use crate::ts::{taitt, utctai};
+
+///  Solar-system body −> observed
+///
+///  Astrometric, CIRS and observed place of a solar-system body.  The
+///  counterpart of iauAtco13 for bodies:  the caller supplies UTC, site
+///  coordinates, ambient air conditions and observing wavelength.
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris  source of the Earth and body ephemerides
+///     target Target     the body
+///     utc1   f64        UTC as a 2-part...
+///     utc2   f64        ...quasi Julian Date
+///     dut1   f64        UT1-UTC (seconds)
+///     elong  f64        longitude (radians, east +ve)
+///     phi    f64        latitude (geodetic, radians)
+///     hm     f64        height above ellipsoid (m, geodetic)
+///     xp,yp  f64        polar motion coordinates (radians)
+///     phpa   f64        pressure at the observer (hPa = mB)
+///     tc     f64        ambient temperature at the observer (deg C)
+///     rh     f64        relative humidity at the observer (range 0-1)
+///     wl     f64        wavelength (micrometers)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(IauBodyPlace)   rca,dca   topocentric astrometric RA,Dec (ICRS)
+///                        ri,di     topocentric CIRS RA,Dec
+///                        eo        equation of the origins (ERA-GST)
+///                        aob       observed azimuth (radians: N=0,E=90)
+///                        zob       observed zenith distance (radians)
+///                        hob       observed hour angle (radians)
+///                        dob       observed declination (radians)
+///                        rob       observed right ascension (CIO-based)
+///                        dist      topocentric distance (au)
+///                        lt        light time (days)
+///     Err(j) j   i32     -1 = unacceptable date, or status from eph
+///  ```
+///  Notes:
+///
+///  1) The arguments are as for iauAtco13, to whose notes the reader
+///     is referred.
+///
+///  2) The observer's barycentric position from apco13e includes the
+///     site, so the astrometric and CIRS places are topocentric:
+///     diurnal parallax is included in the vector from the observer to
+///     the body.  The remaining transformation to observed place is
+///     that of iauAtioq, as in iauAtco13.
+///
+///  3) The ephemeris is called with TT in place of TDB, the difference
+///     being negligible here.
+///
+///  Called:
+///  ```text
+///     iauUtctai    UTC to TAI
+///     iauTaitt     TAI to TT
+///     apco13e      astrometry parameters, ICRS-observed, given ephemeris
+///     atciqb       quick ICRS to CIRS, solar-system body
+///     iauAtioq     quick CIRS to observed
+///  ```
+#[allow(clippy::too_many_arguments)]
+pub fn atco13b<E: Ephemeris>(
+    eph: &E,
+    target: Target,
+    utc1: f64,
+    utc2: f64,
+    dut1: f64,
+    elong: f64,
+    phi: f64,
+    hm: f64,
+    xp: f64,
+    yp: f64,
+    phpa: f64,
+    tc: f64,
+    rh: f64,
+    wl: f64,
+) -> Result<IauBodyPlace, i32> {
+    let astrom = &mut IauAstrom::default();
+    let eo = &mut 0.0;
+
+    /* Star-independent astrometry parameters. */
+    apco13e(
+        eph, utc1, utc2, dut1, elong, phi, hm, xp, yp, phpa, tc, rh, wl, astrom, eo,
+    )?;
+
+    /* TT for the ephemeris. */
+    let (tai1, tai2) = utctai(utc1, utc2)?;
+    let (tt1, tt2) = taitt(tai1, tai2)?;
+
+    /* Body to CIRS. */
+    let (rca, dca, ri, di, dist, lt) = atciqb(eph, target, tt1, tt2, astrom)?;
+
+    /* CIRS to observed. */
+    let (aob, zob, hob, dob, rob) = atioq(ri, di, astrom);
+
+    Ok(IauBodyPlace {
+        rca,
+        dca,
+        ri,
+        di,
+        eo: *eo,
+        aob,
+        zob,
+        hob,
+        dob,
+        rob,
+        dist,
+        lt,
+    })
+}
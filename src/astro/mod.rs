@@ -1,7 +1,8 @@
 //! Astrometry
+use crate::eph::{Ephemeris, Target};
 
 // Star-independent astrometry parameters
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct IauAstrom {
     pub pmt: f64,           // PM time interval (SSB, Julian years)
     pub eb: [f64; 3],       // SSB to observer (vector, au)
@@ -57,6 +58,35 @@ impl IauLdBody {
     pub fn new(bm: f64, dl: f64, pv: [[f64; 3]; 2]) -> Self {
         IauLdBody { bm, dl, pv }
     }
+
+    /// Body with its barycentric PV at TDB date1+date2 from an ephemeris.
+    pub fn from_eph<E: Ephemeris>(
+        eph: &E,
+        target: Target,
+        bm: f64,
+        dl: f64,
+        date1: f64,
+        date2: f64,
+    ) -> Result<Self, i32> {
+        Ok(IauLdBody::new(bm, dl, eph.pvb(target, date1, date2)?))
+    }
+}
+
+// Astrometric, apparent and observed place of a solar-system body
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct IauBodyPlace {
+    pub rca: f64,  // astrometric RA (ICRS, radians)
+    pub dca: f64,  // astrometric Dec (ICRS, radians)
+    pub ri: f64,   // CIRS RA (radians)
+    pub di: f64,   // CIRS Dec (radians)
+    pub eo: f64,   // equation of the origins (ERA-GST, radians)
+    pub aob: f64,  // observed azimuth (radians: N=0,E=90)
+    pub zob: f64,  // observed zenith distance (radians)
+    pub hob: f64,  // observed hour angle (radians)
+    pub dob: f64,  // observed declination (radians)
+    pub rob: f64,  // observed right ascension (CIO-based, radians)
+    pub dist: f64, // distance from the observer (au)
+    pub lt: f64,   // light time (days)
 }
 
 mod ab;
@@ -68,24 +98,36 @@ pub use apcg::*;
 mod apcg13;
 pub use apcg13::*;
 
+mod apcg13e;
+pub use apcg13e::*;
+
 mod apci;
 pub use apci::*;
 
 mod apci13;
 pub use apci13::*;
 
+mod apci13e;
+pub use apci13e::*;
+
 mod apco;
 pub use apco::*;
 
 mod apco13;
 pub use apco13::*;
 
+mod apco13e;
+pub use apco13e::*;
+
 mod apcs;
 pub use apcs::*;
 
 mod apcs13;
 pub use apcs13::*;
 
+mod apcs13e;
+pub use apcs13e::*;
+
 mod aper;
 pub use aper::*;
 
@@ -107,9 +149,15 @@ pub use atccq::*;
 mod atci13;
 pub use atci13::*;
 
+mod atci13b;
+pub use atci13b::*;
+
 mod atciq;
 pub use atciq::*;
 
+mod atciqb;
+pub use atciqb::*;
+
 mod atciqn;
 pub use atciqn::*;
 
@@ -119,6 +167,9 @@ pub use atciqz::*;
 mod atco13;
 pub use atco13::*;
 
+mod atco13b;
+pub use atco13b::*;
+
 mod atic13;
 pub use atic13::*;
 
@@ -76,6 +76,9 @@ pub const ELG: f64 = 6.969290134e-10;
 pub const ELB: f64 = 1.550519768e-8;
 pub const TDB0: f64 = -6.55e-5;
 
+/// Gaussian gravitational constant (au^3/2 per day)
+pub const GK: f64 = 0.01720209895;
+
 /// Schwarzschild radius of the Sun (au)
 pub const SRS: f64 = 1.97412574336e-8;
 
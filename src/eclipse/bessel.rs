@@ -0,0 +1,141 @@
+use super::sunmoon;
+use crate::consts::{AULT, DAS2R, DAU, DAYSEC};
+use crate::eph::Ephemeris;
+use crate::erst::gst06;
+use crate::pnp::pnm06a;
+use crate::vm::{anp, c2s, pm, pmp, rxp, sxp};
+
+/// Equatorial radius of the Earth (m).
+pub(crate) const ERAD: f64 = 6378137.0;
+
+/// Radius of the Sun (au), from its semidiameter at 1 au, 959.63".
+pub(crate) const SRAD: f64 = 959.63 * DAS2R;
+
+/// Moon radius in Earth radii, for the penumbra and the umbra.
+pub(crate) const K1: f64 = 0.272488;
+pub(crate) const K2: f64 = 0.272281;
+
+/// Besselian elements of a solar eclipse
+///
+/// Lengths are in units of the Earth's equatorial radius and angles in
+/// radians.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bessel {
+    pub x: f64,     // x coordinate of the shadow axis
+    pub y: f64,     // y coordinate of the shadow axis
+    pub d: f64,     // declination of the shadow axis
+    pub mu: f64,    // Greenwich hour angle of the shadow axis
+    pub l1: f64,    // radius of the penumbral cone on the fundamental plane
+    pub l2: f64,    // radius of the umbral cone (negative for a total eclipse)
+    pub tanf1: f64, // tangent of the penumbral cone half-angle
+    pub tanf2: f64, // tangent of the umbral cone half-angle
+}
+
+///  Besselian elements of a solar eclipse at a given instant
+///
+///  Given:
+///  ```text
+///     tt1    f64        TT as a 2-part...
+///     tt2    f64        ...Julian Date
+///     deltat f64        TT-UT1 (seconds, Note 4)
+///     eph    Ephemeris  source of the Sun and Moon (Note 1)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(Bessel)   the elements (Notes 2-4)
+///     Err(j) j     i32    status from eph
+///  ```
+///  Notes:
+///
+///  1) The geometric geocentric positions are obtained from eph using
+///     `sunmoon`, for example with the Moon98 ephemeris.  Light time
+///     and annual aberration are allowed for by evaluating each body at
+///     the retarded time (the "planetary aberration" method), after
+///     which the positions are referred to the true equator and equinox
+///     of date using iauPnm06a.
+///
+///  2) The fundamental plane passes through the Earth's center
+///     perpendicular to the shadow axis, the line through the centers
+///     of the Sun and Moon.  x, y are the coordinates of the axis on
+///     that plane, the y axis being towards the north celestial pole;
+///     d and mu are the declination and Greenwich apparent hour angle
+///     of the point towards which the axis points.
+///
+///  3) The Moon's radius is k = 0.272488 Earth radii for the penumbra
+///     and 0.272281 for the umbra, and the Sun's is its semidiameter at
+///     1 au, 959.63 arcsec (as in NASA's eclipse predictions).
+///
+///  4) mu depends on UT1, which is obtained as TT - deltat, so that
+///     it can be used directly with the site longitude.  Published
+///     elements usually tabulate the "ephemeris hour angle", computed
+///     with TT in place of UT1, which is obtained by setting deltat to
+///     zero.
+///
+///  References:
+///
+///     Explanatory Supplement to the Astronomical Almanac,
+///     P. Kenneth Seidelmann (ed), University Science Books (1992),
+///     Chapter 8.
+///
+///     Meeus, J., Elements of Solar Eclipses 1951-2200, Willmann-Bell
+///     (1989).
+///
+///  Called:
+///  ```text
+///     iauPnm06a    classical NPB matrix, IAU 2006/2000A
+///     iauGst06     Greenwich apparent sidereal time, IAU 2006
+///  ```
+pub fn bessel<E: Ephemeris>(tt1: f64, tt2: f64, deltat: f64, eph: &E) -> Result<Bessel, i32> {
+    /* Apparent geocentric Sun and Moon, allowing for light time. */
+    let (mut ps, mut pm0) = sunmoon(eph, tt1, tt2)?;
+    for _ in 0..2 {
+        let (s, _) = sunmoon(eph, tt1, tt2 - pm(ps) * AULT / DAYSEC)?;
+        let (_, m) = sunmoon(eph, tt1, tt2 - pm(pm0) * AULT / DAYSEC)?;
+        ps = s;
+        pm0 = m;
+    }
+
+    /* To the true equator and equinox of date, in Earth radii. */
+    let rnpb = pnm06a(tt1, tt2);
+    let au = DAU / ERAD;
+    let (mut s, mut m) = ([0.0; 3], [0.0; 3]);
+    rxp(&rnpb, &sxp(au, &ps), &mut s);
+    rxp(&rnpb, &sxp(au, &pm0), &mut m);
+
+    /* Direction of the shadow axis, Moon to Sun. */
+    let g = pmp(&s, &m);
+    let gm = pm(g);
+    let (a, d) = c2s(&g);
+
+    /* Moon's coordinates in the fundamental system. */
+    let (am, dm) = c2s(&m);
+    let rm = pm(m);
+    let h = am - a;
+    let x = rm * dm.cos() * h.sin();
+    let y = rm * (dm.sin() * d.cos() - dm.cos() * d.sin() * h.cos());
+    let z = rm * (dm.sin() * d.sin() + dm.cos() * d.cos() * h.cos());
+
+    /* Shadow cones. */
+    let rs = SRAD * au;
+    let f1 = ((rs + K1) / gm).asin();
+    let f2 = ((rs - K2) / gm).asin();
+    let tanf1 = f1.tan();
+    let tanf2 = f2.tan();
+    let l1 = z * tanf1 + K1 / f1.cos();
+    let l2 = z * tanf2 - K2 / f2.cos();
+
+    /* Greenwich hour angle of the axis. */
+    let ut = tt2 - deltat / DAYSEC;
+    let mu = anp(gst06(tt1, ut, tt1, tt2, &rnpb) - a);
+
+    Ok(Bessel {
+        x,
+        y,
+        d,
+        mu,
+        l1,
+        l2,
+        tanf1,
+        tanf2,
+    })
+}
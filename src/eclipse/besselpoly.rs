@@ -0,0 +1,137 @@
+use super::{Bessel, bessel};
+use crate::eph::Ephemeris;
+use crate::vm::{anp, anpm};
+
+/// Besselian elements as polynomials in time
+///
+/// Each polynomial gives the element as a function of t, the time in
+/// hours from the reference epoch, as c[0] + c[1]*t + c[2]*t^2 +
+/// c[3]*t^3.  The cone angles are taken as constant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BesselPoly {
+    pub tt: (f64, f64), // reference epoch t0 (TT, 2-part Julian Date)
+    pub deltat: f64,    // TT-UT1 (seconds)
+    pub x: [f64; 4],    // x
+    pub y: [f64; 4],    // y
+    pub d: [f64; 4],    // d (radians)
+    pub mu: [f64; 4],   // mu (radians)
+    pub l1: [f64; 4],   // l1
+    pub l2: [f64; 4],   // l2
+    pub tanf1: f64,     // tan f1
+    pub tanf2: f64,     // tan f2
+}
+
+/// Value of a cubic at t.
+fn cubic(c: &[f64; 4], t: f64) -> f64 {
+    ((c[3] * t + c[2]) * t + c[1]) * t + c[0]
+}
+
+/// Derivative of a cubic at t.
+fn dcubic(c: &[f64; 4], t: f64) -> f64 {
+    (3.0 * c[3] * t + 2.0 * c[2]) * t + c[1]
+}
+
+/// Taylor coefficients from values at t = -2, -1, 0, +1, +2.
+fn taylor(f: [f64; 5]) -> [f64; 4] {
+    let [fm2, fm1, f0, fp1, fp2] = f;
+    [
+        f0,
+        (8.0 * (fp1 - fm1) - (fp2 - fm2)) / 12.0,
+        (16.0 * (fp1 + fm1) - (fp2 + fm2) - 30.0 * f0) / 24.0,
+        (fp2 - 2.0 * fp1 + 2.0 * fm1 - fm2) / 12.0,
+    ]
+}
+
+impl BesselPoly {
+    ///  Elements at time t (hours from the reference epoch)
+    pub fn at(&self, t: f64) -> Bessel {
+        Bessel {
+            x: cubic(&self.x, t),
+            y: cubic(&self.y, t),
+            d: cubic(&self.d, t),
+            mu: anp(cubic(&self.mu, t)),
+            l1: cubic(&self.l1, t),
+            l2: cubic(&self.l2, t),
+            tanf1: self.tanf1,
+            tanf2: self.tanf2,
+        }
+    }
+
+    ///  Rates of change of the elements at time t (per hour)
+    ///
+    ///  The tanf1 and tanf2 fields of the result are zero.
+    pub fn rates(&self, t: f64) -> Bessel {
+        Bessel {
+            x: dcubic(&self.x, t),
+            y: dcubic(&self.y, t),
+            d: dcubic(&self.d, t),
+            mu: dcubic(&self.mu, t),
+            l1: dcubic(&self.l1, t),
+            l2: dcubic(&self.l2, t),
+            tanf1: 0.0,
+            tanf2: 0.0,
+        }
+    }
+}
+
+///  Polynomial Besselian elements of a solar eclipse
+///
+///  Given:
+///  ```text
+///     tt1    f64        TT as a 2-part...
+///     tt2    f64        ...Julian Date (reference epoch, Note 1)
+///     deltat f64        TT-UT1 (seconds)
+///     eph    Ephemeris  source of the Sun and Moon, as for `bessel`
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(BesselPoly)   cubic polynomials in t (hours from tt1+tt2)
+///     Err(j) j   i32   status from eph
+///  ```
+///  Notes:
+///
+///  1) The reference epoch is normally the whole hour nearest to
+///     greatest eclipse, and the polynomials are then adequate for
+///     about 3 hours either side.
+///
+///  2) The coefficients are the Taylor coefficients at the reference
+///     epoch, from the elements computed by `bessel` at hourly
+///     intervals from -2h to +2h.  tan f1 and tan f2 are those at the
+///     reference epoch.
+///
+///  Called:
+///  ```text
+///     bessel       Besselian elements at a given instant
+///     iauAnpm      normalize angle into range +/- pi
+///  ```
+pub fn besselpoly<E: Ephemeris>(
+    tt1: f64,
+    tt2: f64,
+    deltat: f64,
+    eph: &E,
+) -> Result<BesselPoly, i32> {
+    let mut b = Vec::with_capacity(5);
+    for i in -2..=2 {
+        b.push(bessel(tt1, tt2 + i as f64 / 24.0, deltat, eph)?);
+    }
+    let fit =
+        |f: &dyn Fn(&Bessel) -> f64| taylor([f(&b[0]), f(&b[1]), f(&b[2]), f(&b[3]), f(&b[4])]);
+
+    /* Unwrap mu about its value at the reference epoch. */
+    let mu0 = b[2].mu;
+    let mut mu = fit(&|e| anpm(e.mu - mu0));
+    mu[0] = mu0;
+
+    Ok(BesselPoly {
+        tt: (tt1, tt2),
+        deltat,
+        x: fit(&|e| e.x),
+        y: fit(&|e| e.y),
+        d: fit(&|e| e.d),
+        mu,
+        l1: fit(&|e| e.l1),
+        l2: fit(&|e| e.l2),
+        tanf1: b[2].tanf1,
+        tanf2: b[2].tanf2,
+    })
+}
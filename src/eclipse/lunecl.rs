@@ -0,0 +1,268 @@
+use super::bessel::{ERAD, SRAD};
+use super::sunmoon;
+use crate::consts::{AULT, DAU, DAYSEC, DD2R};
+use crate::eph::Ephemeris;
+use crate::vm::{pdp, pm, pmp, pn, sxp};
+
+/// Maximum number of iterations.
+const MAXIT: usize = 20;
+
+/// Convergence tolerance (days, about 1 ms).
+const TOL: f64 = 1e-8;
+
+/// Step for numerical derivatives (days).
+const DT: f64 = 1e-4;
+
+/// Search step (days).
+const STEP: f64 = 0.5;
+
+/// Moon radius (Earth radii).
+const KM: f64 = 0.2725076;
+
+/// Factor for the Earth's oblateness (radius at latitude 45 deg).
+const OBL: f64 = 0.998340;
+
+/// Type of a lunar eclipse
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LunarKind {
+    Penumbral,
+    Partial,
+    Total,
+}
+
+/// Convention for enlarging the Earth's shadow
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shadow {
+    Danjon,    // Earth's radius enlarged by 1% (Danjon 1951)
+    Chauvenet, // shadow radii enlarged by 2% (Chauvenet 1891)
+}
+
+/// Circumstances of a lunar eclipse
+///
+/// Times are TT as a 2-part Julian Date.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LunarEclipse {
+    pub kind: LunarKind,        // type of eclipse
+    pub p1: (f64, f64),         // penumbral eclipse begins
+    pub u1: Option<(f64, f64)>, // partial eclipse begins
+    pub u2: Option<(f64, f64)>, // total eclipse begins
+    pub max: (f64, f64),        // greatest eclipse
+    pub u3: Option<(f64, f64)>, // total eclipse ends
+    pub u4: Option<(f64, f64)>, // partial eclipse ends
+    pub p4: (f64, f64),         // penumbral eclipse ends
+    pub umag: f64,              // umbral magnitude at greatest eclipse
+    pub pmag: f64,              // penumbral magnitude at greatest eclipse
+    pub sep: f64,               // Moon's distance from the shadow axis (radians)
+}
+
+/// Geocentric geometry at one instant.
+struct Geom {
+    d: [f64; 3], // Moon direction minus antisolar direction
+    rp: f64,     // penumbral radius (radians)
+    ru: f64,     // umbral radius (radians)
+    sm: f64,     // Moon's semidiameter (radians)
+}
+
+impl Geom {
+    /// Angular distance of the Moon from the shadow axis.
+    fn sep(&self) -> f64 {
+        2.0 * (pm(self.d) / 2.0).asin()
+    }
+}
+
+///  Lunar eclipses in a given interval
+///
+///  Given:
+///  ```text
+///     tt1    f64        TT as a 2-part...
+///     tt2    f64        ...Julian Date (start of search)
+///     days   f64        length of the search interval (days)
+///     shadow Shadow     shadow enlargement convention (Note 3)
+///     eph    Ephemeris  source of the Sun and Moon (Note 1)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(eclipses)   Vec<LunarEclipse>   eclipses whose greatest phase
+///                                        falls in the interval, in order
+///     Err(j) j       i32                 status from eph
+///  ```
+///  Notes:
+///
+///  1) The geometric geocentric positions are obtained from eph using
+///     `sunmoon`, as for `bessel`.  Light time and aberration are
+///     allowed for by evaluating each body at its retarded time.
+///
+///  2) The Moon's geocentric distance from the antisolar point is
+///     compared with the angular radii of the Earth's penumbra and
+///     umbra at the Moon's distance,
+///  ```text
+///        rp = F * (E * pi_m + pi_s + s_s)
+///        ru = F * (E * pi_m + pi_s - s_s)
+///  ```
+///     where pi_m and pi_s are the horizontal parallaxes of the Moon
+///     and Sun, s_s is the Sun's semidiameter and E = 0.998340 allows
+///     for the oblateness of the Earth.
+///
+///  3) The shadow is enlarged to allow for the Earth's atmosphere.
+///     Chauvenet's convention, used in the Astronomical Almanac up to
+///     2005, takes F = 1.02 for both terms.  Danjon's, used since,
+///     enlarges only the Earth's radius:  E * pi_m is multiplied by
+///     1.01 and F = 1.  The umbral magnitudes differ by about 0.005.
+///
+///  4) The magnitudes are the fraction of the Moon's diameter inside
+///     the penumbra or umbra at greatest eclipse.  The contacts P1, P4
+///     (penumbra), U1, U4 (umbra, external) and U2, U3 (umbra,
+///     internal) are when the Moon's limb touches the shadow edge;
+///     those that do not occur are None.
+///
+///  5) Greatest eclipse is when the Moon is closest to the shadow
+///     axis.  The search samples twice a day and refines each minimum
+///     by Newton iteration; times are located to about 1 ms.
+///
+///  References:
+///
+///     Explanatory Supplement to the Astronomical Almanac,
+///     P. Kenneth Seidelmann (ed), University Science Books (1992),
+///     Section 8.4.
+///
+///     Meeus, J., Astronomical Algorithms, 2nd edition,
+///     Willmann-Bell (1998), Chapter 54.
+pub fn lunecl<E: Ephemeris>(
+    tt1: f64,
+    tt2: f64,
+    days: f64,
+    shadow: Shadow,
+    eph: &E,
+) -> Result<Vec<LunarEclipse>, i32> {
+    let geom = |x: f64| -> Result<Geom, i32> {
+        /* Apparent Sun and Moon, allowing for light time. */
+        let (mut ps, mut pm0) = sunmoon(eph, tt1, tt2 + x)?;
+        for _ in 0..2 {
+            ps = sunmoon(eph, tt1, tt2 + x - pm(ps) * AULT / DAYSEC)?.0;
+            pm0 = sunmoon(eph, tt1, tt2 + x - pm(pm0) * AULT / DAYSEC)?.1;
+        }
+        let (rs, us) = pn(&ps);
+        let (rm, um) = pn(&pm0);
+
+        /* Parallaxes and semidiameters. */
+        let pim = (ERAD / DAU / rm).asin();
+        let pis = (ERAD / DAU / rs).asin();
+        let ss = (SRAD / rs).asin();
+        let sm = (KM * ERAD / DAU / rm).asin();
+
+        /* Shadow radii. */
+        let (rp, ru) = match shadow {
+            Shadow::Chauvenet => (1.02 * (OBL * pim + pis + ss), 1.02 * (OBL * pim + pis - ss)),
+            Shadow::Danjon => (1.01 * OBL * pim + pis + ss, 1.01 * OBL * pim + pis - ss),
+        };
+
+        Ok(Geom {
+            d: pmp(&um, &sxp(-1.0, &us)),
+            rp,
+            ru,
+            sm,
+        })
+    };
+
+    /* Time when the Moon is nearest the axis, starting from x. */
+    let nearest = |mut x: f64| -> Result<f64, i32> {
+        for _ in 0..MAXIT {
+            let g = geom(x)?;
+            let (g0, g1) = (geom(x - DT)?, geom(x + DT)?);
+            let dd = sxp(0.5 / DT, &pmp(&g1.d, &g0.d));
+            let dx = pdp(&g.d, &dd) / pdp(&dd, &dd);
+            x -= dx;
+            if dx.abs() < TOL {
+                break;
+            }
+        }
+        Ok(x)
+    };
+
+    /* Sample the interval. */
+    let n = (days.max(0.0) / STEP).ceil() as usize;
+    let mut xs = Vec::with_capacity(n + 3);
+    let mut ss = Vec::with_capacity(n + 3);
+    for i in 0..n + 3 {
+        let x = (i as f64 - 1.0) * STEP;
+        xs.push(x);
+        ss.push(pm(geom(x)?.d));
+    }
+
+    let mut eclipses = Vec::new();
+    for i in 1..xs.len() - 1 {
+        /* Local minimum near opposition. */
+        if !(ss[i] < ss[i - 1] && ss[i] <= ss[i + 1] && ss[i] < 5.0 * DD2R) {
+            continue;
+        }
+        let xm = nearest(xs[i])?;
+        if !(0.0..days).contains(&xm) {
+            continue;
+        }
+        let g = geom(xm)?;
+        let sep = g.sep();
+        let pmag = (g.rp + g.sm - sep) / (2.0 * g.sm);
+        if pmag <= 0.0 {
+            continue;
+        }
+        let umag = (g.ru + g.sm - sep) / (2.0 * g.sm);
+
+        /* Rate of motion relative to the axis (radians/day). */
+        let (g0, g1) = (geom(xm - DT)?, geom(xm + DT)?);
+        let rate = pm(pmp(&g1.d, &g0.d)) / (2.0 * DT);
+
+        /* Contact when the separation equals the radius given by f. */
+        let contact = |f: &dyn Fn(&Geom) -> f64, side: f64| -> Result<(f64, f64), i32> {
+            let mut x = xm + side * (f(&g).powi(2) - sep * sep).max(0.0).sqrt() / rate;
+            for _ in 0..MAXIT {
+                let h = |x: f64| geom(x).map(|g| g.sep() - f(&g));
+                let dh = (h(x + DT)? - h(x - DT)?) / (2.0 * DT);
+                let dx = h(x)? / dh;
+                x -= dx;
+                if dx.abs() < TOL {
+                    break;
+                }
+            }
+            Ok((tt1, tt2 + x))
+        };
+        let pen = |g: &Geom| g.rp + g.sm;
+        let uext = |g: &Geom| g.ru + g.sm;
+        let uint = |g: &Geom| g.ru - g.sm;
+
+        let (kind, u1, u4, u2, u3) = if umag >= 1.0 {
+            (
+                LunarKind::Total,
+                Some(contact(&uext, -1.0)?),
+                Some(contact(&uext, 1.0)?),
+                Some(contact(&uint, -1.0)?),
+                Some(contact(&uint, 1.0)?),
+            )
+        } else if umag > 0.0 {
+            (
+                LunarKind::Partial,
+                Some(contact(&uext, -1.0)?),
+                Some(contact(&uext, 1.0)?),
+                None,
+                None,
+            )
+        } else {
+            (LunarKind::Penumbral, None, None, None, None)
+        };
+
+        eclipses.push(LunarEclipse {
+            kind,
+            p1: contact(&pen, -1.0)?,
+            u1,
+            u2,
+            max: (tt1, tt2 + xm),
+            u3,
+            u4,
+            p4: contact(&pen, 1.0)?,
+            umag,
+            pmag,
+            sep,
+        });
+    }
+
+    Ok(eclipses)
+}
@@ -0,0 +1,16 @@
+//! Eclipses
+
+mod bessel;
+pub use bessel::*;
+
+mod besselpoly;
+pub use besselpoly::*;
+
+mod lunecl;
+pub use lunecl::*;
+
+mod solarlocal;
+pub use solarlocal::*;
+
+mod sunmoon;
+pub use sunmoon::*;
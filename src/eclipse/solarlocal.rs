@@ -0,0 +1,207 @@
+use super::BesselPoly;
+use super::bessel::ERAD;
+use crate::consts::DPI;
+use crate::site::Observer;
+
+/// Maximum number of iterations.
+const MAXIT: usize = 20;
+
+/// Convergence tolerance (hours, about 0.4 ms).
+const TOL: f64 = 1e-7;
+
+/// Step for numerical derivatives (hours).
+const DT: f64 = 1e-4;
+
+/// Type of a solar eclipse
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolarKind {
+    Partial,
+    Annular,
+    Total,
+}
+
+/// Local circumstances of a solar eclipse
+///
+/// Times are TT as a 2-part Julian Date.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolarLocal {
+    pub kind: SolarKind,        // type of eclipse at the site
+    pub c1: (f64, f64),         // first contact (partial eclipse begins)
+    pub c2: Option<(f64, f64)>, // second contact (totality/annularity begins)
+    pub max: (f64, f64),        // maximum eclipse
+    pub c3: Option<(f64, f64)>, // third contact (totality/annularity ends)
+    pub c4: (f64, f64),         // fourth contact (partial eclipse ends)
+    pub mag: f64,               // magnitude at maximum
+    pub obsc: f64,              // obscuration at maximum
+    pub alt: f64,               // altitude of the Sun at maximum (radians)
+}
+
+/// Site geometry in the fundamental system at time t (hours).
+struct Geom {
+    u: f64,
+    v: f64,
+    l1: f64,
+    l2: f64,
+    zeta: f64,
+}
+
+///  Local circumstances of a solar eclipse
+///
+///  Given:
+///  ```text
+///     p      BesselPoly   Besselian elements (see `besselpoly`)
+///     obs    Observer     observing site
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(Some(SolarLocal))   circumstances at the site (Notes 1-5)
+///     Ok(None)               no eclipse visible from the site
+///     Err(j) j   i32         -1 = illegal ellipsoid identifier
+///  ```
+///  Notes:
+///
+///  1) The site's geocentric position comes from iauGd2gc (via
+///     Observer::geocentric), and is expressed in units of the Earth's
+///     equatorial radius in the fundamental system.  Polar motion is
+///     neglected.
+///
+///  2) Maximum eclipse is when the distance m of the site from the
+///     shadow axis is least.  The contacts are when m equals the radius
+///     of the penumbra (first and fourth) or of the umbra (second and
+///     third) on the plane through the site parallel to the
+///     fundamental plane.  They are found by Newton iteration, starting
+///     from the maximum.
+///
+///  3) The magnitude is the fraction of the Sun's diameter covered by
+///     the Moon, (L1-m)/(L1+L2), which exceeds 1 for a total eclipse.
+///     The obscuration is the fraction of the Sun's disk area covered.
+///
+///  4) The contacts are reported whether or not the Sun is above the
+///     horizon at the time.  The altitude given for the maximum is
+///     geometric and geocentric:  it neglects refraction and the
+///     difference between the geodetic and geocentric vertical (up to
+///     some 11 arcminutes).
+///
+///  5) The time is TT; UT1 is TT - deltat.
+///
+///  Reference:
+///
+///     Explanatory Supplement to the Astronomical Almanac,
+///     P. Kenneth Seidelmann (ed), University Science Books (1992),
+///     Section 8.3.
+///
+///  Called:
+///  ```text
+///     iauGd2gc     geodetic to geocentric transformation
+///  ```
+pub fn solarlocal(p: &BesselPoly, obs: &Observer) -> Result<Option<SolarLocal>, i32> {
+    /* Geocentric site in Earth radii. */
+    let r = obs.geocentric()?;
+    let rc = r[0].hypot(r[1]) / ERAD;
+    let rs = r[2] / ERAD;
+    let lam = r[1].atan2(r[0]);
+
+    let geom = |t: f64| {
+        let e = p.at(t);
+        let h = e.mu + lam;
+        let xi = rc * h.sin();
+        let eta = rs * e.d.cos() - rc * h.cos() * e.d.sin();
+        let zeta = rs * e.d.sin() + rc * h.cos() * e.d.cos();
+        Geom {
+            u: e.x - xi,
+            v: e.y - eta,
+            l1: e.l1 - zeta * e.tanf1,
+            l2: e.l2 - zeta * e.tanf2,
+            zeta,
+        }
+    };
+    let m = |g: &Geom| g.u.hypot(g.v);
+
+    /* Maximum:  u*u' + v*v' = 0. */
+    let mut tm = 0.0;
+    for _ in 0..MAXIT {
+        let g = geom(tm);
+        let (g0, g1) = (geom(tm - DT), geom(tm + DT));
+        let du = (g1.u - g0.u) / (2.0 * DT);
+        let dv = (g1.v - g0.v) / (2.0 * DT);
+        let dt = (g.u * du + g.v * dv) / (du * du + dv * dv);
+        tm -= dt;
+        if dt.abs() < TOL {
+            break;
+        }
+    }
+    let g = geom(tm);
+    let mmin = m(&g);
+    if mmin >= g.l1 {
+        return Ok(None);
+    }
+
+    /* Speed of the shadow relative to the site. */
+    let (g0, g1) = (geom(tm - DT), geom(tm + DT));
+    let n = (g1.u - g0.u).hypot(g1.v - g0.v) / (2.0 * DT);
+
+    /* Contact where m equals the radius given by f, on the given side. */
+    let contact = |f: &dyn Fn(&Geom) -> f64, side: f64| {
+        let mut t = tm + side * (f(&g).powi(2) - mmin * mmin).max(0.0).sqrt() / n;
+        for _ in 0..MAXIT {
+            let h = |t: f64| {
+                let g = geom(t);
+                m(&g) - f(&g)
+            };
+            let dh = (h(t + DT) - h(t - DT)) / (2.0 * DT);
+            let dt = h(t) / dh;
+            t -= dt;
+            if dt.abs() < TOL {
+                break;
+            }
+        }
+        t
+    };
+    let tt = |t: f64| (p.tt.0, p.tt.1 + t / 24.0);
+
+    let c1 = contact(&|g| g.l1, -1.0);
+    let c4 = contact(&|g| g.l1, 1.0);
+    let (kind, c2, c3) = if mmin < g.l2.abs() {
+        let kind = if g.l2 < 0.0 {
+            SolarKind::Total
+        } else {
+            SolarKind::Annular
+        };
+        let c2 = contact(&|g| g.l2.abs(), -1.0);
+        let c3 = contact(&|g| g.l2.abs(), 1.0);
+        (kind, Some(tt(c2)), Some(tt(c3)))
+    } else {
+        (SolarKind::Partial, None, None)
+    };
+
+    /* Magnitude and obscuration. */
+    let mag = (g.l1 - mmin) / (g.l1 + g.l2);
+    let obsc = obscur((g.l1 + g.l2) / 2.0, (g.l1 - g.l2) / 2.0, mmin);
+
+    Ok(Some(SolarLocal {
+        kind,
+        c1: tt(c1),
+        c2,
+        max: tt(tm),
+        c3,
+        c4: tt(c4),
+        mag,
+        obsc,
+        alt: (g.zeta / rc.hypot(rs)).asin(),
+    }))
+}
+
+/// Fraction of a disk of radius rs covered by one of radius rm at
+/// distance s.
+pub(crate) fn obscur(rs: f64, rm: f64, s: f64) -> f64 {
+    if s >= rs + rm {
+        0.0
+    } else if s <= (rs - rm).abs() {
+        (rm * rm).min(rs * rs) / (rs * rs)
+    } else {
+        let a1 = ((s * s + rm * rm - rs * rs) / (2.0 * s * rm)).acos();
+        let a2 = ((s * s + rs * rs - rm * rm) / (2.0 * s * rs)).acos();
+        let k = ((-s + rm + rs) * (s + rm - rs) * (s - rm + rs) * (s + rm + rs)).sqrt();
+        (rm * rm * a1 + rs * rs * a2 - 0.5 * k) / (DPI * rs * rs)
+    }
+}
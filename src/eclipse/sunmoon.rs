@@ -0,0 +1,39 @@
+use crate::eph::{Ephemeris, Target};
+use crate::vm::{pmp, sxp};
+
+///  Geocentric positions of the Sun and Moon
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris  source of the Earth and Moon ephemerides
+///     tt1    f64     TT as a 2-part...
+///     tt2    f64     ...Julian Date (Note 1)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok((ps, pm))
+///            ps   [f64; 3]   geocentric Sun (GCRS, au)
+///            pm   [f64; 3]   geocentric Moon (GCRS, au)
+///     Err(j) j    i32        status from eph
+///  ```
+///  Notes:
+///
+///  1) TT is used in place of TDB.  The positions are geometric.
+///
+///  2) This is how the eclipse functions obtain their positions.  The
+///     Moon98 ephemeris (Sun from iauEpv00, Moon from iauMoon98) is
+///     the simplest choice, but iauMoon98 is accurate to some 10
+///     arcseconds, which is marginal for eclipse work:  it corresponds
+///     to an error of about 20 km in the position of the shadow on the
+///     Earth, and of a few seconds in contact times.
+///
+///  Called:
+///  ```text
+///     Ephemeris::earth   Earth position and velocity
+///     Ephemeris::pvb     Moon position and velocity
+///  ```
+pub fn sunmoon<E: Ephemeris>(eph: &E, tt1: f64, tt2: f64) -> Result<([f64; 3], [f64; 3]), i32> {
+    let (eh, eb) = eph.earth(tt1, tt2)?;
+    let mb = eph.pvb(Target::Moon, tt1, tt2)?;
+    Ok((sxp(-1.0, &eh[0]), pmp(&mb[0], &eb[0])))
+}
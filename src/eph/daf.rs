@@ -0,0 +1,192 @@
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Length of a DAF physical record (bytes).
+const RECLEN: usize = 1024;
+
+/// Maximum number of summary records followed (guards against loops).
+const MAXREC: usize = 100_000;
+
+/// Descriptor of one array in a DAF
+#[derive(Debug, Clone, PartialEq)]
+pub struct DafSummary {
+    pub name: String, // array name
+    pub dc: Vec<f64>, // double precision components
+    pub ic: Vec<i32>, // integer components (the last two are the
+                      // initial and final addresses of the array)
+}
+
+///  Double precision Array File
+///
+///  The NAIF container format used by SPK (ephemeris) and binary PCK
+///  (orientation) kernels.  The file record and the array summaries are
+///  read when the file is opened; the arrays themselves are read on
+///  demand, so that large kernels need not be held in memory.
+///
+///  Notes:
+///
+///  1) Both IEEE byte orders ("LTL-IEEE" and "BIG-IEEE") are supported.
+///     Files that predate the format identification word are assumed to
+///     be in whichever order gives a sensible number of summary
+///     components.
+///
+///  2) Addresses are 1-based double precision word numbers, as stored
+///     in the summaries.
+///
+///  Reference:
+///
+///     NAIF, DAF Required Reading, NASA/JPL.
+#[derive(Debug)]
+pub struct Daf<R> {
+    reader: R,
+    big: bool,
+    len: u64,
+    pub idword: String,             // identification word, e.g. "DAF/SPK"
+    pub ifname: String,             // internal file name
+    pub nd: usize,                  // number of double components
+    pub ni: usize,                  // number of integer components
+    pub summaries: Vec<DafSummary>, // array summaries, in file order
+}
+
+impl Daf<BufReader<File>> {
+    /// Open a DAF file.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Daf::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read + Seek> Daf<R> {
+    /// Read the file record and the array summaries.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut rec = [0u8; RECLEN];
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_exact(&mut rec)?;
+        let len = reader.seek(SeekFrom::End(0))?;
+
+        let idword = text(&rec[0..8]);
+        if !idword.starts_with("DAF/") && idword != "NAIF/DAF" {
+            return Err(invalid("not a DAF file"));
+        }
+
+        /* Byte order. */
+        let big = match &rec[88..96] {
+            b"BIG-IEEE" => true,
+            b"LTL-IEEE" => false,
+            _ => !(1..=124).contains(&i32::from_le_bytes(word4(&rec, 8))),
+        };
+        let int = |i: usize| int4(&rec, i, big);
+
+        let nd = usize::try_from(int(8)).map_err(|_| invalid("bad ND"))?;
+        let ni = usize::try_from(int(12)).map_err(|_| invalid("bad NI"))?;
+        if nd > 124 || !(2..=250).contains(&ni) {
+            return Err(invalid("bad ND or NI"));
+        }
+        let ifname = text(&rec[16..76]);
+        let fward = int(76);
+
+        let mut daf = Daf {
+            reader,
+            big,
+            len,
+            idword,
+            ifname,
+            nd,
+            ni,
+            summaries: Vec::new(),
+        };
+
+        /* Summary size in doubles, and name size in characters. */
+        let ss = nd + ni.div_ceil(2);
+        let nc = 8 * ss;
+
+        /* Follow the chain of summary records. */
+        let mut recno = fward;
+        let mut count = 0;
+        while recno > 0 {
+            count += 1;
+            if count > MAXREC {
+                return Err(invalid("summary records form a loop"));
+            }
+            let sum = daf.record(recno as usize)?;
+            let names = daf.record(recno as usize + 1)?;
+            let next = daf.dbl(&sum, 0);
+            let nsum = daf.dbl(&sum, 16) as usize;
+            if nsum.checked_mul(ss).is_none_or(|n| n > RECLEN / 8 - 3) {
+                return Err(invalid("bad summary record"));
+            }
+            for i in 0..nsum {
+                let off = (3 + i * ss) * 8;
+                let dc = (0..nd).map(|k| daf.dbl(&sum, off + 8 * k)).collect();
+                let ic = (0..ni)
+                    .map(|k| int4(&sum, off + 8 * nd + 4 * k, big))
+                    .collect();
+                let name = text(&names[i * nc..(i + 1) * nc]);
+                daf.summaries.push(DafSummary { name, dc, ic });
+            }
+            recno = next as i32;
+        }
+
+        Ok(daf)
+    }
+
+    /// Read the double precision words at addresses begin to end
+    /// inclusive.
+    pub fn read(&mut self, begin: usize, end: usize) -> io::Result<Vec<f64>> {
+        if begin == 0 || end < begin {
+            return Err(invalid("bad address range"));
+        }
+        if (end as u64).checked_mul(8).is_none_or(|n| n > self.len) {
+            return Err(invalid("address beyond end of file"));
+        }
+        let mut buf = vec![0u8; 8 * (end - begin + 1)];
+        self.reader.seek(SeekFrom::Start(8 * (begin as u64 - 1)))?;
+        self.reader.read_exact(&mut buf)?;
+        Ok(buf.chunks_exact(8).map(|b| self.dbl(b, 0)).collect())
+    }
+
+    /// Read physical record n (1-based).
+    fn record(&mut self, n: usize) -> io::Result<[u8; RECLEN]> {
+        let mut rec = [0u8; RECLEN];
+        self.reader
+            .seek(SeekFrom::Start(((n - 1) * RECLEN) as u64))?;
+        self.reader.read_exact(&mut rec)?;
+        Ok(rec)
+    }
+
+    /// Double at byte offset i.
+    fn dbl(&self, b: &[u8], i: usize) -> f64 {
+        let mut w = [0u8; 8];
+        w.copy_from_slice(&b[i..i + 8]);
+        if self.big {
+            f64::from_be_bytes(w)
+        } else {
+            f64::from_le_bytes(w)
+        }
+    }
+}
+
+/// Four bytes at offset i.
+pub(crate) fn word4(b: &[u8], i: usize) -> [u8; 4] {
+    [b[i], b[i + 1], b[i + 2], b[i + 3]]
+}
+
+/// Integer at byte offset i.
+fn int4(b: &[u8], i: usize, big: bool) -> i32 {
+    if big {
+        i32::from_be_bytes(word4(b, i))
+    } else {
+        i32::from_le_bytes(word4(b, i))
+    }
+}
+
+/// Text field, without trailing blanks and nulls.
+pub(crate) fn text(b: &[u8]) -> String {
+    String::from_utf8_lossy(b)
+        .trim_end_matches([' ', '\0'])
+        .to_string()
+}
+
+pub(crate) fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
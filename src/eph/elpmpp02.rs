@@ -0,0 +1,622 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use super::daf::invalid;
+use super::jplasc::number;
+use super::{Ephemeris, Epv00, Target};
+use crate::consts::{D2PI, DAS2R, DAU, DJ00, DJC, DPI};
+use crate::coords::ecm06;
+use crate::vm::{pvppv, rxpv, rxr, s2pv, tr};
+
+/// Number of arguments of a perturbation term (Delaunay, planets, zeta).
+const NARG: usize = 13;
+
+/// Ratio of the DE405 to the ELP mean distance.
+const RA0: f64 = 384747.961370173 / 384747.980674318;
+
+/// Semi-major axis used to convert the truncation level to km.
+const A0: f64 = 384747.961370173;
+
+/// Sensitivities of the mean motions of W2, W3 to the constants
+/// (Chapront & Francou 2003, Table 2).
+const BP: [[f64; 2]; 5] = [
+    [0.311079095, -0.103837907],
+    [-0.004482398, 0.000668287],
+    [-0.001102485, -0.001298072],
+    [0.001056062, -0.000178028],
+    [0.000050928, -0.000037342],
+];
+
+/// Laskar's series for the precession of the ecliptic, P and Q.
+const LASKP: [f64; 5] = [
+    0.10180391e-4,
+    0.47020439e-6,
+    -0.5417367e-9,
+    -0.2507948e-11,
+    0.463486e-14,
+];
+const LASKQ: [f64; 5] = [
+    -0.113469002e-3,
+    0.12372674e-6,
+    0.1265417e-8,
+    -0.1371808e-11,
+    -0.320334e-14,
+];
+
+/// Ratio of the mean motions of the Sun and the Moon, and alpha.
+const AM: f64 = 0.074801329;
+const ALPHA: f64 = 0.002571881;
+
+/// The parameter sets of ELP/MPP02
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElpParams {
+    Llr,   // fitted to lunar laser ranging observations
+    De405, // fitted to the JPL DE405 ephemeris
+}
+
+/// Corrections to the constants (arcsec, arcsec/cy^n).
+#[derive(Debug, Clone, Copy)]
+struct Corrections {
+    dw1: [f64; 5],
+    dw2: [f64; 4],
+    dw3: [f64; 4],
+    deart: [f64; 2],
+    dperi: f64,
+    dgam: f64,
+    de: f64,
+    dep: f64,
+}
+
+impl ElpParams {
+    fn corrections(self) -> Corrections {
+        match self {
+            ElpParams::Llr => Corrections {
+                dw1: [-0.10525, -0.32311, -0.03794, 0.0, 0.0],
+                dw2: [0.16826, 0.08017, 0.0, 0.0],
+                dw3: [-0.10760, -0.04317, 0.0, 0.0],
+                deart: [-0.04012, 0.01442],
+                dperi: -0.04854,
+                dgam: 0.00069,
+                de: 0.00005,
+                dep: 0.00226,
+            },
+            ElpParams::De405 => Corrections {
+                dw1: [-0.07008, -0.35106, -0.03743, -0.00018865, -0.00001024],
+                dw2: [0.20794, 0.08017, 0.00470602, -0.00025213],
+                dw3: [-0.07215, -0.04317, -0.00261070, -0.00010712],
+                deart: [-0.00033, 0.00732],
+                dperi: -0.00749,
+                dgam: 0.00085,
+                de: -0.00006,
+                dep: 0.00224,
+            },
+        }
+    }
+}
+
+/// A perturbation term:  amplitude, phase and argument multipliers.
+#[derive(Debug, Clone, Copy)]
+struct Pert {
+    a: f64,
+    phi: f64,
+    ifi: [i32; NARG],
+}
+
+///  ELP/MPP02 lunar theory
+///
+///  The semi-analytical lunar solution of Chapront & Francou, evaluated
+///  from the six series files distributed by the IMCCE:  ELP_MAIN.S1-S3
+///  (main problem) and ELP_PERT.S1-S3 (perturbations), for longitude,
+///  latitude and distance respectively.  The series are held in memory,
+///  so that files embedded with include_str! can be used through
+///  read_main and read_pert.
+///
+///  The series are not distributed with this crate:  they must be
+///  obtained from the IMCCE and given to load, or to read_main and
+///  read_pert, before the Moon can be computed.  Until the main
+///  problem is loaded, pv and pvb(Target::Moon) return -2.
+///
+///  Notes:
+///
+///  1) The constants of the theory are either those fitted to lunar
+///     laser ranging (ElpParams::Llr) or those fitted to DE405
+///     (ElpParams::De405).  The amplitudes of the main problem depend on
+///     the choice, which is made when the theory is created.
+///
+///  2) The series may be truncated by setting tol (arcsec):  terms with
+///     smaller amplitudes are omitted, distance terms being compared
+///     with the same angle at the mean lunar distance.
+///
+///  3) The time argument is TDB (TT can be used).  The accuracy of the
+///     complete solution is discussed by Chapront & Francou (2003);
+///     near J2000.0 it is far better than that of iauMoon98.
+///
+///  4) The longitude is measured in the ecliptic of date from the
+///     departure point, not the equinox.  pv refers the coordinates to
+///     the inertial ecliptic of J2000.0 using Laskar's precession of
+///     the ecliptic, as in ELPMPP02, and then to the GCRS using the
+///     IAU 2006 obliquity and frame bias (see ecm06), so that it can be
+///     used in place of iauMoon98.  The rotation of the ecliptic is
+///     neglected in the velocity.
+///
+///  Reference:
+///
+///     Chapront, J. & Francou, G., 2003, Astron.Astrophys., 404, 735.
+///
+///     Chapront, J. & Francou, G., The lunar theory ELP revisited.
+///     Introduction of new planetary perturbations, and the Fortran
+///     program ELPMPP02 (IMCCE, 2002).
+#[derive(Debug, Clone)]
+pub struct Elpmpp02 {
+    pub tol: f64, // truncation level (arcsec, 0 = complete)
+    params: ElpParams,
+    w: [[f64; 5]; 3],   // W1, W2, W3 (rad, t in centuries)
+    del: [[f64; 5]; 4], // Delaunay arguments D, l', l, F
+    p: [[f64; 2]; 8],   // planetary mean longitudes Me ... Ne
+    zeta: [f64; 2],     // W1 plus precession
+    cor: [f64; 5],      // delnu, dele, delg, delnp, delep
+    main: [Vec<([i32; 4], f64)>; 3],
+    pert: [Vec<Vec<Pert>>; 3],
+}
+
+impl Elpmpp02 {
+    /// The theory with a given parameter set and no series loaded.
+    pub fn new(params: ElpParams) -> Self {
+        let c = params.corrections();
+        let sec = DAS2R;
+        let dms = |d: f64, m: f64, s: f64| (d + m / 60.0 + s / 3600.0) * (DPI / 180.0);
+
+        /* Mean arguments of the Moon:  W1, W2 (perigee), W3 (node). */
+        let mut w = [
+            [
+                dms(-142.0, 18.0, 59.95571 + c.dw1[0]),
+                (1732559343.73604 + c.dw1[1]) * sec,
+                (-6.8084 + c.dw1[2]) * sec,
+                (0.006604 + c.dw1[3]) * sec,
+                (-3.169e-5 + c.dw1[4]) * sec,
+            ],
+            [
+                dms(83.0, 21.0, 11.67475 + c.dw2[0]),
+                (14643420.3171 + c.dw2[1]) * sec,
+                (-38.2631 + c.dw2[2]) * sec,
+                (-0.045047 + c.dw2[3]) * sec,
+                0.00021301 * sec,
+            ],
+            [
+                dms(125.0, 2.0, 40.39816 + c.dw3[0]),
+                (-6967919.5383 + c.dw3[1]) * sec,
+                (6.359 + c.dw3[2]) * sec,
+                (0.007625 + c.dw3[3]) * sec,
+                -3.586e-5 * sec,
+            ],
+        ];
+
+        /* Earth-Moon barycenter (T) and its perihelion. */
+        let ea = [
+            dms(100.0, 27.0, 59.13885 + c.deart[0]),
+            (129597742.293 + c.deart[1]) * sec,
+            -0.0202 * sec,
+            9e-6 * sec,
+            1.5e-7 * sec,
+        ];
+        let pi = [
+            dms(102.0, 56.0, 14.45766 + c.dperi),
+            1161.24342 * sec,
+            0.529265 * sec,
+            -1.1814e-4 * sec,
+            1.1379e-5 * sec,
+        ];
+
+        /* Mean motions of W2, W3 implied by the corrected constants. */
+        let xa = 2.0 * ALPHA / 3.0;
+        let w11 = w[0][1];
+        for (k, b) in [(1, 0), (2, 1)] {
+            let x = w[k][1] / w11;
+            let y = AM * BP[0][b] + xa * BP[4][b];
+            let dw = (x - y) * c.dw1[1]
+                + (y / AM) * c.deart[1]
+                + w11 * BP[1][b] * c.dgam
+                + w11 * BP[2][b] * c.de
+                + w11 * BP[3][b] * c.dep;
+            w[k][1] += dw * sec;
+        }
+
+        /* Delaunay arguments. */
+        let mut del = [[0.0; 5]; 4];
+        for i in 0..5 {
+            del[0][i] = w[0][i] - ea[i];
+            del[1][i] = ea[i] - pi[i];
+            del[2][i] = w[0][i] - w[1][i];
+            del[3][i] = w[0][i] - w[2][i];
+        }
+        del[0][0] += DPI;
+
+        /* Planetary mean longitudes. */
+        let p = [
+            [dms(252.0, 15.0, 3.216919), 538101628.66888 * sec],
+            [dms(181.0, 58.0, 44.758419), 210664136.45777 * sec],
+            [ea[0], ea[1]],
+            [dms(355.0, 26.0, 3.642778), 68905077.65936 * sec],
+            [dms(34.0, 21.0, 5.379392), 10925660.57335 * sec],
+            [dms(50.0, 4.0, 38.902495), 4399609.33632 * sec],
+            [dms(314.0, 3.0, 4.354234), 1542482.57845 * sec],
+            [dms(304.0, 20.0, 56.808371), 786547.89700 * sec],
+        ];
+
+        /* W1 with the general precession in longitude. */
+        let zeta = [w[0][0], w[0][1] + 5029.0966 * sec];
+
+        /* Parameters of the main problem amplitudes. */
+        let cor = [
+            (0.55604 + c.dw1[1]) * sec / w11,
+            (0.01789 + c.de) * sec,
+            (-0.08066 + c.dgam) * sec,
+            (-0.06424 + c.deart[1]) * sec / w11,
+            (-0.12879 + c.dep) * sec,
+        ];
+
+        Elpmpp02 {
+            tol: 0.0,
+            params,
+            w,
+            del,
+            p,
+            zeta,
+            cor,
+            main: Default::default(),
+            pert: Default::default(),
+        }
+    }
+
+    /// The parameter set of the theory.
+    pub fn params(&self) -> ElpParams {
+        self.params
+    }
+
+    /// Read the six series files ELP_MAIN.S1-S3 and ELP_PERT.S1-S3 from
+    /// a directory.
+    pub fn load<P: AsRef<Path>>(params: ElpParams, dir: P) -> io::Result<Self> {
+        let mut elp = Elpmpp02::new(params);
+        for iv in 1..=3 {
+            let f = File::open(dir.as_ref().join(format!("ELP_MAIN.S{iv}")))?;
+            elp.read_main(iv, BufReader::new(f))?;
+            let f = File::open(dir.as_ref().join(format!("ELP_PERT.S{iv}")))?;
+            elp.read_pert(iv, BufReader::new(f))?;
+        }
+        Ok(elp)
+    }
+
+    ///  Read a main-problem file (ELP_MAIN.S1, S2 or S3)
+    ///
+    ///  Given:
+    ///  ```text
+    ///     iv      usize    variable:  1 = longitude, 2 = latitude,
+    ///                      3 = distance
+    ///     reader  BufRead  the file
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok(n)   usize      number of terms read
+    ///     Err(e)  io::Error  read or format error
+    ///  ```
+    ///  Notes:
+    ///
+    ///  1) Each term line gives the multipliers of D, l', l, F (format
+    ///     4i3), the amplitude A (arcsec or km) and the derivatives
+    ///     B1-B5 (B6, if present, is not used).  Lines that do not
+    ///     start with a number are headers and are skipped.
+    ///
+    ///  2) The amplitudes are corrected for the parameter set of the
+    ///     theory as in ELPMPP02.
+    pub fn read_main<R: BufRead>(&mut self, iv: usize, reader: R) -> io::Result<usize> {
+        if !(1..=3).contains(&iv) {
+            return Err(invalid("bad variable"));
+        }
+        let [delnu, dele, delg, delnp, delep] = self.cor;
+        let dtasm = 2.0 * ALPHA / (3.0 * AM);
+
+        let mut terms = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if is_header(&line) {
+                continue;
+            }
+            let mut ilu = [0; 4];
+            for (k, x) in ilu.iter_mut().enumerate() {
+                *x = line
+                    .get(3 * k..3 * k + 3)
+                    .and_then(|f| f.trim().parse().ok())
+                    .ok_or_else(|| invalid("bad multiplier"))?;
+            }
+            let v = line
+                .get(12..)
+                .ok_or_else(|| invalid("bad term line"))?
+                .split_whitespace()
+                .map(number)
+                .collect::<io::Result<Vec<f64>>>()?;
+            if v.len() < 6 {
+                return Err(invalid("bad term line"));
+            }
+            let mut a = v[0];
+            let b = &v[1..6];
+            if iv == 3 {
+                a -= 2.0 * a * delnu / 3.0;
+            }
+            let tgv = b[0] + dtasm * b[4];
+            a += tgv * (delnp - AM * delnu) + b[1] * delg + b[2] * dele + b[3] * delep;
+            terms.push((ilu, a));
+        }
+        let n = terms.len();
+        self.main[iv - 1] = terms;
+        Ok(n)
+    }
+
+    ///  Read a perturbation file (ELP_PERT.S1, S2 or S3)
+    ///
+    ///  Given:
+    ///  ```text
+    ///     iv      usize    variable:  1 = longitude, 2 = latitude,
+    ///                      3 = distance
+    ///     reader  BufRead  the file
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok(n)   usize      number of terms read
+    ///     Err(e)  io::Error  read or format error
+    ///  ```
+    ///  Note:
+    ///
+    ///     The file holds blocks for increasing powers of T, each
+    ///     introduced by a header line.  Each term line gives its
+    ///     number, the sine and cosine amplitudes S and C (arcsec or
+    ///     km, Fortran D exponents allowed) and the multipliers (format
+    ///     i3) of D, l', l, F, the mean longitudes of Mercury to
+    ///     Neptune and zeta.
+    pub fn read_pert<R: BufRead>(&mut self, iv: usize, reader: R) -> io::Result<usize> {
+        if !(1..=3).contains(&iv) {
+            return Err(invalid("bad variable"));
+        }
+        let mut blocks: Vec<Vec<Pert>> = Vec::new();
+        let mut n = 0;
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if is_header(&line) {
+                blocks.push(Vec::new());
+                continue;
+            }
+            let block = blocks
+                .last_mut()
+                .ok_or_else(|| invalid("term before header"))?;
+
+            /* Number, S, C, then the multipliers. */
+            let mut rest = line.as_str();
+            let mut sc = [0.0; 2];
+            for k in 0..3 {
+                rest = rest.trim_start();
+                let e = rest.find(' ').unwrap_or(rest.len());
+                if k > 0 {
+                    sc[k - 1] = number(&rest[..e])?;
+                }
+                rest = &rest[e..];
+            }
+            let ifi = multipliers(rest)?;
+            block.push(Pert {
+                a: sc[0].hypot(sc[1]),
+                phi: sc[1].atan2(sc[0]),
+                ifi,
+            });
+            n += 1;
+        }
+        self.pert[iv - 1] = blocks;
+        Ok(n)
+    }
+
+    ///  Geocentric spherical coordinates of the Moon
+    ///
+    ///  Given:
+    ///  ```text
+    ///     date1   f64    TDB as a 2-part...
+    ///     date2   f64    ...Julian Date
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///            [[f64; 3]; 2]  longitude, latitude (rad) and distance
+    ///                           (km), mean ecliptic and equinox of
+    ///                           date, and their rates per day
+    ///  ```
+    pub fn spherical(&self, date1: f64, date2: f64) -> [[f64; 3]; 2] {
+        let t = ((date1 - DJ00) + date2) / DJC;
+        let tp = [1.0, t, t * t, t * t * t, t * t * t * t];
+
+        /* Polynomial and its derivative. */
+        let poly = |c: &[f64]| -> (f64, f64) {
+            let mut f = 0.0;
+            let mut df = 0.0;
+            for (k, ck) in c.iter().enumerate() {
+                f += ck * tp[k];
+                if k > 0 {
+                    df += k as f64 * ck * tp[k - 1];
+                }
+            }
+            (f, df)
+        };
+
+        /* The arguments and their rates. */
+        let mut arg = [(0.0, 0.0); NARG];
+        for (a, c) in arg.iter_mut().zip(&self.del) {
+            *a = poly(c);
+        }
+        for (a, c) in arg[4..].iter_mut().zip(&self.p) {
+            *a = poly(c);
+        }
+        arg[12] = poly(&self.zeta);
+
+        let mut v = [0.0; 3];
+        let mut dv = [0.0; 3];
+        for iv in 0..3 {
+            let tol = if iv == 2 {
+                self.tol * DAS2R * A0
+            } else {
+                self.tol
+            };
+
+            /* Main problem:  sines, and cosines for the distance. */
+            let off = if iv == 2 { DPI / 2.0 } else { 0.0 };
+            for (ilu, a) in self.main[iv].iter().filter(|m| m.1.abs() >= tol) {
+                let (mut f, mut df) = (off, 0.0);
+                for (m, (x, dx)) in ilu.iter().zip(&arg) {
+                    f += *m as f64 * x;
+                    df += *m as f64 * dx;
+                }
+                let (s, c) = (f % D2PI).sin_cos();
+                v[iv] += a * s;
+                dv[iv] += a * c * df;
+            }
+
+            /* Perturbations, by power of T. */
+            for (k, block) in self.pert[iv].iter().enumerate().take(4) {
+                let (mut f0, mut df0) = (0.0, 0.0);
+                for q in block.iter().filter(|q| q.a >= tol) {
+                    let (mut f, mut df) = (q.phi, 0.0);
+                    for (m, (x, dx)) in q.ifi.iter().zip(&arg) {
+                        f += *m as f64 * x;
+                        df += *m as f64 * dx;
+                    }
+                    let (s, c) = (f % D2PI).sin_cos();
+                    f0 += q.a * s;
+                    df0 += q.a * c * df;
+                }
+                v[iv] += tp[k] * f0;
+                dv[iv] += tp[k] * df0;
+                if k > 0 {
+                    dv[iv] += k as f64 * tp[k - 1] * f0;
+                }
+            }
+        }
+
+        /* Longitude, latitude (rad) and distance (km). */
+        let (w1, dw1) = poly(&self.w[0]);
+        let sph = [w1 + v[0] * DAS2R, v[1] * DAS2R, v[2] * RA0];
+        let rate = [
+            (dw1 + dv[0] * DAS2R) / DJC,
+            dv[1] * DAS2R / DJC,
+            dv[2] * RA0 / DJC,
+        ];
+        [sph, rate]
+    }
+
+    ///  Geocentric position and velocity of the Moon
+    ///
+    ///  Given:
+    ///  ```text
+    ///     date1   f64    TDB as a 2-part...
+    ///     date2   f64    ...Julian Date
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok(pv)  [[f64; 3]; 2]  Moon p,v, GCRS (au, au/d)
+    ///     Err(j)  i32            -2 = main problem series not loaded
+    ///  ```
+    ///  Notes:
+    ///
+    ///  1) The result has the same convention as iauMoon98.
+    ///
+    ///  2) All three ELP_MAIN files must have been read.  The
+    ///     perturbation series are optional, the solution being
+    ///     correspondingly less accurate without them.
+    ///
+    ///  Called:
+    ///  ```text
+    ///     iauS2pv   spherical coordinates to pv-vector
+    ///     ecm06     ICRS to ecliptic rotation matrix, IAU 2006
+    ///     iauTr     transpose r-matrix
+    ///     iauRxr    product of two r-matrices
+    ///     iauRxpv   product of r-matrix and pv-vector
+    ///  ```
+    pub fn pv(&self, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+        if self.main.iter().any(Vec::is_empty) {
+            return Err(-2);
+        }
+        let s = self.spherical(date1, date2);
+        let au = DAU / 1e3;
+        let pv = s2pv(
+            s[0][0],
+            s[0][1],
+            s[0][2] / au,
+            s[1][0],
+            s[1][1],
+            s[1][2] / au,
+        );
+
+        /* Ecliptic of date to the inertial ecliptic of J2000.0 (Laskar). */
+        let t = ((date1 - DJ00) + date2) / DJC;
+        let poly = |c: &[f64; 5]| t * (c[0] + t * (c[1] + t * (c[2] + t * (c[3] + t * c[4]))));
+        let (p, q) = (poly(&LASKP), poly(&LASKQ));
+        let ra = 2.0 * (1.0 - p * p - q * q).sqrt();
+        let (pw2, qw2, pwqw) = (1.0 - 2.0 * p * p, 1.0 - 2.0 * q * q, 2.0 * p * q);
+        let (pw, qw) = (p * ra, q * ra);
+        let pq = [
+            [pw2, pwqw, pw],
+            [pwqw, qw2, -qw],
+            [-pw, qw, pw2 + qw2 - 1.0],
+        ];
+
+        /* Then to the GCRS. */
+        let mut rm = [[0.0; 3]; 3];
+        let mut e = [[0.0; 3]; 3];
+        tr(&ecm06(DJ00, 0.0), &mut e);
+        rxr(&e, &pq, &mut rm);
+
+        let mut res = [[0.0; 3]; 2];
+        rxpv(&rm, &pv, &mut res);
+        Ok(res)
+    }
+}
+
+impl Ephemeris for Elpmpp02 {
+    fn pvb(&self, target: Target, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+        match target {
+            Target::Moon => {
+                let eb = Epv00.pvb(Target::Earth, date1, date2)?;
+                Ok(pvppv(&eb, &self.pv(date1, date2)?))
+            }
+            _ => Epv00.pvb(target, date1, date2),
+        }
+    }
+
+    fn earth(&self, date1: f64, date2: f64) -> Result<([[f64; 3]; 2], [[f64; 3]; 2]), i32> {
+        Epv00.earth(date1, date2)
+    }
+}
+
+/// Whether a line is a header (does not start with a number).
+fn is_header(line: &str) -> bool {
+    line.split_whitespace()
+        .next()
+        .is_some_and(|w| number(w).is_err())
+}
+
+/// Argument multipliers, in 3-character fields (or, failing that,
+/// separated by blanks).
+fn multipliers(s: &str) -> io::Result<[i32; NARG]> {
+    let s = s.trim_end();
+    let w: Vec<&str> = if s.len().is_multiple_of(3) {
+        (0..s.len() / 3)
+            .map(|k| s[3 * k..3 * k + 3].trim())
+            .collect()
+    } else {
+        s.split_whitespace().collect()
+    };
+    if w.len() < NARG {
+        return Err(invalid("bad multipliers"));
+    }
+    let mut ifi = [0; NARG];
+    for (x, f) in ifi.iter_mut().zip(w) {
+        *x = f.parse().map_err(|_| invalid("bad multipliers"))?;
+    }
+    Ok(ifi)
+}
@@ -0,0 +1,191 @@
+use super::{epv00, moon98, plan94};
+use crate::vm::{pvmpv, pvppv};
+
+/// Solar-system bodies that an ephemeris may supply
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Target {
+    Ssb,     // solar-system barycenter
+    Sun,     // Sun
+    Mercury, // Mercury
+    Venus,   // Venus
+    Emb,     // Earth-Moon barycenter
+    Earth,   // Earth
+    Moon,    // Moon
+    Mars,    // Mars
+    Jupiter, // Jupiter (system barycenter)
+    Saturn,  // Saturn (system barycenter)
+    Uranus,  // Uranus (system barycenter)
+    Neptune, // Neptune (system barycenter)
+    Pluto,   // Pluto (system barycenter)
+}
+
+impl Target {
+    /// The iauPlan94 planet number, if the body has one.
+    pub fn np(&self) -> Option<i32> {
+        match self {
+            Target::Mercury => Some(1),
+            Target::Venus => Some(2),
+            Target::Emb => Some(3),
+            Target::Mars => Some(4),
+            Target::Jupiter => Some(5),
+            Target::Saturn => Some(6),
+            Target::Uranus => Some(7),
+            Target::Neptune => Some(8),
+            _ => None,
+        }
+    }
+
+    /// The NAIF integer code of the body.
+    pub fn naif(&self) -> i32 {
+        match self {
+            Target::Ssb => 0,
+            Target::Sun => 10,
+            Target::Mercury => 199,
+            Target::Venus => 299,
+            Target::Emb => 3,
+            Target::Earth => 399,
+            Target::Moon => 301,
+            Target::Mars => 499,
+            Target::Jupiter => 5,
+            Target::Saturn => 6,
+            Target::Uranus => 7,
+            Target::Neptune => 8,
+            Target::Pluto => 9,
+        }
+    }
+}
+
+///  Source of solar-system body positions and velocities
+///
+///  An ephemeris supplies the barycentric position and velocity of a
+///  body at a given TDB.  The heliocentric form, and the Earth's
+///  heliocentric and barycentric vectors needed by the astrometry
+///  functions (iauApcg, iauApci, iauApco, iauApcs), follow from it;
+///  implementations may override them where a cheaper route exists.
+///
+///  Given (all methods):
+///  ```text
+///     target  Target   the body
+///     date1   f64      TDB as a 2-part...
+///     date2   f64      ...Julian Date
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(pv)   [[f64; 3]; 2]   position/velocity (au, au/day, Note 1)
+///     Err(j)   i32             -1 = date outside the ephemeris
+///                              -2 = body not supplied
+///                              (other negative values may be used by
+///                              implementations)
+///  ```
+///  Notes:
+///
+///  1) The vectors are with respect to BCRS axes (ICRS).  The
+///     low-precision implementations Epv00, Plan94 and Moon98 provided
+///     here do not distinguish between ICRS and the mean equator and
+///     equinox of J2000.0, the difference being below their accuracy.
+///
+///  2) The implementations are:
+///  ```text
+///        Epv00    Ssb, Sun, Earth                 iauEpv00
+///        Plan94   as Epv00, plus Mercury, Venus,  iauPlan94
+///                 Emb, Mars ... Neptune
+///        Moon98   as Epv00, plus Moon             iauMoon98
+///        Elpmpp02 as Epv00, plus Moon (once the   ELP/MPP02
+///                 IMCCE series are loaded)
+///        Vsop87   the bodies loaded (the Sun      VSOP87
+///                 from iauEpv00 unless version E)
+///        Spk      the bodies in the file          JPL DE (SPK)
+///        JplEph   all bodies                      JPL DE (ASCII/binary)
+///  ```
+pub trait Ephemeris {
+    /// Barycentric position and velocity (au, au/d).
+    fn pvb(&self, target: Target, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32>;
+
+    /// Heliocentric position and velocity (au, au/d).
+    fn pvh(&self, target: Target, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+        let pvb = self.pvb(target, date1, date2)?;
+        let sb = self.pvb(Target::Sun, date1, date2)?;
+        Ok(pvmpv(&pvb, &sb))
+    }
+
+    /// Earth heliocentric and barycentric position/velocity, as
+    /// returned by iauEpv00.
+    #[allow(clippy::type_complexity)]
+    fn earth(&self, date1: f64, date2: f64) -> Result<([[f64; 3]; 2], [[f64; 3]; 2]), i32> {
+        let eb = self.pvb(Target::Earth, date1, date2)?;
+        let sb = self.pvb(Target::Sun, date1, date2)?;
+        Ok((pvmpv(&eb, &sb), eb))
+    }
+}
+
+/// Ephemeris of the Sun and Earth from iauEpv00.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Epv00;
+
+/// Ephemeris of the planets from iauPlan94 (Sun and Earth from iauEpv00).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Plan94;
+
+/// Ephemeris of the Moon from iauMoon98 (Sun and Earth from iauEpv00).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Moon98;
+
+impl Ephemeris for Epv00 {
+    fn pvb(&self, target: Target, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+        let (eh, eb) = epv00(date1, date2).ok_or(-1)?;
+        match target {
+            Target::Ssb => Ok([[0.0; 3]; 2]),
+            Target::Sun => Ok(pvmpv(&eb, &eh)),
+            Target::Earth => Ok(eb),
+            _ => Err(-2),
+        }
+    }
+
+    fn earth(&self, date1: f64, date2: f64) -> Result<([[f64; 3]; 2], [[f64; 3]; 2]), i32> {
+        epv00(date1, date2).ok_or(-1)
+    }
+}
+
+impl Ephemeris for Plan94 {
+    fn pvb(&self, target: Target, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+        match target.np() {
+            Some(np) => {
+                let (pvh, _) = plan94(date1, date2, np)?;
+                let sb = Epv00.pvb(Target::Sun, date1, date2)?;
+                Ok(pvppv(&pvh, &sb))
+            }
+            None => Epv00.pvb(target, date1, date2),
+        }
+    }
+
+    fn pvh(&self, target: Target, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+        match target.np() {
+            Some(np) => Ok(plan94(date1, date2, np)?.0),
+            None => {
+                let (eh, eb) = epv00(date1, date2).ok_or(-1)?;
+                let pvb = Epv00.pvb(target, date1, date2)?;
+                Ok(pvmpv(&pvb, &pvmpv(&eb, &eh)))
+            }
+        }
+    }
+
+    fn earth(&self, date1: f64, date2: f64) -> Result<([[f64; 3]; 2], [[f64; 3]; 2]), i32> {
+        Epv00.earth(date1, date2)
+    }
+}
+
+impl Ephemeris for Moon98 {
+    fn pvb(&self, target: Target, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+        match target {
+            Target::Moon => {
+                let eb = Epv00.pvb(Target::Earth, date1, date2)?;
+                Ok(pvppv(&eb, &moon98(date1, date2)))
+            }
+            _ => Epv00.pvb(target, date1, date2),
+        }
+    }
+
+    fn earth(&self, date1: f64, date2: f64) -> Result<([[f64; 3]; 2], [[f64; 3]; 2]), i32> {
+        Epv00.earth(date1, date2)
+    }
+}
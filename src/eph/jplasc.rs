@@ -0,0 +1,192 @@
+use std::collections::btree_map::Entry;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use super::JplEph;
+use super::daf::invalid;
+use super::jpleph::NITEMS;
+
+impl JplEph {
+    ///  Read the header file (header.NNN) of a JPL ASCII ephemeris
+    ///
+    ///  The header gives the record size (KSIZE/NCOEFF line), the title
+    ///  (GROUP 1010), the time span and record length (GROUP 1030), the
+    ///  constant names and values (GROUPs 1040 and 1041) and the layout
+    ///  of the coefficients (GROUP 1050).  No records are loaded:  see
+    ///  read_ascii.
+    ///
+    ///  Notes:
+    ///
+    ///  1) GROUP 1050 may have 13 columns (DE405 and earlier), 14 or 15
+    ///     (with the lunar mantle angular velocity and TT-TDB, DE430t,
+    ///     DE440t).  Missing items are treated as absent.
+    ///
+    ///  2) Numbers may use the Fortran D exponent.
+    pub fn from_header<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut eph = JplEph::default();
+        let mut group = 0;
+        let mut ksize = None;
+        let mut span = Vec::new();
+        let mut names = Vec::new();
+        let mut values = Vec::new();
+        let mut ptrs = Vec::new();
+
+        for line in reader.lines() {
+            let line = line?;
+            let t = line.trim();
+            if let Some(g) = t.strip_prefix("GROUP") {
+                group = g.trim().parse().map_err(|_| invalid("bad GROUP line"))?;
+                if group == 1070 {
+                    break;
+                }
+                continue;
+            }
+            if t.is_empty() {
+                continue;
+            }
+            match group {
+                0 => {
+                    if let Some(i) = t.find("NCOEFF=") {
+                        let n = t[i + 7..].split_whitespace().next().unwrap_or("");
+                        ksize = Some(n.parse().map_err(|_| invalid("bad NCOEFF"))?);
+                    }
+                }
+                1010 => eph.title.push(t.to_string()),
+                1030 => {
+                    for w in t.split_whitespace() {
+                        span.push(number(w)?);
+                    }
+                }
+                1040 => names.extend(t.split_whitespace().map(str::to_string)),
+                1041 => {
+                    for w in t.split_whitespace() {
+                        values.push(number(w)?);
+                    }
+                }
+                1050 => {
+                    for w in t.split_whitespace() {
+                        ptrs.push(w.parse::<usize>().map_err(|_| invalid("bad GROUP 1050"))?);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        /* Time span. */
+        if span.len() < 3 || span[2] <= 0.0 {
+            return Err(invalid("bad GROUP 1030"));
+        }
+        (eph.start, eph.end, eph.step) = (span[0], span[1], span[2]);
+
+        /* Constants:  each group is preceded by the count. */
+        if names.is_empty() || values.is_empty() {
+            return Err(invalid("missing constants"));
+        }
+        let n = names[0]
+            .parse::<usize>()
+            .map_err(|_| invalid("bad GROUP 1040"))?;
+        if names.len() != n + 1 || values.len() < n + 1 || values[0] as usize != n {
+            return Err(invalid("inconsistent constants"));
+        }
+        eph.consts = names
+            .into_iter()
+            .skip(1)
+            .zip(values.into_iter().skip(1))
+            .collect();
+
+        /* Coefficient pointers, three rows. */
+        let ncol = ptrs.len() / 3;
+        if ptrs.len() % 3 != 0 || !(13..=NITEMS).contains(&ncol) {
+            return Err(invalid("bad GROUP 1050"));
+        }
+        for i in 0..ncol {
+            eph.ipt[i] = [ptrs[i], ptrs[ncol + i], ptrs[2 * ncol + i]];
+        }
+
+        let extent = JplEph::extent(&eph.ipt);
+        eph.ncoeff = ksize.unwrap_or(extent);
+        if eph.ncoeff < extent {
+            return Err(invalid("NCOEFF too small for GROUP 1050"));
+        }
+        Ok(eph)
+    }
+
+    ///  Load the records of a JPL ASCII data file (ascpYYYY.NNN)
+    ///
+    ///  Each record is a line giving the record number and NCOEFF,
+    ///  followed by the coefficients, three to a line, the first two
+    ///  being the Julian Dates of the start and end of the record.
+    ///
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok(n)    usize      number of records added (Note 1)
+    ///     Err(e)   io::Error  read or format error
+    ///  ```
+    ///  Notes:
+    ///
+    ///  1) Consecutive files overlap by one record, which is loaded only
+    ///     once.  Files may be read in any order, and need not be
+    ///     contiguous.
+    pub fn read_ascii<R: BufRead>(&mut self, reader: R) -> io::Result<usize> {
+        let mut added = 0;
+        let mut rec: Vec<f64> = Vec::new();
+        let mut want = 0;
+
+        for line in reader.lines() {
+            let line = line?;
+            let w: Vec<&str> = line.split_whitespace().collect();
+            if w.is_empty() {
+                continue;
+            }
+
+            /* Record header:  record number and NCOEFF. */
+            if want == 0 {
+                if w.len() != 2 || w[1].parse::<usize>().ok() != Some(self.ncoeff) {
+                    return Err(invalid("bad record header"));
+                }
+                want = self.ncoeff;
+                rec.clear();
+                continue;
+            }
+
+            /* Coefficients;  the last line may be padded. */
+            for x in w {
+                if rec.len() < want {
+                    rec.push(number(x)?);
+                }
+            }
+            if rec.len() == want {
+                if ((rec[1] - rec[0]) - self.step).abs() > 1e-6 {
+                    return Err(invalid("record length differs from header"));
+                }
+                let k = self.index(rec[0]);
+                if let Entry::Vacant(e) = self.records.entry(k) {
+                    e.insert(rec.clone());
+                    added += 1;
+                }
+                want = 0;
+            }
+        }
+        if want != 0 {
+            return Err(invalid("incomplete record"));
+        }
+        Ok(added)
+    }
+
+    /// Read a header file and any number of data files.
+    pub fn load_ascii<P: AsRef<Path>, Q: AsRef<Path>>(header: P, files: &[Q]) -> io::Result<Self> {
+        let mut eph = JplEph::from_header(BufReader::new(File::open(header)?))?;
+        for f in files {
+            eph.read_ascii(BufReader::new(File::open(f)?))?;
+        }
+        Ok(eph)
+    }
+}
+
+/// Number, allowing the Fortran D exponent.
+pub(crate) fn number(w: &str) -> io::Result<f64> {
+    w.replace(['D', 'd'], "E")
+        .parse()
+        .map_err(|_| invalid("bad number"))
+}
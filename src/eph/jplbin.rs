@@ -0,0 +1,219 @@
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::Path;
+
+use super::JplEph;
+use super::daf::{invalid, text, word4};
+
+/// Lengths (bytes) of the title lines and constant names.
+const TTLLEN: usize = 84;
+const CNMLEN: usize = 6;
+
+/// Number of constant names in the fixed part of the first record.
+const NCNAM: usize = 400;
+
+/// Offsets (bytes) of the fields of the first record.
+const OSS: usize = 3 * TTLLEN + NCNAM * CNMLEN;
+const ONCON: usize = OSS + 24;
+const OAU: usize = ONCON + 4;
+const OEMRAT: usize = OAU + 8;
+const OIPT: usize = OEMRAT + 8;
+const ONUMDE: usize = OIPT + 12 * 12;
+const OLPT: usize = ONUMDE + 4;
+const OEXTRA: usize = OLPT + 12;
+
+impl JplEph {
+    ///  Write the loaded records as a JPL binary ephemeris
+    ///
+    ///  The format is that of the JPL export program asc2eph:  fixed-
+    ///  length records of NCOEFF doubles, the first holding the title,
+    ///  constant names, time span, AU, EMRAT and coefficient pointers,
+    ///  the second the constant values, and the rest the Chebyshev
+    ///  records.  Little-endian byte order is used.
+    ///
+    ///  Notes:
+    ///
+    ///  1) The loaded records must be contiguous;  the time span written
+    ///     is that of the loaded records.
+    ///
+    ///  2) The record must be long enough for the header fields and the
+    ///     constants, as is the case for all the JPL DE ephemerides.
+    pub fn write_binary<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let (start, end) = self.loaded().ok_or_else(|| invalid("no records loaded"))?;
+        if self.index(end) - self.index(start) != self.records.len() as i64 {
+            return Err(invalid("loaded records are not contiguous"));
+        }
+
+        let reclen = 8 * self.ncoeff;
+        let ncon = self.consts.len();
+        let extra = ncon.saturating_sub(NCNAM) * CNMLEN;
+        if reclen < OEXTRA + extra + 24 || reclen < 8 * ncon {
+            return Err(invalid("record too short for the header"));
+        }
+
+        /* First record. */
+        let mut r = vec![0u8; reclen];
+        for (i, t) in self.title.iter().take(3).enumerate() {
+            put_text(&mut r[i * TTLLEN..(i + 1) * TTLLEN], t);
+        }
+        for i in 0..NCNAM {
+            let name = self.consts.get(i).map_or("", |c| c.0.as_str());
+            let o = 3 * TTLLEN + i * CNMLEN;
+            put_text(&mut r[o..o + CNMLEN], name);
+        }
+        r[OSS..OSS + 8].copy_from_slice(&start.to_le_bytes());
+        r[OSS + 8..OSS + 16].copy_from_slice(&end.to_le_bytes());
+        r[OSS + 16..OSS + 24].copy_from_slice(&self.step.to_le_bytes());
+        r[ONCON..ONCON + 4].copy_from_slice(&(ncon as i32).to_le_bytes());
+        r[OAU..OAU + 8].copy_from_slice(&self.au().to_le_bytes());
+        r[OEMRAT..OEMRAT + 8].copy_from_slice(&self.emrat().to_le_bytes());
+        let put_ipt = |r: &mut [u8], o: usize, p: &[usize; 3]| {
+            for (j, x) in p.iter().enumerate() {
+                r[o + 4 * j..o + 4 * j + 4].copy_from_slice(&(*x as i32).to_le_bytes());
+            }
+        };
+        for i in 0..12 {
+            put_ipt(&mut r, OIPT + 12 * i, &self.ipt[i]);
+        }
+        r[ONUMDE..ONUMDE + 4].copy_from_slice(&self.denum().to_le_bytes());
+        put_ipt(&mut r, OLPT, &self.ipt[12]);
+        for (i, c) in self.consts.iter().skip(NCNAM).enumerate() {
+            let o = OEXTRA + i * CNMLEN;
+            put_text(&mut r[o..o + CNMLEN], &c.0);
+        }
+        put_ipt(&mut r, OEXTRA + extra, &self.ipt[13]);
+        put_ipt(&mut r, OEXTRA + extra + 12, &self.ipt[14]);
+        w.write_all(&r)?;
+
+        /* Second record:  the constant values. */
+        let mut r = vec![0u8; reclen];
+        for (i, c) in self.consts.iter().enumerate() {
+            r[8 * i..8 * i + 8].copy_from_slice(&c.1.to_le_bytes());
+        }
+        w.write_all(&r)?;
+
+        /* The Chebyshev records. */
+        for rec in self.records.values() {
+            for x in rec {
+                w.write_all(&x.to_le_bytes())?;
+            }
+        }
+        w.flush()
+    }
+
+    ///  Read a JPL binary ephemeris
+    ///
+    ///  Reads a file in the format produced by write_binary or by the
+    ///  JPL program asc2eph, in either byte order.  The record length is
+    ///  deduced from the coefficient pointers.
+    pub fn read_binary<R: Read>(r: &mut R) -> io::Result<Self> {
+        let mut h = vec![0u8; OEXTRA];
+        r.read_exact(&mut h)?;
+
+        /* Byte order, from the number of constants. */
+        let ncon_le = i32::from_le_bytes(word4(&h, ONCON));
+        let big = !(1..=100_000).contains(&ncon_le);
+        let int = |b: &[u8], o: usize| -> usize {
+            let w = word4(b, o);
+            (if big {
+                i32::from_be_bytes(w)
+            } else {
+                i32::from_le_bytes(w)
+            })
+            .max(0) as usize
+        };
+        let dbl = |b: &[u8], o: usize| -> f64 {
+            let mut w = [0u8; 8];
+            w.copy_from_slice(&b[o..o + 8]);
+            if big {
+                f64::from_be_bytes(w)
+            } else {
+                f64::from_le_bytes(w)
+            }
+        };
+
+        let mut eph = JplEph::default();
+        let ncon = int(&h, ONCON);
+        for i in 0..12 {
+            for j in 0..3 {
+                eph.ipt[i][j] = int(&h, OIPT + 12 * i + 4 * j);
+            }
+        }
+        for j in 0..3 {
+            eph.ipt[12][j] = int(&h, OLPT + 4 * j);
+        }
+        if ncon == 0 || eph.ipt[0][0] != 3 {
+            return Err(invalid("not a JPL binary ephemeris"));
+        }
+
+        /* The remainder of the first record. */
+        let extra = ncon.saturating_sub(NCNAM) * CNMLEN;
+        let mut t = vec![0u8; extra + 24];
+        r.read_exact(&mut t)?;
+        for j in 0..3 {
+            eph.ipt[13][j] = int(&t, extra + 4 * j);
+            eph.ipt[14][j] = int(&t, extra + 12 + 4 * j);
+        }
+
+        /* Record length, from the pointers. */
+        eph.ncoeff = JplEph::extent(&eph.ipt);
+        let reclen = 8 * eph.ncoeff;
+        if reclen < OEXTRA + extra + 24 || reclen < 8 * ncon {
+            return Err(invalid("bad record length"));
+        }
+        let mut rest = vec![0u8; reclen - OEXTRA - extra - 24];
+        r.read_exact(&mut rest)?;
+
+        eph.title = (0..3)
+            .map(|i| text(&h[i * TTLLEN..(i + 1) * TTLLEN]))
+            .filter(|s| !s.is_empty())
+            .collect();
+        (eph.start, eph.end, eph.step) = (dbl(&h, OSS), dbl(&h, OSS + 8), dbl(&h, OSS + 16));
+        if eph.step <= 0.0 {
+            return Err(invalid("bad time span"));
+        }
+
+        /* Constants. */
+        let mut v = vec![0u8; reclen];
+        r.read_exact(&mut v)?;
+        for i in 0..ncon {
+            let name = if i < NCNAM {
+                text(&h[3 * TTLLEN + i * CNMLEN..3 * TTLLEN + (i + 1) * CNMLEN])
+            } else {
+                text(&t[(i - NCNAM) * CNMLEN..(i - NCNAM + 1) * CNMLEN])
+            };
+            eph.consts.push((name, dbl(&v, 8 * i)));
+        }
+
+        /* The Chebyshev records, to the end of the file. */
+        let mut b = vec![0u8; reclen];
+        loop {
+            match r.read_exact(&mut b) {
+                Ok(()) => {}
+                Err(e) if e.kind() == ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(e),
+            }
+            let rec: Vec<f64> = (0..eph.ncoeff).map(|i| dbl(&b, 8 * i)).collect();
+            let k = eph.index(rec[0]);
+            eph.records.insert(k, rec);
+        }
+        Ok(eph)
+    }
+
+    /// Read a JPL binary ephemeris file.
+    pub fn open_binary<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        JplEph::read_binary(&mut BufReader::new(File::open(path)?))
+    }
+
+    /// Write the loaded records to a JPL binary ephemeris file.
+    pub fn save_binary<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write_binary(&mut BufWriter::new(File::create(path)?))
+    }
+}
+
+/// Blank-padded text field.
+fn put_text(f: &mut [u8], s: &str) {
+    f.fill(b' ');
+    let n = s.len().min(f.len());
+    f[..n].copy_from_slice(&s.as_bytes()[..n]);
+}
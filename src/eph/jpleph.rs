@@ -0,0 +1,240 @@
+use std::collections::BTreeMap;
+
+use super::spk::cheby;
+use super::{Ephemeris, Target};
+use crate::consts::{DAU, DAYSEC};
+use crate::vm::{ppsp, pvmpv};
+
+/// Number of items in a JPL ephemeris record.
+pub(crate) const NITEMS: usize = 15;
+
+/// Number of components of each item.
+pub(crate) const NCOMP: [usize; NITEMS] = [3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 2, 3, 3, 1];
+
+///  JPL planetary ephemeris in the original DE distribution format
+///
+///  An ephemeris loaded from the ASCII files distributed by JPL
+///  (header.NNN and ascpYYYY.NNN, see from_header and read_ascii), or
+///  from the equivalent binary file (see read_binary and write_binary).
+///  The Chebyshev records are held in memory.
+///
+///  Items (columns of the header's GROUP 1050):
+///  ```text
+///      0-8   Mercury, Venus, EMB, Mars ... Pluto  (barycentric, km)
+///        9   Moon                                  (geocentric, km)
+///       10   Sun                                   (barycentric, km)
+///       11   nutations in longitude and obliquity  (IAU 1980, rad)
+///       12   lunar mantle libration Euler angles   (rad)
+///       13   lunar mantle angular velocity         (rad/day)
+///       14   TT-TDB at the geocenter               (seconds)
+///  ```
+///  Items 11 onwards are present only in some ephemerides.
+///
+///  Notes:
+///
+///  1) The time argument is TDB (strictly, for DE430 onwards, the
+///     ephemeris time scale Teph), as a 2-part Julian Date.
+///
+///  2) Positions are in km and velocities in km/s.  The Ephemeris
+///     implementation uses the ephemeris' own AU constant to convert to
+///     au and au/day, forming the Earth and the barycentric Moon from
+///     the EMB and the geocentric Moon using EMRAT.
+///
+///  Reference:
+///
+///     Standish, E.M., JPL Planetary and Lunar Ephemerides, DE405/LE405,
+///     JPL IOM 312.F-98-048 (1998), and the README files of the
+///     JPL ephemeris distribution.
+#[derive(Debug, Clone, Default)]
+pub struct JplEph {
+    pub title: Vec<String>,                      // title lines
+    pub start: f64,                              // start of the ephemeris (JD)
+    pub end: f64,                                // end of the ephemeris (JD)
+    pub step: f64,                               // length of a record (days)
+    pub ncoeff: usize,                           // doubles per record
+    pub consts: Vec<(String, f64)>,              // named constants
+    pub(crate) ipt: [[usize; 3]; NITEMS],        // offset, coefficients, subintervals
+    pub(crate) records: BTreeMap<i64, Vec<f64>>, // records by index from start
+}
+
+impl JplEph {
+    /// The value of a named constant, for example "AU" or "EMRAT".
+    pub fn constant(&self, name: &str) -> Option<f64> {
+        self.consts.iter().find(|(n, _)| n == name).map(|&(_, v)| v)
+    }
+
+    /// Ephemeris number (constant DENUM).
+    pub fn denum(&self) -> i32 {
+        self.constant("DENUM").unwrap_or(0.0) as i32
+    }
+
+    /// Astronomical unit (km).
+    pub fn au(&self) -> f64 {
+        self.constant("AU").unwrap_or(DAU / 1e3)
+    }
+
+    /// Earth/Moon mass ratio.
+    pub fn emrat(&self) -> f64 {
+        self.constant("EMRAT").unwrap_or(81.30056907419062)
+    }
+
+    /// Whether the ephemeris has item i (see the table above).
+    pub fn has(&self, item: usize) -> bool {
+        item < NITEMS && self.ipt[item].iter().all(|&n| n > 0)
+    }
+
+    /// Range of the loaded records (JD), if any.
+    pub fn loaded(&self) -> Option<(f64, f64)> {
+        let first = self.records.first_key_value()?.1;
+        let last = self.records.last_key_value()?.1;
+        Some((first[0], last[1]))
+    }
+
+    ///  Position and velocity of one body relative to another
+    ///
+    ///  Given:
+    ///  ```text
+    ///     target  Target   the body
+    ///     center  Target   the origin
+    ///     date1   f64      TDB as a 2-part...
+    ///     date2   f64      ...Julian Date
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok(pv)  [[f64; 3]; 2]  position/velocity (km, km/s)
+    ///     Err(j)  i32            -1 = date not in the loaded records
+    ///                            -2 = body not in the ephemeris
+    ///  ```
+    pub fn pv(
+        &self,
+        target: Target,
+        center: Target,
+        date1: f64,
+        date2: f64,
+    ) -> Result<[[f64; 3]; 2], i32> {
+        let t = self.pvkm(target, date1, date2)?;
+        let c = self.pvkm(center, date1, date2)?;
+        let mut pv = pvmpv(&t, &c);
+        pv[1] = pv[1].map(|x| x / DAYSEC);
+        Ok(pv)
+    }
+
+    /// Nutations in longitude and obliquity (rad) and their rates
+    /// (rad/day).
+    pub fn nutation(&self, date1: f64, date2: f64) -> Result<[[f64; 2]; 2], i32> {
+        let (p, v) = self.interp(11, date1, date2)?;
+        Ok([[p[0], p[1]], [v[0], v[1]]])
+    }
+
+    /// Lunar mantle libration angles phi, theta, psi (rad) and their
+    /// rates (rad/day).  See eulrm for the rotation matrix.
+    pub fn libration(&self, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+        let (p, v) = self.interp(12, date1, date2)?;
+        Ok([p, v])
+    }
+
+    /// Lunar mantle angular velocity (rad/day) and its rate (rad/day^2).
+    pub fn mantle(&self, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+        let (p, v) = self.interp(13, date1, date2)?;
+        Ok([p, v])
+    }
+
+    /// TT-TDB at the geocenter (seconds).
+    pub fn ttmtdb(&self, date1: f64, date2: f64) -> Result<f64, i32> {
+        Ok(self.interp(14, date1, date2)?.0[0])
+    }
+
+    /// Barycentric position (km) and velocity (km/day).
+    fn pvkm(&self, target: Target, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+        let item = match target {
+            Target::Ssb => return Ok([[0.0; 3]; 2]),
+            Target::Earth | Target::Moon => {
+                let (pe, ve) = self.interp(2, date1, date2)?;
+                let (pm, vm) = self.interp(9, date1, date2)?;
+                let f = if target == Target::Earth {
+                    -1.0 / (1.0 + self.emrat())
+                } else {
+                    self.emrat() / (1.0 + self.emrat())
+                };
+                return Ok([ppsp(&pe, f, &pm), ppsp(&ve, f, &vm)]);
+            }
+            Target::Mercury => 0,
+            Target::Venus => 1,
+            Target::Emb => 2,
+            Target::Mars => 3,
+            Target::Jupiter => 4,
+            Target::Saturn => 5,
+            Target::Uranus => 6,
+            Target::Neptune => 7,
+            Target::Pluto => 8,
+            Target::Sun => 10,
+        };
+        let (p, v) = self.interp(item, date1, date2)?;
+        Ok([p, v])
+    }
+
+    ///  Evaluate one item of the ephemeris
+    ///
+    ///  Returns the components (up to 3) and their rates per day.
+    pub(crate) fn interp(
+        &self,
+        item: usize,
+        date1: f64,
+        date2: f64,
+    ) -> Result<([f64; 3], [f64; 3]), i32> {
+        if !self.has(item) {
+            return Err(-2);
+        }
+        let [off, nc, ns] = self.ipt[item];
+
+        /* The record, the last one being used at its end epoch. */
+        let x = ((date1 - self.start) + date2) / self.step;
+        let mut k = x.floor() as i64;
+        if !self.records.contains_key(&k) && x == x.floor() {
+            k -= 1;
+        }
+        let rec = self.records.get(&k).ok_or(-1)?;
+
+        /* The subinterval and normalized time. */
+        let dt = self.step / ns as f64;
+        let t = ((date1 - rec[0]) + date2) / dt;
+        let l = (t.floor().max(0.0) as usize).min(ns - 1);
+        let tc = 2.0 * (t - l as f64) - 1.0;
+        if !(-1.0 - 1e-9..=1.0 + 1e-9).contains(&tc) {
+            return Err(-1);
+        }
+
+        let ncomp = NCOMP[item];
+        let mut p = [0.0; 3];
+        let mut v = [0.0; 3];
+        for (i, (pi, vi)) in p.iter_mut().zip(v.iter_mut()).take(ncomp).enumerate() {
+            let a = off - 1 + (l * ncomp + i) * nc;
+            let (f, df) = cheby(&rec[a..a + nc], tc);
+            *pi = f;
+            *vi = df * 2.0 / dt;
+        }
+        Ok((p, v))
+    }
+
+    /// Index of the record starting at jd.
+    pub(crate) fn index(&self, jd: f64) -> i64 {
+        ((jd - self.start) / self.step).round() as i64
+    }
+
+    /// Number of doubles in a record implied by the pointers.
+    pub(crate) fn extent(ipt: &[[usize; 3]; NITEMS]) -> usize {
+        (0..NITEMS)
+            .filter(|&i| ipt[i][0] > 0)
+            .map(|i| ipt[i][0] - 1 + ipt[i][1] * ipt[i][2] * NCOMP[i])
+            .max()
+            .unwrap_or(2)
+    }
+}
+
+impl Ephemeris for JplEph {
+    fn pvb(&self, target: Target, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+        let pv = self.pvkm(target, date1, date2)?;
+        let au = self.au();
+        Ok([pv[0].map(|x| x / au), pv[1].map(|x| x / au)])
+    }
+}
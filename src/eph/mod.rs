@@ -1,4 +1,4 @@
-//! Ephemerides (limited precision)
+//! Ephemerides (SOFA limited-precision models and JPL files)
 mod epv00;
 pub use epv00::*;
 
@@ -7,3 +7,28 @@ pub use moon98::*;
 
 mod plan94;
 pub use plan94::*;
+
+mod ephem;
+pub use ephem::*;
+
+mod daf;
+pub use daf::*;
+
+mod spk;
+pub use spk::*;
+
+mod jpleph;
+pub use jpleph::*;
+
+mod jplasc;
+
+mod jplbin;
+
+mod pck;
+pub use pck::*;
+
+mod vsop87;
+pub use vsop87::*;
+
+mod elpmpp02;
+pub use elpmpp02::*;
@@ -0,0 +1,217 @@
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek};
+use std::path::Path;
+
+use super::Daf;
+use super::daf::invalid;
+use super::spk::Chebyshev;
+use crate::consts::{DAYSEC, DJ00};
+use crate::vm::{ir, rx, rz};
+
+/// One segment of a binary PCK file
+#[derive(Debug, Clone, PartialEq)]
+pub struct PckSegment {
+    pub name: String, // segment name
+    pub body: i32,    // frame class ID of the body-fixed frame
+    pub frame: i32,   // reference frame (1 = J2000/ICRF)
+    pub kind: i32,    // data type (2 or 3 are evaluated)
+    pub start: f64,   // start of coverage (TDB seconds from J2000.0)
+    pub end: f64,     // end of coverage (TDB seconds from J2000.0)
+    cheb: Option<Chebyshev>,
+}
+
+///  Binary PCK (Planetary Constants Kernel) orientation file
+///
+///  A reader for the high-precision orientation kernels distributed by
+///  NAIF, in particular the lunar principal-axes orientation derived
+///  from the JPL ephemerides (moon_pa_de421_1900-2050.bpc,
+///  moon_pa_de440_200625.bpc etc.).  As for Spk, Chebyshev records are
+///  read from the file as needed.
+///
+///  Notes:
+///
+///  1) Each segment gives the 3-1-3 Euler angles phi, theta, psi of a
+///     body-fixed frame with respect to a reference frame.  The matrix
+///     that rotates vectors from the reference frame to the body-fixed
+///     frame is Rz(psi) x Rx(theta) x Rz(phi), see eulrm.
+///
+///  2) Segment types 2 (Chebyshev angles) and 3 (Chebyshev angles and
+///     rates) are evaluated.  Where more than one segment covers a
+///     frame and epoch, the one later in the file is used.
+///
+///  3) Frames are identified by their NAIF frame class IDs, which for
+///     the lunar principal-axes frames are 31006 (MOON_PA_DE421), 31007
+///     (MOON_PA_DE430) and 31008 (MOON_PA_DE440).  The reference frame
+///     of these kernels is J2000, which is taken to be the ICRS.
+///
+///  Reference:
+///
+///     NAIF, PCK Required Reading, NASA/JPL.
+#[derive(Debug)]
+#[allow(clippy::type_complexity)]
+pub struct Pck<R = BufReader<File>> {
+    daf: RefCell<Daf<R>>,
+    segments: Vec<PckSegment>,
+    cache: RefCell<Vec<Option<(usize, Vec<f64>)>>>,
+}
+
+impl Pck {
+    /// Open a binary PCK file.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Pck::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read + Seek> Pck<R> {
+    /// Read the segment directory of a binary PCK file.
+    pub fn new(reader: R) -> io::Result<Self> {
+        let mut daf = Daf::new(reader)?;
+        if daf.idword != "DAF/PCK" || daf.nd != 2 || daf.ni != 5 {
+            return Err(invalid("not a binary PCK file"));
+        }
+
+        let mut segments = Vec::with_capacity(daf.summaries.len());
+        for s in daf.summaries.clone() {
+            let (begin, end) = (s.ic[3].max(0) as usize, s.ic[4].max(0) as usize);
+            segments.push(PckSegment {
+                cheb: Chebyshev::new(&mut daf, s.ic[2], begin, end)?,
+                name: s.name,
+                body: s.ic[0],
+                frame: s.ic[1],
+                kind: s.ic[2],
+                start: s.dc[0],
+                end: s.dc[1],
+            });
+        }
+
+        Ok(Pck {
+            daf: RefCell::new(daf),
+            cache: RefCell::new(vec![None; segments.len()]),
+            segments,
+        })
+    }
+
+    /// The segments, in file order.
+    pub fn segments(&self) -> &[PckSegment] {
+        &self.segments
+    }
+
+    ///  Euler angles of a body-fixed frame and their rates
+    ///
+    ///  Given:
+    ///  ```text
+    ///     body    i32    frame class ID, e.g. 31008 (MOON_PA_DE440)
+    ///     date1   f64    TDB as a 2-part...
+    ///     date2   f64    ...Julian Date
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok(ea)  [[f64; 3]; 2]  phi, theta, psi (rad) and their
+    ///                            rates (rad/s)
+    ///     Err(j)  i32            -1 = epoch not covered
+    ///                            -2 = frame not in the file
+    ///                            -3 = read error or unusable segment
+    ///  ```
+    ///  Note:
+    ///
+    ///     The angles are continuous functions of time and are not
+    ///     normalized;  for the Moon, psi increases by about 2pi per
+    ///     sidereal month.
+    pub fn euler(&self, body: i32, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+        let et = ((date1 - DJ00) + date2) * DAYSEC;
+        let k = self.find(body, et)?;
+        self.eval(k, et)
+    }
+
+    ///  Rotation matrix from the reference frame to a body-fixed frame
+    ///
+    ///  Given:
+    ///  ```text
+    ///     body    i32    frame class ID, e.g. 31008 (MOON_PA_DE440)
+    ///     date1   f64    TDB as a 2-part...
+    ///     date2   f64    ...Julian Date
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok(r)   [[f64; 3]; 3]  rotation matrix (Note 1)
+    ///     Err(j)  i32            status, as for euler
+    ///  ```
+    ///  Notes:
+    ///
+    ///  1) For the lunar kernels, a vector p in the ICRS is expressed in
+    ///     the principal-axes frame by rxp(r, p);  the inverse
+    ///     transformation uses the transpose, trxp(r, p).
+    ///
+    ///  2) The rotation is that of Rz(psi) x Rx(theta) x Rz(phi), where
+    ///     phi, theta, psi are the Euler angles returned by euler.
+    ///
+    ///  Called:
+    ///  ```text
+    ///     eulrm     Euler angles to rotation matrix
+    ///  ```
+    pub fn rm(&self, body: i32, date1: f64, date2: f64) -> Result<[[f64; 3]; 3], i32> {
+        let ea = self.euler(body, date1, date2)?;
+        Ok(eulrm(ea[0][0], ea[0][1], ea[0][2]))
+    }
+
+    /// Index of the segment for a frame at et.
+    fn find(&self, body: i32, et: f64) -> Result<usize, i32> {
+        let mut found = false;
+        for (k, s) in self.segments.iter().enumerate().rev() {
+            if s.body == body {
+                found = true;
+                if (s.start..=s.end).contains(&et) {
+                    return Ok(k);
+                }
+            }
+        }
+        Err(if found { -1 } else { -2 })
+    }
+
+    /// Evaluate segment k at et (rad, rad/s).
+    fn eval(&self, k: usize, et: f64) -> Result<[[f64; 3]; 2], i32> {
+        let cheb = self.segments[k].cheb.as_ref().ok_or(-3)?;
+        cheb.eval(
+            &mut self.daf.borrow_mut(),
+            &mut self.cache.borrow_mut()[k],
+            et,
+        )
+    }
+}
+
+///  Form the rotation matrix corresponding to 3-1-3 Euler angles
+///
+///  Given:
+///  ```text
+///     phi     f64    first rotation, about the z-axis (rad)
+///     theta   f64    second rotation, about the new x-axis (rad)
+///     psi     f64    third rotation, about the new z-axis (rad)
+///  ```
+///  Returned (function value):
+///  ```text
+///             [[f64; 3]; 3]  rotation matrix
+///  ```
+///  Notes:
+///
+///  1) The matrix is Rz(psi) x Rx(theta) x Rz(phi), and rotates vectors
+///     from the reference frame into the rotated frame.
+///
+///  2) This is the convention of the binary PCK orientation kernels and
+///     of the lunar libration angles in the JPL ephemerides (see
+///     JplEph::libration).
+///
+///  Called:
+///  ```text
+///     iauIr     initialize r-matrix to identity
+///     iauRz     rotate around Z-axis
+///     iauRx     rotate around X-axis
+///  ```
+pub fn eulrm(phi: f64, theta: f64, psi: f64) -> [[f64; 3]; 3] {
+    let mut r = [[0.0; 3]; 3];
+    ir(&mut r);
+    rz(phi, &mut r);
+    rx(theta, &mut r);
+    rz(psi, &mut r);
+    r
+}
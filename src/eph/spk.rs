@@ -0,0 +1,312 @@
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek};
+use std::path::Path;
+
+use super::daf::invalid;
+use super::{Daf, Ephemeris, Target};
+use crate::consts::{DAU, DAYSEC, DJ00};
+use crate::vm::{pvmpv, pvppv};
+
+/// Maximum length of a target-to-barycenter chain.
+const MAXCHAIN: usize = 20;
+
+/// One segment of an SPK file
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpkSegment {
+    pub name: String, // segment name
+    pub target: i32,  // NAIF code of the target
+    pub center: i32,  // NAIF code of the center
+    pub frame: i32,   // reference frame (1 = J2000/ICRF)
+    pub kind: i32,    // data type (2 or 3 are evaluated)
+    pub start: f64,   // start of coverage (TDB seconds from J2000.0)
+    pub end: f64,     // end of coverage (TDB seconds from J2000.0)
+    cheb: Option<Chebyshev>,
+}
+
+/// Directory of a Chebyshev (type 2 or 3) array, SPK or binary PCK
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Chebyshev {
+    kind: i32,    // 2 = values only, 3 = values and rates
+    begin: usize, // address of the first record
+    init: f64,    // start of the first record (TDB seconds)
+    intlen: f64,  // length of each record (seconds)
+    rsize: usize, // record size (doubles)
+    n: usize,     // number of records
+}
+
+///  SPK (Spacecraft and Planet Kernel) ephemeris file
+///
+///  A reader for JPL planetary ephemerides (DE405, DE430, DE440 etc.)
+///  in the NAIF binary SPK format.  Chebyshev records are read from the
+///  file as needed, the most recent record of each segment being kept.
+///
+///  Notes:
+///
+///  1) Segment types 2 (Chebyshev position) and 3 (Chebyshev position
+///     and velocity) are evaluated.  Segments of other types are listed
+///     but cannot be used.
+///
+///  2) Where more than one segment covers a body and epoch, the one
+///     later in the file is used, as in the NAIF toolkit.
+///
+///  3) Positions and velocities from pv are in km and km/s, with
+///     respect to the frame of the segments, which for the JPL DE
+///     ephemerides is the ICRF.  The Ephemeris implementation returns
+///     barycentric vectors in au and au/day, using the IAU 2012 value
+///     of the au;  these can be used directly with iauApci, iauApco and
+///     the other astrometry functions, and to build the IauLdBody array
+///     for iauLdn.
+///
+///  4) Target codes are those of NAIF:  0 = solar-system barycenter,
+///     1-9 = planetary-system barycenters, 10 = Sun, 199, 299, 399,
+///     499 = Mercury, Venus, Earth, Mars, 301 = Moon.
+///
+///  5) In the Ephemeris implementation, Mercury, Venus and Mars are
+///     taken from their system barycenters if the file has no segment
+///     for the planet itself.  The Earth and the Moon are not:  a file
+///     without them gives status -2.
+///
+///  Reference:
+///
+///     NAIF, SPK Required Reading, NASA/JPL.
+#[derive(Debug)]
+#[allow(clippy::type_complexity)]
+pub struct Spk<R = BufReader<File>> {
+    daf: RefCell<Daf<R>>,
+    segments: Vec<SpkSegment>,
+    cache: RefCell<Vec<Option<(usize, Vec<f64>)>>>,
+}
+
+impl Spk {
+    /// Open an SPK file.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Spk::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read + Seek> Spk<R> {
+    /// Read the segment directory of an SPK file.
+    pub fn new(reader: R) -> io::Result<Self> {
+        let mut daf = Daf::new(reader)?;
+        if !(daf.idword == "DAF/SPK" || daf.idword == "NAIF/DAF") || daf.nd != 2 || daf.ni != 6 {
+            return Err(invalid("not an SPK file"));
+        }
+
+        let mut segments = Vec::with_capacity(daf.summaries.len());
+        for s in daf.summaries.clone() {
+            let (begin, end) = (s.ic[4].max(0) as usize, s.ic[5].max(0) as usize);
+            segments.push(SpkSegment {
+                cheb: Chebyshev::new(&mut daf, s.ic[3], begin, end)?,
+                name: s.name,
+                target: s.ic[0],
+                center: s.ic[1],
+                frame: s.ic[2],
+                kind: s.ic[3],
+                start: s.dc[0],
+                end: s.dc[1],
+            });
+        }
+
+        Ok(Spk {
+            daf: RefCell::new(daf),
+            cache: RefCell::new(vec![None; segments.len()]),
+            segments,
+        })
+    }
+
+    /// The segments, in file order.
+    pub fn segments(&self) -> &[SpkSegment] {
+        &self.segments
+    }
+
+    ///  Position and velocity of one body relative to another
+    ///
+    ///  Given:
+    ///  ```text
+    ///     target  i32    NAIF code of the target
+    ///     center  i32    NAIF code of the center
+    ///     date1   f64    TDB as a 2-part...
+    ///     date2   f64    ...Julian Date
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok(pv)  [[f64; 3]; 2]  position/velocity (km, km/s)
+    ///     Err(j)  i32            -1 = epoch not covered
+    ///                            -2 = body not in the file
+    ///                            -3 = read error or unusable segment
+    ///  ```
+    ///  Note:
+    ///
+    ///     Both bodies are referred to the solar-system barycenter by
+    ///     following the chain of segment centers, so any pair of
+    ///     bodies in the file can be used.
+    pub fn pv(
+        &self,
+        target: i32,
+        center: i32,
+        date1: f64,
+        date2: f64,
+    ) -> Result<[[f64; 3]; 2], i32> {
+        let et = ((date1 - DJ00) + date2) * DAYSEC;
+        let t = self.pvssb(target, et)?;
+        let c = self.pvssb(center, et)?;
+        Ok(pvmpv(&t, &c))
+    }
+
+    /// Barycentric pv of a body at et (TDB seconds from J2000.0).
+    fn pvssb(&self, body: i32, et: f64) -> Result<[[f64; 3]; 2], i32> {
+        let mut pv = [[0.0; 3]; 2];
+        let mut b = body;
+        for _ in 0..MAXCHAIN {
+            if b == 0 {
+                return Ok(pv);
+            }
+            let k = self.find(b, et)?;
+            pv = pvppv(&pv, &self.eval(k, et)?);
+            b = self.segments[k].center;
+        }
+        Err(-3)
+    }
+
+    /// Index of the segment for a body at et.
+    fn find(&self, body: i32, et: f64) -> Result<usize, i32> {
+        let mut found = false;
+        for (k, s) in self.segments.iter().enumerate().rev() {
+            if s.target == body {
+                found = true;
+                if (s.start..=s.end).contains(&et) {
+                    return Ok(k);
+                }
+            }
+        }
+        Err(if found { -1 } else { -2 })
+    }
+
+    /// Evaluate segment k at et (km, km/s).
+    fn eval(&self, k: usize, et: f64) -> Result<[[f64; 3]; 2], i32> {
+        let cheb = self.segments[k].cheb.as_ref().ok_or(-3)?;
+        cheb.eval(
+            &mut self.daf.borrow_mut(),
+            &mut self.cache.borrow_mut()[k],
+            et,
+        )
+    }
+}
+
+impl Chebyshev {
+    /// Read the directory at the end of the array at addresses begin
+    /// to end;  None if the array is not of type 2 or 3.
+    pub(crate) fn new<R: Read + Seek>(
+        daf: &mut Daf<R>,
+        kind: i32,
+        begin: usize,
+        end: usize,
+    ) -> io::Result<Option<Self>> {
+        if kind != 2 && kind != 3 {
+            return Ok(None);
+        }
+
+        /* The array ends with INIT, INTLEN, RSIZE, N. */
+        if end < begin + 3 {
+            return Err(invalid("bad segment addresses"));
+        }
+        let t = daf.read(end - 3, end)?;
+        let c = Chebyshev {
+            kind,
+            begin,
+            init: t[0],
+            intlen: t[1],
+            rsize: t[2] as usize,
+            n: t[3] as usize,
+        };
+        let ncomp = if kind == 2 { 3 } else { 6 };
+        if c.intlen <= 0.0
+            || c.rsize < 2 + ncomp
+            || c.n == 0
+            || c.rsize.checked_mul(c.n).is_none_or(|m| m > end - 3 - begin)
+        {
+            return Err(invalid("bad segment directory"));
+        }
+        Ok(Some(c))
+    }
+
+    /// Evaluate at et (TDB seconds from J2000.0) the three components
+    /// and their rates per second, keeping the last record read in
+    /// cache.
+    pub(crate) fn eval<R: Read + Seek>(
+        &self,
+        daf: &mut Daf<R>,
+        cache: &mut Option<(usize, Vec<f64>)>,
+        et: f64,
+    ) -> Result<[[f64; 3]; 2], i32> {
+        /* The record, from the cache or the file. */
+        let i = (((et - self.init) / self.intlen).floor().max(0.0) as usize).min(self.n - 1);
+        let rec = match cache {
+            Some((j, rec)) if *j == i => rec,
+            _ => {
+                let a = self.begin + i * self.rsize;
+                let rec = daf.read(a, a + self.rsize - 1).map_err(|_| -3)?;
+                &cache.insert((i, rec)).1
+            }
+        };
+
+        /* Normalized time. */
+        let (mid, radius) = (rec[0], rec[1]);
+        let x = (et - mid) / radius;
+
+        let mut pv = [[0.0; 3]; 2];
+        if self.kind == 2 {
+            let nc = (self.rsize - 2) / 3;
+            for j in 0..3 {
+                let c = &rec[2 + j * nc..2 + (j + 1) * nc];
+                let (p, dp) = cheby(c, x);
+                pv[0][j] = p;
+                pv[1][j] = dp / radius;
+            }
+        } else {
+            let nc = (self.rsize - 2) / 6;
+            for j in 0..6 {
+                let c = &rec[2 + j * nc..2 + (j + 1) * nc];
+                pv[j / 3][j % 3] = cheby(c, x).0;
+            }
+        }
+        Ok(pv)
+    }
+}
+
+impl<R: Read + Seek> Ephemeris for Spk<R> {
+    fn pvb(&self, target: Target, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+        let naif = target.naif();
+        let pv = match self.pv(naif, 0, date1, date2) {
+            /* Inner planets may be present only as system barycenters. */
+            Err(-2) if matches!(naif, 199 | 299 | 499) => self.pv(naif / 100, 0, date1, date2)?,
+            r => r?,
+        };
+
+        /* km, km/s to au, au/day. */
+        let au = DAU / 1e3;
+        Ok([pv[0].map(|x| x / au), pv[1].map(|x| x * DAYSEC / au)])
+    }
+}
+
+/// Chebyshev series and its derivative at x.
+pub(crate) fn cheby(c: &[f64], x: f64) -> (f64, f64) {
+    let (mut t0, mut t1) = (1.0, x);
+    let (mut d0, mut d1) = (0.0, 1.0);
+    let mut p = c[0];
+    let mut dp = 0.0;
+    if c.len() > 1 {
+        p += c[1] * x;
+        dp += c[1];
+    }
+    for &ck in c.iter().skip(2) {
+        let t2 = 2.0 * x * t1 - t0;
+        let d2 = 2.0 * t1 + 2.0 * x * d1 - d0;
+        p += ck * t2;
+        dp += ck * d2;
+        (t0, t1) = (t1, t2);
+        (d0, d1) = (d1, d2);
+    }
+    (p, dp)
+}
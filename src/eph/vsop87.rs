@@ -0,0 +1,469 @@
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use super::daf::invalid;
+use super::{Ephemeris, Epv00, Target};
+use crate::consts::{DJ00, DJM};
+use crate::coords::ecm06;
+use crate::vm::{pvppv, rxp, rxpv, s2pv, trxp, trxpv};
+
+/// Terms (A, B, C) of each variable, by power of time.
+type Series = [Vec<Cow<'static, [[f64; 3]]>>; 3];
+
+/// Terms (A, B, C) of each variable by power of time, as const tables
+/// (see Vsop87::insert).
+pub type Vsop87Tables = [&'static [&'static [[f64; 3]]]; 3];
+
+/// VSOP87 dynamical ecliptic and equinox J2000.0 to FK5 J2000.0.
+const VSOPFK5: [[f64; 3]; 3] = [
+    [1.0, 0.000000440360, -0.000000190919],
+    [-0.000000479966, 0.917482137087, -0.397776982902],
+    [0.0, 0.397776982902, 0.917482137087],
+];
+
+/// The versions of VSOP87
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vsop87Version {
+    A, // heliocentric rectangular, ecliptic and equinox J2000.0
+    B, // heliocentric spherical, ecliptic and equinox J2000.0
+    C, // heliocentric rectangular, ecliptic and equinox of date
+    D, // heliocentric spherical, ecliptic and equinox of date
+    E, // barycentric rectangular, ecliptic and equinox J2000.0
+}
+
+impl Vsop87Version {
+    /// Whether the variables are L, B, R rather than X, Y, Z.
+    pub fn spherical(self) -> bool {
+        matches!(self, Vsop87Version::B | Vsop87Version::D)
+    }
+
+    /// Whether the frame is the ecliptic and equinox of date.
+    pub fn of_date(self) -> bool {
+        matches!(self, Vsop87Version::C | Vsop87Version::D)
+    }
+
+    /// Whether the origin is the solar-system barycenter.
+    pub fn barycentric(self) -> bool {
+        self == Vsop87Version::E
+    }
+}
+
+///  VSOP87 planetary theory
+///
+///  The semi-analytical theory of Bretagnon & Francou, evaluated from
+///  the series files distributed by the IMCCE (VSOP87A.ear,
+///  VSOP87D.mar etc.), one file per body.  The series are either read
+///  from the files or taken from const tables compiled into the
+///  program (see insert), in which case no file is needed at run time.
+///
+///  The series are not distributed with this crate.  They must be
+///  obtained from the IMCCE, then either read with load or written as
+///  const tables by examples/vsop87_tables.rs.  Until a body has been
+///  loaded its positions are not available (status -2).  Given the
+///  files, the test test_vsop87_chk compares the A and D series with
+///  the IMCCE check values (vsop87.chk):
+///  ```text
+///     VSOP87_DIR=dir cargo test --test eph_test -- --ignored
+///  ```
+///
+///  Notes:
+///
+///  1) The bodies are Mercury to Neptune, the Earth-Moon barycenter
+///     (version A only) and the Sun (version E only).
+///
+///  2) The time argument is TDB as a 2-part Julian Date.  The precision
+///     of the complete series is better than 1 arcsecond over 4000
+///     years either side of J2000.0 for Mercury to Mars, 2000 years for
+///     Jupiter and Saturn and 6000 years for Uranus and Neptune.
+///
+///  3) The series may be truncated by setting tol:  terms whose
+///     amplitude is less than tol (au or radians) are omitted.  The
+///     truncation is applied at evaluation time, so the complete
+///     series remain available and tol may be changed at any point.
+///
+///  4) pveq returns vectors in the same convention as iauPlan94:
+///     equatorial J2000.0, au and au/day.  For versions A, B and E the
+///     VSOP87 rotation to FK5 is used;  for C and D the ecliptic of date
+///     is referred to the ICRS by the IAU 2006 precession (see ecm06),
+///     whose ecliptic differs from that of VSOP87 by a few tens of
+///     milliarcseconds.
+///
+///  Reference:
+///
+///     Bretagnon, P. & Francou, G., 1988, Astron.Astrophys., 202, 309.
+///
+///     IMCCE, VSOP87 solution, file vsop87.doc.
+#[derive(Debug, Clone)]
+pub struct Vsop87 {
+    pub version: Vsop87Version, // version of the loaded series
+    pub tol: f64,               // truncation amplitude (0 = complete)
+    bodies: Vec<(Target, Series)>,
+}
+
+impl Vsop87 {
+    /// An empty theory of the given version.
+    pub fn new(version: Vsop87Version) -> Self {
+        Vsop87 {
+            version,
+            tol: 0.0,
+            bodies: Vec::new(),
+        }
+    }
+
+    /// Read the series files of one version.
+    pub fn load<P: AsRef<Path>>(version: Vsop87Version, files: &[P]) -> io::Result<Self> {
+        let mut vsop = Vsop87::new(version);
+        for f in files {
+            vsop.read(BufReader::new(File::open(f)?))?;
+        }
+        Ok(vsop)
+    }
+
+    ///  Read one VSOP87 series file
+    ///
+    ///  Each block of the file is introduced by a header line giving
+    ///  the version, body, variable, power of time and number of terms,
+    ///  for example
+    ///  ```text
+    ///   VSOP87 VERSION A1    EARTH     VARIABLE 1 (XYZ)       *T**0    843 TERMS ...
+    ///  ```
+    ///  and each term line ends with the amplitude A, phase B and
+    ///  frequency C of the term A cos(B + C T).
+    ///
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok(n)    usize      number of terms read
+    ///     Err(e)   io::Error  read or format error, or a file of
+    ///                         another version or body
+    ///  ```
+    pub fn read<R: BufRead>(&mut self, reader: R) -> io::Result<usize> {
+        let mut series: [Vec<Vec<[f64; 3]>>; 3] = Default::default();
+        let mut body = None;
+        let mut block: Option<(usize, usize)> = None;
+        let mut want = 0;
+        let mut nterms = 0;
+
+        for line in reader.lines() {
+            let line = line?;
+            let w: Vec<&str> = line.split_whitespace().collect();
+            if w.is_empty() {
+                continue;
+            }
+
+            /* Block header. */
+            if w[0] == "VSOP87" {
+                if want != 0 {
+                    return Err(invalid("incomplete block"));
+                }
+                let (version, target, ic, it, n) = header(&w)?;
+                if version != self.version {
+                    return Err(invalid("file of another VSOP87 version"));
+                }
+                if body.is_some_and(|b| b != target) {
+                    return Err(invalid("more than one body in the file"));
+                }
+                body = Some(target);
+                let s = &mut series[ic];
+                if s.len() <= it {
+                    s.resize(it + 1, Vec::new());
+                }
+                s[it].reserve(n);
+                block = Some((ic, it));
+                want = n;
+                continue;
+            }
+
+            /* Term:  the last three fields are A, B, C. */
+            let (ic, it) = block.ok_or_else(|| invalid("term before header"))?;
+            if want == 0 || w.len() < 3 {
+                return Err(invalid("bad term line"));
+            }
+            let mut abc = [0.0; 3];
+            for (x, s) in abc.iter_mut().zip(&w[w.len() - 3..]) {
+                *x = s.parse().map_err(|_| invalid("bad number"))?;
+            }
+            series[ic][it].push(abc);
+            want -= 1;
+            nterms += 1;
+        }
+        if want != 0 {
+            return Err(invalid("incomplete block"));
+        }
+
+        let target = body.ok_or_else(|| invalid("no VSOP87 series"))?;
+        self.set(
+            target,
+            series.map(|v| v.into_iter().map(Cow::Owned).collect()),
+        );
+        Ok(nterms)
+    }
+
+    ///  Add the series of one body from const tables
+    ///
+    ///  Given:
+    ///  ```text
+    ///     target  Target        the body
+    ///     tables  Vsop87Tables  terms A, B, C of each variable, by
+    ///                           power of T (Note 1)
+    ///  ```
+    ///  Notes:
+    ///
+    ///  1) The tables are those of one VSOP87 file of the version of
+    ///     the theory, in file order, as returned by terms.  They are
+    ///     used in place, without copying.
+    ///
+    ///  2) Any series already held for the body are replaced.
+    pub fn insert(&mut self, target: Target, tables: &Vsop87Tables) {
+        self.set(
+            target,
+            tables.map(|v| v.iter().map(|t| Cow::Borrowed(*t)).collect()),
+        );
+    }
+
+    /// The terms A, B, C of variable ic (0-2) and power of time it,
+    /// if loaded.
+    pub fn terms(&self, target: Target, ic: usize, it: usize) -> Option<&[[f64; 3]]> {
+        let (_, series) = self.bodies.iter().find(|(b, _)| *b == target)?;
+        series.get(ic)?.get(it).map(|t| t.as_ref())
+    }
+
+    /// Store the series of a body, replacing any already held.
+    fn set(&mut self, target: Target, series: Series) {
+        match self.bodies.iter_mut().find(|(b, _)| *b == target) {
+            Some((_, s)) => *s = series,
+            None => self.bodies.push((target, series)),
+        }
+    }
+
+    /// Whether the series for a body have been loaded.
+    pub fn has(&self, target: Target) -> bool {
+        self.bodies.iter().any(|(b, _)| *b == target)
+    }
+
+    ///  The VSOP87 variables of a body and their rates
+    ///
+    ///  Given:
+    ///  ```text
+    ///     target  Target   the body
+    ///     date1   f64      TDB as a 2-part...
+    ///     date2   f64      ...Julian Date
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok(v)   [[f64; 3]; 2]  X, Y, Z (au) or L, B, R (rad, rad,
+    ///                            au), and their rates per day
+    ///     Err(j)  i32            -2 = body not loaded
+    ///  ```
+    pub fn variables(&self, target: Target, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+        let (_, series) = self.bodies.iter().find(|(b, _)| *b == target).ok_or(-2)?;
+
+        /* Thousands of Julian years since J2000.0. */
+        let t = ((date1 - DJ00) + date2) / DJM;
+
+        let mut v = [[0.0; 3]; 2];
+        for (ic, blocks) in series.iter().enumerate() {
+            let mut tk = 1.0;
+            for (k, terms) in blocks.iter().enumerate() {
+                let (mut f, mut df) = (0.0, 0.0);
+                for &[a, b, c] in terms.iter().filter(|abc| abc[0].abs() >= self.tol) {
+                    let (s, co) = (b + c * t).sin_cos();
+                    f += a * co;
+                    df -= a * c * s;
+                }
+                v[0][ic] += tk * f;
+                v[1][ic] += tk * df;
+                if k > 0 {
+                    v[1][ic] += k as f64 * t.powi(k as i32 - 1) * f;
+                }
+                tk *= t;
+            }
+        }
+        v[1] = v[1].map(|x| x / DJM);
+        Ok(v)
+    }
+
+    ///  Position and velocity of a body, ecliptic J2000.0
+    ///
+    ///  Given:
+    ///  ```text
+    ///     target  Target   the body
+    ///     date1   f64      TDB as a 2-part...
+    ///     date2   f64      ...Julian Date
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok((pv, j))  ([[f64; 3]; 2], i32)
+    ///                   pv  heliocentric (barycentric for version E)
+    ///                       position/velocity, ecliptic and equinox
+    ///                       J2000.0 (au, au/day)
+    ///                   j   0 = OK
+    ///                      +1 = warning: date outside the range of
+    ///                           validity of the theory
+    ///     Err(j)  i32   -2 = body not loaded
+    ///  ```
+    ///  Note:
+    ///
+    ///     For versions A, B and E the frame is the VSOP87 dynamical
+    ///     ecliptic;  for C and D it is the IAU 2006 ecliptic of J2000.0.
+    pub fn pvecl(
+        &self,
+        target: Target,
+        date1: f64,
+        date2: f64,
+    ) -> Result<([[f64; 3]; 2], i32), i32> {
+        if self.version.of_date() {
+            let (pv, j) = self.pveq(target, date1, date2)?;
+            let mut e = [[0.0; 3]; 2];
+            rxpv(&ecm06(DJ00, 0.0), &pv, &mut e);
+            return Ok((e, j));
+        }
+        Ok((
+            self.rect(target, date1, date2)?,
+            self.status(target, date1, date2),
+        ))
+    }
+
+    ///  Position and velocity of a body, equatorial J2000.0
+    ///
+    ///  Given:
+    ///  ```text
+    ///     target  Target   the body
+    ///     date1   f64      TDB as a 2-part...
+    ///     date2   f64      ...Julian Date
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok((pv, j))  ([[f64; 3]; 2], i32)
+    ///                   pv  heliocentric (barycentric for version E)
+    ///                       position/velocity, equatorial J2000.0
+    ///                       (au, au/day)
+    ///                   j   0 = OK
+    ///                      +1 = warning: date outside the range of
+    ///                           validity of the theory
+    ///     Err(j)  i32   -2 = body not loaded
+    ///  ```
+    ///  Note:
+    ///
+    ///     The convention is that of iauPlan94, which this function can
+    ///     replace where better accuracy is required.
+    pub fn pveq(
+        &self,
+        target: Target,
+        date1: f64,
+        date2: f64,
+    ) -> Result<([[f64; 3]; 2], i32), i32> {
+        let e = self.rect(target, date1, date2)?;
+        let j = self.status(target, date1, date2);
+        let mut pv = [[0.0; 3]; 2];
+        if self.version.of_date() {
+            /* Ecliptic of date to ICRS, allowing for the rotation. */
+            let rm = ecm06(date1, date2);
+            trxpv(&rm, &e, &mut pv);
+            let (mut p1, mut p2) = ([0.0; 3], [0.0; 3]);
+            trxp(&ecm06(date1, date2 + 1.0), &e[0], &mut p1);
+            trxp(&ecm06(date1, date2 - 1.0), &e[0], &mut p2);
+            for (v, (a, b)) in pv[1].iter_mut().zip(p1.iter().zip(&p2)) {
+                *v += (a - b) / 2.0;
+            }
+        } else {
+            rxp(&VSOPFK5, &e[0], &mut pv[0]);
+            rxp(&VSOPFK5, &e[1], &mut pv[1]);
+        }
+        Ok((pv, j))
+    }
+
+    /// Rectangular pv in the frame of the version.
+    fn rect(&self, target: Target, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+        let v = self.variables(target, date1, date2)?;
+        Ok(if self.version.spherical() {
+            s2pv(v[0][0], v[0][1], v[0][2], v[1][0], v[1][1], v[1][2])
+        } else {
+            v
+        })
+    }
+
+    /// Warning status for dates outside the range of validity.
+    fn status(&self, target: Target, date1: f64, date2: f64) -> i32 {
+        let span = match target {
+            Target::Jupiter | Target::Saturn => 2000.0,
+            Target::Uranus | Target::Neptune => 6000.0,
+            _ => 4000.0,
+        };
+        let y = ((date1 - DJ00) + date2) / (DJM / 1000.0);
+        if y.abs() > span { 1 } else { 0 }
+    }
+}
+
+impl Ephemeris for Vsop87 {
+    fn pvb(&self, target: Target, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+        if target == Target::Ssb {
+            return Ok([[0.0; 3]; 2]);
+        }
+        if self.version.barycentric() {
+            return Ok(self.pveq(target, date1, date2)?.0);
+        }
+
+        /* Heliocentric theories:  the Sun from iauEpv00. */
+        let sb = Epv00.pvb(Target::Sun, date1, date2)?;
+        if target == Target::Sun {
+            return Ok(sb);
+        }
+        Ok(pvppv(&self.pveq(target, date1, date2)?.0, &sb))
+    }
+}
+
+/// Version, body, variable (0-2), power of T and number of terms
+/// from a block header.
+fn header(w: &[&str]) -> io::Result<(Vsop87Version, Target, usize, usize, usize)> {
+    let after = |key: &str| {
+        w.iter()
+            .position(|s| *s == key)
+            .and_then(|i| w.get(i + 1))
+            .copied()
+            .ok_or_else(|| invalid("bad block header"))
+    };
+    let version = match after("VERSION")?.chars().next() {
+        Some('A') => Vsop87Version::A,
+        Some('B') => Vsop87Version::B,
+        Some('C') => Vsop87Version::C,
+        Some('D') => Vsop87Version::D,
+        Some('E') => Vsop87Version::E,
+        _ => return Err(invalid("unsupported VSOP87 version")),
+    };
+    let target = match w
+        .iter()
+        .position(|s| *s == "VERSION")
+        .and_then(|i| w.get(i + 2))
+    {
+        Some(&"MERCURY") => Target::Mercury,
+        Some(&"VENUS") => Target::Venus,
+        Some(&"EARTH") => Target::Earth,
+        Some(&"EMB") => Target::Emb,
+        Some(&"MARS") => Target::Mars,
+        Some(&"JUPITER") => Target::Jupiter,
+        Some(&"SATURN") => Target::Saturn,
+        Some(&"URANUS") => Target::Uranus,
+        Some(&"NEPTUNE") => Target::Neptune,
+        Some(&"SUN") => Target::Sun,
+        _ => return Err(invalid("unknown body")),
+    };
+    let ic = after("VARIABLE")?
+        .parse::<usize>()
+        .ok()
+        .filter(|i| (1..=3).contains(i))
+        .ok_or_else(|| invalid("bad variable"))?;
+    let it = w
+        .iter()
+        .find_map(|s| s.strip_prefix("*T**"))
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| invalid("bad power of T"))?;
+    let n = w
+        .iter()
+        .position(|s| *s == "TERMS")
+        .filter(|&i| i > 0)
+        .and_then(|i| w[i - 1].parse().ok())
+        .ok_or_else(|| invalid("bad number of terms"))?;
+    Ok((version, target, ic - 1, it, n))
+}
@@ -0,0 +1,67 @@
+use super::FrameKind;
+
+/// Edges of the frame transformation graph.  Every edge can be
+/// traversed in either direction.
+pub const FRAME_EDGES: [(FrameKind, FrameKind); 9] = [
+    (FrameKind::Icrs, FrameKind::Gcrs),
+    (FrameKind::Gcrs, FrameKind::Cirs),
+    (FrameKind::Gcrs, FrameKind::Tete),
+    (FrameKind::Cirs, FrameKind::Itrs),
+    (FrameKind::Cirs, FrameKind::AltAz),
+    (FrameKind::Icrs, FrameKind::Galactic),
+    (FrameKind::Icrs, FrameKind::Ecliptic),
+    (FrameKind::Icrs, FrameKind::Fk5),
+    (FrameKind::Fk5, FrameKind::Fk4),
+];
+
+///  Shortest path between two frames
+///
+///  Breadth-first search of the frame transformation graph.
+///
+///  Given:
+///  ```text
+///     from   FrameKind   starting frame
+///     to     FrameKind   final frame
+///  ```
+///  Returned (function value):
+///  ```text
+///            Vec<FrameKind>   the frames visited, including both ends
+///  ```
+///  Note:
+///
+///     The graph is connected, so a path always exists.  If from and to
+///     are the same the path has a single element.
+pub fn frpath(from: FrameKind, to: FrameKind) -> Vec<FrameKind> {
+    let mut prev: Vec<(FrameKind, FrameKind)> = vec![(from, from)];
+    let mut queue = vec![from];
+    let mut i = 0;
+
+    /* Breadth-first search, recording the predecessor of each node. */
+    while i < queue.len() && !prev.iter().any(|&(k, _)| k == to) {
+        let node = queue[i];
+        i += 1;
+        for &(a, b) in FRAME_EDGES.iter() {
+            let next = if a == node {
+                b
+            } else if b == node {
+                a
+            } else {
+                continue;
+            };
+            if !prev.iter().any(|&(k, _)| k == next) {
+                prev.push((next, node));
+                queue.push(next);
+            }
+        }
+    }
+
+    /* Walk back from the destination. */
+    let mut path = vec![to];
+    let mut node = to;
+    while node != from {
+        node = prev.iter().find(|&&(k, _)| k == node).unwrap().1;
+        path.push(node);
+    }
+    path.reverse();
+    path
+}
@@ -0,0 +1,111 @@
+//! Celestial coordinate frames and transformations
+//!
+//! A [`SkyCoord`] is a direction (and optionally a distance and space
+//! motion) tagged with the [`Frame`] in which it is expressed.  The
+//! frames are the nodes of a transformation graph whose edges are
+//! implemented with the SOFA functions in `astro`, `coords`, `pnp` and
+//! `star`;  [`SkyCoord::transform_to`] finds the shortest path between
+//! two frames and applies each step in turn.
+//!
+//! ```text
+//!     FK4 --- FK5 --- ICRS --- Galactic
+//!                     |  \
+//!                     |   Ecliptic
+//!                     |
+//!            TETE --- GCRS --- CIRS --- ITRS
+//!                               |
+//!                             AltAz
+//! ```
+
+use crate::site::{Eop, Observer, Weather};
+
+mod graph;
+pub use graph::*;
+
+mod skycoord;
+pub use skycoord::*;
+
+mod xform;
+
+/// Coordinate frame, with the attributes needed to define it.
+///
+/// Epochs of observation (obstime) are UTC 2-part quasi Julian Dates,
+/// as used by the *13 functions in `astro`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Frame {
+    /// International Celestial Reference System (barycentric).
+    Icrs,
+    /// Geocentric Celestial Reference System (apparent direction).
+    Gcrs { obstime: (f64, f64) },
+    /// Celestial Intermediate Reference System.
+    Cirs { obstime: (f64, f64) },
+    /// True equator and true equinox of date.
+    Tete { obstime: (f64, f64) },
+    /// International Terrestrial Reference System.
+    Itrs { obstime: (f64, f64), eop: Eop },
+    /// Observed azimuth (N=0,E=90) and altitude.
+    AltAz {
+        obstime: (f64, f64),
+        eop: Eop,
+        observer: Observer,
+        weather: Weather,
+    },
+    /// IAU 1958 galactic coordinates.
+    Galactic,
+    /// Ecliptic and equinox of date, IAU 2006 (date is TT).
+    Ecliptic { equinox: (f64, f64) },
+    /// FK4, equinox B1950.0, observed at a Besselian epoch.
+    Fk4 { epoch: f64 },
+    /// FK5, at a Julian epoch equinox.
+    Fk5 { equinox: f64 },
+}
+
+/// The kind of a frame, ignoring its attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameKind {
+    Icrs,
+    Gcrs,
+    Cirs,
+    Tete,
+    Itrs,
+    AltAz,
+    Galactic,
+    Ecliptic,
+    Fk4,
+    Fk5,
+}
+
+impl Frame {
+    /// FK4 at the catalog epoch B1950.0.
+    pub const FK4_B1950: Frame = Frame::Fk4 { epoch: 1950.0 };
+
+    /// FK5 at equinox J2000.0.
+    pub const FK5_J2000: Frame = Frame::Fk5 { equinox: 2000.0 };
+
+    pub fn kind(&self) -> FrameKind {
+        match self {
+            Frame::Icrs => FrameKind::Icrs,
+            Frame::Gcrs { .. } => FrameKind::Gcrs,
+            Frame::Cirs { .. } => FrameKind::Cirs,
+            Frame::Tete { .. } => FrameKind::Tete,
+            Frame::Itrs { .. } => FrameKind::Itrs,
+            Frame::AltAz { .. } => FrameKind::AltAz,
+            Frame::Galactic => FrameKind::Galactic,
+            Frame::Ecliptic { .. } => FrameKind::Ecliptic,
+            Frame::Fk4 { .. } => FrameKind::Fk4,
+            Frame::Fk5 { .. } => FrameKind::Fk5,
+        }
+    }
+
+    /// UTC epoch of observation, for the Earth-based frames.
+    pub fn obstime(&self) -> Option<(f64, f64)> {
+        match *self {
+            Frame::Gcrs { obstime }
+            | Frame::Cirs { obstime }
+            | Frame::Tete { obstime }
+            | Frame::Itrs { obstime, .. }
+            | Frame::AltAz { obstime, .. } => Some(obstime),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate::vm::{s2c, seps, sxp};
+
+use super::{Frame, FrameKind, frpath, xform};
+
+/// Celestial coordinates tagged with their frame
+///
+/// Longitude and latitude are RA,Dec for the equatorial frames,
+/// azimuth (N=0,E=90) and altitude for AltAz, and l,b or lambda,beta for
+/// the galactic and ecliptic frames.  Distance, proper motion and
+/// radial velocity are optional;  proper motions are rates of change of
+/// longitude and latitude (radians/year, not cos(lat)*dlon/dt), as in
+/// the SOFA star catalog functions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkyCoord {
+    pub frame: Frame,
+    pub lon: f64,               // longitude (radians)
+    pub lat: f64,               // latitude (radians)
+    pub distance: Option<f64>,  // distance (au)
+    pub pm: Option<(f64, f64)>, // dlon/dt, dlat/dt (radians/year)
+    pub rv: Option<f64>,        // radial velocity (km/s, +ve receding)
+}
+
+impl SkyCoord {
+    pub fn new(frame: Frame, lon: f64, lat: f64) -> Self {
+        SkyCoord {
+            frame,
+            lon,
+            lat,
+            distance: None,
+            pm: None,
+            rv: None,
+        }
+    }
+
+    /// The same coordinate with a distance (au).
+    pub fn with_distance(self, distance: f64) -> Self {
+        SkyCoord {
+            distance: Some(distance),
+            ..self
+        }
+    }
+
+    /// The same coordinate with proper motion (radians/year) and radial
+    /// velocity (km/s).
+    pub fn with_motion(self, pmlon: f64, pmlat: f64, rv: f64) -> Self {
+        SkyCoord {
+            pm: Some((pmlon, pmlat)),
+            rv: Some(rv),
+            ..self
+        }
+    }
+
+    /// Cartesian position:  a unit vector, or in au if the distance is
+    /// known.
+    pub fn cartesian(&self) -> [f64; 3] {
+        sxp(self.distance.unwrap_or(1.0), &s2c(self.lon, self.lat))
+    }
+
+    ///  Transform to another frame
+    ///
+    ///  Given:
+    ///  ```text
+    ///     to     Frame   the frame required, with its attributes
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok(SkyCoord)   the coordinate in the new frame
+    ///     Err(j) j  i32  -1 = unacceptable date
+    ///  ```
+    ///  Notes:
+    ///
+    ///  1) The route is the shortest path through the frame graph (see
+    ///     frpath), each step using the corresponding SOFA functions:
+    ///  ```text
+    ///        ICRS <-> GCRS       iauApcg13, iauAtciq, iauAticq
+    ///        GCRS <-> CIRS       iauC2i06a
+    ///        GCRS <-> TETE       iauPnm06a
+    ///        CIRS <-> ITRS       iauEra00, iauPom00, iauSp00
+    ///        CIRS <-> AltAz      iauApio13, iauAtioq, iauAtoiq
+    ///        ICRS <-> Galactic   iauIcrs2g
+    ///        ICRS <-> Ecliptic   iauEcm06
+    ///        ICRS <-> FK5        iauFk52h, iauH2fk5, iauPmat76
+    ///        FK5  <-> FK4        iauFk425, iauFk524, iauFk45z, iauFk54z
+    ///  ```
+    ///  2) Intermediate frames take their attributes from the
+    ///     destination frame, or failing that the starting frame.  If
+    ///     the two ends are the same kind of frame, or are Earth-based
+    ///     frames with different obstimes, the transformation goes via
+    ///     the ICRS.
+    ///
+    ///  3) ICRS to GCRS gives the geocentric apparent direction, the
+    ///     space motion being applied from J2000.0 to the obstime.  The
+    ///     distance, if given, is used as the parallax.  The reverse
+    ///     step gives the astrometric place.
+    ///
+    ///  4) Proper motion is carried through the rotations but is
+    ///     dropped on entry to the ITRS and AltAz frames.  When the
+    ///     distance is known, topocentric parallax is applied between
+    ///     CIRS and AltAz.
+    pub fn transform_to(&self, to: &Frame) -> Result<SkyCoord, i32> {
+        let from = self.frame;
+        if from == *to {
+            return Ok(*self);
+        }
+
+        /* Decide whether to go via the ICRS. */
+        let hub = from.kind() == to.kind()
+            || matches!((from.obstime(), to.obstime()), (Some(a), Some(b)) if a != b);
+        if hub {
+            return self.transform_to(&Frame::Icrs)?.transform_to(to);
+        }
+
+        /* Shortest path, then one step at a time. */
+        let mut c = *self;
+        for kind in frpath(from.kind(), to.kind()).into_iter().skip(1) {
+            let next = if kind == to.kind() {
+                *to
+            } else {
+                intermediate(kind, &from, to)
+            };
+            c = xform::step(&c, &next)?;
+        }
+        Ok(c)
+    }
+
+    ///  Angular separation from another coordinate (radians), which is
+    ///  first transformed into the present frame.
+    pub fn separation(&self, other: &SkyCoord) -> Result<f64, i32> {
+        let o = other.transform_to(&self.frame)?;
+        Ok(seps(self.lon, self.lat, o.lon, o.lat))
+    }
+}
+
+/* Attributes for an intermediate frame. */
+fn intermediate(kind: FrameKind, from: &Frame, to: &Frame) -> Frame {
+    let obstime = to.obstime().or(from.obstime()).unwrap_or((0.0, 0.0));
+    match kind {
+        FrameKind::Gcrs => Frame::Gcrs { obstime },
+        FrameKind::Cirs => Frame::Cirs { obstime },
+        FrameKind::Fk5 => Frame::FK5_J2000,
+        _ => Frame::Icrs,
+    }
+}
@@ -0,0 +1,367 @@
+use crate::astro::{IauAstrom, apcg13, atciq, aticq, atioq, atoiq};
+use crate::cal::epj2jd;
+use crate::consts::{DAU, DPI, DR2AS};
+use crate::coords::{ecm06, icrs2g};
+use crate::erst::era00;
+use crate::pnp::{c2i06a, c2tcio, pmat76, pnm06a, pom00, sp00};
+use crate::star::{fk45z, fk52h, fk54z, fk425, fk524, h2fk5};
+use crate::ts::{taitt, utctai, utcut1};
+use crate::vm::{anp, c2s, ir, pdp, pm, pmp, ppp, rxp, s2c, sxp, tr};
+
+use super::{Frame, SkyCoord};
+
+/* UTC to TT. */
+fn utctt(utc: (f64, f64)) -> Result<(f64, f64), i32> {
+    let (tai1, tai2) = utctai(utc.0, utc.1)?;
+    taitt(tai1, tai2)
+}
+
+/* Rotate a coordinate, including any proper motion, into a new frame. */
+fn rotate(c: &SkyCoord, r: &[[f64; 3]; 3], frame: Frame) -> SkyCoord {
+    let p = s2c(c.lon, c.lat);
+    let mut rp = [0.0; 3];
+    rxp(r, &p, &mut rp);
+    let (lon, lat) = c2s(&rp);
+
+    let pm = c.pm.map(|(pr, pd)| {
+        /* Rate of change of the unit vector. */
+        let (sa, ca) = c.lon.sin_cos();
+        let (sd, cd) = c.lat.sin_cos();
+        let v = [
+            -sa * cd * pr - ca * sd * pd,
+            ca * cd * pr - sa * sd * pd,
+            cd * pd,
+        ];
+        let mut rv = [0.0; 3];
+        rxp(r, &v, &mut rv);
+
+        /* Back to rates in longitude and latitude. */
+        let w2 = rp[0] * rp[0] + rp[1] * rp[1];
+        if w2 > 0.0 {
+            (
+                (rp[0] * rv[1] - rp[1] * rv[0]) / w2,
+                (rv[2] - rp[2] * pdp(&rp, &rv)) / w2.sqrt(),
+            )
+        } else {
+            (0.0, 0.0)
+        }
+    });
+
+    SkyCoord {
+        frame,
+        lon: anp(lon),
+        lat,
+        distance: c.distance,
+        pm,
+        rv: c.rv,
+    }
+}
+
+/* Apply the transpose of a rotation. */
+fn unrotate(c: &SkyCoord, r: &[[f64; 3]; 3], frame: Frame) -> SkyCoord {
+    let mut rt = [[0.0; 3]; 3];
+    tr(r, &mut rt);
+    rotate(c, &rt, frame)
+}
+
+/* Star-independent parameters for a geocentric observer. */
+fn gcrs_astrom(obstime: (f64, f64)) -> Result<IauAstrom, i32> {
+    let (tt1, tt2) = utctt(obstime)?;
+    let mut astrom = IauAstrom::default();
+    apcg13(tt1, tt2, &mut astrom);
+    Ok(astrom)
+}
+
+/* ICRS to GCRS:  space motion, parallax, light deflection, aberration. */
+fn icrs2gcrs(c: &SkyCoord, to: Frame, obstime: (f64, f64)) -> Result<SkyCoord, i32> {
+    let mut astrom = gcrs_astrom(obstime)?;
+    let (pr, pd) = c.pm.unwrap_or((0.0, 0.0));
+    let px = c.distance.map_or(0.0, |d| DR2AS / d);
+    let (ri, di) = atciq(c.lon, c.lat, pr, pd, px, c.rv.unwrap_or(0.0), &mut astrom);
+    let distance = c
+        .distance
+        .map(|d| pm(pmp(&sxp(d, &s2c(c.lon, c.lat)), &astrom.eb)));
+    Ok(SkyCoord {
+        frame: to,
+        lon: ri,
+        lat: di,
+        distance,
+        ..*c
+    })
+}
+
+/* GCRS to ICRS (astrometric place). */
+fn gcrs2icrs(c: &SkyCoord, obstime: (f64, f64)) -> Result<SkyCoord, i32> {
+    let mut astrom = gcrs_astrom(obstime)?;
+    let (rc, dc) = aticq(c.lon, c.lat, &mut astrom);
+    let (lon, lat, distance) = match c.distance {
+        Some(d) => {
+            let p = ppp(&sxp(d, &s2c(rc, dc)), &astrom.eb);
+            let (a, b) = c2s(&p);
+            (anp(a), b, Some(pm(p)))
+        }
+        None => (rc, dc, None),
+    };
+    Ok(SkyCoord {
+        frame: Frame::Icrs,
+        lon,
+        lat,
+        distance,
+        ..*c
+    })
+}
+
+/* CIRS to terrestrial rotation matrix:  RPOM * R_3(ERA). */
+fn cirs2itrs_mat(obstime: (f64, f64), eop: &crate::site::Eop) -> Result<[[f64; 3]; 3], i32> {
+    let (tt1, tt2) = utctt(obstime)?;
+    let (ut11, ut12) = utcut1(obstime.0, obstime.1, eop.dut1)?;
+    let rpom = pom00(eop.xp, eop.yp, sp00(tt1, tt2));
+    let mut ri = [[0.0; 3]; 3];
+    ir(&mut ri);
+    Ok(c2tcio(&ri, era00(ut11, ut12), &rpom))
+}
+
+/* Observer's CIRS position (au), for topocentric parallax. */
+fn observer_cirs(to: &Frame) -> Result<[f64; 3], i32> {
+    match to {
+        Frame::AltAz {
+            obstime,
+            eop,
+            observer,
+            ..
+        } => {
+            let (tt1, tt2) = utctt(*obstime)?;
+            let (ut11, ut12) = utcut1(obstime.0, obstime.1, eop.dut1)?;
+            let pv = observer.pvtob(eop, sp00(tt1, tt2), era00(ut11, ut12))?;
+            Ok(sxp(1.0 / DAU, &pv[0]))
+        }
+        _ => Err(-1),
+    }
+}
+
+/* ICRS to FK5 at equinox J2000.0 and the reverse. */
+fn icrs2fk5(c: &SkyCoord, to: Frame, equinox: f64) -> SkyCoord {
+    let (pr, pd) = c.pm.unwrap_or((0.0, 0.0));
+    let px = c.distance.map_or(0.0, |d| DR2AS / d);
+    let (r5, d5, dr5, dd5, _, rv5) = h2fk5(c.lon, c.lat, pr, pd, px, c.rv.unwrap_or(0.0));
+    let c5 = SkyCoord {
+        frame: to,
+        lon: r5,
+        lat: d5,
+        pm: c.pm.map(|_| (dr5, dd5)),
+        rv: c.rv.map(|_| rv5),
+        ..*c
+    };
+    let (e1, e2) = epj2jd(equinox);
+    rotate(&c5, &pmat76(e1, e2), to)
+}
+
+fn fk52icrs(c: &SkyCoord, equinox: f64) -> SkyCoord {
+    let (e1, e2) = epj2jd(equinox);
+    let c5 = unrotate(c, &pmat76(e1, e2), Frame::Icrs);
+    let (pr, pd) = c5.pm.unwrap_or((0.0, 0.0));
+    let px = c5.distance.map_or(0.0, |d| DR2AS / d);
+    let (rh, dh, drh, ddh, _, rvh) = fk52h(c5.lon, c5.lat, pr, pd, px, c5.rv.unwrap_or(0.0));
+    SkyCoord {
+        frame: Frame::Icrs,
+        lon: rh,
+        lat: dh,
+        pm: c5.pm.map(|_| (drh, ddh)),
+        rv: c5.rv.map(|_| rvh),
+        ..c5
+    }
+}
+
+/* FK4 B1950.0 to FK5 and the reverse. */
+fn fk42fk5(c: &SkyCoord, to: Frame, epoch: f64, equinox: f64) -> SkyCoord {
+    let px = c.distance.map_or(0.0, |d| DR2AS / d);
+    let c5 = match c.pm {
+        Some((pr, pd)) => {
+            let (r, d, dr, dd, p, v) = fk425(c.lon, c.lat, pr, pd, px, c.rv.unwrap_or(0.0));
+            SkyCoord {
+                frame: to,
+                lon: r,
+                lat: d,
+                distance: c.distance.map(|_| DR2AS / p),
+                pm: Some((dr, dd)),
+                rv: c.rv.map(|_| v),
+            }
+        }
+        None => {
+            let (r, d) = fk45z(c.lon, c.lat, epoch);
+            SkyCoord {
+                frame: to,
+                lon: r,
+                lat: d,
+                ..*c
+            }
+        }
+    };
+    let (e1, e2) = epj2jd(equinox);
+    rotate(&c5, &pmat76(e1, e2), to)
+}
+
+fn fk52fk4(c: &SkyCoord, to: Frame, epoch: f64, equinox: f64) -> SkyCoord {
+    let (e1, e2) = epj2jd(equinox);
+    let c5 = unrotate(c, &pmat76(e1, e2), to);
+    match c5.pm {
+        Some((pr, pd)) => {
+            let px = c5.distance.map_or(0.0, |d| DR2AS / d);
+            let (r, d, dr, dd, p, v) = fk524(c5.lon, c5.lat, pr, pd, px, c5.rv.unwrap_or(0.0));
+            SkyCoord {
+                frame: to,
+                lon: r,
+                lat: d,
+                distance: c5.distance.map(|_| DR2AS / p),
+                pm: Some((dr, dd)),
+                rv: c5.rv.map(|_| v),
+            }
+        }
+        None => {
+            let (r, d, _, _) = fk54z(c5.lon, c5.lat, epoch);
+            SkyCoord {
+                frame: to,
+                lon: r,
+                lat: d,
+                ..c5
+            }
+        }
+    }
+}
+
+/* Galactic rotation matrix, from iauIcrs2g applied to the ICRS axes. */
+fn icrs2g_mat() -> [[f64; 3]; 3] {
+    let mut r = [[0.0; 3]; 3];
+    for (j, e) in [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]
+        .iter()
+        .enumerate()
+    {
+        let (a, b) = c2s(e);
+        let (l, m) = icrs2g(a, b);
+        let g = s2c(l, m);
+        for (i, row) in r.iter_mut().enumerate() {
+            row[j] = g[i];
+        }
+    }
+    r
+}
+
+/* A single step along an edge of the frame graph. */
+pub(super) fn step(c: &SkyCoord, to: &Frame) -> Result<SkyCoord, i32> {
+    let to = *to;
+    match (c.frame, to) {
+        /* ICRS <-> GCRS. */
+        (Frame::Icrs, Frame::Gcrs { obstime }) => icrs2gcrs(c, to, obstime),
+        (Frame::Gcrs { obstime }, Frame::Icrs) => gcrs2icrs(c, obstime),
+
+        /* GCRS <-> CIRS. */
+        (Frame::Gcrs { .. }, Frame::Cirs { obstime }) => {
+            let (tt1, tt2) = utctt(obstime)?;
+            Ok(rotate(c, &c2i06a(tt1, tt2), to))
+        }
+        (Frame::Cirs { obstime }, Frame::Gcrs { .. }) => {
+            let (tt1, tt2) = utctt(obstime)?;
+            Ok(unrotate(c, &c2i06a(tt1, tt2), to))
+        }
+
+        /* GCRS <-> TETE. */
+        (Frame::Gcrs { .. }, Frame::Tete { obstime }) => {
+            let (tt1, tt2) = utctt(obstime)?;
+            Ok(rotate(c, &pnm06a(tt1, tt2), to))
+        }
+        (Frame::Tete { obstime }, Frame::Gcrs { .. }) => {
+            let (tt1, tt2) = utctt(obstime)?;
+            Ok(unrotate(c, &pnm06a(tt1, tt2), to))
+        }
+
+        /* CIRS <-> ITRS (the space motion is not carried over). */
+        (Frame::Cirs { .. }, Frame::Itrs { obstime, eop }) => {
+            let r = cirs2itrs_mat(obstime, &eop)?;
+            let c = SkyCoord { pm: None, ..*c };
+            Ok(rotate(&c, &r, to))
+        }
+        (Frame::Itrs { obstime, eop }, Frame::Cirs { .. }) => {
+            let r = cirs2itrs_mat(obstime, &eop)?;
+            Ok(unrotate(c, &r, to))
+        }
+
+        /* CIRS <-> observed. */
+        (
+            Frame::Cirs { .. },
+            Frame::AltAz {
+                obstime,
+                eop,
+                observer,
+                weather,
+            },
+        ) => {
+            let astrom = observer.apio13(obstime.0, obstime.1, &eop, &weather)?;
+            let (mut ri, mut di, mut distance) = (c.lon, c.lat, c.distance);
+            if let Some(d) = c.distance {
+                let p = pmp(&sxp(d, &s2c(ri, di)), &observer_cirs(&to)?);
+                (ri, di) = c2s(&p);
+                distance = Some(pm(p));
+            }
+            let (aob, zob, _, _, _) = atioq(ri, di, &astrom);
+            Ok(SkyCoord {
+                frame: to,
+                lon: aob,
+                lat: DPI / 2.0 - zob,
+                distance,
+                pm: None,
+                rv: c.rv,
+            })
+        }
+        (
+            Frame::AltAz {
+                obstime,
+                eop,
+                observer,
+                weather,
+            },
+            Frame::Cirs { .. },
+        ) => {
+            let astrom = observer.apio13(obstime.0, obstime.1, &eop, &weather)?;
+            let (ri, di) = atoiq("A", c.lon, DPI / 2.0 - c.lat, &astrom);
+            let (lon, lat, distance) = match c.distance {
+                Some(d) => {
+                    let p = ppp(&sxp(d, &s2c(ri, di)), &observer_cirs(&c.frame)?);
+                    let (a, b) = c2s(&p);
+                    (anp(a), b, Some(pm(p)))
+                }
+                None => (ri, di, None),
+            };
+            Ok(SkyCoord {
+                frame: to,
+                lon,
+                lat,
+                distance,
+                pm: None,
+                rv: c.rv,
+            })
+        }
+
+        /* ICRS <-> Galactic. */
+        (Frame::Icrs, Frame::Galactic) => Ok(rotate(c, &icrs2g_mat(), to)),
+        (Frame::Galactic, Frame::Icrs) => Ok(unrotate(c, &icrs2g_mat(), to)),
+
+        /* ICRS <-> ecliptic of date. */
+        (Frame::Icrs, Frame::Ecliptic { equinox }) => {
+            Ok(rotate(c, &ecm06(equinox.0, equinox.1), to))
+        }
+        (Frame::Ecliptic { equinox }, Frame::Icrs) => {
+            Ok(unrotate(c, &ecm06(equinox.0, equinox.1), to))
+        }
+
+        /* ICRS <-> FK5. */
+        (Frame::Icrs, Frame::Fk5 { equinox }) => Ok(icrs2fk5(c, to, equinox)),
+        (Frame::Fk5 { equinox }, Frame::Icrs) => Ok(fk52icrs(c, equinox)),
+
+        /* FK4 <-> FK5. */
+        (Frame::Fk4 { epoch }, Frame::Fk5 { equinox }) => Ok(fk42fk5(c, to, epoch, equinox)),
+        (Frame::Fk5 { equinox }, Frame::Fk4 { epoch }) => Ok(fk52fk4(c, to, epoch, equinox)),
+
+        /* Not an edge of the graph. */
+        _ => Err(-1),
+    }
+}
@@ -206,15 +206,24 @@
 //! }
 //! ```
 //!
+pub mod almanac;
 pub mod astro;
 pub mod cal;
 pub mod consts;
 pub mod coords;
+pub mod eclipse;
 pub mod eph;
 pub mod erst;
+pub mod frames;
 pub mod fundargs;
+pub mod orbit;
+pub mod physical;
 pub mod projection;
+pub mod satellites;
+pub mod site;
+pub mod solar;
 pub mod pnp;
 pub mod star;
+pub mod track;
 pub mod ts;
 pub mod vm;
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use super::Observation;
+use crate::cal::cal2jd;
+use crate::consts::{DAS2R, DAU, DAYSEC, DD2R};
+use crate::eph::invalid;
+
+/// UTC quasi-JD from an ADES time, "2016-08-29T12:23:34.12Z".
+fn isotime(s: &str) -> Option<[f64; 2]> {
+    let (d, t) = s.trim_end_matches('Z').split_once('T')?;
+    let d: Vec<i32> = d
+        .split('-')
+        .map(|x| x.parse().ok())
+        .collect::<Option<_>>()?;
+    let t: Vec<f64> = t
+        .split(':')
+        .map(|x| x.parse().ok())
+        .collect::<Option<_>>()?;
+    if d.len() != 3 || t.len() != 3 {
+        return None;
+    }
+    let (u1, u2) = cal2jd(d[0], d[1], d[2]).ok()?;
+    Some([u1, u2 + (t[0] * 3600.0 + t[1] * 60.0 + t[2]) / DAYSEC])
+}
+
+impl Observation {
+    ///  Read optical observations in the ADES PSV format
+    ///
+    ///  Notes:
+    ///
+    ///  1) The pipe-separated form of the IAU Astrometry Data Exchange
+    ///     Standard is read.  Lines beginning with '#' (header
+    ///     sections) or '!' (header keywords, such as "! mpcCode 568")
+    ///     are skipped;  the first other line after them names the
+    ///     columns, in any order.  The columns obsTime, ra, dec and stn
+    ///     are required.
+    ///
+    ///  2) The designation is permID if present, else provID, else
+    ///     trkSub.  The kind is 'S' for an observer with a geocentric
+    ///     ICRF position (sys ICRF_KM or ICRF_AU, ctr 399), 'V' for a
+    ///     roving observer (sys WGS84), and otherwise the first letter
+    ///     of mode.
+    ///
+    ///  3) obsTime is UTC, returned as a SOFA quasi-JD.  The
+    ///     uncertainties rmsRA (which includes cos(dec)) and rmsDec are
+    ///     converted to radians.
+    ///
+    ///  4) Radar observations are not read.
+    ///
+    ///  Reference:
+    ///
+    ///     IAU Minor Planet Center, "ADES:  Astrometry Data Exchange
+    ///     Standard", https://minorplanetcenter.net/iau/info/ADES.html
+    pub fn read_ades<R: BufRead>(reader: R) -> io::Result<Vec<Self>> {
+        let mut obs = Vec::new();
+        let mut cols: Option<HashMap<String, usize>> = None;
+        for line in reader.lines() {
+            let line = line?;
+            if line.starts_with('#') || line.starts_with('!') {
+                cols = None;
+                continue;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            let w: Vec<&str> = line.split('|').map(str::trim).collect();
+            let Some(c) = &cols else {
+                cols = Some(
+                    w.iter()
+                        .enumerate()
+                        .map(|(i, k)| (k.to_string(), i))
+                        .collect(),
+                );
+                continue;
+            };
+            let get = |k: &str| c.get(k).and_then(|&i| w.get(i)).copied().unwrap_or("");
+            let num = |k: &str| get(k).parse::<f64>().ok();
+
+            let desig = [get("permID"), get("provID"), get("trkSub")]
+                .into_iter()
+                .find(|s| !s.is_empty())
+                .unwrap_or("")
+                .to_string();
+            let utc = isotime(get("obsTime")).ok_or_else(|| invalid("bad obsTime"))?;
+            let ra = num("ra").ok_or_else(|| invalid("bad ra"))? * DD2R;
+            let dec = num("dec").ok_or_else(|| invalid("bad dec"))? * DD2R;
+            let code = get("stn");
+            if code.is_empty() {
+                return Err(invalid("missing stn"));
+            }
+            let rms = match (num("rmsRA"), num("rmsDec")) {
+                (Some(a), Some(d)) => Some([a * DAS2R, d * DAS2R]),
+                _ => None,
+            };
+
+            /* Observer position, if any. */
+            let mut kind = get("mode").chars().next().unwrap_or(' ');
+            let (mut pos, mut site) = (None, None);
+            let p = [num("pos1"), num("pos2"), num("pos3")];
+            let sys = get("sys");
+            if !sys.is_empty() {
+                let [Some(x), Some(y), Some(z)] = p else {
+                    return Err(invalid("missing pos1-3"));
+                };
+                match sys {
+                    "ICRF_KM" | "ICRF_AU" => {
+                        if get("ctr") != "399" {
+                            return Err(invalid("unsupported ctr"));
+                        }
+                        let u = if sys == "ICRF_KM" { 1e3 / DAU } else { 1.0 };
+                        pos = Some([x * u, y * u, z * u]);
+                        kind = 'S';
+                    }
+                    "WGS84" => {
+                        site = Some([x * DD2R, y * DD2R, z]);
+                        kind = 'V';
+                    }
+                    _ => return Err(invalid("unsupported sys")),
+                }
+            }
+
+            obs.push(Observation {
+                desig,
+                disc: get("disc") == "*",
+                note1: get("notes").chars().next().unwrap_or(' '),
+                kind,
+                utc,
+                ra,
+                dec,
+                rms,
+                mag: num("mag"),
+                band: get("band").to_string(),
+                code: code.to_string(),
+                pos,
+                site,
+            });
+        }
+        Ok(obs)
+    }
+
+    /// Read an ADES PSV observation file.
+    pub fn open_ades<P: AsRef<Path>>(path: P) -> io::Result<Vec<Self>> {
+        Observation::read_ades(BufReader::new(File::open(path)?))
+    }
+}
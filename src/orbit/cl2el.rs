@@ -0,0 +1,57 @@
+use super::Elements;
+
+///  Cometary elements from classical (mean anomaly) elements
+///
+///  Given:
+///  ```text
+///     a      f64     semi-major axis (au, negative for e > 1)
+///     e      f64     eccentricity (not 1)
+///     incl   f64     inclination (radians)
+///     node   f64     longitude of the ascending node (radians)
+///     argp   f64     argument of perihelion (radians)
+///     ma     f64     mean anomaly at the epoch (radians)
+///     date1  f64     epoch (TDB) as a 2-part...
+///     date2  f64     ...Julian Date
+///     gm     f64     gravitational parameter (au^3/d^2)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(el) Elements  cometary elements
+///     Err(j) i32       -1 = illegal a, e or gm
+///  ```
+///  Notes:
+///
+///  1) The time of perihelion is epoch - ma/n, where n is the mean
+///     motion, so that the perihelion used is the one selected by the
+///     given mean anomaly.
+///
+///  2) Parabolic orbits have no semi-major axis or mean anomaly and
+///     must be given in cometary form.
+#[allow(clippy::too_many_arguments)]
+pub fn cl2el(
+    a: f64,
+    e: f64,
+    incl: f64,
+    node: f64,
+    argp: f64,
+    ma: f64,
+    date1: f64,
+    date2: f64,
+    gm: f64,
+) -> Result<Elements, i32> {
+    if e < 0.0 || gm <= 0.0 || a == 0.0 || (e < 1.0) != (a > 0.0) {
+        return Err(-1);
+    }
+
+    /* Mean motion (radians per day). */
+    let n = (gm / (a * a * a).abs()).sqrt();
+
+    Ok(Elements {
+        q: a * (1.0 - e),
+        e,
+        incl,
+        node,
+        argp,
+        tp: (date1 - ma / n) + date2,
+    })
+}
@@ -0,0 +1,238 @@
+use super::{Elements, Sight, ecl2000, flat, pv2el, unflat};
+use crate::vm::{anpm, pm, rxpv};
+
+/// A least-squares orbit and its uncertainty
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrbitFit {
+    pub epoch: [f64; 2],      // epoch (TDB, 2-part JD)
+    pub pv: [[f64; 3]; 2],    // heliocentric p,v (ICRS, au, au/d)
+    pub cov: [[f64; 6]; 6],   // covariance of x, y, z, vx, vy, vz
+    pub el: Elements,         // elements (ecliptic and equinox J2000.0)
+    pub elcov: [[f64; 6]; 6], // covariance of q, e, incl, node, argp, tp
+    pub resid: Vec<[f64; 2]>, // residuals in RA cos(dec), dec (radians)
+    pub rms: f64,             // r.m.s. of the normalized residuals
+    pub niter: usize,         // number of iterations
+}
+
+/* Normalized residuals of all the observations. */
+fn residuals(s: &[Sight], x: &[f64; 6], d1: f64, d2: f64, gm: f64) -> Result<Vec<f64>, i32> {
+    let pv = unflat(x);
+    let mut r = Vec::with_capacity(2 * s.len());
+    for o in s {
+        let (ra, dec, _) = o.predict(&pv, d1, d2, gm)?;
+        r.push(anpm(o.ra - ra) * dec.cos() / o.sigma[0]);
+        r.push((o.dec - dec) / o.sigma[1]);
+    }
+    Ok(r)
+}
+
+/* Inverse of a symmetric positive-definite 6x6 matrix. */
+fn inv6(a: &[[f64; 6]; 6]) -> Option<[[f64; 6]; 6]> {
+    let mut m = *a;
+    let mut v = [[0.0; 6]; 6];
+    for (i, row) in v.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+    for c in 0..6 {
+        let p = (c..6).max_by(|&i, &j| m[i][c].abs().total_cmp(&m[j][c].abs()))?;
+        if m[p][c] == 0.0 || !m[p][c].is_finite() {
+            return None;
+        }
+        m.swap(c, p);
+        v.swap(c, p);
+        let d = m[c][c];
+        for k in 0..6 {
+            m[c][k] /= d;
+            v[c][k] /= d;
+        }
+        for i in 0..6 {
+            if i != c {
+                let f = m[i][c];
+                for k in 0..6 {
+                    m[i][k] -= f * m[c][k];
+                    v[i][k] -= f * v[c][k];
+                }
+            }
+        }
+    }
+    Some(v)
+}
+
+/* Elements as an array of six, for the covariance transformation. */
+fn elems(x: &[f64; 6], d1: f64, d2: f64, gm: f64) -> Result<[f64; 6], i32> {
+    let mut pve = [[0.0; 3]; 2];
+    rxpv(&ecl2000(), &unflat(x), &mut pve);
+    let el = pv2el(&pve, gm, d1, d2)?;
+    Ok([el.q, el.e, el.incl, el.node, el.argp, el.tp])
+}
+
+///  Refine an orbit by differential correction
+///
+///  Given:
+///  ```text
+///     s      &[Sight]       three or more observations
+///     pv     [[f64; 3]; 2]  starting heliocentric p,v (ICRS, au, au/d)
+///     date1  f64            TDB of pv as a 2-part...
+///     date2  f64            ...Julian Date
+///     gm     f64            gravitational parameter (au^3/d^2)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(fit) OrbitFit      the least-squares orbit (Notes 1-3)
+///     Err(j)  i32           -1 = too few observations or singular
+///                                normal equations
+///                           -2 = failed to converge
+///                           -3 = propagation failed
+///  ```
+///  Notes:
+///
+///  1) The six components of the state at the epoch are adjusted to
+///     minimize the sum of the squared residuals, each divided by the
+///     uncertainty given in the Sight.  The predicted places include
+///     the light time (see Sight::predict).  The motion is two-body;
+///     planetary perturbations are neglected.
+///
+///  2) The partial derivatives are formed by central differences, and
+///     the iteration stops when the corrections are below 1e-4 of the
+///     formal uncertainties.  A starting orbit from gauss or laplace is
+///     usually adequate.
+///
+///  3) cov is the inverse of the normal matrix, and so assumes the
+///     uncertainties of the observations are realistic;  it may be
+///     scaled by rms^2 otherwise.  The elements are referred to the
+///     ecliptic of J2000.0 as used by the MPC (see ecl2000), and their
+///     covariance is obtained from cov by a numerical Jacobian.  The
+///     angles and tp are in radians and days.
+///
+///  Called:
+///  ```text
+///     Sight::predict  predicted astrometric place
+///     ecl2000         ICRS to MPC ecliptic rotation matrix
+///     iauRxpv         product of r-matrix and pv-vector
+///     pv2el           cometary elements from p,v
+///     iauAnpm         normalize angle into range +/- pi
+///  ```
+pub fn diffcorr(
+    s: &[Sight],
+    pv: &[[f64; 3]; 2],
+    date1: f64,
+    date2: f64,
+    gm: f64,
+) -> Result<OrbitFit, i32> {
+    /* Maximum number of iterations. */
+    const KMAX: usize = 50;
+
+    if s.len() < 3 || s.iter().any(|o| o.sigma[0] <= 0.0 || o.sigma[1] <= 0.0) {
+        return Err(-1);
+    }
+    let m = 2 * s.len();
+
+    let mut x = flat(pv);
+    let mut niter = 0;
+    let mut cov;
+    loop {
+        niter += 1;
+        let r = residuals(s, &x, date1, date2, gm).map_err(|_| -3)?;
+
+        /* Partial derivatives by central differences. */
+        let h = [1e-7 * pm(pv[0]), 1e-7 * pm(pv[1])];
+        let mut a = vec![[0.0; 6]; m];
+        for k in 0..6 {
+            let hk = h[k / 3];
+            let (mut xp, mut xm) = (x, x);
+            xp[k] += hk;
+            xm[k] -= hk;
+            let rp = residuals(s, &xp, date1, date2, gm).map_err(|_| -3)?;
+            let rm = residuals(s, &xm, date1, date2, gm).map_err(|_| -3)?;
+            for i in 0..m {
+                /* Partial of the computed place (minus that of O-C). */
+                a[i][k] = (rm[i] - rp[i]) / (2.0 * hk);
+            }
+        }
+
+        /* Normal equations. */
+        let mut n = [[0.0; 6]; 6];
+        let mut b = [0.0; 6];
+        for i in 0..m {
+            for j in 0..6 {
+                b[j] += a[i][j] * r[i];
+                for k in 0..6 {
+                    n[j][k] += a[i][j] * a[i][k];
+                }
+            }
+        }
+        cov = inv6(&n).ok_or(-1)?;
+
+        /* Apply the correction, which is small when it is well within
+        the uncertainty of the solution. */
+        let mut small = true;
+        for j in 0..6 {
+            let dx: f64 = (0..6).map(|k| cov[j][k] * b[k]).sum();
+            x[j] += dx;
+            if dx.abs() > 1e-4 * cov[j][j].sqrt() {
+                small = false;
+            }
+        }
+        if small {
+            break;
+        }
+        if niter >= KMAX {
+            return Err(-2);
+        }
+    }
+
+    /* Final residuals. */
+    let r = residuals(s, &x, date1, date2, gm).map_err(|_| -3)?;
+    let rms = (r.iter().map(|v| v * v).sum::<f64>() / m as f64).sqrt();
+    let resid = r
+        .chunks(2)
+        .zip(s)
+        .map(|(v, o)| [v[0] * o.sigma[0], v[1] * o.sigma[1]])
+        .collect();
+
+    /* Elements and their covariance. */
+    let h = [1e-7 * pm(pv[0]), 1e-7 * pm(pv[1])];
+    let mut jac = [[0.0; 6]; 6];
+    for k in 0..6 {
+        let hk = h[k / 3];
+        let (mut xp, mut xm) = (x, x);
+        xp[k] += hk;
+        xm[k] -= hk;
+        let ep = elems(&xp, date1, date2, gm).map_err(|_| -3)?;
+        let em = elems(&xm, date1, date2, gm).map_err(|_| -3)?;
+        for i in 0..6 {
+            let d = if (2..5).contains(&i) {
+                anpm(ep[i] - em[i])
+            } else {
+                ep[i] - em[i]
+            };
+            jac[i][k] = d / (2.0 * hk);
+        }
+    }
+    let mut elcov = [[0.0; 6]; 6];
+    for i in 0..6 {
+        for j in 0..6 {
+            let mut sum = 0.0;
+            for k in 0..6 {
+                for l in 0..6 {
+                    sum += jac[i][k] * cov[k][l] * jac[j][l];
+                }
+            }
+            elcov[i][j] = sum;
+        }
+    }
+
+    let pvf = unflat(&x);
+    let mut pve = [[0.0; 3]; 2];
+    rxpv(&ecl2000(), &pvf, &mut pve);
+    Ok(OrbitFit {
+        epoch: [date1, date2],
+        pv: pvf,
+        cov,
+        el: pv2el(&pve, gm, date1, date2).map_err(|_| -3)?,
+        elcov,
+        resid,
+        rms,
+        niter,
+    })
+}
@@ -0,0 +1,35 @@
+use super::Elements;
+
+///  Semi-major axis and mean anomaly from cometary elements
+///
+///  Given:
+///  ```text
+///     el     Elements  cometary elements
+///     gm     f64       gravitational parameter (au^3/d^2)
+///     date1  f64       TDB as a 2-part...
+///     date2  f64       ...Julian Date
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok((a, ma))  (f64, f64)  semi-major axis (au, negative for
+///                              e > 1) and mean anomaly at the date
+///                              (radians, Note 1)
+///     Err(j)       i32         -1 = illegal elements, or parabolic
+///  ```
+///  Notes:
+///
+///  1) The mean anomaly is n (t - tp), where n is the mean motion.  It
+///     is not normalized, so that the number of revolutions since tp
+///     is preserved;  use iauAnp or iauAnpm if required.
+///
+///  2) The remaining classical elements (inclination, node, argument
+///     of perihelion) are those of el.
+pub fn el2cl(el: &Elements, gm: f64, date1: f64, date2: f64) -> Result<(f64, f64), i32> {
+    if el.q <= 0.0 || el.e < 0.0 || el.e == 1.0 || gm <= 0.0 {
+        return Err(-1);
+    }
+
+    let a = el.q / (1.0 - el.e);
+    let n = (gm / (a * a * a).abs()).sqrt();
+    Ok((a, n * ((date1 - el.tp) + date2)))
+}
@@ -0,0 +1,60 @@
+use super::{Elements, pvprop};
+
+///  Two-body position and velocity from cometary elements
+///
+///  Given:
+///  ```text
+///     el     Elements  osculating elements (Note 1)
+///     gm     f64       gravitational parameter (au^3/d^2, Note 2)
+///     date1  f64       TDB as a 2-part...
+///     date2  f64       ...Julian Date
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(pv) [[f64; 3]; 2]  position and velocity (au, au/d)
+///     Err(j) i32            -1 = illegal elements
+///                           -2 = failed to converge
+///  ```
+///  Notes:
+///
+///  1) The position and velocity are in the frame to which the angles
+///     of the elements are referred.  For elements referred to the
+///     ecliptic and equinox of J2000.0, such as those of the Minor
+///     Planet Center, the ICRS vectors are obtained by multiplying by
+///     the transpose of ecl2000().
+///
+///  2) For a heliocentric orbit, gm is GK^2 (1 + m), where GK is the
+///     Gaussian gravitational constant and m the mass of the body in
+///     solar masses.  The state is heliocentric;  add the barycentric
+///     state of the Sun before using it with the astro functions.
+///
+///  3) All conics are supported.  The perihelion state is propagated
+///     to the given date by pvprop.
+///
+///  Called:
+///  ```text
+///     pvprop    propagate a two-body state vector
+///  ```
+pub fn el2pv(el: &Elements, gm: f64, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+    if el.q <= 0.0 || el.e < 0.0 || gm <= 0.0 {
+        return Err(-1);
+    }
+
+    /* Unit vectors towards perihelion and 90 degrees ahead. */
+    let (si, ci) = el.incl.sin_cos();
+    let (sn, cn) = el.node.sin_cos();
+    let (sw, cw) = el.argp.sin_cos();
+    let p = [cw * cn - sw * sn * ci, cw * sn + sw * cn * ci, sw * si];
+    let q = [-sw * cn - cw * sn * ci, -sw * sn + cw * cn * ci, cw * si];
+
+    /* State at perihelion. */
+    let vq = (gm * (1.0 + el.e) / el.q).sqrt();
+    let mut pv = [[0.0; 3]; 2];
+    for i in 0..3 {
+        pv[0][i] = el.q * p[i];
+        pv[1][i] = vq * q[i];
+    }
+
+    /* Propagate to the date. */
+    pvprop(&pv, gm, (date1 - el.tp) + date2)
+}
@@ -0,0 +1,220 @@
+use super::pvprop::fg;
+use super::{Sight, pvprop};
+use crate::consts::DC;
+use crate::vm::{pdp, pxp, s2c};
+
+///  Preliminary orbit from three observations by Gauss's method
+///
+///  Given:
+///  ```text
+///     s      &[Sight; 3]    three observations, in time order (Note 1)
+///     gm     f64            gravitational parameter (au^3/d^2, Note 2)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(v)  Vec<[[f64; 3]; 2]>  heliocentric p,v (ICRS, au, au/d) at
+///                                the TDB of s[1], one for each
+///                                solution (Note 3)
+///     Err(j) i32                 -1 = observations unusable (Note 4)
+///                                -2 = no solution found
+///  ```
+///  Notes:
+///
+///  1) Each Sight gives the TDB, the astrometric RA,Dec and the
+///     heliocentric position of the observer;  see Sight::new.
+///
+///  2) For heliocentric orbits gm is GK^2.
+///
+///  3) The distance of the middle observation is first found from
+///     Gauss's eighth-degree equation, which can have up to three
+///     positive roots;  each is refined by iterating with the exact
+///     two-body Lagrange coefficients, correcting the times for light
+///     time.  Roots that put the body behind the observer, or for
+///     which the iteration fails to converge, are discarded.  With
+///     exact two-body data the solutions satisfy all three
+///     observations exactly.
+///
+///  4) The observations must be distinct in time and the three
+///     directions must not be coplanar.  The method is most reliable
+///     for arcs spanning a small fraction of the orbit, and degrades
+///     when the observations lie close to a great circle through the
+///     Sun.
+///
+///  Called:
+///  ```text
+///     iauS2c    spherical coordinates to unit vector
+///     iauPxp    vector product of two p-vectors
+///     iauPdp    scalar product of two p-vectors
+///     pvprop    propagate a two-body state vector
+///  ```
+///
+///  Reference:
+///
+///     Curtis, H.D., Orbital Mechanics for Engineering Students, 3rd
+///     ed., Butterworth-Heinemann (2014), Algorithms 5.5 and 5.6.
+pub fn gauss(s: &[Sight; 3], gm: f64) -> Result<Vec<[[f64; 3]; 2]>, i32> {
+    /* Maximum number of refining iterations. */
+    const KMAX: usize = 200;
+
+    /* Times from the middle observation (days). */
+    let t: [f64; 3] =
+        std::array::from_fn(|i| (s[i].tdb[0] - s[1].tdb[0]) + (s[i].tdb[1] - s[1].tdb[1]));
+    if t[0] >= 0.0 || t[2] <= 0.0 || gm <= 0.0 {
+        return Err(-1);
+    }
+
+    /* Directions and observer positions. */
+    let l: [[f64; 3]; 3] = std::array::from_fn(|i| s2c(s[i].ra, s[i].dec));
+    let rr: [[f64; 3]; 3] = std::array::from_fn(|i| s[i].obs[0]);
+
+    /* Gauss's determinants. */
+    let p = [pxp(&l[1], &l[2]), pxp(&l[0], &l[2]), pxp(&l[0], &l[1])];
+    let d0 = pdp(&l[0], &p[0]);
+    if d0.abs() < 1e-14 {
+        return Err(-1);
+    }
+    let mut d = [[0.0; 3]; 3];
+    for i in 0..3 {
+        for j in 0..3 {
+            d[i][j] = pdp(&rr[i], &p[j]);
+        }
+    }
+
+    /* The eighth-degree equation for r2. */
+    let (t1, t3) = (t[0], t[2]);
+    let tau = t3 - t1;
+    let a = (-d[0][1] * t3 / tau + d[1][1] + d[2][1] * t1 / tau) / d0;
+    let b = (d[0][1] * (t3 * t3 - tau * tau) * t3 / tau
+        + d[2][1] * (tau * tau - t1 * t1) * t1 / tau)
+        / (6.0 * d0);
+    let e = pdp(&rr[1], &l[1]);
+    let r22 = pdp(&rr[1], &rr[1]);
+    let roots = roots8(
+        -(a * a + 2.0 * a * e + r22),
+        -2.0 * gm * b * (a + e),
+        -gm * gm * b * b,
+    );
+
+    let mut sols = Vec::new();
+    'roots: for r2 in roots {
+        /* Approximate distances, from the truncated f and g series. */
+        let r23 = r2 * r2 * r2;
+        let den1 = 6.0 * r23 + gm * (tau * tau - t3 * t3);
+        let den3 = 6.0 * r23 + gm * (tau * tau - t1 * t1);
+        let mut rho = [
+            ((6.0 * (d[2][0] * t1 / t3 + d[1][0] * tau / t3) * r23
+                + gm * d[2][0] * (tau * tau - t1 * t1) * t1 / t3)
+                / den1
+                - d[0][0])
+                / d0,
+            a + gm * b / r23,
+            ((6.0 * (d[0][2] * t3 / t1 - d[1][2] * tau / t1) * r23
+                + gm * d[0][2] * (tau * tau - t3 * t3) * t3 / t1)
+                / den3
+                - d[2][2])
+                / d0,
+        ];
+        if rho[1] <= 0.0 {
+            continue;
+        }
+        let pos = |rho: &[f64; 3]| -> [[f64; 3]; 3] {
+            std::array::from_fn(|i| std::array::from_fn(|k| rr[i][k] + rho[i] * l[i][k]))
+        };
+        let mut r = pos(&rho);
+        let mut ff = [
+            1.0 - 0.5 * gm * t1 * t1 / r23,
+            1.0 - 0.5 * gm * t3 * t3 / r23,
+        ];
+        let mut gg = [
+            t1 - gm * t1 * t1 * t1 / (6.0 * r23),
+            t3 - gm * t3 * t3 * t3 / (6.0 * r23),
+        ];
+        let mut v2 = velocity(&r, &ff, &gg);
+
+        /* Refine with exact f and g and light-time corrected times. */
+        let mut ok = false;
+        for _ in 0..KMAX {
+            let lt: [f64; 3] = std::array::from_fn(|i| rho[i] / DC);
+            let pv2 = [r[1], v2];
+            let Ok(c1) = fg(&pv2, gm, (t1 - lt[0]) + lt[1]) else {
+                continue 'roots;
+            };
+            let Ok(c3) = fg(&pv2, gm, (t3 - lt[2]) + lt[1]) else {
+                continue 'roots;
+            };
+            (ff, gg) = ([c1[0], c3[0]], [c1[1], c3[1]]);
+            let det = ff[0] * gg[1] - ff[1] * gg[0];
+            let k1 = gg[1] / det;
+            let k3 = -gg[0] / det;
+            let new = [
+                (-d[0][0] + d[1][0] / k1 - k3 / k1 * d[2][0]) / d0,
+                (-k1 * d[0][1] + d[1][1] - k3 * d[2][1]) / d0,
+                (-k1 / k3 * d[0][2] + d[1][2] / k3 - d[2][2]) / d0,
+            ];
+            let change = (0..3)
+                .map(|i| (new[i] - rho[i]).abs() / new[i].abs())
+                .fold(0.0, f64::max);
+            rho = new;
+            r = pos(&rho);
+            v2 = velocity(&r, &ff, &gg);
+            if !change.is_finite() {
+                continue 'roots;
+            }
+            if change < 1e-13 {
+                ok = true;
+                break;
+            }
+        }
+        if !ok || rho.iter().any(|&x| x <= 0.0) {
+            continue;
+        }
+
+        /* State at the time of the middle observation. */
+        if let Ok(pv) = pvprop(&[r[1], v2], gm, rho[1] / DC) {
+            sols.push(pv);
+        }
+    }
+
+    if sols.is_empty() { Err(-2) } else { Ok(sols) }
+}
+
+/* Velocity at the middle observation from the Lagrange coefficients. */
+fn velocity(r: &[[f64; 3]; 3], f: &[f64; 2], g: &[f64; 2]) -> [f64; 3] {
+    let det = f[0] * g[1] - f[1] * g[0];
+    std::array::from_fn(|k| (-f[1] * r[0][k] + f[0] * r[2][k]) / det)
+}
+
+/* Positive roots of x^8 + a x^6 + b x^3 + c = 0. */
+pub(crate) fn roots8(a: f64, b: f64, c: f64) -> Vec<f64> {
+    let f = |x: f64| ((x * x + a) * x * x * x + b) * x * x * x + c;
+
+    /* Bracket sign changes on a logarithmic grid, then bisect. */
+    let mut roots = Vec::new();
+    let (x0, x1, n) = (1e-4f64, 1e4f64, 4000);
+    let step = (x1 / x0).ln() / n as f64;
+    let mut xa = x0;
+    let mut fa = f(xa);
+    for i in 1..=n {
+        let xb = x0 * (step * i as f64).exp();
+        let fb = f(xb);
+        if fa == 0.0 {
+            roots.push(xa);
+        } else if fa * fb < 0.0 {
+            let (mut lo, mut hi) = (xa, xb);
+            for _ in 0..200 {
+                let mid = 0.5 * (lo + hi);
+                if f(mid) * fa > 0.0 {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+                if hi - lo <= 1e-15 * hi {
+                    break;
+                }
+            }
+            roots.push(0.5 * (lo + hi));
+        }
+        (xa, fa) = (xb, fb);
+    }
+    roots
+}
@@ -0,0 +1,86 @@
+use crate::vm::anpm;
+
+///  Solve Kepler's equation, elliptic or hyperbolic
+///
+///  Given:
+///  ```text
+///     m      f64     mean anomaly (radians)
+///     e      f64     eccentricity (Note 1)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(x)  f64     eccentric anomaly E (e < 1) or hyperbolic
+///                    anomaly H (e > 1), radians
+///     Err(j) i32     -1 = e negative or equal to 1
+///                    -2 = failed to converge
+///  ```
+///  Notes:
+///
+///  1) For e < 1 the equation is E - e sin E = M;  the result is in
+///     the same revolution as M.  For e > 1 it is e sinh H - H = M.
+///     The parabolic case has no mean anomaly and is excluded;  see
+///     pvprop for a formulation valid for all conics.
+///
+///  2) Halley's method is used, from a starting value suitable for
+///     all eccentricities, converging to machine precision in a few
+///     iterations.  (iauPlan94 uses a simple Newton iteration, which
+///     is adequate for the small eccentricities of the planets.)
+///
+///  Called:
+///  ```text
+///     iauAnpm   normalize angle into range +/- pi
+///  ```
+///
+///  Reference:
+///
+///     Danby, J.M.A., Fundamentals of Celestial Mechanics, 2nd ed.,
+///     Willmann-Bell (1988), Section 6.6.
+pub fn kepler(m: f64, e: f64) -> Result<f64, i32> {
+    /* Maximum number of iterations. */
+    const KMAX: usize = 50;
+
+    if e < 0.0 || e == 1.0 {
+        return Err(-1);
+    }
+
+    if e < 1.0 {
+        /* Reduce to -pi..+pi, remembering the revolutions. */
+        let mr = anpm(m);
+        let base = m - mr;
+
+        /* Starting value (Danby). */
+        let mut x = if e < 0.3 {
+            mr + e * mr.sin()
+        } else {
+            mr + 0.85 * e * mr.signum()
+        };
+        for _ in 0..KMAX {
+            let (s, c) = x.sin_cos();
+            let f = x - e * s - mr;
+            let f1 = 1.0 - e * c;
+            let dx = -f / (f1 - 0.5 * f * e * s / f1);
+            x += dx;
+            if dx.abs() <= 1e-14 * (1.0 + x.abs()) {
+                return Ok(base + x);
+            }
+        }
+    } else {
+        /* Starting value. */
+        let mut x = if m.abs() < 6.0 * e {
+            (m / e).asinh()
+        } else {
+            m.signum() * (2.0 * m.abs() / e).ln()
+        };
+        for _ in 0..KMAX {
+            let (s, c) = (x.sinh(), x.cosh());
+            let f = e * s - x - m;
+            let f1 = e * c - 1.0;
+            let dx = -f / (f1 - 0.5 * f * e * s / f1);
+            x += dx;
+            if dx.abs() <= 1e-14 * (1.0 + x.abs()) {
+                return Ok(x);
+            }
+        }
+    }
+    Err(-2)
+}
@@ -0,0 +1,142 @@
+use super::gauss::roots8;
+use super::{Sight, pvprop};
+use crate::consts::DC;
+use crate::vm::{pdp, pxp, s2c};
+
+///  Preliminary orbit from three or more observations by Laplace's
+///  method
+///
+///  Given:
+///  ```text
+///     s      &[Sight]       three or more observations, in time
+///                           order (Note 1)
+///     gm     f64            gravitational parameter (au^3/d^2)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(v)  Vec<[[f64; 3]; 2]>  heliocentric p,v (ICRS, au, au/d) at
+///                                the TDB of the middle observation,
+///                                s[n/2], one for each solution
+///     Err(j) i32                 -1 = observations unusable
+///                                -2 = no solution found
+///  ```
+///  Notes:
+///
+///  1) The direction to the body and the observer position, with
+///     their first and second derivatives, are obtained at the time of
+///     the middle observation by fitting polynomials through all the
+///     observations.  The observer is interpolated in the same way as
+///     the directions, which keeps the two consistent when the
+///     observations are hours or days apart.
+///
+///  2) The distance follows from an eighth-degree equation, as in
+///     Gauss's method, and the solutions with the body in front of
+///     the observer are returned.  A first-order correction for light
+///     time is applied.
+///
+///  3) The result is an approximation whose accuracy depends on the
+///     arc and the spacing of the observations;  it is intended as a
+///     starting value for diffcorr.  For three observations gauss is
+///     usually better.
+///
+///  Called:
+///  ```text
+///     iauS2c    spherical coordinates to unit vector
+///     iauPxp    vector product of two p-vectors
+///     iauPdp    scalar product of two p-vectors
+///     pvprop    propagate a two-body state vector
+///  ```
+///
+///  Reference:
+///
+///     Escobal, P.R., Methods of Orbit Determination, Wiley (1965),
+///     Chapter 7.
+pub fn laplace(s: &[Sight], gm: f64) -> Result<Vec<[[f64; 3]; 2]>, i32> {
+    let n = s.len();
+    if n < 3 || gm <= 0.0 {
+        return Err(-1);
+    }
+    let m = s[n / 2].tdb;
+
+    /* Times from the middle observation (days), strictly increasing. */
+    let t: Vec<f64> = s
+        .iter()
+        .map(|x| (x.tdb[0] - m[0]) + (x.tdb[1] - m[1]))
+        .collect();
+    if t.windows(2).any(|w| w[1] <= w[0]) {
+        return Err(-1);
+    }
+
+    /* Interpolated direction and observer, with derivatives. */
+    let w = weights(&t);
+    let mut l = [[0.0; 3]; 3];
+    let mut r = [[0.0; 3]; 3];
+    for (j, x) in s.iter().enumerate() {
+        let u = s2c(x.ra, x.dec);
+        for d in 0..3 {
+            for k in 0..3 {
+                l[d][k] += w[j][d] * u[k];
+                r[d][k] += w[j][d] * x.obs[0][k];
+            }
+        }
+    }
+
+    /* Determinants. */
+    let ll1 = pxp(&l[0], &l[1]);
+    let ll2 = pxp(&l[0], &l[2]);
+    let dd = pdp(&l[2], &ll1);
+    if dd.abs() < 1e-20 {
+        return Err(-1);
+    }
+    let a = -pdp(&r[2], &ll1) / dd;
+    let b = -gm * pdp(&r[0], &ll1) / dd;
+    let e = pdp(&l[0], &r[0]);
+    let r2 = pdp(&r[0], &r[0]);
+
+    let mut sols = Vec::new();
+    for rh in roots8(-(a * a + 2.0 * a * e + r2), -2.0 * b * (a + e), -b * b) {
+        let r3 = rh * rh * rh;
+        let rho = a + b / r3;
+        if rho <= 0.0 {
+            continue;
+        }
+        let rhod = (pdp(&r[2], &ll2) + gm / r3 * pdp(&r[0], &ll2)) / (2.0 * dd);
+
+        /* State at the time of emission, then at the observation. */
+        let pv: [[f64; 3]; 2] = [
+            std::array::from_fn(|k| r[0][k] + rho * l[0][k]),
+            std::array::from_fn(|k| r[1][k] + rhod * l[0][k] + rho * l[1][k]),
+        ];
+        if let Ok(pv) = pvprop(&pv, gm, rho / DC) {
+            sols.push(pv);
+        }
+    }
+
+    if sols.is_empty() { Err(-2) } else { Ok(sols) }
+}
+
+/* Weights giving the value and first two derivatives at t = 0 of the
+interpolating polynomial through the points t[j]. */
+fn weights(t: &[f64]) -> Vec<[f64; 3]> {
+    let n = t.len();
+    let mut w = Vec::with_capacity(n);
+    for j in 0..n {
+        /* Coefficients of the Lagrange basis polynomial, in powers of t. */
+        let mut c = vec![0.0; n];
+        c[0] = 1.0;
+        let mut deg = 0;
+        for (m, &tm) in t.iter().enumerate() {
+            if m == j {
+                continue;
+            }
+            let s = t[j] - tm;
+            deg += 1;
+            for k in (0..=deg).rev() {
+                let lower = if k > 0 { c[k - 1] } else { 0.0 };
+                c[k] = (lower - tm * c[k]) / s;
+            }
+        }
+        w.push([c[0], c[1], 2.0 * c.get(2).copied().unwrap_or(0.0)]);
+    }
+    w
+}
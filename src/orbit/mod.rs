@@ -0,0 +1,75 @@
+//! Two-body orbits (elements, Kepler's equation and propagation) and the
+//! Minor Planet Center orbit and observation formats
+
+/// Osculating elements in cometary form
+///
+/// The form is valid for all conics:  elliptic (e < 1), parabolic
+/// (e = 1) and hyperbolic (e > 1).  The angles are referred to the
+/// reference plane and direction of the state vectors from which the
+/// elements were obtained, for example the ecliptic and equinox of
+/// J2000.0 for elements published by the Minor Planet Center.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Elements {
+    pub q: f64,    // perihelion distance (au)
+    pub e: f64,    // eccentricity
+    pub incl: f64, // inclination (radians)
+    pub node: f64, // longitude of the ascending node (radians)
+    pub argp: f64, // argument of perihelion (radians)
+    pub tp: f64,   // time of perihelion passage (TDB Julian Date)
+}
+
+/* State vector as an array of six, and back. */
+pub(crate) fn flat(pv: &[[f64; 3]; 2]) -> [f64; 6] {
+    [pv[0][0], pv[0][1], pv[0][2], pv[1][0], pv[1][1], pv[1][2]]
+}
+
+pub(crate) fn unflat(x: &[f64; 6]) -> [[f64; 3]; 2] {
+    [[x[0], x[1], x[2]], [x[3], x[4], x[5]]]
+}
+
+mod ades;
+
+mod cl2el;
+pub use cl2el::*;
+
+mod diffcorr;
+pub use diffcorr::*;
+
+mod el2cl;
+pub use el2cl::*;
+
+mod el2pv;
+pub use el2pv::*;
+
+mod gauss;
+pub use gauss::*;
+
+mod kepler;
+pub use kepler::*;
+
+mod laplace;
+pub use laplace::*;
+
+mod mpcdes;
+pub use mpcdes::*;
+
+mod mpcorb;
+pub use mpcorb::*;
+
+mod nbody;
+pub use nbody::*;
+
+mod obs80;
+pub use obs80::*;
+
+mod pv2el;
+pub use pv2el::*;
+
+mod pvprop;
+pub use pvprop::*;
+
+mod sight;
+pub use sight::*;
+
+mod stumpff;
+pub use stumpff::*;
@@ -0,0 +1,322 @@
+use crate::cal::cal2jd;
+
+/// Value of a base-62 digit (0-9, A-Z, a-z).
+fn b62(c: char) -> Option<u32> {
+    match c {
+        '0'..='9' => Some(c as u32 - '0' as u32),
+        'A'..='Z' => Some(c as u32 - 'A' as u32 + 10),
+        'a'..='z' => Some(c as u32 - 'a' as u32 + 36),
+        _ => None,
+    }
+}
+
+/// Base-62 digit of a value.
+fn c62(n: u32) -> char {
+    let n = n as u8;
+    (match n {
+        0..=9 => b'0' + n,
+        10..=35 => b'A' + n - 10,
+        _ => b'a' + n - 36,
+    }) as char
+}
+
+/// Year from the century letter and two digits.
+fn year(s: &[char]) -> Option<i32> {
+    let c = match s[0] {
+        'A'..='L' => s[0] as i32 - 'A' as i32 + 10,
+        _ => return None,
+    };
+    let y = s[1].to_digit(10)? * 10 + s[2].to_digit(10)?;
+    Some(c * 100 + y as i32)
+}
+
+/// Century letter and two digits of a year.
+fn pyear(y: i32) -> Option<String> {
+    if !(1000..=2199).contains(&y) {
+        return None;
+    }
+    Some(format!("{}{:02}", c62((y / 100) as u32), y % 100))
+}
+
+///  Unpack a Minor Planet Center packed designation
+///
+///  Given:
+///  ```text
+///     packed  &str    packed designation (Note 1)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(s)   String  designation in readable form (Note 2)
+///     Err(j)  i32     -1 = not a packed designation
+///  ```
+///  Notes:
+///
+///  1) Leading and trailing spaces are ignored.  The forms accepted are
+///     those of the MPCORB and 80-column observation files:
+///     ```text
+///     numbered minor planets    "03202", "A0345", "~0001"
+///     provisional designations  "J95X00A", "K07Tf8A"
+///     survey designations       "PLS2040", "T1S3138"
+///     numbered comets           "0001P"
+///     comet designations        "CJ95O010", "PK05F02b", "J95O010"
+///     ```
+///
+///  2) The readable forms are "3202", "100345", "620001", "1995 XA",
+///     "2007 TA418", "2040 P-L", "3138 T-1", "1P", "C/1995 O1",
+///     "P/2005 F2-B" and "1995 O1".
+///
+///  Reference:
+///
+///     Minor Planet Center, "Packed Provisional and Permanent
+///     Designations", https://minorplanetcenter.net/iau/info/
+///     PackedDes.html
+pub fn unpackdes(packed: &str) -> Result<String, i32> {
+    let s: Vec<char> = packed.trim().chars().collect();
+    let digits = |s: &[char]| s.iter().all(char::is_ascii_digit);
+    let num = |s: &[char]| s.iter().collect::<String>().parse::<u32>().ok();
+
+    match s.len() {
+        5 => {
+            /* Numbered comet. */
+            if (s[4] == 'P' || s[4] == 'D') && digits(&s[..4]) {
+                return Ok(format!("{}{}", num(&s[..4]).ok_or(-1)?, s[4]));
+            }
+
+            /* Numbered minor planet. */
+            if !digits(&s[1..]) && s[0] != '~' {
+                return Err(-1);
+            }
+            let n = if s[0] == '~' {
+                let mut n = 0;
+                for &c in &s[1..] {
+                    n = n * 62 + b62(c).ok_or(-1)?;
+                }
+                620000 + n
+            } else if s[0].is_ascii_alphanumeric() {
+                b62(s[0]).ok_or(-1)? * 10000 + num(&s[1..]).ok_or(-1)?
+            } else {
+                return Err(-1);
+            };
+            Ok(n.to_string())
+        }
+        7 => {
+            /* Survey designations. */
+            let pre: String = s[..3].iter().collect();
+            let survey = match pre.as_str() {
+                "PLS" => Some("P-L"),
+                "T1S" => Some("T-1"),
+                "T2S" => Some("T-2"),
+                "T3S" => Some("T-3"),
+                _ => None,
+            };
+            if let Some(sv) = survey {
+                if !digits(&s[3..]) {
+                    return Err(-1);
+                }
+                return Ok(format!("{} {}", num(&s[3..]).ok_or(-1)?, sv));
+            }
+
+            /* Provisional designations, minor planet or comet. */
+            let y = year(&s[..3]).ok_or(-1)?;
+            if !s[3].is_ascii_uppercase() || !s[5].is_ascii_digit() {
+                return Err(-1);
+            }
+            let n = b62(s[4]).ok_or(-1)? * 10 + s[5].to_digit(10).ok_or(-1)?;
+            if s[6].is_ascii_uppercase() {
+                if n == 0 {
+                    Ok(format!("{} {}{}", y, s[3], s[6]))
+                } else {
+                    Ok(format!("{} {}{}{}", y, s[3], s[6], n))
+                }
+            } else if s[6] == '0' {
+                Ok(format!("{} {}{}", y, s[3], n))
+            } else if s[6].is_ascii_lowercase() {
+                Ok(format!("{} {}{}-{}", y, s[3], n, s[6].to_ascii_uppercase()))
+            } else {
+                Err(-1)
+            }
+        }
+        8 => {
+            /* Comet designation with orbit type. */
+            if !"PCDXAI".contains(s[0]) || s[7].is_ascii_uppercase() {
+                return Err(-1);
+            }
+            let rest: String = s[1..].iter().collect();
+            Ok(format!("{}/{}", s[0], unpackdes(&rest)?))
+        }
+        _ => Err(-1),
+    }
+}
+
+///  Pack a minor planet or comet designation in the MPC form
+///
+///  Given:
+///  ```text
+///     des     &str    designation in readable form (Note 1)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(s)   String  packed designation
+///     Err(j)  i32     -1 = designation not recognized or out of range
+///  ```
+///  Note:
+///
+///     The forms accepted are those returned by unpackdes.  Numbered
+///     minor planets are packed in five characters, as far as number
+///     15396335.
+pub fn packdes(des: &str) -> Result<String, i32> {
+    let des = des.trim();
+    let w: Vec<&str> = des.split_whitespace().collect();
+
+    match w.len() {
+        1 => {
+            /* Numbered comet. */
+            if let Some(n) = des.strip_suffix(['P', 'D']) {
+                let n: u32 = n.parse().map_err(|_| -1)?;
+                if !(1..=9999).contains(&n) {
+                    return Err(-1);
+                }
+                return Ok(format!("{:04}{}", n, &des[des.len() - 1..]));
+            }
+
+            /* Numbered minor planet. */
+            let n: u32 = des.parse().map_err(|_| -1)?;
+            match n {
+                1..=99999 => Ok(format!("{:05}", n)),
+                100000..=619999 => Ok(format!("{}{:04}", c62(n / 10000), n % 10000)),
+                620000..=15396335 => {
+                    let mut m = n - 620000;
+                    let mut s = ['0'; 4];
+                    for c in s.iter_mut().rev() {
+                        *c = c62(m % 62);
+                        m /= 62;
+                    }
+                    Ok(format!("~{}", s.iter().collect::<String>()))
+                }
+                _ => Err(-1),
+            }
+        }
+        2 => {
+            /* Survey designation. */
+            let survey = match w[1] {
+                "P-L" => Some("PLS"),
+                "T-1" => Some("T1S"),
+                "T-2" => Some("T2S"),
+                "T-3" => Some("T3S"),
+                _ => None,
+            };
+            if let Some(sv) = survey {
+                let n: u32 = w[0].parse().map_err(|_| -1)?;
+                if n > 9999 {
+                    return Err(-1);
+                }
+                return Ok(format!("{}{:04}", sv, n));
+            }
+
+            /* Comet orbit type. */
+            let (kind, y) = match w[0].split_once('/') {
+                Some((k, y)) if k.len() == 1 && "PCDXAI".contains(k) => (Some(k), y),
+                Some(_) => return Err(-1),
+                None => (None, w[0]),
+            };
+            let y: i32 = y.parse().map_err(|_| -1)?;
+            let py = pyear(y).ok_or(-1)?;
+
+            /* Half-month letter, then a letter (minor planet) or the
+            order number (comet), then the cycle count or fragment. */
+            let (body, frag) = match w[1].split_once('-') {
+                Some((b, f)) => (b, Some(f)),
+                None => (w[1], None),
+            };
+            let c: Vec<char> = body.chars().collect();
+            if c.is_empty() || !c[0].is_ascii_uppercase() || c[0] == 'I' || c[0] == 'Z' {
+                return Err(-1);
+            }
+            let asteroid = c.len() >= 2 && c[1].is_ascii_uppercase();
+            let digits: String = c[if asteroid { 2 } else { 1 }..].iter().collect();
+            if !digits.chars().all(|d| d.is_ascii_digit()) || (!asteroid && digits.is_empty()) {
+                return Err(-1);
+            }
+            let n: u32 = if digits.is_empty() {
+                0
+            } else {
+                digits.parse().map_err(|_| -1)?
+            };
+            if n > 619 {
+                return Err(-1);
+            }
+            let cyc = format!("{}{}", c62(n / 10), n % 10);
+
+            if asteroid {
+                if kind.is_some() || frag.is_some() {
+                    return Err(-1);
+                }
+                return Ok(format!("{}{}{}{}", py, c[0], cyc, c[1]));
+            }
+            let f = match frag {
+                None => '0',
+                Some(f) if f.len() == 1 && f.chars().all(|x| x.is_ascii_alphabetic()) => {
+                    f.chars().next().unwrap_or('0').to_ascii_lowercase()
+                }
+                Some(_) => return Err(-1),
+            };
+            Ok(format!("{}{}{}{}{}", kind.unwrap_or(""), py, c[0], cyc, f))
+        }
+        _ => Err(-1),
+    }
+}
+
+///  Decode an MPC packed date
+///
+///  Given:
+///  ```text
+///     packed  &str    packed date, e.g. "K24AM" (Note 1)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok((d1, d2))  (f64, f64)  the date as a 2-part Julian Date
+///     Err(j)        i32         -1 = bad packed date
+///  ```
+///  Notes:
+///
+///  1) The five characters are the century letter, the last two digits
+///     of the year, the month (1-9, A-C) and the day (1-9, A-V).  Any
+///     further digits are a decimal fraction of the day:  "K24AM5"
+///     is 2024 October 22.5.
+///
+///  2) The time scale is that of the context;  the epochs of the MPC
+///     orbits are TT.
+///
+///  Called:
+///  ```text
+///     iauCal2jd Gregorian calendar to JD
+///  ```
+pub fn unpackdate(packed: &str) -> Result<(f64, f64), i32> {
+    let s: Vec<char> = packed.trim().chars().collect();
+    if s.len() < 5 {
+        return Err(-1);
+    }
+    let y = year(&s[..3]).ok_or(-1)?;
+    let m = b62(s[3]).ok_or(-1)? as i32;
+    let d = b62(s[4]).ok_or(-1)? as i32;
+    let mut f = 0.0;
+    if s.len() > 5 {
+        let frac: String = s[5..].iter().collect();
+        if !frac.chars().all(|c| c.is_ascii_digit()) {
+            return Err(-1);
+        }
+        f = format!("0.{}", frac).parse().map_err(|_| -1)?;
+    }
+    if !(1..=31).contains(&d) {
+        return Err(-1);
+    }
+    let (d1, d2) = cal2jd(y, m, d).map_err(|_| -1)?;
+    Ok((d1, d2 + f))
+}
+
+/// Columns a to b (1-based, inclusive) of a fixed-format line, trimmed;
+/// empty if the line is too short.
+pub(crate) fn field(line: &str, a: usize, b: usize) -> &str {
+    let b = b.min(line.len());
+    line.get(a - 1..b).map_or("", str::trim)
+}
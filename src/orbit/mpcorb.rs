@@ -0,0 +1,196 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use super::mpcdes::field;
+use super::{Elements, el2pv, unpackdate, unpackdes};
+use crate::consts::{DAS2R, DD2R, GK};
+use crate::eph::invalid;
+use crate::vm::{ir, rx, trxpv};
+
+/// Obliquity of the ecliptic of J2000.0 used by the MPC and JPL (IAU
+/// 1976 value, referred to the ICRS).
+const EPS0: f64 = 84381.448 * DAS2R;
+
+///  One orbit from the Minor Planet Center orbit database
+///
+///  The one-line format of MPCORB.DAT, also used by NEA.txt and the
+///  other MPC orbit extracts.  The angles are referred to the ecliptic
+///  and equinox of J2000.0 and the epoch is TT.
+///
+///  Reference:
+///
+///     Minor Planet Center, "Export Format for Minor-Planet Orbits",
+///     https://minorplanetcenter.net/iau/info/MPOrbitFormat.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct MpcOrbit {
+    pub desig: String,    // designation, unpacked ("3202", "1995 XA")
+    pub name: String,     // readable designation, e.g. "(1) Ceres"
+    pub h: Option<f64>,   // absolute magnitude H
+    pub g: Option<f64>,   // slope parameter G
+    pub epoch: [f64; 2],  // epoch of osculation (TT, 2-part JD)
+    pub ma: f64,          // mean anomaly at the epoch (radians)
+    pub argp: f64,        // argument of perihelion (radians)
+    pub node: f64,        // longitude of the ascending node (radians)
+    pub incl: f64,        // inclination (radians)
+    pub e: f64,           // eccentricity
+    pub n: f64,           // mean daily motion (radians/day)
+    pub a: f64,           // semi-major axis (au)
+    pub u: char,          // uncertainty parameter (' ' if none)
+    pub nobs: u32,        // number of observations
+    pub nopp: u32,        // number of oppositions
+    pub rms: Option<f64>, // r.m.s. residual (arcsec)
+    pub flags: u16,       // hexadecimal flags (orbit type etc.)
+}
+
+impl MpcOrbit {
+    ///  Parse one line of an MPC orbit file
+    ///
+    ///  Columns 1-103 (designation to semi-major axis) are required;
+    ///  the remainder, if present, supplies the uncertainty, counts,
+    ///  r.m.s. residual, flags and readable designation.
+    pub fn parse(line: &str) -> io::Result<Self> {
+        let num = |a, b, what: &str| -> io::Result<f64> {
+            field(line, a, b)
+                .parse()
+                .map_err(|_| invalid(&format!("bad {}", what)))
+        };
+        let opt = |a, b| field(line, a, b).parse().ok();
+
+        let desig = unpackdes(field(line, 1, 7)).map_err(|_| invalid("bad designation"))?;
+        let (e1, e2) = unpackdate(field(line, 21, 25)).map_err(|_| invalid("bad epoch"))?;
+        let e = num(71, 79, "eccentricity")?;
+        let a = num(93, 103, "semi-major axis")?;
+        if !(0.0..1.0).contains(&e) || a <= 0.0 {
+            return Err(invalid("not an elliptic orbit"));
+        }
+
+        Ok(MpcOrbit {
+            desig,
+            name: field(line, 167, 194).to_string(),
+            h: opt(9, 13),
+            g: opt(15, 19),
+            epoch: [e1, e2],
+            ma: num(27, 35, "mean anomaly")? * DD2R,
+            argp: num(38, 46, "argument of perihelion")? * DD2R,
+            node: num(49, 57, "node")? * DD2R,
+            incl: num(60, 68, "inclination")? * DD2R,
+            e,
+            n: num(81, 91, "mean motion")? * DD2R,
+            a,
+            u: field(line, 106, 106).chars().next().unwrap_or(' '),
+            nobs: field(line, 118, 122).parse().unwrap_or(0),
+            nopp: field(line, 124, 126).parse().unwrap_or(0),
+            rms: opt(138, 141),
+            flags: u16::from_str_radix(field(line, 162, 165), 16).unwrap_or(0),
+        })
+    }
+
+    ///  Read an MPC orbit file
+    ///
+    ///  Any header, ending with a line of dashes as in MPCORB.DAT, is
+    ///  skipped, as are blank lines.
+    pub fn read<R: BufRead>(reader: R) -> io::Result<Vec<Self>> {
+        let mut orbits = Vec::new();
+        let mut error = None;
+        for line in reader.lines() {
+            let line = line?;
+            if line.starts_with("-----") {
+                orbits.clear();
+                error = None;
+                continue;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            match MpcOrbit::parse(&line) {
+                Ok(o) => orbits.push(o),
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
+            }
+        }
+        match error {
+            Some(e) => Err(e),
+            None => Ok(orbits),
+        }
+    }
+
+    /// Read an MPC orbit file (MPCORB.DAT etc.).
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Vec<Self>> {
+        MpcOrbit::read(BufReader::new(File::open(path)?))
+    }
+
+    ///  The orbit as cometary elements
+    ///
+    ///  The time of perihelion is obtained from the mean anomaly and
+    ///  the mean motion, and is TT, which for this purpose is the
+    ///  same as TDB.  The angles remain referred to the ecliptic and
+    ///  equinox of J2000.0.
+    pub fn elements(&self) -> Elements {
+        Elements {
+            q: self.a * (1.0 - self.e),
+            e: self.e,
+            incl: self.incl,
+            node: self.node,
+            argp: self.argp,
+            tp: (self.epoch[0] - self.ma / self.n) + self.epoch[1],
+        }
+    }
+
+    ///  Heliocentric position and velocity
+    ///
+    ///  Given:
+    ///  ```text
+    ///     date1  f64     TDB as a 2-part...
+    ///     date2  f64     ...Julian Date
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok(pv) [[f64; 3]; 2]  heliocentric p,v (ICRS, au, au/d)
+    ///     Err(j) i32            status from el2pv
+    ///  ```
+    ///  Note:
+    ///
+    ///     This is the unperturbed two-body motion, adequate for dates
+    ///     close to the epoch.  The gravitational parameter is GK^2.
+    ///
+    ///  Called:
+    ///  ```text
+    ///     el2pv     cometary elements to p,v
+    ///     ecl2000   ICRS to MPC ecliptic rotation matrix
+    ///     iauTrxpv  product of transpose of r-matrix and pv-vector
+    ///  ```
+    pub fn pv(&self, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+        let pv = el2pv(&self.elements(), GK * GK, date1, date2)?;
+        let mut out = [[0.0; 3]; 2];
+        trxpv(&ecl2000(), &pv, &mut out);
+        Ok(out)
+    }
+}
+
+///  Rotation matrix from the ICRS to the ecliptic of J2000.0 as used
+///  for published orbital elements
+///
+///  Returned (function value):
+///  ```text
+///             [[f64; 3]; 3]  rotation matrix
+///  ```
+///  Note:
+///
+///     This is the frame of the MPC and JPL elements:  a rotation
+///     about the ICRS x-axis by the IAU 1976 obliquity 84381.448
+///     arcsec.  It differs by a few hundredths of an arcsecond from
+///     the IAU 2006 ecliptic of J2000.0 given by ecm06(DJ00, 0.0).
+///
+///  Called:
+///  ```text
+///     iauIr     initialize r-matrix to identity
+///     iauRx     rotate around X-axis
+///  ```
+pub fn ecl2000() -> [[f64; 3]; 3] {
+    let mut r = [[0.0; 3]; 3];
+    ir(&mut r);
+    rx(EPS0, &mut r);
+    r
+}
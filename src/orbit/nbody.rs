@@ -0,0 +1,364 @@
+use super::{flat, unflat};
+use crate::consts::DC;
+use crate::eph::{Ephemeris, Target};
+use crate::vm::pm;
+
+/// Gravitational parameters (au^3/d^2) from JPL DE430.
+const GMDE430: [(Target, f64); 9] = [
+    (Target::Sun, 2.959122082855912e-4),
+    (Target::Mercury, 4.912547451450812e-11),
+    (Target::Venus, 7.243452486162703e-10),
+    (Target::Emb, 8.997011603631609e-10),
+    (Target::Mars, 9.549535105779258e-11),
+    (Target::Jupiter, 2.825345842083778e-7),
+    (Target::Saturn, 8.459715185680659e-8),
+    (Target::Uranus, 1.292024916781969e-8),
+    (Target::Neptune, 1.524358900784276e-8),
+];
+
+/* Longest step, as a fraction of the dynamical time scale r/v. */
+const HMAX: f64 = 0.05;
+
+/* Substep sequence of the extrapolation. */
+const NSEQ: [usize; 10] = [2, 4, 6, 8, 10, 12, 14, 16, 18, 20];
+
+/// One accepted step of an integration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Node {
+    t: f64,            // days from the start
+    pv: [[f64; 3]; 2], // position and velocity
+    acc: [f64; 3],     // acceleration
+}
+
+///  Numerically integrated trajectory with dense output
+///
+///  Returned by NBody::integrate.  The position and velocity can be
+///  obtained at any time within the span of the integration, for
+///  example inside a light-time iteration:  a closure
+///  |d1, d2| traj.pv(d1, d2).unwrap() serves as the spacecraft
+///  function of lt1w.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trajectory {
+    start: [f64; 2],  // TDB at the start (2-part JD)
+    nodes: Vec<Node>, // the steps, in integration order
+}
+
+impl Trajectory {
+    /// TDB span covered, as Julian Dates (first, last).
+    pub fn span(&self) -> (f64, f64) {
+        let t0 = self.start[0] + self.start[1];
+        let (a, b) = (self.nodes[0].t, self.nodes[self.nodes.len() - 1].t);
+        (t0 + a.min(b), t0 + a.max(b))
+    }
+
+    /// Number of integration steps.
+    pub fn steps(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    ///  Position and velocity at a given TDB
+    ///
+    ///  Given:
+    ///  ```text
+    ///     date1  f64     TDB as a 2-part...
+    ///     date2  f64     ...Julian Date
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok(pv) [[f64; 3]; 2]  barycentric p,v (ICRS, au, au/d)
+    ///     Err(j) i32            -1 = date outside the integration
+    ///  ```
+    ///  Note:
+    ///
+    ///     Between steps the position is interpolated by the quintic
+    ///     Hermite polynomial matching the positions, velocities and
+    ///     accelerations at both ends, and the velocity is its
+    ///     derivative.  The interpolation error is far below the
+    ///     integration error for the step sizes chosen by integrate.
+    pub fn pv(&self, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+        let t = (date1 - self.start[0]) + (date2 - self.start[1]);
+        let n = &self.nodes;
+        let fwd = n[n.len() - 1].t >= n[0].t;
+        let key = |x: f64| if fwd { x } else { -x };
+        if key(t) < key(n[0].t) || key(t) > key(n[n.len() - 1].t) {
+            return Err(-1);
+        }
+        if n.len() == 1 {
+            return Ok(n[0].pv);
+        }
+
+        /* The step containing t. */
+        let i = n
+            .partition_point(|x| key(x.t) <= key(t))
+            .clamp(1, n.len() - 1);
+        let (a, b) = (&n[i - 1], &n[i]);
+        let h = b.t - a.t;
+        let s = (t - a.t) / h;
+
+        /* Quintic Hermite basis and derivatives. */
+        let (s2, s3) = (s * s, s * s * s);
+        let (s4, s5) = (s3 * s, s3 * s2);
+        let p = [
+            1.0 - 10.0 * s3 + 15.0 * s4 - 6.0 * s5,
+            s - 6.0 * s3 + 8.0 * s4 - 3.0 * s5,
+            0.5 * s2 - 1.5 * s3 + 1.5 * s4 - 0.5 * s5,
+            0.5 * s3 - s4 + 0.5 * s5,
+            -4.0 * s3 + 7.0 * s4 - 3.0 * s5,
+            10.0 * s3 - 15.0 * s4 + 6.0 * s5,
+        ];
+        let d = [
+            -30.0 * s2 + 60.0 * s3 - 30.0 * s4,
+            1.0 - 18.0 * s2 + 32.0 * s3 - 15.0 * s4,
+            s - 4.5 * s2 + 6.0 * s3 - 2.5 * s4,
+            1.5 * s2 - 4.0 * s3 + 2.5 * s4,
+            -12.0 * s2 + 28.0 * s3 - 15.0 * s4,
+            30.0 * s2 - 60.0 * s3 + 30.0 * s4,
+        ];
+
+        let c: [[f64; 6]; 3] = std::array::from_fn(|k| {
+            [
+                a.pv[0][k],
+                h * a.pv[1][k],
+                h * h * a.acc[k],
+                h * h * b.acc[k],
+                h * b.pv[1][k],
+                b.pv[0][k],
+            ]
+        });
+        let dot = |w: &[f64; 6], c: &[f64; 6]| (0..6).map(|j| w[j] * c[j]).sum::<f64>();
+        let pv = [
+            std::array::from_fn(|k| dot(&p, &c[k])),
+            std::array::from_fn(|k| dot(&d, &c[k]) / h),
+        ];
+        Ok(pv)
+    }
+}
+
+///  Numerical integration of a small body in the solar system
+///
+///  The body moves in the gravitational field of the Sun and planets,
+///  whose positions are supplied by an ephemeris, with optionally the
+///  post-Newtonian (Schwarzschild) term of the Sun.  The body itself
+///  is massless.
+///
+///  Notes:
+///
+///  1) States are barycentric (BCRS, au, au/d) and times TDB.  For a
+///     heliocentric state, add or subtract the barycentric state of
+///     the Sun from the same ephemeris, eph.pvb(Target::Sun, ...).
+///
+///  2) By default the perturbers are the Sun, Mercury, Venus, the
+///     Earth-Moon barycenter, Mars and the system barycenters of
+///     Jupiter to Neptune, with the DE430 masses, as supplied for
+///     example by Plan94.  With a JPL ephemeris the Emb entry may be
+///     replaced by separate Earth and Moon entries (GM 8.8876924467e-10
+///     and 1.0931894624e-11), improving close approaches to the Earth.
+///
+///  3) The integrator is the Bulirsch-Stoer method:  a step is divided
+///     into 2, 4, 6 ... substeps by the modified midpoint rule and the
+///     results extrapolated to zero substep length.  The step size
+///     adapts to meet the tolerance tol, the relative error permitted
+///     in position and velocity in one step.
+///
+///  Reference:
+///
+///     Hairer, E., Norsett, S.P. and Wanner, G., Solving Ordinary
+///     Differential Equations I, 2nd ed., Springer (1993), Section
+///     II.9.
+#[derive(Debug, Clone)]
+pub struct NBody<E> {
+    pub eph: E,                     // source of the perturber positions
+    pub bodies: Vec<(Target, f64)>, // perturbers and GM (au^3/d^2)
+    pub gr: bool,                   // include the solar relativistic term
+    pub tol: f64,                   // relative error per step
+}
+
+impl<E: Ephemeris> NBody<E> {
+    /// Integrator with the default perturbers, relativity and
+    /// tolerance 1e-13.
+    pub fn new(eph: E) -> Self {
+        NBody {
+            eph,
+            bodies: GMDE430.to_vec(),
+            gr: true,
+            tol: 1e-13,
+        }
+    }
+
+    ///  Acceleration of the body
+    ///
+    ///  Given:
+    ///  ```text
+    ///     date1  f64            TDB as a 2-part...
+    ///     date2  f64            ...Julian Date
+    ///     pv     [[f64; 3]; 2]  barycentric p,v (au, au/d)
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok(a)  [f64; 3]       acceleration (au/d^2)
+    ///     Err(j) i32            status from the ephemeris
+    ///  ```
+    ///  Note:
+    ///
+    ///     The relativistic term, for the Sun only, is that of a test
+    ///     particle in the Schwarzschild field in harmonic coordinates
+    ///     (PPN beta = gamma = 1):  a = GM/(c^2 r^3) ((4GM/r - v^2) r
+    ///     + 4 (r.v) v), with r and v relative to the Sun.
+    pub fn accel(&self, date1: f64, date2: f64, pv: &[[f64; 3]; 2]) -> Result<[f64; 3], i32> {
+        let mut a = [0.0; 3];
+        for &(body, gm) in &self.bodies {
+            let pb = self.eph.pvb(body, date1, date2)?;
+            let r: [f64; 3] = std::array::from_fn(|k| pv[0][k] - pb[0][k]);
+            let r2 = r[0] * r[0] + r[1] * r[1] + r[2] * r[2];
+            let rm = r2.sqrt();
+            let w = gm / (r2 * rm);
+            for k in 0..3 {
+                a[k] -= w * r[k];
+            }
+
+            /* Post-Newtonian term of the Sun. */
+            if self.gr && body == Target::Sun {
+                let v: [f64; 3] = std::array::from_fn(|k| pv[1][k] - pb[1][k]);
+                let v2 = v[0] * v[0] + v[1] * v[1] + v[2] * v[2];
+                let rv = r[0] * v[0] + r[1] * v[1] + r[2] * v[2];
+                let f = w / (DC * DC);
+                for k in 0..3 {
+                    a[k] += f * ((4.0 * gm / rm - v2) * r[k] + 4.0 * rv * v[k]);
+                }
+            }
+        }
+        Ok(a)
+    }
+
+    ///  Integrate the motion of the body
+    ///
+    ///  Given:
+    ///  ```text
+    ///     pv     [[f64; 3]; 2]  barycentric p,v at the start (au, au/d)
+    ///     date1  f64            TDB at the start as a 2-part...
+    ///     date2  f64            ...Julian Date
+    ///     dt     f64            interval (days, either sign)
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok(tr) Trajectory     the trajectory, from date to date+dt
+    ///     Err(j) i32            -10 = step size underflow, else
+    ///                           status from the ephemeris
+    ///  ```
+    pub fn integrate(
+        &self,
+        pv: &[[f64; 3]; 2],
+        date1: f64,
+        date2: f64,
+        dt: f64,
+    ) -> Result<Trajectory, i32> {
+        let acc = self.accel(date1, date2, pv)?;
+        let mut nodes = vec![Node {
+            t: 0.0,
+            pv: *pv,
+            acc,
+        }];
+
+        /* First step:  a fraction of the dynamical time scale. */
+        let mut h = (0.5 * HMAX * tscale(pv)).min(dt.abs()).copysign(dt);
+
+        let mut t = 0.0;
+        let mut y = *pv;
+        while (dt - t) * dt.signum() > 0.0 {
+            if (t + h - dt) * dt.signum() > 0.0 {
+                h = dt - t;
+            }
+            if h.abs() < 1e-12 * (1.0 + dt.abs()) {
+                return Err(-10);
+            }
+            match self.bsstep(date1, date2 + t, &y, h)? {
+                Some((yn, k)) => {
+                    t = if (t + h - dt) * dt.signum() >= 0.0 {
+                        dt
+                    } else {
+                        t + h
+                    };
+                    y = yn;
+                    let acc = self.accel(date1, date2 + t, &y)?;
+                    nodes.push(Node { t, pv: y, acc });
+
+                    /* Lengthen or shorten according to the work needed. */
+                    if k <= 4 {
+                        h *= 1.5;
+                    } else if k >= 7 {
+                        h *= 0.7;
+                    }
+
+                    /* Short enough for the dense output. */
+                    h = h.abs().min(HMAX * tscale(&y)).copysign(dt);
+                }
+                None => h *= 0.4,
+            }
+        }
+
+        Ok(Trajectory {
+            start: [date1, date2],
+            nodes,
+        })
+    }
+
+    /* One extrapolated step;  None if the tolerance was not met. */
+    #[allow(clippy::type_complexity)]
+    fn bsstep(
+        &self,
+        d1: f64,
+        d2: f64,
+        y: &[[f64; 3]; 2],
+        h: f64,
+    ) -> Result<Option<([[f64; 3]; 2], usize)>, i32> {
+        let mut tab: Vec<Vec<[f64; 6]>> = Vec::with_capacity(NSEQ.len());
+        let f0 = self.deriv(d1, d2, &flat(y))?;
+        let scale = [pm(y[0]), pm(y[1]).max(1e-10)];
+        for (k, &n) in NSEQ.iter().enumerate() {
+            /* Modified midpoint rule with n substeps. */
+            let hs = h / n as f64;
+            let mut z0 = flat(y);
+            let mut z1: [f64; 6] = std::array::from_fn(|i| z0[i] + hs * f0[i]);
+            for m in 1..n {
+                let f = self.deriv(d1, d2 + m as f64 * hs, &z1)?;
+                let z2: [f64; 6] = std::array::from_fn(|i| z0[i] + 2.0 * hs * f[i]);
+                (z0, z1) = (z1, z2);
+            }
+            let f = self.deriv(d1, d2 + h, &z1)?;
+            let yn: [f64; 6] = std::array::from_fn(|i| 0.5 * (z0[i] + z1[i] + hs * f[i]));
+
+            /* Polynomial extrapolation in the square of the substep. */
+            let mut row = vec![yn];
+            for j in 1..=k {
+                let r = (n as f64 / NSEQ[k - j] as f64).powi(2) - 1.0;
+                let prev = &tab[k - 1][j - 1];
+                let last = row[j - 1];
+                row.push(std::array::from_fn(|i| last[i] + (last[i] - prev[i]) / r));
+            }
+
+            if k >= 2 {
+                let (a, b) = (&row[k], &row[k - 1]);
+                let err = (0..6)
+                    .map(|i| (a[i] - b[i]).abs() / scale[i / 3])
+                    .fold(0.0, f64::max);
+                if err <= self.tol {
+                    return Ok(Some((unflat(a), k)));
+                }
+            }
+            tab.push(row);
+        }
+        Ok(None)
+    }
+
+    /* Derivative of the state (velocity, acceleration). */
+    fn deriv(&self, d1: f64, d2: f64, z: &[f64; 6]) -> Result<[f64; 6], i32> {
+        let pv = unflat(z);
+        let a = self.accel(d1, d2, &pv)?;
+        Ok([pv[1][0], pv[1][1], pv[1][2], a[0], a[1], a[2]])
+    }
+}
+
+/* Dynamical time scale r/v (days). */
+fn tscale(pv: &[[f64; 3]; 2]) -> f64 {
+    pm(pv[0]) / pm(pv[1]).max(1e-10)
+}
@@ -0,0 +1,202 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use super::mpcdes::field;
+use super::unpackdes;
+use crate::cal::cal2jd;
+use crate::consts::{DAU, DD2R};
+use crate::eph::invalid;
+
+/// One optical astrometric observation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Observation {
+    pub desig: String,          // designation, unpacked where possible
+    pub disc: bool,             // discovery observation
+    pub note1: char,            // program code or note (' ' if none)
+    pub kind: char,             // observation type (Note 1 of read_obs80)
+    pub utc: [f64; 2],          // UTC of the observation (2-part quasi JD)
+    pub ra: f64,                // right ascension (ICRS, radians)
+    pub dec: f64,               // declination (ICRS, radians)
+    pub rms: Option<[f64; 2]>,  // uncertainties in RA cos(dec), dec (radians)
+    pub mag: Option<f64>,       // magnitude
+    pub band: String,           // photometric band
+    pub code: String,           // observatory code
+    pub pos: Option<[f64; 3]>,  // geocentric observer position (ICRS, au)
+    pub site: Option<[f64; 3]>, // roving observer E long, lat (rad), height (m)
+}
+
+/// Sexagesimal value from one to three fields.
+fn sexa(s: &str) -> Option<f64> {
+    let mut v = 0.0;
+    let mut u = 1.0;
+    let mut n = 0;
+    for w in s.split_whitespace() {
+        v += w.parse::<f64>().ok()? / u;
+        u *= 60.0;
+        n += 1;
+    }
+    (1..=3).contains(&n).then_some(v)
+}
+
+/// Signed decimal number with spaces between sign and digits.
+fn signed(s: &str) -> Option<f64> {
+    s.replace(' ', "").parse().ok()
+}
+
+impl Observation {
+    ///  Parse the first line of an 80-column observation
+    ///
+    ///  The second line of a satellite or roving observation is applied
+    ///  with add_line80.
+    pub fn parse_obs80(line: &str) -> io::Result<Self> {
+        if line.len() < 80 {
+            return Err(invalid("observation line too short"));
+        }
+
+        /* Designation:  number, else provisional or temporary. */
+        let (num, prov) = (field(line, 1, 5), field(line, 6, 12));
+        let desig = if num.len() == 5 {
+            unpackdes(num)
+        } else {
+            unpackdes(field(line, 5, 12))
+        }
+        .unwrap_or_else(|_| if prov.is_empty() { num } else { prov }.to_string());
+
+        /* Date:  year, month and decimal day. */
+        let d: Vec<&str> = field(line, 16, 32).split_whitespace().collect();
+        if d.len() != 3 {
+            return Err(invalid("bad date"));
+        }
+        let iy = d[0].parse().map_err(|_| invalid("bad year"))?;
+        let im = d[1].parse().map_err(|_| invalid("bad month"))?;
+        let day: f64 = d[2].parse().map_err(|_| invalid("bad day"))?;
+        let (u1, u2) = cal2jd(iy, im, day as i32).map_err(|_| invalid("bad date"))?;
+
+        /* Right ascension and declination. */
+        let ra = sexa(field(line, 33, 44)).ok_or_else(|| invalid("bad RA"))? * 15.0 * DD2R;
+        let dec = sexa(field(line, 46, 56)).ok_or_else(|| invalid("bad Dec"))? * DD2R;
+        let dec = match field(line, 45, 45) {
+            "-" => -dec,
+            "+" | "" => dec,
+            _ => return Err(invalid("bad Dec sign")),
+        };
+
+        let flag = |c| field(line, c, c).chars().next().unwrap_or(' ');
+        Ok(Observation {
+            desig,
+            disc: flag(13) == '*',
+            note1: flag(14),
+            kind: flag(15),
+            utc: [u1, u2 + day.fract()],
+            ra,
+            dec,
+            rms: None,
+            mag: field(line, 66, 70).parse().ok(),
+            band: field(line, 71, 71).to_string(),
+            code: field(line, 78, 80).to_string(),
+            pos: None,
+            site: None,
+        })
+    }
+
+    ///  Apply the second line of a satellite ('s') or roving ('v')
+    ///  observation in the 80-column format
+    pub fn add_line80(&mut self, line: &str) -> io::Result<()> {
+        let bad = || invalid("bad second observation line");
+        match field(line, 15, 15) {
+            "s" => {
+                /* Geocentric position, km (1) or au (2). */
+                let unit = match field(line, 33, 33) {
+                    "1" => 1e3 / DAU,
+                    "2" => 1.0,
+                    _ => return Err(bad()),
+                };
+                let mut p = [0.0; 3];
+                for (i, a) in [35, 47, 59].into_iter().enumerate() {
+                    p[i] = signed(field(line, a, a + 10)).ok_or_else(bad)? * unit;
+                }
+                self.pos = Some(p);
+            }
+            "v" => {
+                /* Longitude and latitude (degrees), height (m). */
+                let elong: f64 = field(line, 35, 44).parse().map_err(|_| bad())?;
+                let phi: f64 = signed(field(line, 46, 55)).ok_or_else(bad)?;
+                let hm: f64 = field(line, 57, 61).parse().map_err(|_| bad())?;
+                self.site = Some([elong * DD2R, phi * DD2R, hm]);
+            }
+            _ => return Err(bad()),
+        }
+        Ok(())
+    }
+
+    ///  Read observations in the MPC 80-column format
+    ///
+    ///  Notes:
+    ///
+    ///  1) The observation type (column 15) is ' ' or 'P' for
+    ///     photographic, 'C' for CCD, 'S' for an observer on an Earth
+    ///     satellite and 'V' for a roving observer, among others.  For
+    ///     the 'S' and 'V' types the second line supplies the observer
+    ///     position (pos) or site (site).
+    ///
+    ///  2) Radar observations ('R', 'r') are skipped, as are blank
+    ///     lines and the header lines (COD, OBS, etc.) of submissions.
+    ///
+    ///  3) The date is that of the observation file, which is UTC
+    ///     (UT before 1972) and is returned as a SOFA quasi-JD, the
+    ///     fraction of the day being taken as given.
+    ///
+    ///  Reference:
+    ///
+    ///     Minor Planet Center, "Format For Optical Astrometric
+    ///     Observations Of Comets, Minor Planets and Natural
+    ///     Satellites", https://minorplanetcenter.net/iau/info/
+    ///     OpticalObs.html
+    pub fn read_obs80<R: BufRead>(reader: R) -> io::Result<Vec<Self>> {
+        let mut obs: Vec<Observation> = Vec::new();
+        let mut pending = false;
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() || is_header(&line) {
+                continue;
+            }
+            match field(&line, 15, 15) {
+                "R" | "r" => {}
+                "s" | "v" => {
+                    let o = obs
+                        .last_mut()
+                        .filter(|_| pending)
+                        .ok_or_else(|| invalid("second line without first"))?;
+                    o.add_line80(&line)?;
+                    pending = false;
+                }
+                _ => {
+                    if pending {
+                        return Err(invalid("missing second line"));
+                    }
+                    let o = Observation::parse_obs80(&line)?;
+                    pending = o.kind == 'S' || o.kind == 'V';
+                    obs.push(o);
+                }
+            }
+        }
+        if pending {
+            return Err(invalid("missing second line"));
+        }
+        Ok(obs)
+    }
+
+    /// Read an 80-column observation file.
+    pub fn open_obs80<P: AsRef<Path>>(path: P) -> io::Result<Vec<Self>> {
+        Observation::read_obs80(BufReader::new(File::open(path)?))
+    }
+}
+
+/// Header line of an observation submission ("COD 568" etc.).
+fn is_header(line: &str) -> bool {
+    const KEYS: [&str; 12] = [
+        "COD", "CON", "OBS", "MEA", "TEL", "NET", "BND", "COM", "NUM", "ACK", "AC2", "ADD",
+    ];
+    line.len() >= 4 && line.as_bytes()[3] == b' ' && KEYS.contains(&&line[..3])
+}
@@ -0,0 +1,117 @@
+use super::{Elements, stumpff};
+use crate::consts::D2PI;
+use crate::vm::{anp, pdp, pm, pxp};
+
+///  Cometary elements from a two-body position and velocity
+///
+///  Given:
+///  ```text
+///     pv     [[f64; 3]; 2]  position and velocity (au, au/d, Note 1)
+///     gm     f64            gravitational parameter (au^3/d^2)
+///     date1  f64            TDB of the state as a 2-part...
+///     date2  f64            ...Julian Date
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(el) Elements       osculating elements (Note 2)
+///     Err(j) i32            -1 = illegal pv or gm
+///  ```
+///  Notes:
+///
+///  1) The angles of the elements are referred to the frame of the
+///     state vector.  For elements in the Minor Planet Center style,
+///     first rotate an ICRS state to the ecliptic of J2000.0 using
+///     ecl2000().
+///
+///  2) All conics are supported.  The time of perihelion is that of
+///     the nearest perihelion, before or after the date.  For
+///     circular orbits the perihelion is placed at the node, and for
+///     zero inclination the node is placed on the x-axis.
+///
+///  3) A rectilinear orbit (zero angular momentum) is rejected.
+///
+///  Called:
+///  ```text
+///     iauPm     modulus of p-vector
+///     iauPdp    scalar product of two p-vectors
+///     iauPxp    vector product of two p-vectors
+///     iauAnp    normalize angle into range 0 to 2pi
+///     stumpff   Stumpff functions c2, c3
+///  ```
+pub fn pv2el(pv: &[[f64; 3]; 2], gm: f64, date1: f64, date2: f64) -> Result<Elements, i32> {
+    let r = pm(pv[0]);
+    if r <= 0.0 || gm <= 0.0 {
+        return Err(-1);
+    }
+
+    /* Angular momentum. */
+    let h = pxp(&pv[0], &pv[1]);
+    let hm = pm(h);
+    if hm <= 0.0 {
+        return Err(-1);
+    }
+    let w: [f64; 3] = [h[0] / hm, h[1] / hm, h[2] / hm];
+
+    /* Eccentricity vector. */
+    let vh = pxp(&pv[1], &h);
+    let ev: [f64; 3] = std::array::from_fn(|i| vh[i] / gm - pv[0][i] / r);
+    let e = pm(ev);
+    let q = hm * hm / gm / (1.0 + e);
+
+    /* Inclination and node. */
+    let incl = (h[0] * h[0] + h[1] * h[1]).sqrt().atan2(h[2]);
+    let node = if h[0] == 0.0 && h[1] == 0.0 {
+        0.0
+    } else {
+        anp(h[0].atan2(-h[1]))
+    };
+    let (sn, cn) = node.sin_cos();
+    let nv = [cn, sn, 0.0];
+    let nw = pxp(&w, &nv);
+
+    /* Argument of perihelion, from the node. */
+    let argp = anp(pdp(&ev, &nw).atan2(pdp(&ev, &nv)));
+
+    /* True anomaly, from perihelion. */
+    let (sw, cw) = argp.sin_cos();
+    let pp: [f64; 3] = std::array::from_fn(|i| cw * nv[i] + sw * nw[i]);
+    let qq = pxp(&w, &pp);
+    let nu = pdp(&pv[0], &qq).atan2(pdp(&pv[0], &pp));
+
+    /* Time from perihelion, via the universal anomaly. */
+    let s = (0.5 * nu).tan();
+    let u = (1.0 - e) / (1.0 + e) * s * s;
+    let f = if u.abs() < 0.01 {
+        let mut f = 0.0;
+        let mut t = 1.0;
+        for k in 0..10 {
+            f += t / (2 * k + 1) as f64;
+            t *= -u;
+        }
+        f
+    } else if u > 0.0 {
+        u.sqrt().atan() / u.sqrt()
+    } else {
+        (-u).sqrt().atanh() / (-u).sqrt()
+    };
+    let x = 2.0 * (q / (1.0 + e)).sqrt() * s * f;
+    let z = (1.0 - e) / q * x * x;
+    let (_, c3) = stumpff(z);
+    let mut dt = (x * x * x * c3 + q * x * (1.0 - z * c3)) / gm.sqrt();
+
+    /* Nearest perihelion, for an ellipse. */
+    if e < 1.0 {
+        let a = q / (1.0 - e);
+        let per = D2PI * (a * a * a / gm).sqrt();
+        dt -= per * (dt / per).round();
+    }
+
+    Ok(Elements {
+        q,
+        e,
+        incl,
+        node,
+        argp,
+        tp: (date1 - dt) + date2,
+    })
+}
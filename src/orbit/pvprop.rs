@@ -0,0 +1,149 @@
+use super::stumpff;
+use crate::consts::D2PI;
+use crate::vm::{pdp, pm};
+
+///  Propagate a two-body state vector
+///
+///  Given:
+///  ```text
+///     pv     [[f64; 3]; 2]  position and velocity (Note 1)
+///     gm     f64            gravitational parameter (Note 1)
+///     dt     f64            time interval (Note 1)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(pv) [[f64; 3]; 2]  position and velocity after dt
+///     Err(j) i32            -1 = illegal pv or gm
+///                           -2 = failed to converge
+///  ```
+///  Notes:
+///
+///  1) Any consistent units may be used.  For heliocentric orbits in
+///     au and au/d, gm is GK^2 (1 + m), where GK is the Gaussian
+///     gravitational constant and m the mass of the body in solar
+///     masses (usually negligible).  The time is then in days, and
+///     should be TDB.
+///
+///  2) The universal-variable formulation is used, valid for all
+///     conics including the parabola and the rectilinear cases.
+///     Kepler's equation in the universal anomaly is solved by the
+///     Laguerre-Conway method, which converges for any starting
+///     value, and stops when the residual of the equation reaches the
+///     rounding level of its terms.  For elliptic orbits, whole periods
+///     are removed from dt first.
+///
+///  3) The result is in the frame of the given pv, and can be passed
+///     to the vm functions and, once made barycentric, to the astro
+///     functions (iauAtciq etc.).
+///
+///  Called:
+///  ```text
+///     iauPdp    scalar product of two p-vectors
+///     iauPm     modulus of p-vector
+///     stumpff   Stumpff functions c2, c3
+///  ```
+///
+///  References:
+///
+///     Conway, B.A., "An improved algorithm due to Laguerre for the
+///     solution of Kepler's equation", Celest. Mech. 39, 199 (1986).
+///
+///     Vallado, D.A., Fundamentals of Astrodynamics and Applications,
+///     4th ed., Microcosm Press (2013), Algorithm 8.
+pub fn pvprop(pv: &[[f64; 3]; 2], gm: f64, dt: f64) -> Result<[[f64; 3]; 2], i32> {
+    let [f, g, fd, gd] = fg(pv, gm, dt)?;
+
+    let mut out = [[0.0; 3]; 2];
+    for i in 0..3 {
+        out[0][i] = f * pv[0][i] + g * pv[1][i];
+        out[1][i] = fd * pv[0][i] + gd * pv[1][i];
+    }
+    Ok(out)
+}
+
+/* Lagrange coefficients f, g, f', g' for an interval dt. */
+pub(crate) fn fg(pv: &[[f64; 3]; 2], gm: f64, dt: f64) -> Result<[f64; 4], i32> {
+    /* Maximum number of iterations. */
+    const KMAX: usize = 100;
+
+    let r0 = pm(pv[0]);
+    if r0 <= 0.0 || gm <= 0.0 {
+        return Err(-1);
+    }
+    if dt == 0.0 {
+        return Ok([1.0, 0.0, 0.0, 1.0]);
+    }
+    let sgm = gm.sqrt();
+    let v2 = pdp(&pv[1], &pv[1]);
+    let s0 = pdp(&pv[0], &pv[1]) / sgm;
+
+    /* Reciprocal semi-major axis. */
+    let alpha = 2.0 / r0 - v2 / gm;
+
+    /* Remove whole revolutions of an ellipse. */
+    let mut t = dt;
+    if alpha > 0.0 {
+        let per = D2PI / (alpha * alpha.sqrt() * sgm);
+        t -= per * (t / per).round();
+    }
+    let st = sgm * t;
+
+    /* Starting value. */
+    let mut x = if alpha > 0.0 {
+        st * alpha
+    } else if alpha < 0.0 {
+        let a = 1.0 / alpha;
+        let w =
+            -2.0 * gm * alpha * t / (s0 * sgm + t.signum() * (-gm * a).sqrt() * (1.0 - r0 * alpha));
+        t.signum() * (-a).sqrt() * w.abs().max(1.0).ln()
+    } else {
+        st / r0
+    };
+
+    /* Laguerre-Conway iteration. */
+    let n = 5.0;
+    let mut ok = false;
+    for _ in 0..KMAX {
+        let x2 = x * x;
+        let z = alpha * x2;
+        let (c2, c3) = stumpff(z);
+        let f = r0 * x * (1.0 - z * c3) + s0 * x2 * c2 + x2 * x * c3 - st;
+        let r = x2 * c2 + s0 * x * (1.0 - z * c3) + r0 * (1.0 - z * c2);
+        let f2 = s0 * (1.0 - z * c2) + (1.0 - alpha * r0) * x * (1.0 - z * c3);
+
+        /* Converged to the rounding level of the terms of f. */
+        let fs =
+            (r0 * x * (1.0 - z * c3)).abs() + (s0 * x2 * c2).abs() + (x2 * x * c3).abs() + st.abs();
+        if f.abs() <= 4.0 * f64::EPSILON * fs {
+            ok = true;
+            break;
+        }
+        let d = (((n - 1.0) * (n - 1.0) * r * r - n * (n - 1.0) * f * f2).abs()).sqrt();
+        let dx = n * f / (r + r.signum() * d);
+        x -= dx;
+        if dx.abs() <= 1e-14 * (1.0 + x.abs()) {
+            ok = true;
+            break;
+        }
+    }
+    if !ok {
+        return Err(-2);
+    }
+
+    /* Final radius, with the converged anomaly. */
+    let x2 = x * x;
+    let z = alpha * x2;
+    let (c2, c3) = stumpff(z);
+    let r = x2 * c2 + s0 * x * (1.0 - z * c3) + r0 * (1.0 - z * c2);
+    if r <= 0.0 {
+        return Err(-2);
+    }
+
+    /* Lagrange coefficients. */
+    let f = 1.0 - x2 * c2 / r0;
+    let g = t - x2 * x * c3 / sgm;
+    let fd = sgm * x * (z * c3 - 1.0) / (r * r0);
+    let gd = 1.0 - x2 * c2 / r;
+
+    Ok([f, g, fd, gd])
+}
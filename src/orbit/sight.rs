@@ -0,0 +1,150 @@
+use super::{Observation, pvprop};
+use crate::consts::{DAS2R, DC};
+use crate::eph::epv00;
+use crate::track::stapvb;
+use crate::ts::{dtdb, taitt, tttdb, utctai};
+use crate::vm::{anp, c2s, pm, pmp, ppp, pvmpv};
+
+/// One astrometric observation prepared for orbit determination
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Sight {
+    pub tdb: [f64; 2],      // TDB of the observation (2-part JD)
+    pub ra: f64,            // astrometric right ascension (ICRS, radians)
+    pub dec: f64,           // astrometric declination (ICRS, radians)
+    pub obs: [[f64; 3]; 2], // heliocentric observer p,v (ICRS, au, au/d)
+    pub sigma: [f64; 2],    // uncertainties in RA cos(dec), dec (radians)
+}
+
+impl Sight {
+    ///  Prepare an observation for orbit determination
+    ///
+    ///  Given:
+    ///  ```text
+    ///     ob     &Observation     the observation (Note 1)
+    ///     site   Option<[f64; 3]> observatory longitude, geodetic
+    ///                             latitude (radians, east +ve) and
+    ///                             height (m), Note 2
+    ///     dut1   f64              UT1-UTC (seconds)
+    ///     xp,yp  f64              polar motion coordinates (radians)
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok(s)  Sight            the observation with TDB and the
+    ///                             heliocentric observer position
+    ///     Err(j) i32              -1 = unacceptable date
+    ///                             -2 = no observer position
+    ///  ```
+    ///  Notes:
+    ///
+    ///  1) The RA and Dec are astrometric (ICRS) as in the MPC and ADES
+    ///     formats.  Where the observation has no uncertainties,
+    ///     1 arcsec is assumed in each coordinate.
+    ///
+    ///  2) A satellite observer (ob.pos) is placed relative to the
+    ///     geocenter, a roving observer at ob.site, and any other at
+    ///     site, which for MPC observatory codes is derived from the
+    ///     longitude and parallax constants of the MPC list.  The
+    ///     station position and velocity come from stapvb (iauPvtob
+    ///     and iauEpv00);  the velocity of a satellite observer is
+    ///     taken to be that of the geocenter.
+    ///
+    ///  Called:
+    ///  ```text
+    ///     stapvb    station barycentric position and velocity
+    ///     iauEpv00  Earth position and velocity
+    ///     iauUtctai UTC to TAI
+    ///     iauTaitt  TAI to TT
+    ///     iauDtdb   TDB-TT
+    ///     iauTttdb  TT to TDB
+    ///     iauPvmpv  pv-vector minus pv-vector
+    ///     iauPpp    p-vector plus p-vector
+    ///  ```
+    pub fn new(
+        ob: &Observation,
+        site: Option<[f64; 3]>,
+        dut1: f64,
+        xp: f64,
+        yp: f64,
+    ) -> Result<Sight, i32> {
+        let [u1, u2] = ob.utc;
+        let (tdb1, tdb2, obs) = if let Some(p) = ob.pos {
+            /* Geocentric observer:  TDB-TT at the geocenter. */
+            let (tai1, tai2) = utctai(u1, u2).map_err(|_| -1)?;
+            let (tt1, tt2) = taitt(tai1, tai2).map_err(|_| -1)?;
+            let dtr = dtdb(tt1, tt2, 0.0, 0.0, 0.0, 0.0);
+            let (tdb1, tdb2) = tttdb(tt1, tt2, dtr).map_err(|_| -1)?;
+            let (eh, _) = epv00(tdb1, tdb2).ok_or(-1)?;
+            (tdb1, tdb2, [ppp(&eh[0], &p), eh[1]])
+        } else {
+            let [elong, phi, hm] = ob.site.or(site).ok_or(-2)?;
+            let (tdb1, tdb2, pvb) = stapvb(u1, u2, dut1, elong, phi, hm, xp, yp).map_err(|_| -1)?;
+
+            /* Barycentric to heliocentric. */
+            let (eh, eb) = epv00(tdb1, tdb2).ok_or(-1)?;
+            (tdb1, tdb2, pvmpv(&pvb, &pvmpv(&eb, &eh)))
+        };
+
+        Ok(Sight {
+            tdb: [tdb1, tdb2],
+            ra: ob.ra,
+            dec: ob.dec,
+            obs,
+            sigma: ob.rms.unwrap_or([DAS2R; 2]),
+        })
+    }
+
+    ///  Predicted astrometric place of a two-body orbit
+    ///
+    ///  Given:
+    ///  ```text
+    ///     pv     [[f64; 3]; 2]  heliocentric p,v (ICRS, au, au/d)
+    ///     date1  f64            TDB of pv as a 2-part...
+    ///     date2  f64            ...Julian Date
+    ///     gm     f64            gravitational parameter (au^3/d^2)
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok((ra, dec, rho))    RA, Dec (radians) and distance (au)
+    ///     Err(j) i32            status from pvprop
+    ///  ```
+    ///  Note:
+    ///
+    ///     The place is the direction from the observer at the time of
+    ///     the observation to the body at the time of emission, found
+    ///     by iterating on the light time.  Aberration is not applied,
+    ///     consistent with astrometric positions referred to catalog
+    ///     stars;  light deflection is neglected.  The residuals of the
+    ///     observation are then (self.ra - ra) cos(dec), self.dec - dec.
+    ///
+    ///  Called:
+    ///  ```text
+    ///     pvprop    propagate a two-body state vector
+    ///     iauPmp    p-vector minus p-vector
+    ///     iauPm     modulus of p-vector
+    ///     iauC2s    p-vector to spherical
+    ///     iauAnp    normalize angle into range 0 to 2pi
+    ///  ```
+    pub fn predict(
+        &self,
+        pv: &[[f64; 3]; 2],
+        date1: f64,
+        date2: f64,
+        gm: f64,
+    ) -> Result<(f64, f64, f64), i32> {
+        let dt = (self.tdb[0] - date1) + (self.tdb[1] - date2);
+        let mut tau = 0.0;
+        let mut d = [0.0; 3];
+        for _ in 0..10 {
+            let p = pvprop(pv, gm, dt - tau)?;
+            d = pmp(&p[0], &self.obs[0]);
+            let t = pm(d) / DC;
+            let done = (t - tau).abs() < 1e-13;
+            tau = t;
+            if done {
+                break;
+            }
+        }
+        let (ra, dec) = c2s(&d);
+        Ok((anp(ra), dec, tau * DC))
+    }
+}
@@ -0,0 +1,46 @@
+///  Stumpff functions c2 and c3
+///
+///  Given:
+///  ```text
+///     z      f64     argument (alpha x^2 in the universal-variable
+///                    formulation of the two-body problem)
+///  ```
+///  Returned (function value):
+///  ```text
+///            (f64, f64)  c2(z), c3(z)
+///  ```
+///  Notes:
+///
+///  1) The functions are c2(z) = (1 - cos(sqrt(z)))/z and
+///     c3(z) = (sqrt(z) - sin(sqrt(z)))/sqrt(z)^3 for z > 0, with the
+///     hyperbolic equivalents for z < 0 and c2 = 1/2, c3 = 1/6 at z = 0.
+///
+///  2) Near z = 0 the power series is used, avoiding the loss of
+///     precision of the closed forms.
+///
+///  Reference:
+///
+///     Stumpff, K., Himmelsmechanik, Band I (1959).
+pub fn stumpff(z: f64) -> (f64, f64) {
+    if z.abs() < 0.1 {
+        /* Series:  c2 = sum (-z)^k/(2k+2)!, c3 = sum (-z)^k/(2k+3)!. */
+        let mut c2 = 0.0;
+        let mut c3 = 0.0;
+        let mut t2 = 0.5;
+        let mut t3 = 1.0 / 6.0;
+        for k in 0..12 {
+            c2 += t2;
+            c3 += t3;
+            let k = k as f64;
+            t2 *= -z / ((2.0 * k + 3.0) * (2.0 * k + 4.0));
+            t3 *= -z / ((2.0 * k + 4.0) * (2.0 * k + 5.0));
+        }
+        (c2, c3)
+    } else if z > 0.0 {
+        let s = z.sqrt();
+        ((1.0 - s.cos()) / z, (s - s.sin()) / (s * z))
+    } else {
+        let s = (-z).sqrt();
+        ((s.cosh() - 1.0) / -z, (s.sinh() - s) / (s * -z))
+    }
+}
@@ -0,0 +1,41 @@
+use super::wgccre;
+use crate::consts::DPI;
+use crate::eph::Target;
+use crate::vm::{ir, rx, rz};
+
+///  Rotation matrix from the ICRS to a body-fixed frame
+///
+///  Given:
+///  ```text
+///     target Target  the body
+///     date1  f64     TDB as a 2-part...
+///     date2  f64     ...Julian Date
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(r)  [[f64; 3]; 3]  rotation matrix (Note)
+///     Err(j) i32            -1 = no rotation model for the body
+///  ```
+///  Note:
+///
+///     The matrix is Rz(W) x Rx(pi/2 - d0) x Rz(pi/2 + a0), where a0,
+///     d0 and W are from wgccre.  A vector p in the ICRS is expressed
+///     in the body-fixed frame, whose x-axis is the prime meridian and
+///     z-axis the north pole, by rxp(r, p).
+///
+///  Called:
+///  ```text
+///     wgccre    orientation of the body, IAU WGCCRE 2015
+///     iauIr     initialize r-matrix to identity
+///     iauRz     rotate around Z-axis
+///     iauRx     rotate around X-axis
+///  ```
+pub fn bodyfix(target: Target, date1: f64, date2: f64) -> Result<[[f64; 3]; 3], i32> {
+    let (a0, d0, w) = wgccre(target, date1, date2)?;
+    let mut r = [[0.0; 3]; 3];
+    ir(&mut r);
+    rz(DPI / 2.0 + a0, &mut r);
+    rx(DPI / 2.0 - d0, &mut r);
+    rz(w, &mut r);
+    Ok(r)
+}
@@ -0,0 +1,41 @@
+use crate::eph::Target;
+
+///  Size and shape of the Sun, Moon and planets, IAU WGCCRE 2015
+///
+///  Given:
+///  ```text
+///     target Target  the body
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok((a, f))
+///            a    f64   equatorial radius (km)
+///            f    f64   flattening, (a - c)/a, c the polar radius
+///     Err(j) i32        -1 = not a body with a rotation model
+///  ```
+///  Note:
+///
+///     The radii of the giant planets are at the 1 bar level.  The
+///     Moon and Pluto are taken as spheres of their mean radius, and
+///     the Sun of its nominal radius (IAU 2015 Resolution B3).
+///
+///  Reference:
+///
+///     Archinal, B.A. et al., 2018, Celest.Mech.Dyn.Astr. 130, 22.
+pub fn bodyshape(target: Target) -> Result<(f64, f64), i32> {
+    let (a, c) = match target {
+        Target::Sun => (695700.0, 695700.0),
+        Target::Mercury => (2440.53, 2438.26),
+        Target::Venus => (6051.8, 6051.8),
+        Target::Earth => (6378.1366, 6356.7519),
+        Target::Moon => (1737.4, 1737.4),
+        Target::Mars => (3396.19, 3376.20),
+        Target::Jupiter => (71492.0, 66854.0),
+        Target::Saturn => (60268.0, 54364.0),
+        Target::Uranus => (25559.0, 24973.0),
+        Target::Neptune => (24764.0, 24341.0),
+        Target::Pluto => (1188.3, 1188.3),
+        _ => return Err(-1),
+    };
+    Ok((a, (a - c) / a))
+}
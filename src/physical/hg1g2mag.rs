@@ -0,0 +1,113 @@
+use crate::consts::{DD2R, DPI};
+
+/* Spline nodes (deg), values and end derivatives (per radian) of the
+basis functions (Muinonen et al. 2010, Table 1). */
+const X12: [f64; 6] = [7.5, 30.0, 60.0, 90.0, 120.0, 150.0];
+const PHI1: [f64; 6] = [
+    7.5e-1,
+    3.3486016e-1,
+    1.3410560e-1,
+    5.1104756e-2,
+    2.1465687e-2,
+    3.6396989e-3,
+];
+const DPHI1: (f64, f64) = (-1.9098593, -9.1328612e-2);
+const PHI2: [f64; 6] = [
+    9.25e-1,
+    6.2884169e-1,
+    3.1755495e-1,
+    1.2716367e-1,
+    2.2373903e-2,
+    1.6505689e-4,
+];
+const DPHI2: (f64, f64) = (-5.7295780e-1, -8.6573138e-8);
+const X3: [f64; 9] = [0.0, 0.3, 1.0, 2.0, 4.0, 8.0, 12.0, 20.0, 30.0];
+const PHI3: [f64; 9] = [
+    1.0,
+    8.3381185e-1,
+    5.7735424e-1,
+    4.2144772e-1,
+    2.3174230e-1,
+    1.0348178e-1,
+    6.1733473e-2,
+    1.6107006e-2,
+    0.0,
+];
+const DPHI3: (f64, f64) = (-1.0630097e-1, 0.0);
+
+///  Apparent magnitude of an asteroid, IAU H,G1,G2 system
+///
+///  Given:
+///  ```text
+///     h      f64     absolute magnitude H
+///     g1     f64     phase parameter G1
+///     g2     f64     phase parameter G2
+///     r      f64     distance from the Sun (au)
+///     delta  f64     distance from the observer (au)
+///     phase  f64     phase angle (radians, Note 1)
+///  ```
+///  Returned (function value):
+///  ```text
+///            f64     V magnitude
+///  ```
+///  Notes:
+///
+///  1) The phase function is the three-parameter one adopted by IAU
+///     Commission 15 in 2012, a linear combination of basis functions
+///     defined by cubic splines for phase angles up to 150 deg.  The
+///     first two are linear below 7.5 deg and the third, which
+///     represents the opposition effect, is zero beyond 30 deg.
+///
+///  2) With G1 = 0.26 and G2 = 0.38 the phase function is within
+///     about 0.1 mag of that of the H,G system with G = 0.15 (see
+///     hgmag) up to 40 deg.
+///
+///  Reference:
+///
+///     Muinonen, K. et al., 2010, Icarus 209, 542.
+pub fn hg1g2mag(h: f64, g1: f64, g2: f64, r: f64, delta: f64, phase: f64) -> f64 {
+    let a = phase.abs();
+
+    let (phi1, phi2) = if a < X12[0] * DD2R {
+        (1.0 - 6.0 * a / DPI, 1.0 - 9.0 * a / (5.0 * DPI))
+    } else {
+        (spline(&X12, &PHI1, DPHI1, a), spline(&X12, &PHI2, DPHI2, a))
+    };
+    let phi3 = if a < X3[8] * DD2R {
+        spline(&X3, &PHI3, DPHI3, a)
+    } else {
+        0.0
+    };
+
+    h + 5.0 * (r * delta).log10() - 2.5 * (g1 * phi1 + g2 * phi2 + (1.0 - g1 - g2) * phi3).log10()
+}
+
+/* Cubic spline through (x deg, y) with end derivatives d, at a (rad). */
+fn spline<const N: usize>(x: &[f64; N], y: &[f64; N], d: (f64, f64), a: f64) -> f64 {
+    let x = x.map(|v| v * DD2R);
+
+    /* Second derivatives, by the tridiagonal algorithm. */
+    let mut y2 = [0.0; N];
+    let mut u = [0.0; N];
+    y2[0] = -0.5;
+    u[0] = 3.0 / (x[1] - x[0]) * ((y[1] - y[0]) / (x[1] - x[0]) - d.0);
+    for i in 1..N - 1 {
+        let sig = (x[i] - x[i - 1]) / (x[i + 1] - x[i - 1]);
+        let p = sig * y2[i - 1] + 2.0;
+        y2[i] = (sig - 1.0) / p;
+        let dy = (y[i + 1] - y[i]) / (x[i + 1] - x[i]) - (y[i] - y[i - 1]) / (x[i] - x[i - 1]);
+        u[i] = (6.0 * dy / (x[i + 1] - x[i - 1]) - sig * u[i - 1]) / p;
+    }
+    let un = 3.0 / (x[N - 1] - x[N - 2]) * (d.1 - (y[N - 1] - y[N - 2]) / (x[N - 1] - x[N - 2]));
+    y2[N - 1] = (un - 0.5 * u[N - 2]) / (0.5 * y2[N - 2] + 1.0);
+    for k in (0..N - 1).rev() {
+        y2[k] = y2[k] * y2[k + 1] + u[k];
+    }
+
+    /* Interval containing a, and interpolation. */
+    let k = x.partition_point(|&v| v <= a).clamp(1, N - 1);
+    let h = x[k] - x[k - 1];
+    let p = (x[k] - a) / h;
+    let q = (a - x[k - 1]) / h;
+    p * y[k - 1] + q * y[k] + ((p * p * p - p) * y2[k - 1] + (q * q * q - q) * y2[k]) * h * h / 6.0
+}
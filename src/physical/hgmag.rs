@@ -0,0 +1,33 @@
+///  Apparent magnitude of an asteroid, IAU H,G system
+///
+///  Given:
+///  ```text
+///     h      f64     absolute magnitude H
+///     g      f64     slope parameter G
+///     r      f64     distance from the Sun (au)
+///     delta  f64     distance from the observer (au)
+///     phase  f64     phase angle (radians, Note 1)
+///  ```
+///  Returned (function value):
+///  ```text
+///            f64     V magnitude
+///  ```
+///  Notes:
+///
+///  1) The phase function is the two-parameter one adopted by IAU
+///     Commission 20 in 1985, valid for phase angles up to 120 deg.  H
+///     and G are those of the MPC orbit catalogues (see MpcOrbit), G
+///     being 0.15 where no value has been determined.
+///
+///  2) See hg1g2mag for the three-parameter system of 2012.
+///
+///  Reference:
+///
+///     Bowell, E. et al., 1989, in Asteroids II, R.P. Binzel et al.
+///     (eds), University of Arizona Press, p. 524.
+pub fn hgmag(h: f64, g: f64, r: f64, delta: f64, phase: f64) -> f64 {
+    let t = (phase / 2.0).tan();
+    let phi1 = (-3.33 * t.powf(0.63)).exp();
+    let phi2 = (-1.87 * t.powf(1.22)).exp();
+    h + 5.0 * (r * delta).log10() - 2.5 * ((1.0 - g) * phi1 + g * phi2).log10()
+}
@@ -0,0 +1,31 @@
+//! Physical ephemerides (IAU rotation models, planetographic coordinates and magnitudes)
+
+mod bodyfix;
+pub use bodyfix::*;
+
+mod bodyshape;
+pub use bodyshape::*;
+
+mod hg1g2mag;
+pub use hg1g2mag::*;
+
+mod hgmag;
+pub use hgmag::*;
+
+mod moonlibr;
+pub use moonlibr::*;
+
+mod physeph;
+pub use physeph::*;
+
+mod planmag;
+pub use planmag::*;
+
+mod planmage;
+pub use planmage::*;
+
+mod selciq;
+pub use selciq::*;
+
+mod wgccre;
+pub use wgccre::*;
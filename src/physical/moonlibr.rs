@@ -0,0 +1,118 @@
+use super::physeph;
+use crate::consts::{AULT, DAYSEC, DD2R, DJ00, DJC};
+use crate::coords::ecm06;
+use crate::eph::{Ephemeris, Target};
+use crate::fundargs::{faf03, faom03};
+use crate::vm::{anpm, c2s, pmp, rxp};
+
+/// Inclination of the mean lunar equator to the ecliptic.
+const INCL: f64 = 1.54242 * DD2R;
+
+/// Libration and orientation of the Moon, as seen from the geocenter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoonLibr {
+    pub l: f64,     // libration in longitude (radians)
+    pub b: f64,     // libration in latitude (radians)
+    pub lopt: f64,  // optical libration in longitude (radians)
+    pub bopt: f64,  // optical libration in latitude (radians)
+    pub lphys: f64, // physical libration in longitude (radians)
+    pub bphys: f64, // physical libration in latitude (radians)
+    pub p: f64,     // position angle of the axis (radians)
+    pub l0: f64,    // selenographic longitude of the Sun (radians)
+    pub b0: f64,    // selenographic latitude of the Sun (radians)
+}
+
+///  Optical and physical libration of the Moon, position angle of its
+///  axis, and selenographic position of the Sun
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris  source of the Earth, Sun and Moon (Note 1)
+///     tt1    f64        TT as a 2-part...
+///     tt2    f64        ...Julian Date
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(MoonLibr)
+///            l      f64   libration in longitude (radians, Note 2)
+///            b      f64   libration in latitude (radians)
+///            lopt   f64   optical libration in longitude (radians,
+///                         Note 3)
+///            bopt   f64   optical libration in latitude (radians)
+///            lphys  f64   physical libration in longitude (radians)
+///            bphys  f64   physical libration in latitude (radians)
+///            p      f64   position angle of the axis (radians, 0-2pi,
+///                         Note 4)
+///            l0     f64   selenographic longitude of the Sun (radians,
+///                         0-2pi, Note 5)
+///            b0     f64   selenographic latitude of the Sun (radians)
+///     Err(j) i32          status from eph
+///  ```
+///  Notes:
+///
+///  1) The Moon is seen from the geocenter, corrected for light time;
+///     aberration is neglected and TT is used in place of TDB.  Moon98
+///     is a suitable ephemeris.
+///
+///  2) The libration is the selenographic longitude (east positive,
+///     -pi to +pi) and latitude of the sub-Earth point, in the mean
+///     Earth/polar axis frame of the IAU WGCCRE rotation model of the
+///     Moon (see physeph), which is the frame of lunar maps.
+///
+///  3) The optical libration is the part due to the uniform rotation
+///     of the Moon about the pole of its mean equator, inclined 1.54242
+///     deg to the ecliptic with its node on the node of the mean orbit
+///     (Cassini's laws), as given by Meeus.  The physical libration is
+///     the remainder, l - lopt and b - bopt, and is a few hundredths of
+///     a degree.
+///
+///  4) The position angle is measured from the north point of the
+///     disk towards the east, with respect to the true equator of
+///     date.
+///
+///  5) The selenographic colongitude of the Sun, which locates the
+///     morning terminator, is 90 deg - l0;  the Sun is rising at the
+///     sub-Earth point when it is near zero.
+///
+///  Called:
+///  ```text
+///     physeph     physical ephemeris of a body
+///     iauEcm06    ICRS to ecliptic rotation matrix, IAU 2006
+///     iauFaom03   mean longitude of the Moon's ascending node
+///     iauFaf03    mean argument of the latitude of the Moon
+///  ```
+///
+///  Reference:
+///
+///     Meeus, J., Astronomical Algorithms, 2nd edition,
+///     Willmann-Bell (1998), Chapter 53.
+pub fn moonlibr<E: Ephemeris>(eph: &E, tt1: f64, tt2: f64) -> Result<MoonLibr, i32> {
+    let e = physeph(eph, Target::Moon, tt1, tt2)?;
+    let l = anpm(e.lon);
+
+    /* Geocentric Moon at the retarded time, mean ecliptic of date. */
+    let (_, eb) = eph.earth(tt1, tt2)?;
+    let pb = eph.pvb(Target::Moon, tt1, tt2 - e.dist * AULT / DAYSEC)?;
+    let mut p = [0.0; 3];
+    rxp(&ecm06(tt1, tt2), &pmp(&pb[0], &eb[0]), &mut p);
+    let (lam, bet) = c2s(&p);
+
+    /* Optical libration (Meeus 53.1). */
+    let t = ((tt1 - DJ00) + tt2) / DJC;
+    let w = lam - faom03(t);
+    let a = (w.sin() * bet.cos() * INCL.cos() - bet.sin() * INCL.sin()).atan2(w.cos() * bet.cos());
+    let lopt = anpm(a - faf03(t));
+    let bopt = (-w.sin() * bet.cos() * INCL.sin() - bet.sin() * INCL.cos()).asin();
+
+    Ok(MoonLibr {
+        l,
+        b: e.lat,
+        lopt,
+        bopt,
+        lphys: anpm(l - lopt),
+        bphys: e.lat - bopt,
+        p: e.p,
+        l0: e.slon,
+        b0: e.slat,
+    })
+}
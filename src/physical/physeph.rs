@@ -0,0 +1,156 @@
+use super::{bodyfix, bodyshape, wgccre};
+use crate::consts::{AULT, DAU, DAYSEC};
+use crate::eph::{Ephemeris, Target};
+use crate::pnp::pnm06a;
+use crate::vm::{anp, c2s, pm, pmp, pn, rxp, s2c, sepp, sxp};
+
+/// Physical ephemeris of a body, as seen from the geocenter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhysEph {
+    pub dist: f64,  // distance from the Earth (au)
+    pub r: f64,     // distance from the Sun (au)
+    pub diam: f64,  // apparent equatorial diameter (radians)
+    pub phase: f64, // phase angle, Sun-body-Earth (radians)
+    pub k: f64,     // illuminated fraction of the disk
+    pub p: f64,     // position angle of the north pole (radians)
+    pub lat: f64,   // planetographic latitude of the sub-Earth point (radians)
+    pub lon: f64,   // longitude of the sub-Earth point (radians)
+    pub slat: f64,  // planetographic latitude of the sub-solar point (radians)
+    pub slon: f64,  // longitude of the sub-solar point (radians)
+}
+
+///  Physical ephemeris of the Sun, Moon or a planet
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris  source of the Earth, Sun and body (Note 1)
+///     target Target     the body
+///     tt1    f64        TT as a 2-part...
+///     tt2    f64        ...Julian Date
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(PhysEph)
+///            dist   f64   distance (au)
+///            r      f64   distance from the Sun (au)
+///            diam   f64   apparent equatorial diameter (radians)
+///            phase  f64   phase angle (radians, Note 2)
+///            k      f64   illuminated fraction
+///            p      f64   position angle of the north pole (radians,
+///                         0-2pi, Note 3)
+///            lat    f64   sub-Earth latitude (radians, Note 4)
+///            lon    f64   sub-Earth longitude (radians, 0-2pi, Note 4)
+///            slat   f64   sub-solar latitude (radians)
+///            slon   f64   sub-solar longitude (radians, 0-2pi)
+///     Err(j) i32          -1 = no rotation model for the body
+///                         -2 = the body is the Earth
+///                         else status from eph
+///  ```
+///  Notes:
+///
+///  1) The body is taken from eph at the time the light left it, and
+///     the Sun at the time its light left for the body.  The
+///     orientation is from wgccre at the time the light left the body.
+///     TT is used in place of TDB, and aberration is neglected.
+///
+///  2) The phase angle is the angle at the body between the directions
+///     to the Sun and to the Earth, and the illuminated fraction is
+///     (1 + cos(phase))/2.  For the Sun both are 0 and 1.
+///
+///  3) The position angle is measured from the north point of the
+///     disk towards the east, with respect to the true equator of
+///     date.
+///
+///  4) The latitudes are planetographic, that is the angle between the
+///     equator and the normal to the reference ellipsoid (see
+///     bodyshape).  The longitudes follow the IAU conventions:  for
+///     Mercury, Mars, Jupiter, Saturn and Neptune, whose rotation is
+///     direct, they are measured toward the west, so that the sub-Earth
+///     longitude (the central meridian) increases with time;  for the
+///     Sun, the Moon, Venus, Uranus and Pluto they are measured toward
+///     the east.  The sub-Earth point is where the line from the center
+///     of the body toward the Earth meets the surface.
+///
+///  Called:
+///  ```text
+///     wgccre      orientation of the body, IAU WGCCRE 2015
+///     bodyfix     ICRS to body-fixed matrix
+///     bodyshape   size and shape of the body
+///     iauPnm06a   classical NPB matrix, IAU 2006/2000A
+///  ```
+///
+///  References:
+///
+///     Archinal, B.A. et al., 2018, Celest.Mech.Dyn.Astr. 130, 22.
+///
+///     Explanatory Supplement to the Astronomical Almanac,
+///     Sean E. Urban & P. Kenneth Seidelmann (eds), University Science
+///     Books (2013), Section 10.3.
+pub fn physeph<E: Ephemeris>(eph: &E, target: Target, tt1: f64, tt2: f64) -> Result<PhysEph, i32> {
+    if target == Target::Earth {
+        return Err(-2);
+    }
+    let (a, f) = bodyshape(target)?;
+
+    /* The body, allowing for light time. */
+    let (_, eb) = eph.earth(tt1, tt2)?;
+    let mut pb = eph.pvb(target, tt1, tt2)?;
+    let mut tau = 0.0;
+    for _ in 0..2 {
+        tau = pm(pmp(&pb[0], &eb[0])) * AULT / DAYSEC;
+        pb = eph.pvb(target, tt1, tt2 - tau)?;
+    }
+    let (dist, u) = pn(&pmp(&pb[0], &eb[0]));
+
+    /* The Sun, when the light reaching the body left it. */
+    let (ps, phase, r) = if target == Target::Sun {
+        (sxp(-1.0, &u), 0.0, 0.0)
+    } else {
+        let sb = eph.pvb(Target::Sun, tt1, tt2 - tau)?;
+        let ts = pm(pmp(&pb[0], &sb[0])) * AULT / DAYSEC;
+        let sb = eph.pvb(Target::Sun, tt1, tt2 - tau - ts)?;
+        let ps = pmp(&sb[0], &pb[0]);
+        let phase = sepp(&ps, &sxp(-1.0, &u));
+        (ps, phase, pm(ps))
+    };
+
+    /* Sub-Earth and sub-solar points. */
+    let rm = bodyfix(target, tt1, tt2 - tau)?;
+    let west = matches!(
+        target,
+        Target::Mercury | Target::Mars | Target::Jupiter | Target::Saturn | Target::Neptune
+    );
+    let subpoint = |v: &[f64; 3]| {
+        let mut vb = [0.0; 3];
+        rxp(&rm, v, &mut vb);
+        let (lon, lat) = c2s(&vb);
+        let lat = (lat.tan() / ((1.0 - f) * (1.0 - f))).atan();
+        (lat, anp(if west { -lon } else { lon }))
+    };
+    let (lat, lon) = subpoint(&sxp(-1.0, &u));
+    let (slat, slon) = subpoint(&ps);
+
+    /* Position angle of the pole, true equator of date. */
+    let (a0, d0, _) = wgccre(target, tt1, tt2 - tau)?;
+    let npb = pnm06a(tt1, tt2);
+    let (mut vp, mut vu) = ([0.0; 3], [0.0; 3]);
+    rxp(&npb, &s2c(a0, d0), &mut vp);
+    rxp(&npb, &u, &mut vu);
+    let (ap, dp) = c2s(&vp);
+    let (au, du) = c2s(&vu);
+    let p = anp((dp.cos() * (ap - au).sin())
+        .atan2(dp.sin() * du.cos() - dp.cos() * du.sin() * (ap - au).cos()));
+
+    Ok(PhysEph {
+        dist,
+        r,
+        diam: 2.0 * (a * 1e3 / DAU / dist).asin(),
+        phase,
+        k: (1.0 + phase.cos()) / 2.0,
+        p,
+        lat,
+        lon,
+        slat,
+        slon,
+    })
+}
@@ -0,0 +1,129 @@
+use super::bodyshape;
+use crate::consts::DR2D;
+use crate::eph::Target;
+
+///  Apparent visual magnitude of a planet
+///
+///  Given:
+///  ```text
+///     target Target  the planet (Note 1)
+///     r      f64     distance from the Sun (au)
+///     delta  f64     distance from the observer (au)
+///     phase  f64     phase angle (radians)
+///     lat    f64     planetographic latitude of the sub-observer
+///                    point (radians, Note 2)
+///     slat   f64     planetographic latitude of the sub-solar point
+///                    (radians, Note 2)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(v)  f64     V magnitude
+///     Err(j) i32     -1 = not a planet
+///  ```
+///  Notes:
+///
+///  1) The planets are Mercury to Neptune, including the Earth as
+///     seen from elsewhere.  The models are those of Mallama & Hilton,
+///     in which the magnitude at unit distances is a function of the
+///     phase angle fitted to photometry from the ground and from
+///     spacecraft.  They are valid for the phase angles observed:  for
+///     example 2 to 170 deg for Mercury, and up to about 12 deg for
+///     Jupiter from the Earth.  Mars is the mean over its rotation and
+///     orbit, which can differ from the observed magnitude by a few
+///     tenths.  Neptune is that after 2000, having brightened by 0.11
+///     mag since 1980.
+///
+///  2) The latitudes are used only for Saturn and Uranus.  For Saturn
+///     the brightness of the rings depends on their tilt to the line
+///     of sight, which is the planetocentric latitude of the observer.
+///     The model with the rings applies for phase angles up to 6.5 deg
+///     and tilts up to 27 deg, when the observer and Sun are on the
+///     same side of the rings;  otherwise that of the globe alone is
+///     used.  For Uranus the brightness depends on the mean of the
+///     absolute values of the two latitudes.
+///
+///  Called:
+///  ```text
+///     bodyshape   size and shape of a body
+///  ```
+///
+///  Reference:
+///
+///     Mallama, A. & Hilton, J.L., 2018, Astronomy and Computing 25,
+///     10.
+pub fn planmag(
+    target: Target,
+    r: f64,
+    delta: f64,
+    phase: f64,
+    lat: f64,
+    slat: f64,
+) -> Result<f64, i32> {
+    /* Phase angle in degrees. */
+    let a = phase * DR2D;
+
+    let v1 = match target {
+        Target::Mercury => {
+            -0.613
+                + a * (6.3280e-2
+                    + a * (-1.6336e-3
+                        + a * (3.3644e-5 + a * (-3.4265e-7 + a * (1.6893e-9 + a * -3.0334e-12)))))
+        }
+        Target::Venus => {
+            if a <= 163.7 {
+                -4.384 + a * (-1.044e-3 + a * (3.687e-4 + a * (-2.814e-6 + a * 8.938e-9)))
+            } else {
+                236.05828 + a * (-2.81914 + a * 8.39034e-3)
+            }
+        }
+        Target::Earth => -3.99 + a * (-1.060e-3 + a * 2.054e-4),
+        Target::Mars => {
+            if a <= 50.0 {
+                -1.601 + a * (2.267e-2 + a * -1.302e-4)
+            } else {
+                -0.367 + a * (-2.573e-2 + a * 3.445e-4)
+            }
+        }
+        Target::Jupiter => {
+            if a <= 12.0 {
+                -9.395 + a * (-3.7e-4 + a * 6.16e-4)
+            } else {
+                let x = a / 180.0;
+                -9.428
+                    - 2.5
+                        * (1.0
+                            + x * (-1.507 + x * (-0.363 + x * (-0.062 + x * (2.809 + x * -1.876)))))
+                            .log10()
+            }
+        }
+        Target::Saturn => {
+            /* Tilt of the rings, planetocentric. */
+            let (_, f) = bodyshape(target)?;
+            let w = (1.0 - f) * (1.0 - f);
+            let be = (lat.tan() * w).atan();
+            let bs = (slat.tan() * w).atan();
+            let sb = be.abs().sin();
+            if a <= 6.5 && be.abs() * DR2D <= 27.0 && be * bs > 0.0 {
+                -8.914 - 1.825 * sb + 0.026 * a - 0.378 * sb * (-2.25 * a).exp()
+            } else if a <= 6.0 {
+                -8.95 + a * (-3.7e-4 + a * 6.16e-4)
+            } else {
+                -8.94 + a * (2.446e-4 + a * (2.672e-4 + a * (-1.505e-6 + a * 4.767e-9)))
+            }
+        }
+        Target::Uranus => {
+            let phi = (lat.abs() + slat.abs()) / 2.0 * DR2D;
+            -7.110 - 8.4e-4 * phi + a * (6.587e-3 + a * 1.045e-4)
+        }
+        Target::Neptune => {
+            if a <= 1.9 {
+                -7.00
+            } else {
+                -7.00 + a * (7.944e-3 + a * 9.617e-5)
+            }
+        }
+        _ => return Err(-1),
+    };
+
+    Ok(v1 + 5.0 * (r * delta).log10())
+}
@@ -0,0 +1,46 @@
+use super::{physeph, planmag};
+use crate::eph::{Ephemeris, Target};
+
+///  Apparent visual magnitude of a planet, seen from the geocenter
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris  source of the Earth, Sun and planet (Note 1)
+///     target Target     the planet
+///     tt1    f64        TT as a 2-part...
+///     tt2    f64        ...Julian Date
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(v)  f64    V magnitude
+///     Err(j) i32    -1 = not a planet
+///                   -2 = the planet is the Earth
+///                   else status from eph
+///  ```
+///  Notes:
+///
+///  1) The distances, phase angle and latitudes are from physeph, for
+///     example with Plan94 or Epv00, and the magnitude from planmag.
+///
+///  Called:
+///  ```text
+///     physeph     physical ephemeris of a body
+///     planmag     apparent magnitude of a planet
+///  ```
+pub fn planmage<E: Ephemeris>(eph: &E, target: Target, tt1: f64, tt2: f64) -> Result<f64, i32> {
+    if !matches!(
+        target,
+        Target::Mercury
+            | Target::Venus
+            | Target::Earth
+            | Target::Mars
+            | Target::Jupiter
+            | Target::Saturn
+            | Target::Uranus
+            | Target::Neptune
+    ) {
+        return Err(-1);
+    }
+    let e = physeph(eph, target, tt1, tt2)?;
+    planmag(target, e.r, e.dist, e.phase, e.lat, e.slat)
+}
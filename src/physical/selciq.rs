@@ -0,0 +1,85 @@
+use super::{bodyfix, bodyshape};
+use crate::astro::{IauAstrom, bodyapp};
+use crate::consts::DAU;
+use crate::coords::gd2gce;
+use crate::eph::{Ephemeris, Target};
+use crate::vm::{anp, c2s, pm, ppp, rxp, trxp};
+
+///  Quick ICRS to CIRS transformation for a point on the surface of
+///  the Moon
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris  source of the Moon's ephemeris (Note 1)
+///     lon    f64        selenographic longitude (radians, east +ve)
+///     lat    f64        selenographic latitude (radians)
+///     h      f64        height above the mean radius (km, Note 2)
+///     date1  f64        TDB as a 2-part...
+///     date2  f64        ...Julian Date
+///     astrom IauAstrom  star-independent astrometry parameters
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok((ri, di, dist))
+///            ri,di  f64   CIRS RA,Dec (radians, Note 3)
+///            dist   f64   distance from the observer (au)
+///     Err(j) i32          status from eph
+///  ```
+///  Notes:
+///
+///  1) The center of the Moon is from eph, for example Moon98, and the
+///     orientation of the Moon from the IAU WGCCRE model (see
+///     bodyfix), both at the time the light left the point.  The
+///     coordinates are in the mean Earth/polar axis frame of that
+///     model, which is the frame of lunar maps and of the coordinates
+///     of craters and landing sites.
+///
+///  2) The Moon is the sphere of radius 1737.4 km (see bodyshape), and
+///     the point is h above it along the normal.
+///
+///  3) The place is as for atciqb:  light time, light deflection by
+///     the Sun and aberration are included, for the observer in
+///     astrom.  With astrom from apco13 (or Observer::apco13) the place
+///     is topocentric and can be passed to atioq for the observed
+///     place.  No check is made that the point is on the hemisphere
+///     facing the observer.
+///
+///  Called:
+///  ```text
+///     bodyshape   size and shape of a body
+///     bodyfix     ICRS to body-fixed matrix
+///     iauGd2gce   geodetic to geocentric, general ellipsoid
+///     iauTrxp     product of transpose of r-matrix and p-vector
+///     iauRxp      product of r-matrix and p-vector
+///     iauC2s      p-vector to spherical
+///     iauAnp      normalize angle into range 0 to 2pi
+///  ```
+pub fn selciq<E: Ephemeris>(
+    eph: &E,
+    lon: f64,
+    lat: f64,
+    h: f64,
+    date1: f64,
+    date2: f64,
+    astrom: &IauAstrom,
+) -> Result<(f64, f64, f64), i32> {
+    /* Selenocentric vector of the point (au). */
+    let (a, f) = bodyshape(Target::Moon)?;
+    let x = gd2gce(a, f, lon, lat, h)?.map(|v| v * 1e3 / DAU);
+
+    /* Barycentric point, at the retarded time. */
+    let pvb = |d1, d2| -> Result<[[f64; 3]; 2], i32> {
+        let pv = eph.pvb(Target::Moon, d1, d2)?;
+        let r = bodyfix(Target::Moon, d1, d2)?;
+        let mut s = [0.0; 3];
+        trxp(&r, &x, &mut s);
+        Ok([ppp(&pv[0], &s), pv[1]])
+    };
+    let (p, ppr, _) = bodyapp(&pvb, date1, date2, astrom)?;
+
+    /* Bias-precession-nutation, giving CIRS proper direction. */
+    let mut pi = [0.0; 3];
+    rxp(&astrom.bpn, &ppr, &mut pi);
+    let (w, di) = c2s(&pi);
+    Ok((anp(w), di, pm(p)))
+}
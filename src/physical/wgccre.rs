@@ -0,0 +1,184 @@
+use crate::consts::{DD2R, DJ00, DJC};
+use crate::eph::Target;
+use crate::vm::anp;
+
+///  Orientation of the Sun, Moon and planets, IAU WGCCRE 2015
+///
+///  Given:
+///  ```text
+///     target Target  the body (Note 1)
+///     date1  f64     TDB as a 2-part...
+///     date2  f64     ...Julian Date (Note 2)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok((a0, d0, w))
+///            a0   f64   right ascension of the north pole (ICRS, radians)
+///            d0   f64   declination of the north pole (ICRS, radians)
+///            w    f64   prime meridian (radians, 0-2pi, Note 3)
+///     Err(j) i32        -1 = no rotation model for the body
+///  ```
+///  Notes:
+///
+///  1) The Sun, the Moon, the Earth and the planets are supported,
+///     Jupiter to Neptune and Pluto being the bodies themselves rather
+///     than the system barycenters.  The Earth model is the low-
+///     precision one of the report;  for the Earth itself use the
+///     IAU 2006/2000A precession-nutation and the Earth rotation angle
+///     (iauC2t06a etc.).
+///
+///  2) The date is the time at the body:  for the orientation seen
+///     from the Earth, subtract the light time.
+///
+///  3) W is the angle, measured eastward along the equator, from the
+///     ascending node of the equator on the ICRS equator to the prime
+///     meridian.  It decreases with time for Venus and Uranus, whose
+///     rotation is retrograde.  Jupiter's is System III.
+///
+///  4) The north pole is the pole on the north side of the invariable
+///     plane, except for Pluto, whose pole is the positive pole given
+///     by the right-hand rule.
+///
+///  Reference:
+///
+///     Archinal, B.A. et al., 2018, Celest.Mech.Dyn.Astr. 130, 22
+///     (Report of the IAU Working Group on Cartographic Coordinates
+///     and Rotational Elements:  2015).
+pub fn wgccre(target: Target, date1: f64, date2: f64) -> Result<(f64, f64, f64), i32> {
+    /* Days and centuries since J2000.0. */
+    let d = (date1 - DJ00) + date2;
+    let t = d / DJC;
+    let s = |x: f64| (x * DD2R).sin();
+    let c = |x: f64| (x * DD2R).cos();
+
+    let (a0, d0, w) = match target {
+        Target::Sun => (286.13, 63.87, 84.176 + 14.1844000 * d),
+        Target::Mercury => {
+            let m = [
+                174.7910857 + 4.092335 * d,
+                349.5821714 + 8.184670 * d,
+                164.3732571 + 12.277005 * d,
+                339.1643429 + 16.369340 * d,
+                153.9554286 + 20.461675 * d,
+            ];
+            (
+                281.0103 - 0.0328 * t,
+                61.4155 - 0.0049 * t,
+                329.5988 + 6.1385108 * d + 0.01067257 * s(m[0])
+                    - 0.00112309 * s(m[1])
+                    - 0.00011040 * s(m[2])
+                    - 0.00002539 * s(m[3])
+                    - 0.00000571 * s(m[4]),
+            )
+        }
+        Target::Venus => (272.76, 67.16, 160.20 - 1.4813688 * d),
+        Target::Earth => (-0.641 * t, 90.0 - 0.557 * t, 190.147 + 360.9856235 * d),
+        Target::Moon => {
+            let e = [
+                125.045 - 0.0529921 * d,
+                250.089 - 0.1059842 * d,
+                260.008 + 13.0120009 * d,
+                176.625 + 13.3407154 * d,
+                357.529 + 0.9856003 * d,
+                311.589 + 26.4057084 * d,
+                134.963 + 13.0649930 * d,
+                276.617 + 0.3287146 * d,
+                34.226 + 1.7484877 * d,
+                15.134 - 0.1589763 * d,
+                119.743 + 0.0036096 * d,
+                239.961 + 0.1643573 * d,
+                25.053 + 12.9590088 * d,
+            ];
+            (
+                269.9949 + 0.0031 * t - 3.8787 * s(e[0]) - 0.1204 * s(e[1]) + 0.0700 * s(e[2])
+                    - 0.0172 * s(e[3])
+                    + 0.0072 * s(e[5])
+                    - 0.0052 * s(e[9])
+                    + 0.0043 * s(e[12]),
+                66.5392 + 0.0130 * t + 1.5419 * c(e[0]) + 0.0239 * c(e[1]) - 0.0278 * c(e[2])
+                    + 0.0068 * c(e[3])
+                    - 0.0029 * c(e[5])
+                    + 0.0009 * c(e[6])
+                    + 0.0008 * c(e[9])
+                    - 0.0009 * c(e[12]),
+                38.3213 + 13.17635815 * d - 1.4e-12 * d * d + 3.5610 * s(e[0]) + 0.1208 * s(e[1])
+                    - 0.0642 * s(e[2])
+                    + 0.0158 * s(e[3])
+                    + 0.0252 * s(e[4])
+                    - 0.0066 * s(e[5])
+                    - 0.0047 * s(e[6])
+                    - 0.0046 * s(e[7])
+                    + 0.0028 * s(e[8])
+                    + 0.0052 * s(e[9])
+                    + 0.0040 * s(e[10])
+                    + 0.0019 * s(e[11])
+                    - 0.0044 * s(e[12]),
+            )
+        }
+        Target::Mars => (
+            317.269202 - 0.10927547 * t
+                + 0.000068 * s(198.991226 + 19139.4819985 * t)
+                + 0.000238 * s(226.292679 + 38280.8511281 * t)
+                + 0.000052 * s(249.663391 + 57420.7251593 * t)
+                + 0.000009 * s(266.183510 + 76560.6367950 * t)
+                + 0.419057 * s(79.398797 + 0.5042615 * t),
+            54.432516 - 0.05827105 * t
+                + 0.000051 * c(122.433576 + 19139.9407476 * t)
+                + 0.000141 * c(43.058401 + 38280.8753272 * t)
+                + 0.000031 * c(57.663379 + 57420.7517205 * t)
+                + 0.000005 * c(79.476401 + 76560.6495004 * t)
+                + 1.591274 * c(166.325722 + 0.5042615 * t),
+            176.049863
+                + 350.891982443297 * d
+                + 0.000145 * s(129.071773 + 19140.0328244 * t)
+                + 0.000157 * s(36.352167 + 38281.0473591 * t)
+                + 0.000040 * s(56.668646 + 57420.9295360 * t)
+                + 0.000001 * s(67.364003 + 76560.2552215 * t)
+                + 0.000001 * s(104.792680 + 95700.4387578 * t)
+                + 0.584542 * s(95.391654 + 0.5042615 * t),
+        ),
+        Target::Jupiter => {
+            let j = [
+                99.360714 + 4850.4046 * t,
+                175.895369 + 1191.9605 * t,
+                300.323162 + 262.5475 * t,
+                114.012305 + 6070.2476 * t,
+                49.511251 + 64.3000 * t,
+            ];
+            (
+                268.056595 - 0.006499 * t
+                    + 0.000117 * s(j[0])
+                    + 0.000938 * s(j[1])
+                    + 0.001432 * s(j[2])
+                    + 0.000030 * s(j[3])
+                    + 0.002150 * s(j[4]),
+                64.495303
+                    + 0.002413 * t
+                    + 0.000050 * c(j[0])
+                    + 0.000404 * c(j[1])
+                    + 0.000617 * c(j[2])
+                    - 0.000013 * c(j[3])
+                    + 0.000926 * c(j[4]),
+                284.95 + 870.5360000 * d,
+            )
+        }
+        Target::Saturn => (
+            40.589 - 0.036 * t,
+            83.537 - 0.004 * t,
+            38.90 + 810.7939024 * d,
+        ),
+        Target::Uranus => (257.311, -15.175, 203.81 - 501.1600928 * d),
+        Target::Neptune => {
+            let n = 357.85 + 52.316 * t;
+            (
+                299.36 + 0.70 * s(n),
+                43.46 - 0.51 * c(n),
+                249.978 + 541.1397757 * d - 0.48 * s(n),
+            )
+        }
+        Target::Pluto => (132.993, -6.163, 302.695 + 56.3625225 * d),
+        _ => return Err(-1),
+    };
+
+    Ok((anp(a0 * DD2R), d0 * DD2R, anp((w % 360.0) * DD2R)))
+}
@@ -0,0 +1,384 @@
+use crate::consts::{DAU, DD2R};
+use crate::coords::ecm06;
+use crate::vm::{ir, rx, rxp, rxr, rz, tr};
+
+/// Unit of the radius vectors of the theory (km), Jupiter's
+/// equatorial radius as adopted by Lieske.
+const RJ: f64 = 71398.0;
+
+///  Jovicentric positions of the Galilean satellites
+///
+///  Given:
+///  ```text
+///     date1  f64     TDB as a 2-part...
+///     date2  f64     ...Julian Date (Note 1)
+///  ```
+///  Returned (function value):
+///  ```text
+///            [[f64; 3]; 4]  Io, Europa, Ganymede and Callisto,
+///                           jovicentric (ICRS, au, Note 2)
+///  ```
+///  Notes:
+///
+///  1) The date is that at the satellite:  for the position seen from
+///     the Earth, subtract the light time from Jupiter.
+///
+///  2) The theory is the abridgement of Lieske's E5 theory given by
+///     Meeus, which includes the mutual perturbations (in particular
+///     the Laplace resonance of Io, Europa and Ganymede), the
+///     perturbations by the Sun and the oblateness of Jupiter.  It is
+///     referred to the ecliptic and equinox of date, which is rotated
+///     to the ICRS with iauEcm06.  The errors are at most a few
+///     hundred km for Io, Europa and Ganymede and some 1000 km for
+///     Callisto (about 0.3 arcsec at opposition).
+///
+///  Called:
+///  ```text
+///     jupeq       Jupiter equator to ICRS matrix
+///     iauRxp      product of r-matrix and p-vector
+///  ```
+///
+///  References:
+///
+///     Lieske, J.H., 1998, Astron.Astrophys.Suppl.Ser. 129, 205.
+///
+///     Meeus, J., Astronomical Algorithms, 2nd edition,
+///     Willmann-Bell (1998), Chapter 44.
+pub fn galsat(date1: f64, date2: f64) -> [[f64; 3]; 4] {
+    let (p, r) = e5(date1, date2);
+    let mut out = [[0.0; 3]; 4];
+    for i in 0..4 {
+        rxp(&r, &p[i], &mut out[i]);
+    }
+    out
+}
+
+///  Rotation matrix from Jupiter's equator to the ICRS
+///
+///  Given:
+///  ```text
+///     date1  f64     TDB as a 2-part...
+///     date2  f64     ...Julian Date
+///  ```
+///  Returned (function value):
+///  ```text
+///            [[f64; 3]; 3]  matrix (Note)
+///  ```
+///  Note:
+///
+///     The matrix rotates a vector in the frame of Jupiter's equator,
+///     the x-axis toward the ascending node on Jupiter's orbit, into
+///     the ICRS;  its third column is Jupiter's north pole.  The
+///     equator is that of the theory used by galsat.
+pub fn jupeq(date1: f64, date2: f64) -> [[f64; 3]; 3] {
+    e5(date1, date2).1
+}
+
+/* E5 positions in Jupiter's equatorial frame (au) and the matrix. */
+fn e5(date1: f64, date2: f64) -> ([[f64; 3]; 4], [[f64; 3]; 3]) {
+    let t = (date1 - 2443000.5) + date2;
+    let s = |x: f64| (x * DD2R).sin();
+    let c = |x: f64| (x * DD2R).cos();
+
+    /* Mean longitudes, perijoves and nodes (degrees). */
+    let l1 = 106.07719 + 203.488955790 * t;
+    let l2 = 175.73161 + 101.374724735 * t;
+    let l3 = 120.55883 + 50.317609207 * t;
+    let l4 = 84.44459 + 21.571071177 * t;
+    let p1 = 97.0881 + 0.16138586 * t;
+    let p2 = 154.8663 + 0.04726307 * t;
+    let p3 = 188.1840 + 0.00712734 * t;
+    let p4 = 335.2868 + 0.00184000 * t;
+    let w1 = 312.3346 - 0.13279386 * t;
+    let w2 = 100.4411 - 0.03263064 * t;
+    let w3 = 119.1942 - 0.00717703 * t;
+    let w4 = 322.6186 - 0.00175934 * t;
+
+    /* Great inequality, libration, node of the equator, anomalies. */
+    let gam = 0.33033 * s(163.679 + 0.0010512 * t) + 0.03439 * s(34.486 - 0.0161731 * t);
+    let phl = 199.6766 + 0.17379190 * t;
+    let psi = 316.5182 - 0.00000208 * t;
+    let g = 30.23756 + 0.0830925701 * t + gam;
+    let gs = 31.97853 + 0.0334597339 * t;
+    let pj = 13.469942;
+
+    /* Periodic terms in longitude. */
+    let sig1 = 0.47259 * s(2.0 * (l1 - l2)) - 0.03478 * s(p3 - p4)
+        + 0.01081 * s(l2 - 2.0 * l3 + p3)
+        + 0.00738 * s(phl)
+        + 0.00713 * s(l2 - 2.0 * l3 + p2)
+        - 0.00674 * s(p1 + p3 - 2.0 * pj - 2.0 * g)
+        + 0.00666 * s(l2 - 2.0 * l3 + p4)
+        + 0.00445 * s(l1 - p3)
+        - 0.00354 * s(l1 - l2)
+        - 0.00317 * s(2.0 * psi - 2.0 * pj)
+        + 0.00265 * s(l1 - p4)
+        - 0.00186 * s(g)
+        + 0.00162 * s(p2 - p3)
+        + 0.00158 * s(4.0 * (l1 - l2))
+        - 0.00155 * s(l1 - l3)
+        - 0.00138 * s(psi + w3 - 2.0 * pj - 2.0 * g)
+        - 0.00115 * s(2.0 * (l1 - 2.0 * l2 + w2))
+        + 0.00089 * s(p2 - p4)
+        + 0.00085 * s(l1 + p3 - 2.0 * pj - 2.0 * g)
+        + 0.00083 * s(w2 - w3)
+        + 0.00053 * s(psi - w2);
+
+    let sig2 = 1.06476 * s(2.0 * (l2 - l3))
+        + 0.04256 * s(l1 - 2.0 * l2 + p3)
+        + 0.03581 * s(l2 - p3)
+        + 0.02395 * s(l1 - 2.0 * l2 + p4)
+        + 0.01984 * s(l2 - p4)
+        - 0.01778 * s(phl)
+        + 0.01654 * s(l2 - p2)
+        + 0.01334 * s(l2 - 2.0 * l3 + p2)
+        + 0.01294 * s(p3 - p4)
+        - 0.01142 * s(l2 - l3)
+        - 0.01057 * s(g)
+        - 0.00775 * s(2.0 * (psi - pj))
+        + 0.00524 * s(2.0 * (l1 - l2))
+        - 0.00460 * s(l1 - l3)
+        + 0.00316 * s(psi - 2.0 * g + w3 - 2.0 * pj)
+        - 0.00203 * s(p1 + p3 - 2.0 * pj - 2.0 * g)
+        + 0.00146 * s(psi - w3)
+        - 0.00145 * s(2.0 * g)
+        + 0.00125 * s(psi - w4)
+        - 0.00115 * s(l1 - 2.0 * l3 + p3)
+        - 0.00094 * s(2.0 * (l2 - w2))
+        + 0.00086 * s(2.0 * (l1 - 2.0 * l2 + w2))
+        - 0.00086 * s(5.0 * gs - 2.0 * g + 52.225)
+        - 0.00078 * s(l2 - l4)
+        - 0.00064 * s(3.0 * l3 - 7.0 * l4 + 4.0 * p4)
+        + 0.00064 * s(p1 - p4)
+        - 0.00063 * s(l1 - 2.0 * l3 + p4)
+        + 0.00058 * s(w3 - w4)
+        + 0.00056 * s(2.0 * (psi - pj - g))
+        + 0.00056 * s(2.0 * (l2 - l4))
+        + 0.00055 * s(2.0 * (l1 - l3))
+        + 0.00052 * s(3.0 * l3 - 7.0 * l4 + p3 + 3.0 * p4)
+        - 0.00043 * s(l1 - p3)
+        + 0.00041 * s(5.0 * (l2 - l3))
+        + 0.00041 * s(p4 - pj)
+        + 0.00032 * s(w2 - w3)
+        + 0.00032 * s(2.0 * (l3 - g - pj));
+
+    let sig3 = 0.16490 * s(l3 - p3) + 0.09081 * s(l3 - p4) - 0.06907 * s(l2 - l3)
+        + 0.03784 * s(p3 - p4)
+        + 0.01846 * s(2.0 * (l3 - l4))
+        - 0.01340 * s(g)
+        - 0.01014 * s(2.0 * (psi - pj))
+        + 0.00704 * s(l2 - 2.0 * l3 + p3)
+        - 0.00620 * s(l2 - 2.0 * l3 + p2)
+        - 0.00541 * s(l3 - l4)
+        + 0.00381 * s(l2 - 2.0 * l3 + p4)
+        + 0.00235 * s(psi - w3)
+        + 0.00198 * s(psi - w4)
+        + 0.00176 * s(phl)
+        + 0.00130 * s(3.0 * (l3 - l4))
+        + 0.00125 * s(l1 - l3)
+        - 0.00119 * s(5.0 * gs - 2.0 * g + 52.225)
+        + 0.00109 * s(l1 - l2)
+        - 0.00100 * s(3.0 * l3 - 7.0 * l4 + 4.0 * p4)
+        + 0.00091 * s(w3 - w4)
+        + 0.00080 * s(3.0 * l3 - 7.0 * l4 + p3 + 3.0 * p4)
+        - 0.00075 * s(2.0 * l2 - 3.0 * l3 + p3)
+        + 0.00072 * s(p1 + p3 - 2.0 * pj - 2.0 * g)
+        + 0.00069 * s(p4 - pj)
+        - 0.00058 * s(2.0 * l3 - 3.0 * l4 + p4)
+        - 0.00057 * s(l3 - 2.0 * l4 + p4)
+        + 0.00056 * s(l3 + p3 - 2.0 * pj - 2.0 * g)
+        - 0.00052 * s(l2 - 2.0 * l3 + p1)
+        - 0.00050 * s(p2 - p3)
+        + 0.00048 * s(l3 - 2.0 * l4 + p3)
+        - 0.00045 * s(2.0 * l2 - 3.0 * l3 + p4)
+        - 0.00041 * s(p2 - p4)
+        - 0.00038 * s(2.0 * g)
+        - 0.00037 * s(p3 - p4 + w3 - w4)
+        - 0.00032 * s(3.0 * l3 - 7.0 * l4 + 2.0 * p3 + 2.0 * p4)
+        + 0.00030 * s(4.0 * (l3 - l4))
+        + 0.00029 * s(l3 + p4 - 2.0 * pj - 2.0 * g)
+        - 0.00028 * s(w3 + psi - 2.0 * pj - 2.0 * g)
+        + 0.00026 * s(l3 - pj - g)
+        + 0.00024 * s(l2 - 3.0 * l3 + 2.0 * l4)
+        + 0.00021 * s(2.0 * (l3 - pj - g))
+        - 0.00021 * s(l3 - p2)
+        + 0.00017 * s(2.0 * (l3 - p3));
+
+    let sig4 = 0.84287 * s(l4 - p4) + 0.03431 * s(p4 - p3)
+        - 0.03305 * s(2.0 * (psi - pj))
+        - 0.03211 * s(g)
+        - 0.01862 * s(l4 - p3)
+        + 0.01186 * s(psi - w4)
+        + 0.00623 * s(l4 + p4 - 2.0 * g - 2.0 * pj)
+        + 0.00387 * s(2.0 * (l4 - p4))
+        - 0.00284 * s(5.0 * gs - 2.0 * g + 52.225)
+        - 0.00234 * s(2.0 * (psi - p4))
+        - 0.00223 * s(l3 - l4)
+        - 0.00208 * s(l4 - pj)
+        + 0.00178 * s(psi + w4 - 2.0 * p4)
+        + 0.00134 * s(p4 - pj)
+        + 0.00125 * s(2.0 * (l4 - g - pj))
+        - 0.00117 * s(2.0 * g)
+        - 0.00112 * s(2.0 * (l3 - l4))
+        + 0.00107 * s(3.0 * l3 - 7.0 * l4 + 4.0 * p4)
+        + 0.00102 * s(l4 - g - pj)
+        + 0.00096 * s(2.0 * l4 - psi - w4)
+        + 0.00087 * s(2.0 * (psi - w4))
+        - 0.00085 * s(3.0 * l3 - 7.0 * l4 + p3 + 3.0 * p4)
+        + 0.00085 * s(l3 - 2.0 * l4 + p4)
+        - 0.00081 * s(2.0 * (l4 - psi))
+        + 0.00071 * s(l4 + p4 - 2.0 * pj - 3.0 * g)
+        + 0.00061 * s(l1 - l4)
+        - 0.00056 * s(psi - w3)
+        - 0.00054 * s(l3 - 2.0 * l4 + p3)
+        + 0.00051 * s(l2 - l4)
+        + 0.00042 * s(2.0 * (psi - g - pj))
+        + 0.00039 * s(2.0 * (p4 - w4))
+        + 0.00036 * s(psi + pj - p4 - w4)
+        + 0.00035 * s(2.0 * gs - g + 188.37)
+        - 0.00035 * s(l4 - p4 + 2.0 * pj - 2.0 * psi)
+        - 0.00032 * s(l4 + p4 - 2.0 * pj - g)
+        + 0.00030 * s(2.0 * gs - 2.0 * g + 149.15)
+        + 0.00029 * s(3.0 * l3 - 7.0 * l4 + 2.0 * p3 + 2.0 * p4)
+        + 0.00028 * s(l4 - p4 + 2.0 * psi - 2.0 * pj)
+        - 0.00028 * s(2.0 * (l4 - w4))
+        - 0.00027 * s(p3 - p4 + w3 - w4)
+        - 0.00026 * s(5.0 * gs - 3.0 * g + 188.37)
+        + 0.00025 * s(w4 - w3)
+        - 0.00025 * s(l2 - 3.0 * l3 + 2.0 * l4)
+        - 0.00023 * s(3.0 * (l3 - l4))
+        + 0.00021 * s(2.0 * l4 - 2.0 * pj - 3.0 * g)
+        - 0.00021 * s(2.0 * l3 - 3.0 * l4 + p4)
+        + 0.00019 * s(l4 - p4 - g)
+        - 0.00019 * s(2.0 * l4 - p3 - p4)
+        - 0.00018 * s(l4 - p4 + g)
+        - 0.00016 * s(l4 + p3 - 2.0 * pj - 2.0 * g);
+
+    /* True longitudes. */
+    let ll = [l1 + sig1, l2 + sig2, l3 + sig3, l4 + sig4];
+
+    /* Latitudes above Jupiter's equator. */
+    let b = [
+        (0.0006393 * s(ll[0] - w1) + 0.0001825 * s(ll[0] - w2) + 0.0000329 * s(ll[0] - w3)
+            - 0.0000311 * s(ll[0] - psi)
+            + 0.0000093 * s(ll[0] - w4)
+            + 0.0000075 * s(3.0 * ll[0] - 4.0 * l2 - 1.9927 * sig1 + w2)
+            + 0.0000046 * s(ll[0] + psi - 2.0 * pj - 2.0 * g))
+        .atan(),
+        (0.0081004 * s(ll[1] - w2) + 0.0004512 * s(ll[1] - w3) - 0.0003284 * s(ll[1] - psi)
+            + 0.0001160 * s(ll[1] - w4)
+            + 0.0000272 * s(l1 - 2.0 * l3 + 1.0146 * sig2 + w2)
+            - 0.0000144 * s(ll[1] - w1)
+            + 0.0000143 * s(ll[1] + psi - 2.0 * pj - 2.0 * g)
+            + 0.0000035 * s(ll[1] - psi + g)
+            - 0.0000028 * s(l1 - 2.0 * l3 + 1.0146 * sig2 + w3))
+        .atan(),
+        (0.0032402 * s(ll[2] - w3) - 0.0016911 * s(ll[2] - psi) + 0.0006847 * s(ll[2] - w4)
+            - 0.0002797 * s(ll[2] - w2)
+            + 0.0000321 * s(ll[2] + psi - 2.0 * pj - 2.0 * g)
+            + 0.0000051 * s(ll[2] - psi + g)
+            - 0.0000045 * s(ll[2] - psi - g)
+            - 0.0000045 * s(ll[2] + psi - 2.0 * pj)
+            + 0.0000037 * s(ll[2] + psi - 2.0 * pj - 3.0 * g)
+            + 0.0000030 * s(2.0 * l2 - 3.0 * ll[2] + 4.03 * sig3 + w2)
+            - 0.0000021 * s(2.0 * l2 - 3.0 * ll[2] + 4.03 * sig3 + w3))
+        .atan(),
+        (-0.0076579 * s(ll[3] - psi) + 0.0044134 * s(ll[3] - w4) - 0.0005112 * s(ll[3] - w3)
+            + 0.0000773 * s(ll[3] + psi - 2.0 * pj - 2.0 * g)
+            + 0.0000104 * s(ll[3] - psi + g)
+            - 0.0000102 * s(ll[3] - psi - g)
+            + 0.0000088 * s(ll[3] + psi - 2.0 * pj - 3.0 * g)
+            - 0.0000038 * s(ll[3] + psi - 2.0 * pj - g))
+        .atan(),
+    ];
+
+    /* Radius vectors (Jupiter radii). */
+    let r = [
+        5.90569
+            * (1.0
+                - 0.0041339 * c(2.0 * (l1 - l2))
+                - 0.0000387 * c(l1 - p3)
+                - 0.0000214 * c(l1 - p4)
+                + 0.0000170 * c(l1 - l2)
+                - 0.0000131 * c(4.0 * (l1 - l2))
+                + 0.0000106 * c(l1 - l3)
+                - 0.0000066 * c(l1 + p3 - 2.0 * pj - 2.0 * g)),
+        9.39657
+            * (1.0 + 0.0093848 * c(l1 - l2)
+                - 0.0003116 * c(l2 - p3)
+                - 0.0001744 * c(l2 - p4)
+                - 0.0001442 * c(l2 - p2)
+                + 0.0000553 * c(l2 - l3)
+                + 0.0000523 * c(l1 - l3)
+                - 0.0000290 * c(2.0 * (l1 - l2))
+                + 0.0000164 * c(2.0 * (l2 - w2))
+                + 0.0000107 * c(l1 - 2.0 * l3 + p3)
+                - 0.0000102 * c(l2 - p1)
+                - 0.0000091 * c(2.0 * (l1 - l3))),
+        14.98832
+            * (1.0 - 0.0014388 * c(l3 - p3) - 0.0007919 * c(l3 - p4) + 0.0006342 * c(l2 - l3)
+                - 0.0001761 * c(2.0 * (l3 - l4))
+                + 0.0000294 * c(l3 - l4)
+                - 0.0000156 * c(3.0 * (l3 - l4))
+                + 0.0000156 * c(l1 - l3)
+                - 0.0000153 * c(l1 - l2)
+                + 0.0000070 * c(2.0 * l2 - 3.0 * l3 + p3)
+                - 0.0000051 * c(l3 + p3 - 2.0 * pj - 2.0 * g)),
+        26.36273
+            * (1.0 - 0.0073546 * c(l4 - p4) + 0.0001621 * c(l4 - p3) + 0.0000974 * c(l3 - l4)
+                - 0.0000543 * c(l4 + p4 - 2.0 * pj - 2.0 * g)
+                - 0.0000271 * c(2.0 * (l4 - p4))
+                + 0.0000182 * c(l4 - pj)
+                + 0.0000177 * c(2.0 * (l3 - l4))
+                - 0.0000167 * c(2.0 * l4 - psi - w4)
+                + 0.0000167 * c(psi - w4)
+                - 0.0000155 * c(2.0 * (l4 - pj - g))
+                + 0.0000142 * c(2.0 * (l4 - psi))
+                + 0.0000105 * c(l1 - l4)
+                + 0.0000092 * c(l2 - l4)
+                - 0.0000089 * c(l4 - pj - g)
+                - 0.0000062 * c(l4 + p4 - 2.0 * pj - 3.0 * g)
+                + 0.0000048 * c(2.0 * (l4 - w4))),
+    ];
+
+    /* Rectangular coordinates, x-axis toward the node psi (au). */
+    let mut p = [[0.0; 3]; 4];
+    for i in 0..4 {
+        let rr = r[i] * RJ * 1e3 / DAU;
+        let u = (ll[i] - psi) * DD2R;
+        p[i] = [
+            rr * u.cos() * b[i].cos(),
+            rr * u.sin() * b[i].cos(),
+            rr * b[i].sin(),
+        ];
+    }
+
+    /* Precession in longitude from B1950.0. */
+    let t0 = ((date1 - 2433282.423) + date2) / 36525.0;
+    let pr = 1.3966626 * t0 + 0.0003088 * t0 * t0;
+
+    /* Inclination of Jupiter's equator on its orbit. */
+    let t = ((date1 - 2415020.5) + date2) / 36525.0;
+    let inc = 3.120262 + 0.0006 * t;
+
+    /* Node and inclination of Jupiter's orbit (ecliptic of date). */
+    let t = ((date1 - 2451545.0) + date2) / 36525.0;
+    let om = 100.464407 + t * (1.0209774 + t * (0.00040315 + t * 0.000000404));
+    let i = 1.303267 + t * (-0.0054965 + t * (0.00000466 - t * 0.000000002));
+
+    /* Equator to ecliptic of date. */
+    let mut rq = [[0.0; 3]; 3];
+    ir(&mut rq);
+    rx(-inc * DD2R, &mut rq);
+    rz(-(psi + pr - om) * DD2R, &mut rq);
+    rx(-i * DD2R, &mut rq);
+    rz(-om * DD2R, &mut rq);
+
+    /* Ecliptic of date to ICRS. */
+    let mut re = [[0.0; 3]; 3];
+    tr(&ecm06(date1, date2), &mut re);
+    let mut m = [[0.0; 3]; 3];
+    rxr(&re, &rq, &mut m);
+
+    (p, m)
+}
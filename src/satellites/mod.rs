@@ -0,0 +1,16 @@
+//! Planetary satellites (Galilean satellites and Titan)
+
+mod galsat;
+pub use galsat::*;
+
+mod satellite;
+pub use satellite::*;
+
+mod satevents;
+pub use satevents::*;
+
+mod satxy;
+pub use satxy::*;
+
+mod titan;
+pub use titan::*;
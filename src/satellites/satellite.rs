@@ -0,0 +1,91 @@
+use super::{galsat, jupeq, saturneq, titan};
+use crate::eph::Target;
+
+/// Equatorial radius (km) and flattening of Jupiter and Saturn (IAU
+/// WGCCRE 2015).
+const JUPITER: (f64, f64) = (71492.0, 0.06487);
+const SATURN: (f64, f64) = (60268.0, 0.09796);
+
+/// Planetary satellite
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Satellite {
+    Io,
+    Europa,
+    Ganymede,
+    Callisto,
+    Titan,
+}
+
+impl Satellite {
+    /// All the satellites, in the order above.
+    pub const ALL: [Satellite; 5] = [
+        Satellite::Io,
+        Satellite::Europa,
+        Satellite::Ganymede,
+        Satellite::Callisto,
+        Satellite::Titan,
+    ];
+
+    /// The primary.
+    pub fn planet(&self) -> Target {
+        match self {
+            Satellite::Titan => Target::Saturn,
+            _ => Target::Jupiter,
+        }
+    }
+
+    /// Mean radius (km, IAU WGCCRE 2015).
+    pub fn radius(&self) -> f64 {
+        match self {
+            Satellite::Io => 1821.49,
+            Satellite::Europa => 1560.8,
+            Satellite::Ganymede => 2631.2,
+            Satellite::Callisto => 2410.3,
+            Satellite::Titan => 2575.0,
+        }
+    }
+
+    /// Equatorial radius (km) and flattening of the primary.
+    pub fn planet_radius(&self) -> (f64, f64) {
+        match self {
+            Satellite::Titan => SATURN,
+            _ => JUPITER,
+        }
+    }
+
+    ///  Planetocentric position of the satellite
+    ///
+    ///  Given:
+    ///  ```text
+    ///     date1  f64     TDB as a 2-part...
+    ///     date2  f64     ...Julian Date (at the satellite)
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///            [f64; 3]  position relative to the planet (ICRS, au)
+    ///  ```
+    ///  Called:
+    ///  ```text
+    ///     galsat     Galilean satellites
+    ///     titan      Titan
+    ///  ```
+    pub fn pos(&self, date1: f64, date2: f64) -> [f64; 3] {
+        match self {
+            Satellite::Io => galsat(date1, date2)[0],
+            Satellite::Europa => galsat(date1, date2)[1],
+            Satellite::Ganymede => galsat(date1, date2)[2],
+            Satellite::Callisto => galsat(date1, date2)[3],
+            Satellite::Titan => titan(date1, date2),
+        }
+    }
+
+    /// North pole of the primary (ICRS unit vector), as used by the
+    /// theory of the satellite.
+    pub fn pole(&self, date1: f64, date2: f64) -> [f64; 3] {
+        let r = match self {
+            Satellite::Titan => saturneq(),
+            _ => jupeq(date1, date2),
+        };
+        [r[0][2], r[1][2], r[2][2]]
+    }
+}
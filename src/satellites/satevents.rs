@@ -0,0 +1,155 @@
+use super::Satellite;
+use super::satxy::view;
+use crate::almanac::search::crossings;
+use crate::eph::Ephemeris;
+
+/// Search step (days).
+const STEP: f64 = 0.02;
+
+/// Phenomenon of a planetary satellite
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SatPhenom {
+    Transit,     // satellite in front of the disk
+    Shadow,      // shadow of the satellite on the disk
+    Eclipse,     // satellite in the shadow of the planet
+    Occultation, // satellite behind the disk
+}
+
+/// A phenomenon of a planetary satellite
+///
+/// Times are TT as a 2-part Julian Date.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SatEvent {
+    pub sat: Satellite,            // the satellite
+    pub kind: SatPhenom,           // type of phenomenon
+    pub begin: Option<(f64, f64)>, // ingress or disappearance (None if before the interval)
+    pub end: Option<(f64, f64)>,   // egress or reappearance (None if after the interval)
+}
+
+///  Phenomena of planetary satellites in a given interval
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris    source of the Earth, Sun and planets
+///     sats   [Satellite]  the satellites
+///     tt1    f64          TT as a 2-part...
+///     tt2    f64          ...Julian Date (start of search)
+///     days   f64          length of the search interval (days)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(events)  Vec<SatEvent>  phenomena, in order of their first
+///                                contact in the interval
+///     Err(j)      i32            status from eph
+///  ```
+///  Notes:
+///
+///  1) The phenomena are the transits of the satellites and of their
+///     shadows across the disk of the planet, and their eclipses by the
+///     shadow and occultations by the disk of the planet.  The times
+///     are those of the passage of the center of the satellite, or of
+///     its shadow, across the limb of the planet or the edge of the
+///     umbra, as seen from the center of the Earth.  This is the
+///     convention of the Astronomical Almanac.
+///
+///  2) The disk of the planet is an ellipse, allowing for the
+///     flattening of the planet and the tilt of its pole.  The umbra is
+///     the cone tangent to the Sun and to the planet's equator, which
+///     slightly overestimates its width at the poles.  The penumbra and
+///     refraction in the planet's atmosphere are neglected.  The
+///     positions are from satxy.
+///
+///  3) All the geometric phenomena are listed, including those that
+///     are invisible because, for example, the reappearance from
+///     eclipse takes place behind the disk, or because the planet is
+///     close to the Sun.  Phenomena shorter than the search step of
+///     0.02 day (grazing transits, for example) may be missed.
+///
+///  Called:
+///  ```text
+///     satxy       apparent position of a satellite
+///  ```
+///
+///  Reference:
+///
+///     Explanatory Supplement to the Astronomical Almanac,
+///     P. Kenneth Seidelmann (ed), University Science Books (1992),
+///     Section 6.4.
+pub fn satevents<E: Ephemeris>(
+    eph: &E,
+    sats: &[Satellite],
+    tt1: f64,
+    tt2: f64,
+    days: f64,
+) -> Result<Vec<SatEvent>, i32> {
+    let n = (days.max(0.0) / STEP).ceil() as usize;
+    let xs: Vec<f64> = (0..=n)
+        .map(|i| (i as f64 * STEP).min(days.max(0.0)))
+        .collect();
+
+    let mut events = Vec::new();
+    for &sat in sats {
+        /* Sample the distances from the disk and the shadow. */
+        let mut fe = Vec::with_capacity(xs.len());
+        let mut fs = Vec::with_capacity(xs.len());
+        for &x in &xs {
+            let v = view(eph, sat, tt1, tt2 + x)?;
+            fe.push(v.rho() - 1.0);
+            fs.push(v.srho());
+        }
+
+        /* Contacts seen from the Earth, then from the Sun. */
+        for sun in [false, true] {
+            let mut f = |x: f64| {
+                view(eph, sat, tt1, tt2 + x).map(|v| if sun { v.srho() } else { v.rho() - 1.0 })
+            };
+            let fx = if sun { &fs } else { &fe };
+            let ins = crossings(&mut f, &xs, fx, false, usize::MAX)?;
+            let outs = crossings(&mut f, &xs, fx, true, usize::MAX)?;
+
+            /* Which phenomenon:  from the side of the planet. */
+            let kind = |x: f64| -> Result<SatPhenom, i32> {
+                let v = view(eph, sat, tt1, tt2 + x)?;
+                Ok(match (sun, if sun { v.sxyz[2] } else { v.xyz[2] } < 0.0) {
+                    (false, true) => SatPhenom::Transit,
+                    (false, false) => SatPhenom::Occultation,
+                    (true, true) => SatPhenom::Shadow,
+                    (true, false) => SatPhenom::Eclipse,
+                })
+            };
+
+            /* Already in progress at the start. */
+            let mut j = 0;
+            if fx[0] < 0.0 {
+                let end = outs.first().copied();
+                events.push(SatEvent {
+                    sat,
+                    kind: kind(end.unwrap_or(0.0))?,
+                    begin: None,
+                    end: end.map(|x| (tt1, tt2 + x)),
+                });
+                j = 1;
+            }
+
+            /* Ingress and the following egress. */
+            for &x in &ins {
+                while j < outs.len() && outs[j] < x {
+                    j += 1;
+                }
+                let end = outs.get(j).copied();
+                j += 1;
+                events.push(SatEvent {
+                    sat,
+                    kind: kind(x)?,
+                    begin: Some((tt1, tt2 + x)),
+                    end: end.map(|x| (tt1, tt2 + x)),
+                });
+            }
+        }
+    }
+
+    /* Order by the first contact in the interval. */
+    let first = |e: &SatEvent| e.begin.or(e.end).map_or(tt2, |t| t.1);
+    events.sort_by(|a, b| first(a).total_cmp(&first(b)));
+    Ok(events)
+}
@@ -0,0 +1,137 @@
+use super::Satellite;
+use crate::consts::{AULT, DAU, DAYSEC};
+use crate::eph::{Ephemeris, Target};
+use crate::vm::{pdp, pm, pmp, pn, ppp, pxp, sxp};
+
+/// Radius of the Sun (km).
+const RSUN: f64 = 695700.0;
+
+/// The satellite seen from the Earth and from the Sun.
+pub(crate) struct View {
+    pub(crate) xyz: [f64; 3],  // seen from the Earth (planet radii)
+    pub(crate) ba: f64,        // axis ratio of the disk seen from the Earth
+    pub(crate) sxyz: [f64; 3], // seen from the Sun (planet radii)
+    pub(crate) sba: f64,       // axis ratio of the disk seen from the Sun
+    pub(crate) k: f64,         // convergence of the umbra (radii per radius)
+}
+
+impl View {
+    /// Distance from the center of the disk seen from the Earth, in
+    /// units of the radius of the limb in that direction.
+    pub(crate) fn rho(&self) -> f64 {
+        self.xyz[0].hypot(self.xyz[1] / self.ba)
+    }
+
+    /// As rho, seen from the Sun, less the radius of the shadow.
+    pub(crate) fn srho(&self) -> f64 {
+        let r = self.sxyz[0].hypot(self.sxyz[1] / self.sba);
+        r - (1.0 - self.k * self.sxyz[2].max(0.0))
+    }
+}
+
+///  Apparent position of a satellite relative to its planet
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris  source of the Earth, Sun and planet (Note 1)
+///     sat    Satellite  the satellite
+///     tt1    f64        TT as a 2-part...
+///     tt2    f64        ...Julian Date
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(xyz)  [f64; 3]  X, Y, Z (Note 2)
+///     Err(j)   i32       status from eph
+///  ```
+///  Notes:
+///
+///  1) The planet is taken from eph, for example Plan94, and the
+///     satellite from its theory (galsat, titan).  The geocentric
+///     positions allow for light time, including the difference in
+///     light time between the planet and the satellite;  aberration
+///     is the same for both and is neglected, as are the differences
+///     between TT and TDB.
+///
+///  2) X and Y are the offsets of the satellite from the center of the
+///     planet in the plane of the sky, in units of the equatorial
+///     radius of the planet:  X in the direction of the planet's
+///     equator, positive to the west, and Y toward the planet's north
+///     pole.  Z is the distance beyond the center of the planet along
+///     the line of sight, in the same units;  the satellite is in
+///     front of the planet if Z is negative.
+///
+///  3) The satellite is in front of or behind the disk when X^2 +
+///     (Y/k)^2 < 1, where k = sqrt(1 - e^2 cos^2 D), e being the
+///     eccentricity of the meridian of the planet and D the
+///     planetocentric declination of the Earth.  See satevents.
+///
+///  Called:
+///  ```text
+///     Ephemeris::earth   Earth position
+///     Ephemeris::pvb     planet position
+///     Satellite::pos     satellite position
+///     Satellite::pole    planet's north pole
+///  ```
+///
+///  Reference:
+///
+///     Meeus, J., Astronomical Algorithms, 2nd edition,
+///     Willmann-Bell (1998), Chapter 44.
+pub fn satxy<E: Ephemeris>(eph: &E, sat: Satellite, tt1: f64, tt2: f64) -> Result<[f64; 3], i32> {
+    Ok(view(eph, sat, tt1, tt2)?.xyz)
+}
+
+/// The satellite seen from the Earth and the Sun at TT tt1+tt2.
+pub(crate) fn view<E: Ephemeris>(eph: &E, sat: Satellite, tt1: f64, tt2: f64) -> Result<View, i32> {
+    let (rp, f) = sat.planet_radius();
+    let rp = rp * 1e3 / DAU;
+
+    /* Planet, allowing for light time. */
+    let (_, eb) = eph.earth(tt1, tt2)?;
+    let mut pb = eph.pvb(sat.planet(), tt1, tt2)?;
+    let mut tau = 0.0;
+    for _ in 0..2 {
+        tau = pm(pmp(&pb[0], &eb[0])) * AULT / DAYSEC;
+        pb = eph.pvb(sat.planet(), tt1, tt2 - tau)?;
+    }
+    let d = pmp(&pb[0], &eb[0]);
+    let u = pn(&d).1;
+
+    /* Satellite, with the extra light time across the system. */
+    let s = sat.pos(tt1, tt2 - tau);
+    let s = sat.pos(tt1, tt2 - tau - pdp(&s, &u) * AULT / DAYSEC);
+    let n = sat.pole(tt1, tt2 - tau);
+
+    /* The Sun, when the light reaching the planet left it. */
+    let sb = eph.pvb(Target::Sun, tt1, tt2 - tau)?;
+    let ts = pm(pmp(&pb[0], &sb[0])) * AULT / DAYSEC;
+    let sb = eph.pvb(Target::Sun, tt1, tt2 - tau - ts)?;
+    let ds = pmp(&pb[0], &sb[0]);
+
+    let (xyz, ba) = project(&d, &s, &n, rp, f);
+    let (sxyz, sba) = project(&ds, &s, &n, rp, f);
+    Ok(View {
+        xyz,
+        ba,
+        sxyz,
+        sba,
+        k: (RSUN * 1e3 / DAU - rp) / pm(ds),
+    })
+}
+
+/* Satellite in the sky plane of an observer at -d, and axis ratio. */
+fn project(d: &[f64; 3], s: &[f64; 3], n: &[f64; 3], rp: f64, f: f64) -> ([f64; 3], f64) {
+    let (dist, u) = pn(d);
+    let sd = pdp(n, &u);
+    let y = pn(&pmp(n, &sxp(sd, &u))).1;
+    let w = pxp(&u, &y);
+
+    /* Observer to satellite, and the scale at the planet's distance. */
+    let v = ppp(d, s);
+    let z = pdp(&v, &u);
+    let k = dist / z / rp;
+    let xyz = [pdp(&v, &w) * k, pdp(&v, &y) * k, (z - dist) / rp];
+
+    let e2 = f * (2.0 - f);
+    (xyz, (1.0 - e2 * (1.0 - sd * sd)).sqrt())
+}
@@ -0,0 +1,154 @@
+use crate::consts::{D2PI, DAU, DD2R};
+use crate::coords::ecm06;
+use crate::orbit::kepler;
+use crate::vm::{ir, rx, rxr, rz, tr, trxp};
+
+/// Unit of the semi-major axis of the theory (km), Saturn's equatorial
+/// radius as adopted by Dourneau.
+const RS: f64 = 60330.0;
+
+/// Node and inclination of Saturn's equator on the B1950.0 ecliptic
+/// (degrees).
+const NODE: f64 = 168.8112;
+const INCL: f64 = 28.0817;
+
+///  Saturnocentric position of Titan
+///
+///  Given:
+///  ```text
+///     date1  f64     TDB as a 2-part...
+///     date2  f64     ...Julian Date (Note 1)
+///  ```
+///  Returned (function value):
+///  ```text
+///            [f64; 3]  saturnocentric position (ICRS, au)
+///  ```
+///  Notes:
+///
+///  1) The date is that at the satellite:  for the position seen from
+///     the Earth, subtract the light time from Saturn.
+///
+///  2) The theory is that of Dourneau as given by Meeus:  an ellipse
+///     whose node, apse, inclination and eccentricity vary under the
+///     action of the Sun and of Saturn's oblateness, with the leading
+///     solar inequalities in longitude.  It is referred to the
+///     ecliptic and equinox of B1950.0, which is rotated to the ICRS
+///     with iauEcm06.  The error is a few hundred km (0.1 arcsec at
+///     opposition).
+///
+///  Called:
+///  ```text
+///     kepler      solve Kepler's equation
+///     iauEcm06    ICRS to ecliptic rotation matrix, IAU 2006
+///     iauTrxp     product of transpose of r-matrix and p-vector
+///  ```
+///
+///  References:
+///
+///     Dourneau, G., 1987, Thesis, Observatoire de Bordeaux.
+///
+///     Meeus, J., Astronomical Algorithms, 2nd edition,
+///     Willmann-Bell (1998), Chapter 46.
+pub fn titan(date1: f64, date2: f64) -> [f64; 3] {
+    let s = |x: f64| (x * DD2R).sin();
+    let c = |x: f64| (x * DD2R).cos();
+
+    /* Time arguments. */
+    let t4 = (date1 - 2411368.0) + date2;
+    let t5 = t4 / 365.25;
+    let t7 = ((date1 - 2415020.0) + date2) / 36525.0;
+
+    /* Saturn's orbit and the solar perturbations (degrees). */
+    let w3 = 42.0 - 0.5118 * t5;
+    let w4 = 276.59 + 0.5118 * t5;
+    let w5 = 267.2635 + 1222.1136 * t7;
+    let w6 = 175.4762 + 1221.5515 * t7;
+    let w7 = 2.4891 + 0.002435 * t7;
+    let w8 = 113.35 - 0.2597 * t7;
+    let e1 = 0.05589 - 0.000346 * t7;
+
+    /* Mean longitude, inclination and node of the mean orbit. */
+    let l = 261.1582 + 22.57697855 * t4 + 0.074025 * s(w3);
+    let id = 27.45141 + 0.295999 * c(w3);
+    let od = 168.66925 + 0.628808 * s(w3);
+
+    /* Inclination of the orbit to Saturn's orbit. */
+    let a1 = s(w7) * s(od - w8);
+    let a2 = c(w7) * s(id) - s(w7) * c(id) * c(od - w8);
+    let psi = a1.atan2(a2) / DD2R;
+    let sp = (a1 * a1 + a2 * a2).sqrt();
+
+    /* Perisaturnium and eccentricity. */
+    let g0 = 102.8623;
+    let mut g = w4 - od - psi;
+    let mut w = w4;
+    for _ in 0..3 {
+        w = w4 + 0.37515 * (s(2.0 * g) - s(2.0 * g0));
+        g = w - od - psi;
+    }
+    let ed = 0.029092 + 0.00019048 * (c(2.0 * g) - c(2.0 * g0));
+    let q = 2.0 * (w5 - w);
+    let b1 = s(id) * s(od - w8);
+    let b2 = c(w7) * s(id) * c(od - w8) - s(w7) * c(id);
+    let th = b1.atan2(b2) / DD2R + w8;
+    let e = ed + 0.002778797 * ed * c(q);
+    let p = w + 0.159215 * s(q);
+    let u = 2.0 * w5 - 2.0 * th + psi;
+    let h = 0.9375 * ed * ed * s(q) + 0.1875 * sp * sp * s(2.0 * (w5 - th));
+
+    /* Osculating elements. */
+    let lam = l - 0.254744 * (e1 * s(w6) + 0.75 * e1 * e1 * s(2.0 * w6) + h);
+    let i = id + 0.031843 * sp * c(u);
+    let om = od + 0.031843 * sp * s(u) / s(id);
+    let a = 20.216193 * RS * 1e3 / DAU;
+
+    /* Position in the orbit. */
+    let m = ((lam - p) * DD2R).rem_euclid(D2PI);
+    let ea = kepler(m, e).unwrap_or(m);
+    let v = 2.0 * (((1.0 + e) / (1.0 - e)).sqrt() * (ea / 2.0).tan()).atan();
+    let r = a * (1.0 - e * ea.cos());
+    let u = v + (p - om) * DD2R;
+
+    /* B1950.0 ecliptic coordinates. */
+    let (so, co) = (om * DD2R).sin_cos();
+    let (si, ci) = (i * DD2R).sin_cos();
+    let pe = [
+        r * (co * u.cos() - so * u.sin() * ci),
+        r * (so * u.cos() + co * u.sin() * ci),
+        r * u.sin() * si,
+    ];
+
+    /* ICRS. */
+    let mut pos = [0.0; 3];
+    trxp(&ecm1950(), &pe, &mut pos);
+    pos
+}
+
+///  Rotation matrix from Saturn's equator to the ICRS
+///
+///  Returned (function value):
+///  ```text
+///            [[f64; 3]; 3]  matrix (Note)
+///  ```
+///  Note:
+///
+///     The matrix rotates a vector in the frame of Saturn's equator,
+///     the x-axis toward the ascending node on the B1950.0 ecliptic,
+///     into the ICRS;  its third column is Saturn's north pole.  The
+///     equator is that adopted by Dourneau and is taken as fixed.
+pub fn saturneq() -> [[f64; 3]; 3] {
+    let mut rq = [[0.0; 3]; 3];
+    ir(&mut rq);
+    rx(-INCL * DD2R, &mut rq);
+    rz(-NODE * DD2R, &mut rq);
+    let mut re = [[0.0; 3]; 3];
+    tr(&ecm1950(), &mut re);
+    let mut m = [[0.0; 3]; 3];
+    rxr(&re, &rq, &mut m);
+    m
+}
+
+/* ICRS to ecliptic of B1950.0. */
+fn ecm1950() -> [[f64; 3]; 3] {
+    ecm06(2400000.5, 33281.92345905)
+}
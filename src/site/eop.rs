@@ -0,0 +1,17 @@
+/// Earth orientation parameters
+///
+/// The UT1-UTC offset and polar motion required by the *13 family of
+/// observed-place functions, as tabulated in IERS bulletins.  For many
+/// applications the default (all zero) is adequate.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Eop {
+    pub dut1: f64, // UT1-UTC (seconds)
+    pub xp: f64,   // polar motion x (radians)
+    pub yp: f64,   // polar motion y (radians)
+}
+
+impl Eop {
+    pub fn new(dut1: f64, xp: f64, yp: f64) -> Self {
+        Eop { dut1, xp, yp }
+    }
+}
@@ -0,0 +1,10 @@
+//! Observing site, weather and Earth orientation context
+
+mod eop;
+pub use eop::*;
+
+mod observer;
+pub use observer::*;
+
+mod weather;
+pub use weather::*;
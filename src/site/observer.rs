@@ -0,0 +1,265 @@
+use crate::astro::{IauAstrom, apco13e, apio13, atciq, aticq, atioq, atoiq, pvtob};
+use crate::consts::WGS84;
+use crate::coords::{eform, gc2gd, gd2gc};
+use crate::eph::{Ephemeris, Epv00};
+
+use super::{Eop, Weather};
+
+/// Terrestrial observer
+///
+/// Geodetic position of an observing site on a chosen reference
+/// ellipsoid.  The methods wrap the SOFA functions that take site
+/// coordinates, meteorological data and Earth orientation parameters
+/// as long lists of positional arguments.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Observer {
+    pub elong: f64, // longitude (radians, east +ve)
+    pub phi: f64,   // latitude (geodetic, radians)
+    pub hm: f64,    // height above ellipsoid (m, geodetic)
+    pub n: i32,     // ellipsoid identifier (1=WGS84, 2=GRS80, 3=WGS72)
+}
+
+impl Observer {
+    /// Observer on the WGS84 ellipsoid.
+    pub fn new(elong: f64, phi: f64, hm: f64) -> Self {
+        Observer {
+            elong,
+            phi,
+            hm,
+            n: WGS84,
+        }
+    }
+
+    ///  Observer on a nominated ellipsoid
+    ///
+    ///  Given:
+    ///  ```text
+    ///     n      i32     ellipsoid identifier (see iauEform)
+    ///     elong  f64     longitude (radians, east +ve)
+    ///     phi    f64     latitude (geodetic, radians)
+    ///     hm     f64     height above ellipsoid (m, geodetic)
+    ///  ```
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok(Observer)
+    ///     Err(j) j   i32    -1 = illegal identifier
+    ///  ```
+    pub fn with_ellipsoid(n: i32, elong: f64, phi: f64, hm: f64) -> Result<Self, i32> {
+        eform(n)?;
+        Ok(Observer { elong, phi, hm, n })
+    }
+
+    /// Geocentric position of the site (ITRS, m), using iauGd2gc.
+    pub fn geocentric(&self) -> Result<[f64; 3], i32> {
+        gd2gc(self.n, self.elong, self.phi, self.hm)
+    }
+
+    ///  Site coordinates referred to WGS84
+    ///
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok((elong, phi, hm))   WGS84 longitude, latitude (radians)
+    ///                            and height (m)
+    ///     Err(j) j   i32         -1 = illegal identifier
+    ///                            -2 = illegal case
+    ///  ```
+    ///  Note:
+    ///
+    ///     The SOFA observed-place functions assume WGS84 coordinates.
+    ///     Sites given on another ellipsoid are converted via their
+    ///     geocentric position.
+    pub fn wgs84(&self) -> Result<(f64, f64, f64), i32> {
+        if self.n == WGS84 {
+            Ok((self.elong, self.phi, self.hm))
+        } else {
+            gc2gd(WGS84, self.geocentric()?)
+        }
+    }
+
+    ///  Position and velocity of the site (m, m/s, CIRS), via iauPvtob.
+    ///
+    ///  Given:
+    ///  ```text
+    ///     eop    Eop     polar motion (dut1 is not used)
+    ///     sp     f64     the TIO locator s' (radians)
+    ///     theta  f64     Earth rotation angle (radians)
+    ///  ```
+    pub fn pvtob(&self, eop: &Eop, sp: f64, theta: f64) -> Result<[[f64; 3]; 2], i32> {
+        let (elong, phi, hm) = self.wgs84()?;
+        let mut pv = [[0.0; 3]; 2];
+        pvtob(elong, phi, hm, eop.xp, eop.yp, sp, theta, &mut pv);
+        Ok(pv)
+    }
+
+    ///  Star-independent astrometry parameters for ICRS <-> observed
+    ///  (iauApco13).
+    ///
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok((astrom, eo))
+    ///            astrom  IauAstrom  star-independent astrometry parameters
+    ///            eo      f64        equation of the origins (ERA-GST)
+    ///     Err(j) j       i32        -1 = unacceptable date
+    ///  ```
+    pub fn apco13(
+        &self,
+        utc1: f64,
+        utc2: f64,
+        eop: &Eop,
+        wx: &Weather,
+    ) -> Result<(IauAstrom, f64), i32> {
+        self.apco13e(&Epv00, utc1, utc2, eop, wx)
+    }
+
+    ///  Star-independent astrometry parameters for ICRS <-> observed,
+    ///  with the Earth from a given ephemeris (apco13e).
+    ///
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok((astrom, eo))
+    ///            astrom  IauAstrom  star-independent astrometry parameters
+    ///            eo      f64        equation of the origins (ERA-GST)
+    ///     Err(j) j       i32        -1 = unacceptable date, or status
+    ///                               from eph
+    ///  ```
+    pub fn apco13e<E: Ephemeris>(
+        &self,
+        eph: &E,
+        utc1: f64,
+        utc2: f64,
+        eop: &Eop,
+        wx: &Weather,
+    ) -> Result<(IauAstrom, f64), i32> {
+        let (elong, phi, hm) = self.wgs84()?;
+        let mut astrom = IauAstrom::default();
+        let mut eo = 0.0;
+        apco13e(
+            eph,
+            utc1,
+            utc2,
+            eop.dut1,
+            elong,
+            phi,
+            hm,
+            eop.xp,
+            eop.yp,
+            wx.phpa,
+            wx.tc,
+            wx.rh,
+            wx.wl,
+            &mut astrom,
+            &mut eo,
+        )?;
+        Ok((astrom, eo))
+    }
+
+    ///  Star-independent astrometry parameters for CIRS <-> observed
+    ///  (iauApio13).
+    pub fn apio13(&self, utc1: f64, utc2: f64, eop: &Eop, wx: &Weather) -> Result<IauAstrom, i32> {
+        let (elong, phi, hm) = self.wgs84()?;
+        let mut astrom = IauAstrom::default();
+        apio13(
+            utc1,
+            utc2,
+            eop.dut1,
+            elong,
+            phi,
+            hm,
+            eop.xp,
+            eop.yp,
+            wx.phpa,
+            wx.tc,
+            wx.rh,
+            wx.wl,
+            &mut astrom,
+        )?;
+        Ok(astrom)
+    }
+
+    ///  ICRS RA,Dec to observed place (iauAtco13).
+    ///
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok((aob, zob, hob, dob, rob, eo))   as for iauAtco13
+    ///     Err(j) j   i32    -1 = unacceptable date
+    ///  ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn atco13(
+        &self,
+        rc: f64,
+        dc: f64,
+        pr: f64,
+        pd: f64,
+        px: f64,
+        rv: f64,
+        utc1: f64,
+        utc2: f64,
+        eop: &Eop,
+        wx: &Weather,
+    ) -> Result<(f64, f64, f64, f64, f64, f64), i32> {
+        let (mut astrom, eo) = self.apco13(utc1, utc2, eop, wx)?;
+        let (ri, di) = atciq(rc, dc, pr, pd, px, rv, &mut astrom);
+        let (aob, zob, hob, dob, rob) = atioq(ri, di, &astrom);
+        Ok((aob, zob, hob, dob, rob, eo))
+    }
+
+    ///  CIRS RA,Dec to observed place (iauAtio13).
+    ///
+    ///  Returned (function value):
+    ///  ```text
+    ///     Ok((aob, zob, hob, dob, rob))   as for iauAtio13
+    ///     Err(j) j   i32    -1 = unacceptable date
+    ///  ```
+    pub fn atio13(
+        &self,
+        ri: f64,
+        di: f64,
+        utc1: f64,
+        utc2: f64,
+        eop: &Eop,
+        wx: &Weather,
+    ) -> Result<(f64, f64, f64, f64, f64), i32> {
+        let astrom = self.apio13(utc1, utc2, eop, wx)?;
+        Ok(atioq(ri, di, &astrom))
+    }
+
+    ///  Observed place to ICRS astrometric RA,Dec (iauAtoc13).
+    ///
+    ///  Given:
+    ///  ```text
+    ///     type_  &str    type of coordinates - "R", "H" or "A"
+    ///     ob1    f64     observed Az, HA or RA (radians; Az is N=0,E=90)
+    ///     ob2    f64     observed ZD or Dec (radians)
+    ///  ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn atoc13(
+        &self,
+        type_: &str,
+        ob1: f64,
+        ob2: f64,
+        utc1: f64,
+        utc2: f64,
+        eop: &Eop,
+        wx: &Weather,
+    ) -> Result<(f64, f64), i32> {
+        let (mut astrom, _) = self.apco13(utc1, utc2, eop, wx)?;
+        let (ri, di) = atoiq(type_, ob1, ob2, &astrom);
+        Ok(aticq(ri, di, &mut astrom))
+    }
+
+    ///  Observed place to CIRS RA,Dec (iauAtoi13).
+    #[allow(clippy::too_many_arguments)]
+    pub fn atoi13(
+        &self,
+        type_: &str,
+        ob1: f64,
+        ob2: f64,
+        utc1: f64,
+        utc2: f64,
+        eop: &Eop,
+        wx: &Weather,
+    ) -> Result<(f64, f64), i32> {
+        let astrom = self.apio13(utc1, utc2, eop, wx)?;
+        Ok(atoiq(type_, ob1, ob2, &astrom))
+    }
+}
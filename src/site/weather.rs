@@ -0,0 +1,73 @@
+use crate::astro::refco;
+
+/// Ambient conditions at the observer
+///
+/// Meteorological data and observing wavelength, used to compute the
+/// refraction constants.  Setting the pressure to zero switches
+/// refraction off (see iauRefco).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Weather {
+    pub phpa: f64, // pressure at the observer (hPa = mB)
+    pub tc: f64,   // ambient temperature at the observer (deg C)
+    pub rh: f64,   // relative humidity at the observer (range 0-1)
+    pub wl: f64,   // wavelength (micrometers)
+}
+
+impl Default for Weather {
+    fn default() -> Self {
+        Weather::vacuum()
+    }
+}
+
+impl Weather {
+    pub fn new(phpa: f64, tc: f64, rh: f64, wl: f64) -> Self {
+        Weather { phpa, tc, rh, wl }
+    }
+
+    ///  Weather with the pressure estimated from the site height
+    ///
+    ///  Given:
+    ///  ```text
+    ///     hm     f64     height above sea level (m)
+    ///     tc     f64     ambient temperature at the observer (deg C)
+    ///     rh     f64     relative humidity at the observer (range 0-1)
+    ///     wl     f64     wavelength (micrometers)
+    ///  ```
+    ///  Notes:
+    ///
+    ///  1) The pressure is estimated from the expression given in the
+    ///     iauAtco13 notes,
+    ///
+    ///        phpa = 1013.25 * exp ( -hm / ( 29.3 * tsl ) )
+    ///
+    ///     where tsl is the approximate sea-level air temperature in K,
+    ///     here obtained from tc using the standard lapse rate of
+    ///     6.5 K/km.
+    ///
+    ///  2) The refraction is nearly proportional to the pressure, so a
+    ///     measured value should be preferred for precise work.
+    pub fn standard(hm: f64, tc: f64, rh: f64, wl: f64) -> Self {
+        let tsl = tc + 273.15 + 0.0065 * hm;
+        Weather {
+            phpa: 1013.25 * (-hm / (29.3 * tsl)).exp(),
+            tc,
+            rh,
+            wl,
+        }
+    }
+
+    /// No atmosphere:  zero pressure, so that refraction is omitted.
+    pub fn vacuum() -> Self {
+        Weather {
+            phpa: 0.0,
+            tc: 0.0,
+            rh: 0.0,
+            wl: 0.55,
+        }
+    }
+
+    /// Refraction constants A and B (radians), from iauRefco.
+    pub fn refco(&self) -> (f64, f64) {
+        refco(self.phpa, self.tc, self.rh, self.wl)
+    }
+}
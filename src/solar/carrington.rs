@@ -0,0 +1,52 @@
+use super::sunpbl;
+use crate::consts::{D2PI, DJ00};
+use crate::eph::Ephemeris;
+
+/// Start of Carrington rotation 0 (JD) and mean synodic period (days).
+pub(crate) const CARR1: f64 = 2398140.2270;
+pub(crate) const CARRP: f64 = 27.2752316;
+
+///  Carrington rotation number
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris  source of the Earth and Sun
+///     tt1    f64        TT as a 2-part...
+///     tt2    f64        ...Julian Date
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok(c)  f64    Carrington rotation number, with the fraction of
+///                   the rotation that has elapsed (Note 1)
+///     Err(j) i32    status from eph
+///  ```
+///  Notes:
+///
+///  1) A Carrington rotation starts when the Carrington longitude of
+///     the center of the disk, L0, passes through zero, rotation 1
+///     having started on 1853 November 9.  The integer part of the
+///     result is the number of the current rotation and the fraction
+///     is (360 deg - L0)/360 deg.
+///
+///  2) The number of the rotation comes from the mean synodic period,
+///     27.2752316 days, which is accurate to better than a day for
+///     several centuries either side of the present;  the fraction is
+///     from sunpbl.
+///
+///  Called:
+///  ```text
+///     sunpbl      P, B0 and L0 of the Sun
+///  ```
+///
+///  Reference:
+///
+///     Meeus, J., Astronomical Algorithms, 2nd edition,
+///     Willmann-Bell (1998), Chapter 29.
+pub fn carrington<E: Ephemeris>(eph: &E, tt1: f64, tt2: f64) -> Result<f64, i32> {
+    let (_, _, l0, _) = sunpbl(eph, tt1, tt2)?;
+    let f = 1.0 - l0 / D2PI;
+
+    /* The nearest rotation number consistent with the mean motion. */
+    let c = ((tt1 - DJ00) + tt2 - (CARR1 - DJ00)) / CARRP;
+    Ok((c - f).round() + f)
+}
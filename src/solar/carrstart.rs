@@ -0,0 +1,50 @@
+use super::carrington;
+use super::carrington::{CARR1, CARRP};
+use crate::eph::Ephemeris;
+
+///  Start of a Carrington rotation
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris  source of the Earth and Sun
+///     n      i32        Carrington rotation number
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok((tt1, tt2))
+///            tt1  f64   TT as a 2-part...
+///            tt2  f64   ...Julian Date (Note 1)
+///     Err(j) i32        status from eph
+///  ```
+///  Notes:
+///
+///  1) The result is the instant at which the Carrington longitude of
+///     the center of the disk, seen from the geocenter, passes through
+///     zero (see carrington).  tt1 is the mean start of the rotation,
+///     rounded down to midnight, and tt2 the remainder;  they are
+///     accurate to about 1e-6 day, relative to the solar ephemeris.
+///
+///  Called:
+///  ```text
+///     carrington  Carrington rotation number
+///  ```
+///
+///  Reference:
+///
+///     Meeus, J., Astronomical Algorithms, 2nd edition,
+///     Willmann-Bell (1998), Chapter 29.
+pub fn carrstart<E: Ephemeris>(eph: &E, n: i32) -> Result<(f64, f64), i32> {
+    let t = CARR1 + CARRP * n as f64;
+    let tt1 = (t - 0.5).floor() + 0.5;
+    let mut tt2 = t - tt1;
+
+    /* Newton iterations on the rotation number. */
+    for _ in 0..10 {
+        let dt = (carrington(eph, tt1, tt2)? - n as f64) * CARRP;
+        tt2 -= dt;
+        if dt.abs() < 1e-7 {
+            break;
+        }
+    }
+    Ok((tt1, tt2))
+}
@@ -0,0 +1,48 @@
+///  Heliographic Stonyhurst to helioprojective coordinates
+///
+///  Given:
+///  ```text
+///     lon    f64     Stonyhurst longitude (radians, Note 1)
+///     lat    f64     heliographic latitude (radians)
+///     r      f64     distance from the center of the Sun (Note 2)
+///     b0     f64     heliographic latitude of the observer (radians)
+///     dsun   f64     distance of the observer from the Sun (Note 2)
+///  ```
+///  Returned (function value):
+///  ```text
+///            (f64, f64, f64)
+///            tx   f64   helioprojective longitude (radians, Note 3)
+///            ty   f64   helioprojective latitude (radians)
+///            d    f64   distance from the observer (Note 2)
+///  ```
+///  Notes:
+///
+///  1) Stonyhurst longitude is measured from the central meridian
+///     seen by the observer, positive toward the west limb.  Adding
+///     the observer's L0 (see sunpbl) gives the Carrington longitude.
+///
+///  2) r, dsun and d are in the same units, which can be any.
+///
+///  3) Helioprojective coordinates are angles from the center of the
+///     disk:  tx toward the west limb and ty toward the projected
+///     north pole of the Sun.  To refer them to celestial north,
+///     rotate them through the position angle P (see sunpbl).
+///
+///  4) A point on the surface of the sphere is visible when cos(c) >
+///     r/dsun, c being its angular distance from the sub-observer
+///     point:  cos(c) = sin(lat)sin(b0) + cos(lat)cos(lon)cos(b0).
+///
+///  Reference:
+///
+///     Thompson, W.T., 2006, Astron.Astrophys. 449, 791.
+pub fn hgs2hpc(lon: f64, lat: f64, r: f64, b0: f64, dsun: f64) -> (f64, f64, f64) {
+    /* Heliocentric cartesian:  z toward the observer, y north. */
+    let x = r * lat.cos() * lon.sin();
+    let y = r * (lat.sin() * b0.cos() - lat.cos() * lon.cos() * b0.sin());
+    let z = r * (lat.sin() * b0.sin() + lat.cos() * lon.cos() * b0.cos());
+
+    /* Seen from the observer. */
+    let zo = dsun - z;
+    let d = (x * x + y * y + zo * zo).sqrt();
+    (x.atan2(zo), (y / d).asin(), d)
+}
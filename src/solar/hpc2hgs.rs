@@ -0,0 +1,48 @@
+///  Helioprojective to heliographic Stonyhurst coordinates
+///
+///  Given:
+///  ```text
+///     tx     f64     helioprojective longitude (radians, Note 1)
+///     ty     f64     helioprojective latitude (radians)
+///     b0     f64     heliographic latitude of the observer (radians)
+///     dsun   f64     distance of the observer from the Sun (Note 2)
+///     r      f64     radius of the sphere (Note 2)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok((lon, lat, d))
+///            lon  f64   Stonyhurst longitude (radians, -pi to +pi)
+///            lat  f64   heliographic latitude (radians)
+///            d    f64   distance from the observer (Note 2)
+///     Err(j) i32        -1 = the line of sight misses the sphere
+///  ```
+///  Notes:
+///
+///  1) The coordinates are as for hgs2hpc.
+///
+///  2) dsun, r and d are in the same units, which can be any.  The
+///     result is the point where the line of sight first meets the
+///     sphere of radius r centered on the Sun:  for the photosphere,
+///     r is the radius of the Sun.
+///
+///  Reference:
+///
+///     Thompson, W.T., 2006, Astron.Astrophys. 449, 791.
+pub fn hpc2hgs(tx: f64, ty: f64, b0: f64, dsun: f64, r: f64) -> Result<(f64, f64, f64), i32> {
+    /* Distance to the sphere along the line of sight. */
+    let q = ty.cos() * tx.cos();
+    let disc = dsun * dsun * (q * q - 1.0) + r * r;
+    if disc < 0.0 {
+        return Err(-1);
+    }
+    let d = dsun * q - disc.sqrt();
+
+    /* Heliocentric cartesian:  z toward the observer, y north. */
+    let x = d * ty.cos() * tx.sin();
+    let y = d * ty.sin();
+    let z = dsun - d * q;
+
+    let lat = ((y * b0.cos() + z * b0.sin()) / r).asin();
+    let lon = x.atan2(z * b0.cos() - y * b0.sin());
+    Ok((lon, lat, d))
+}
@@ -0,0 +1,16 @@
+//! Solar coordinates (P, B0, L0, Carrington rotations and heliographic coordinates)
+
+mod carrington;
+pub use carrington::*;
+
+mod carrstart;
+pub use carrstart::*;
+
+mod hgs2hpc;
+pub use hgs2hpc::*;
+
+mod hpc2hgs;
+pub use hpc2hgs::*;
+
+mod sunpbl;
+pub use sunpbl::*;
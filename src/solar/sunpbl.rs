@@ -0,0 +1,54 @@
+use crate::eph::{Ephemeris, Target};
+use crate::physical::physeph;
+
+///  Position angle of the Sun's axis, and heliographic latitude and
+///  longitude of the center of the disk
+///
+///  Given:
+///  ```text
+///     eph    Ephemeris  source of the Earth and Sun (Note 1)
+///     tt1    f64        TT as a 2-part...
+///     tt2    f64        ...Julian Date
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok((p, b0, l0, dist))
+///            p     f64   position angle of the north pole of the
+///                        Sun's axis (radians, 0-2pi, Note 2)
+///            b0    f64   heliographic latitude of the center of the
+///                        disk (radians)
+///            l0    f64   Carrington longitude of the center of the
+///                        disk (radians, 0-2pi, Note 3)
+///            dist  f64   distance of the Sun (au)
+///     Err(j) i32         status from eph
+///  ```
+///  Notes:
+///
+///  1) The Sun is seen from the geocenter, corrected for light time;
+///     aberration is neglected and TT is used in place of TDB.  See
+///     physeph.
+///
+///  2) The position angle is measured from the north point of the
+///     disk towards the east, with respect to the true equator of
+///     date.  It is within 26.3 deg of zero, east in the second half
+///     of the year.
+///
+///  3) The Carrington longitude is measured eastward from the prime
+///     meridian of the IAU WGCCRE rotation model of the Sun, which is
+///     Carrington's, rotating with the sidereal period 25.38 days.
+///     It decreases with time, from 360 deg at the start of each
+///     Carrington rotation (see carrington).
+///
+///  Called:
+///  ```text
+///     physeph     physical ephemeris of a body
+///  ```
+///
+///  Reference:
+///
+///     Meeus, J., Astronomical Algorithms, 2nd edition,
+///     Willmann-Bell (1998), Chapter 29.
+pub fn sunpbl<E: Ephemeris>(eph: &E, tt1: f64, tt2: f64) -> Result<(f64, f64, f64, f64), i32> {
+    let e = physeph(eph, Target::Sun, tt1, tt2)?;
+    Ok((e.p, e.lat, e.lon, e.dist))
+}
@@ -0,0 +1,65 @@
+use super::dop3w;
+
+///  Two-way Doppler
+///
+///  Doppler observable and range rate for a signal transmitted from a
+///  tracking station, coherently retransmitted by a spacecraft and
+///  received back at the same station, averaged over a count interval.
+///
+///  Given:
+///  ```text
+///     utc1   f64     UTC at the middle of the count interval...
+///     utc2   f64     ...as a 2-part quasi Julian Date
+///     tc     f64     count interval (seconds)
+///     dut1   f64     UT1-UTC (seconds)
+///     elong  f64     station longitude (radians, east +ve)
+///     phi    f64     station latitude (geodetic, radians)
+///     hm     f64     station height above ellipsoid (m, geodetic)
+///     xp,yp  f64     polar motion coordinates (radians)
+///     ft     f64     transmitted frequency (Hz)
+///     m2     f64     spacecraft transponder turnaround ratio
+///     sc     Fn      spacecraft state, given TDB (Note 1)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok((fd, rr))
+///            fd     f64     Doppler shift (Hz, Note 2)
+///            rr     f64     mean range rate (m/s, +ve receding)
+///     Err(j) j      i32     -1 = unacceptable date, or outside the
+///                                range of iauEpv00
+///                           -2 = illegal station coordinates
+///  ```
+///  Notes:
+///
+///  1) sc(d1,d2) returns the barycentric position and velocity of the
+///     spacecraft (au, au/d, BCRS) at the TDB Julian Date d1+d2.
+///
+///  2) See dop3w for the sign conventions and for details of how
+///     the observable is formed.
+///
+///  Called:
+///  ```text
+///     dop3w        three-way Doppler
+///  ```
+#[allow(clippy::too_many_arguments)]
+pub fn dop2w<F>(
+    utc1: f64,
+    utc2: f64,
+    tc: f64,
+    dut1: f64,
+    elong: f64,
+    phi: f64,
+    hm: f64,
+    xp: f64,
+    yp: f64,
+    ft: f64,
+    m2: f64,
+    sc: F,
+) -> Result<(f64, f64), i32>
+where
+    F: Fn(f64, f64) -> [[f64; 3]; 2],
+{
+    dop3w(
+        utc1, utc2, tc, dut1, elong, phi, hm, elong, phi, hm, xp, yp, ft, m2, sc,
+    )
+}
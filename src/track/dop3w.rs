@@ -0,0 +1,126 @@
+use crate::consts::{CMPS, DAYSEC};
+
+use super::lt3w;
+
+///  Three-way Doppler
+///
+///  Doppler observable and range rate for a signal transmitted from one
+///  tracking station, coherently retransmitted by a spacecraft and
+///  received at a second station, averaged over a count interval.
+///
+///  Given:
+///  ```text
+///     utc1    f64     UTC at the middle of the count interval...
+///     utc2    f64     ...as a 2-part quasi Julian Date
+///     tc      f64     count interval (seconds, Note 1)
+///     dut1    f64     UT1-UTC (seconds)
+///     elongr  f64     receiving station longitude (radians, east +ve)
+///     phir    f64     receiving station latitude (geodetic, radians)
+///     hmr     f64     receiving station height (m, geodetic)
+///     elongt  f64     transmitting station longitude (radians, east +ve)
+///     phit    f64     transmitting station latitude (geodetic, radians)
+///     hmt     f64     transmitting station height (m, geodetic)
+///     xp,yp   f64     polar motion coordinates (radians)
+///     ft      f64     transmitted frequency (Hz, Note 2)
+///     m2      f64     spacecraft transponder turnaround ratio
+///     sc      Fn      spacecraft state, given TDB (Note 3)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok((fd, rr))
+///            fd     f64     Doppler shift (Hz, Note 4)
+///            rr     f64     mean range rate (m/s, Note 5)
+///     Err(j) j      i32     -1 = unacceptable date, or outside the
+///                                range of iauEpv00
+///                           -2 = illegal station coordinates
+///  ```
+///  Notes:
+///
+///  1) The observable is formed, as for the DSN, from the difference of
+///     the round-trip light times at the end and the start of the count
+///     interval, the interval being centred on utc1+utc2.  Noise falls
+///     as the interval is lengthened but so does the fidelity with
+///     which rapid accelerations are represented.
+///
+///  2) The transmitted frequency is assumed constant (unramped) over
+///     the count interval.
+///
+///  3) sc(d1,d2) returns the barycentric position and velocity of the
+///     spacecraft (au, au/d, BCRS) at the TDB Julian Date d1+d2.
+///
+///  4) The Doppler shift is the received frequency minus m2 times the
+///     transmitted frequency, and so is negative when the round-trip
+///     light time is increasing (spacecraft receding).
+///
+///  5) The range rate is c/2 times the rate of change of round-trip
+///     light time and is positive when receding.
+///
+///  Reference:
+///
+///     Moyer, T.D., Formulation for Observed and Computed Values of
+///     Deep Space Network Data Types for Navigation, JPL Publication
+///     00-7 (2000), Section 13.
+///
+///  Called:
+///  ```text
+///     lt3w         round-trip light time, separate stations
+///  ```
+#[allow(clippy::too_many_arguments)]
+pub fn dop3w<F>(
+    utc1: f64,
+    utc2: f64,
+    tc: f64,
+    dut1: f64,
+    elongr: f64,
+    phir: f64,
+    hmr: f64,
+    elongt: f64,
+    phit: f64,
+    hmt: f64,
+    xp: f64,
+    yp: f64,
+    ft: f64,
+    m2: f64,
+    sc: F,
+) -> Result<(f64, f64), i32>
+where
+    F: Fn(f64, f64) -> [[f64; 3]; 2],
+{
+    /* Half the count interval (days). */
+    let dt = 0.5 * tc / DAYSEC;
+
+    /* Round-trip light times at the start and end of the interval. */
+    let (_, _, _, _, rs) = lt3w(
+        utc1,
+        utc2 - dt,
+        dut1,
+        elongr,
+        phir,
+        hmr,
+        elongt,
+        phit,
+        hmt,
+        xp,
+        yp,
+        &sc,
+    )?;
+    let (_, _, _, _, re) = lt3w(
+        utc1,
+        utc2 + dt,
+        dut1,
+        elongr,
+        phir,
+        hmr,
+        elongt,
+        phit,
+        hmt,
+        xp,
+        yp,
+        &sc,
+    )?;
+
+    /* Rate of change of the round-trip light time. */
+    let rdot = (re - rs) / tc;
+
+    Ok((-m2 * ft * rdot, 0.5 * CMPS * rdot))
+}
@@ -0,0 +1,105 @@
+use crate::consts::{DAYSEC, DC};
+use crate::eph::epv00;
+use crate::vm::{pm, pmp};
+
+use super::{shapiro, stapvb};
+
+///  One-way light time
+///
+///  One-way (downlink) light time from a spacecraft to a receiving
+///  tracking station.  The caller supplies the UTC of reception, the
+///  station coordinates and a function giving the spacecraft state.
+///
+///  Given:
+///  ```text
+///     utc1   f64     UTC of reception as a 2-part...
+///     utc2   f64     ...quasi Julian Date
+///     dut1   f64     UT1-UTC (seconds)
+///     elong  f64     station longitude (radians, east +ve)
+///     phi    f64     station latitude (geodetic, radians)
+///     hm     f64     station height above ellipsoid (m, geodetic)
+///     xp,yp  f64     polar motion coordinates (radians)
+///     sc     Fn      spacecraft state, given TDB (Note 1)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok((tdb1, tdb2, lt, pv))
+///            tdb1   f64             TDB of transmission as a 2-part...
+///            tdb2   f64             ...Julian Date
+///            lt     f64             light time (TDB seconds, Note 2)
+///            pv     [[f64; 3]; 2]   spacecraft p,v at transmission
+///                                   (BCRS, au, au/d)
+///     Err(j) j      i32             -1 = unacceptable date, or
+///                                        outside the range of iauEpv00
+///                                   -2 = illegal station coordinates
+///  ```
+///  Notes:
+///
+///  1) sc(d1,d2) returns the barycentric position and velocity of the
+///     spacecraft (au, au/d, BCRS) at the TDB Julian Date d1+d2.
+///
+///  2) The light time is the difference between the TDB of reception
+///     at the station and the TDB of transmission at the spacecraft.  It
+///     includes the Shapiro delay due to the Sun.  Tropospheric and
+///     ionospheric delays are not included.
+///
+///  3) The light-time equation is solved by fixed-point iteration,
+///     which converges to 1 ps in a handful of iterations for any
+///     solar-system spacecraft.
+///
+///  Called:
+///  ```text
+///     stapvb       station barycentric position and velocity
+///     iauEpv00     Earth position and velocity
+///     shapiro      Shapiro delay
+///  ```
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn lt1w<F>(
+    utc1: f64,
+    utc2: f64,
+    dut1: f64,
+    elong: f64,
+    phi: f64,
+    hm: f64,
+    xp: f64,
+    yp: f64,
+    sc: F,
+) -> Result<(f64, f64, f64, [[f64; 3]; 2]), i32>
+where
+    F: Fn(f64, f64) -> [[f64; 3]; 2],
+{
+    /* Receiving station at the epoch of reception. */
+    let (tdb1, tdb2, pvr) = stapvb(utc1, utc2, dut1, elong, phi, hm, xp, yp)?;
+
+    /* Solve the light-time equation. */
+    let (lt, pv) = ltrcv(tdb1, tdb2, &pvr[0], &sc)?;
+
+    Ok((tdb1, tdb2 - lt, lt * DAYSEC, pv))
+}
+
+/* Light time (days) to a receiver at position p at TDB d1+d2. */
+pub(crate) fn ltrcv<F>(d1: f64, d2: f64, p: &[f64; 3], sc: &F) -> Result<(f64, [[f64; 3]; 2]), i32>
+where
+    F: Fn(f64, f64) -> [[f64; 3]; 2],
+{
+    /* Maximum number of iterations and convergence criterion (days). */
+    const NITER: usize = 10;
+    const TOL: f64 = 1e-17;
+
+    /* Sun barycentric position. */
+    let (ehpv, ebpv) = epv00(d1, d2).ok_or(-1)?;
+    let ps = pmp(&ebpv[0], &ehpv[0]);
+
+    let mut lt = 0.0;
+    let mut pv = sc(d1, d2);
+    for _ in 0..NITER {
+        pv = sc(d1, d2 - lt);
+        let ltn = pm(pmp(&pv[0], p)) / DC + shapiro(&pv[0], p, &ps);
+        let dlt = ltn - lt;
+        lt = ltn;
+        if dlt.abs() < TOL {
+            break;
+        }
+    }
+    Ok((lt, pv))
+}
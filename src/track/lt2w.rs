@@ -0,0 +1,59 @@
+use super::lt3w;
+
+///  Round-trip light time
+///
+///  Round-trip light time for a signal transmitted from a tracking
+///  station, retransmitted by a spacecraft and received back at the
+///  same station (the "two-way" configuration).
+///
+///  Given:
+///  ```text
+///     utc1   f64     UTC of reception as a 2-part...
+///     utc2   f64     ...quasi Julian Date
+///     dut1   f64     UT1-UTC (seconds)
+///     elong  f64     station longitude (radians, east +ve)
+///     phi    f64     station latitude (geodetic, radians)
+///     hm     f64     station height above ellipsoid (m, geodetic)
+///     xp,yp  f64     polar motion coordinates (radians)
+///     sc     Fn      spacecraft state, given TDB (Note 1)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok((utc1t, utc2t, tdb1s, tdb2s, rtlt))
+///            utc1t  f64     UTC of transmission as a 2-part...
+///            utc2t  f64     ...quasi Julian Date
+///            tdb1s  f64     TDB of retransmission at the spacecraft
+///            tdb2s  f64     ...as a 2-part Julian Date
+///            rtlt   f64     round-trip light time (TDB seconds)
+///     Err(j) j      i32     -1 = unacceptable date, or outside the
+///                                range of iauEpv00
+///                           -2 = illegal station coordinates
+///  ```
+///  Notes:
+///
+///  1) sc(d1,d2) returns the barycentric position and velocity of the
+///     spacecraft (au, au/d, BCRS) at the TDB Julian Date d1+d2.
+///
+///  2) See lt3w for details of the light-time solution.
+///
+///  Called:
+///  ```text
+///     lt3w         round-trip light time, separate stations
+///  ```
+#[allow(clippy::too_many_arguments)]
+pub fn lt2w<F>(
+    utc1: f64,
+    utc2: f64,
+    dut1: f64,
+    elong: f64,
+    phi: f64,
+    hm: f64,
+    xp: f64,
+    yp: f64,
+    sc: F,
+) -> Result<(f64, f64, f64, f64, f64), i32>
+where
+    F: Fn(f64, f64) -> [[f64; 3]; 2],
+{
+    lt3w(utc1, utc2, dut1, elong, phi, hm, elong, phi, hm, xp, yp, sc)
+}
@@ -0,0 +1,113 @@
+use crate::consts::{DAYSEC, DC};
+use crate::eph::epv00;
+use crate::vm::{pm, pmp};
+
+use super::{lt1w::ltrcv, shapiro, stapvb};
+
+///  Round-trip light time, separate stations
+///
+///  Round-trip light time for a signal transmitted from one tracking
+///  station, retransmitted by a spacecraft and received at a second
+///  station (the "three-way" configuration).
+///
+///  Given:
+///  ```text
+///     utc1    f64     UTC of reception as a 2-part...
+///     utc2    f64     ...quasi Julian Date
+///     dut1    f64     UT1-UTC (seconds)
+///     elongr  f64     receiving station longitude (radians, east +ve)
+///     phir    f64     receiving station latitude (geodetic, radians)
+///     hmr     f64     receiving station height (m, geodetic)
+///     elongt  f64     transmitting station longitude (radians, east +ve)
+///     phit    f64     transmitting station latitude (geodetic, radians)
+///     hmt     f64     transmitting station height (m, geodetic)
+///     xp,yp   f64     polar motion coordinates (radians)
+///     sc      Fn      spacecraft state, given TDB (Note 1)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok((utc1t, utc2t, tdb1s, tdb2s, rtlt))
+///            utc1t  f64     UTC of transmission as a 2-part...
+///            utc2t  f64     ...quasi Julian Date
+///            tdb1s  f64     TDB of retransmission at the spacecraft
+///            tdb2s  f64     ...as a 2-part Julian Date
+///            rtlt   f64     round-trip light time (TDB seconds, Note 2)
+///     Err(j) j      i32     -1 = unacceptable date, or outside the
+///                                range of iauEpv00
+///                           -2 = illegal station coordinates
+///  ```
+///  Notes:
+///
+///  1) sc(d1,d2) returns the barycentric position and velocity of the
+///     spacecraft (au, au/d, BCRS) at the TDB Julian Date d1+d2.
+///
+///  2) The round-trip light time is the TDB of reception minus the TDB
+///     of transmission, and is the sum of the downlink and uplink light
+///     times, each including the Shapiro delay.  The spacecraft
+///     transponder delay is not included.  Multiplying by c/2 gives
+///     the conventional range observable.
+///
+///  3) The downlink leg is solved first, as in lt1w.  The uplink leg
+///     is then solved by iterating on the UTC of transmission, so that
+///     the motion of the transmitting station is rigorously included.
+///
+///  4) For two-way tracking, where the same station transmits and
+///     receives, use lt2w.
+///
+///  Called:
+///  ```text
+///     stapvb       station barycentric position and velocity
+///     iauEpv00     Earth position and velocity
+///     shapiro      Shapiro delay
+///  ```
+#[allow(clippy::too_many_arguments)]
+pub fn lt3w<F>(
+    utc1: f64,
+    utc2: f64,
+    dut1: f64,
+    elongr: f64,
+    phir: f64,
+    hmr: f64,
+    elongt: f64,
+    phit: f64,
+    hmt: f64,
+    xp: f64,
+    yp: f64,
+    sc: F,
+) -> Result<(f64, f64, f64, f64, f64), i32>
+where
+    F: Fn(f64, f64) -> [[f64; 3]; 2],
+{
+    /* Maximum number of iterations and convergence criterion (days). */
+    const NITER: usize = 10;
+    const TOL: f64 = 1e-17;
+
+    /* Downlink: receiving station to spacecraft. */
+    let (tdb1r, tdb2r, pvr) = stapvb(utc1, utc2, dut1, elongr, phir, hmr, xp, yp)?;
+    let (ltd, pvs) = ltrcv(tdb1r, tdb2r, &pvr[0], &sc)?;
+    let tdb1s = tdb1r;
+    let tdb2s = tdb2r - ltd;
+
+    /* Sun barycentric position. */
+    let (ehpv, ebpv) = epv00(tdb1s, tdb2s).ok_or(-1)?;
+    let ps = pmp(&ebpv[0], &ehpv[0]);
+
+    /* Uplink: iterate on the UTC of transmission. */
+    let utc1t = utc1;
+    let mut utc2t = utc2 - 2.0 * ltd;
+    let mut rtlt = 2.0 * ltd;
+    for _ in 0..NITER {
+        let (tdb1t, tdb2t, pvt) = stapvb(utc1t, utc2t, dut1, elongt, phit, hmt, xp, yp)?;
+        let ltu = pm(pmp(&pvs[0], &pvt[0])) / DC + shapiro(&pvs[0], &pvt[0], &ps);
+
+        /* Mismatch between transmission + light time and retransmission. */
+        let d = ((tdb1t - tdb1s) + (tdb2t - tdb2s)) + ltu;
+        utc2t -= d;
+        rtlt = ltd + ltu;
+        if d.abs() < TOL {
+            break;
+        }
+    }
+
+    Ok((utc1t, utc2t, tdb1s, tdb2s, rtlt * DAYSEC))
+}
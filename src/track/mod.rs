@@ -0,0 +1,22 @@
+//! Spacecraft tracking (light time, range and Doppler)
+
+mod dop2w;
+pub use dop2w::*;
+
+mod dop3w;
+pub use dop3w::*;
+
+mod lt1w;
+pub use lt1w::*;
+
+mod lt2w;
+pub use lt2w::*;
+
+mod lt3w;
+pub use lt3w::*;
+
+mod shapiro;
+pub use shapiro::*;
+
+mod stapvb;
+pub use stapvb::*;
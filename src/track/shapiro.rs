@@ -0,0 +1,58 @@
+use crate::consts::{DC, SRS};
+use crate::vm::{pm, pmp};
+
+///  Shapiro delay
+///
+///  Relativistic light-time delay due to the Sun's gravitational field
+///  for a signal travelling between two points.
+///
+///  Given:
+///  ```text
+///     p1     [f64; 3]   position of the first point (BCRS, au)
+///     p2     [f64; 3]   position of the second point (BCRS, au)
+///     ps     [f64; 3]   position of the Sun (BCRS, au)
+///  ```
+///  Returned (function value):
+///  ```text
+///            f64        light-time delay (days, Note 1)
+///  ```
+///  Notes:
+///
+///  1) The delay is computed for PPN gamma = 1 from the expression
+///
+///        dt = ( 2 GM / c^3 ) * ln ( ( r1 + r2 + r12 ) / ( r1 + r2 - r12 ) )
+///
+///     where r1 and r2 are the heliocentric distances of the two points
+///     and r12 their separation.  Only the Sun is considered;  the
+///     contributions of the planets are below 1 ns except for grazing
+///     paths.
+///
+///  2) If the path passes through the Sun the argument of the logarithm
+///     diverges and zero is returned.
+///
+///  Reference:
+///
+///     Moyer, T.D., Formulation for Observed and Computed Values of
+///     Deep Space Network Data Types for Navigation, JPL Publication
+///     00-7 (2000), Section 8.3.
+///
+///  Called:
+///  ```text
+///     iauPmp       p-vector minus p-vector
+///     iauPm        modulus of p-vector
+///  ```
+pub fn shapiro(p1: &[f64; 3], p2: &[f64; 3], ps: &[f64; 3]) -> f64 {
+    /* Heliocentric distances and separation (au). */
+    let r1 = pm(pmp(p1, ps));
+    let r2 = pm(pmp(p2, ps));
+    let r12 = pm(pmp(p1, p2));
+
+    /* Guard against a path through the Sun. */
+    let den = r1 + r2 - r12;
+    if den <= 0.0 {
+        return 0.0;
+    }
+
+    /* 2GM/c^3 is the Schwarzschild radius divided by c (days). */
+    SRS / DC * ((r1 + r2 + r12) / den).ln()
+}
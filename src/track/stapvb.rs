@@ -0,0 +1,110 @@
+use crate::astro::pvtob;
+use crate::consts::{DAU, DAYSEC};
+use crate::coords::gd2gc;
+use crate::eph::epv00;
+use crate::pnp::c2t06a;
+use crate::ts::{dtdb, taitt, tttdb, utctai, utcut1};
+use crate::vm::{ppp, sxp, trxpv};
+
+///  Station barycentric position and velocity
+///
+///  Barycentric position and velocity of a terrestrial tracking station
+///  at a given UTC, together with the corresponding TDB.
+///
+///  Given:
+///  ```text
+///     utc1   f64     UTC as a 2-part...
+///     utc2   f64     ...quasi Julian Date (Note 1)
+///     dut1   f64     UT1-UTC (seconds)
+///     elong  f64     longitude (radians, east +ve, Note 2)
+///     phi    f64     latitude (geodetic, radians, Note 2)
+///     hm     f64     height above ellipsoid (m, geodetic)
+///     xp,yp  f64     polar motion coordinates (radians)
+///  ```
+///  Returned (function value):
+///  ```text
+///     Ok((tdb1, tdb2, pv))
+///            tdb1   f64             TDB as a 2-part...
+///            tdb2   f64             ...Julian Date (Note 3)
+///            pv     [[f64; 3]; 2]   station p,v (BCRS, au, au/d, Note 4)
+///     Err(j) j      i32             -1 = unacceptable date, or
+///                                        outside the range of iauEpv00
+///                                   -2 = illegal geodetic coordinates
+///  ```
+///  Notes:
+///
+///  1) utc1+utc2 is a quasi Julian Date as used by iauUtctai.  Leap
+///     seconds are handled in the same way as in that function.
+///
+///  2) The geographical coordinates are with respect to the WGS84
+///     reference ellipsoid.
+///
+///  3) TDB-TT is evaluated with iauDtdb using the topocentric terms for
+///     the station, so the returned TDB is the coordinate time of the
+///     station event.  The two parts are apportioned as in iauTttdb.
+///
+///  4) The station GCRS vector is obtained by rotating the ITRS vector
+///     from iauPvtob with the IAU 2006/2000A celestial-to-terrestrial
+///     matrix, and is added to the SOFA Earth ephemeris iauEpv00.  The
+///     small Lorentz and TCG/TCB scaling differences between GCRS and
+///     BCRS distances (about 2cm for a station) are neglected.
+///
+///  Called:
+///  ```text
+///     iauUtctai    UTC to TAI
+///     iauTaitt     TAI to TT
+///     iauUtcut1    UTC to UT1
+///     iauGd2gc     geodetic to geocentric transformation
+///     iauDtdb      TDB-TT
+///     iauTttdb     TT to TDB
+///     iauEpv00     Earth position and velocity
+///     iauC2t06a    celestial-to-terrestrial matrix, IAU 2006/2000A
+///     iauPvtob     observatory position and velocity
+///     iauTrxpv     product of transpose of r-matrix and pv-vector
+///  ```
+#[allow(clippy::too_many_arguments)]
+pub fn stapvb(
+    utc1: f64,
+    utc2: f64,
+    dut1: f64,
+    elong: f64,
+    phi: f64,
+    hm: f64,
+    xp: f64,
+    yp: f64,
+) -> Result<(f64, f64, [[f64; 3]; 2]), i32> {
+    /* UTC to other time scales. */
+    let (tai1, tai2) = utctai(utc1, utc2)?;
+    let (tt1, tt2) = taitt(tai1, tai2)?;
+    let (ut11, ut12) = utcut1(utc1, utc2, dut1)?;
+
+    /* Station distances from the spin axis and the equator (km). */
+    let xyz = gd2gc(1, elong, phi, hm)?;
+    let u = xyz[0].hypot(xyz[1]) / 1e3;
+    let v = xyz[2] / 1e3;
+
+    /* TDB, including the topocentric terms. */
+    let ut = (ut11.rem_euclid(1.0) + ut12.rem_euclid(1.0) + 0.5).rem_euclid(1.0);
+    let dtr = dtdb(tt1, tt2, ut, elong, u, v);
+    let (tdb1, tdb2) = tttdb(tt1, tt2, dtr)?;
+
+    /* Earth barycentric position and velocity (au, au/d). */
+    let (_, ebpv) = epv00(tdb1, tdb2).ok_or(-1)?;
+
+    /* Station ITRS position and velocity (m, m/s). */
+    let mut pvt = [[0.0; 3]; 2];
+    pvtob(elong, phi, hm, 0.0, 0.0, 0.0, 0.0, &mut pvt);
+
+    /* Rotate into the GCRS. */
+    let rc2t = c2t06a(tt1, tt2, ut11, ut12, xp, yp);
+    let mut pvg = [[0.0; 3]; 2];
+    trxpv(&rc2t, &pvt, &mut pvg);
+
+    /* Barycentric station (au, au/d). */
+    let pv = [
+        ppp(&ebpv[0], &sxp(1.0 / DAU, &pvg[0])),
+        ppp(&ebpv[1], &sxp(DAYSEC / DAU, &pvg[1])),
+    ];
+
+    Ok((tdb1, tdb2, pv))
+}
@@ -6,7 +6,7 @@ use std::ops::Rem;
 pub fn anpm(a: f64) -> f64 {
     let mut w = a.rem(D2PI);
     if w.abs() >= DPI {
-        w -= D2PI;
+        w -= D2PI.copysign(a);
     }
     w
 }
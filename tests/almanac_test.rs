@@ -0,0 +1,460 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::common::{viv, vvd};
+    use sofars::almanac::*;
+    use sofars::astro::atco13b;
+    use sofars::consts::{DAS2R, DAU, DD2R, DS2R};
+    use sofars::eph::{Ephemeris, Epv00, Moon98, Plan94, Target};
+    use sofars::site::{Eop, Observer, Weather};
+
+    fn greenwich() -> Observer {
+        Observer::new(0.0, 51.4769 * DD2R, 46.0)
+    }
+
+    /* iauEpv00 with the Earth displaced by 0.01 au. */
+    struct Shifted;
+
+    impl Ephemeris for Shifted {
+        fn pvb(&self, target: Target, date1: f64, date2: f64) -> Result<[[f64; 3]; 2], i32> {
+            let mut pv = Epv00.pvb(target, date1, date2)?;
+            if target == Target::Earth {
+                pv[0][2] += 0.01;
+            }
+            Ok(pv)
+        }
+    }
+
+    #[test]
+    fn test_topo() {
+        let obs = Observer::new(-0.527800806, -1.2345856, 2738.0);
+        let eop = Eop::new(-0.1550675, 2.47230737e-7, 1.82640464e-6);
+        let (rc, dc) = (2.71, 0.174);
+        let (pr, pd, px, rv) = (1e-5, 5e-6, 0.1, 55.0);
+        let b = Body::star(rc, dc, pr, pd, px, rv);
+        let t = topo(&Epv00, &b, &obs, &eop, 2456384.5, 0.969254051).unwrap();
+        let (aob, zob, hob, dob, rob, _) = obs
+            .atco13(
+                rc,
+                dc,
+                pr,
+                pd,
+                px,
+                rv,
+                2456384.5,
+                0.969254051,
+                &eop,
+                &Weather::vacuum(),
+            )
+            .unwrap();
+        vvd(t.aob, aob, 1e-12, "topo", "aob");
+        vvd(t.zob, zob, 1e-12, "topo", "zob");
+        vvd(t.hob, hob, 1e-12, "topo", "hob");
+        vvd(t.dob, dob, 1e-12, "topo", "dob");
+        vvd(t.rob, rob, 1e-12, "topo", "rob");
+        vvd(t.dist, 0.0, 0.0, "topo", "dist");
+
+        let t = topo(
+            &Moon98,
+            &Body::Solar(Target::Moon),
+            &obs,
+            &eop,
+            2456384.5,
+            0.969254051,
+        )
+        .unwrap();
+        vvd(t.aob, 2.33874941569783, 1e-12, "topo", "moon aob");
+        vvd(t.zob, 1.4867102591367416, 1e-12, "topo", "moon zob");
+        vvd(t.rob, 4.906067596206584, 1e-12, "topo", "moon rob");
+        vvd(t.dist, 0.0024725053506787996, 1e-15, "topo", "moon dist");
+        vvd(t.sd, 0.0046971968616000654, 1e-15, "topo", "moon sd");
+
+        let t = topo(
+            &Epv00,
+            &Body::Solar(Target::Sun),
+            &obs,
+            &eop,
+            2456384.5,
+            0.969254051,
+        )
+        .unwrap();
+        vvd(t.hob, 2.405960646016927, 1e-12, "topo", "sun hob");
+        vvd(t.dob, 0.09211889776239293, 1e-12, "topo", "sun dob");
+        vvd(t.dist, 0.9998232890565122, 1e-12, "topo", "sun dist");
+        vvd(t.sd, 0.004651305966206632, 1e-15, "topo", "sun sd");
+
+        let t = topo(&Plan94, &Target::Mars.into(), &obs, &eop, 2456384.5, 0.5).unwrap();
+        vvd(
+            t.sd,
+            (3396.19e3 / DAU / t.dist).asin(),
+            1e-15,
+            "topo",
+            "mars sd",
+        );
+        vvd(t.dist, 2.4, 0.1, "topo", "mars dist");
+
+        let b = Body::Solar(Target::Earth);
+        viv(
+            topo(&Plan94, &b, &obs, &eop, 2456384.5, 0.5).unwrap_err(),
+            -2,
+            "topo",
+            "j 1",
+        );
+        let b = Body::Solar(Target::Mars);
+        viv(
+            topo(&Moon98, &b, &obs, &eop, 2456384.5, 0.5).unwrap_err(),
+            -2,
+            "topo",
+            "j 2",
+        );
+
+        /* The observer, as well as the body, comes from eph. */
+        let sun = Body::Solar(Target::Sun);
+        let t0 = topo(&Epv00, &sun, &obs, &eop, 2456384.5, 0.969254051).unwrap();
+        let t = topo(&Shifted, &sun, &obs, &eop, 2456384.5, 0.969254051).unwrap();
+        let b = atco13b(
+            &Shifted,
+            Target::Sun,
+            2456384.5,
+            0.969254051,
+            eop.dut1,
+            obs.elong,
+            obs.phi,
+            obs.hm,
+            eop.xp,
+            eop.yp,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+        )
+        .unwrap();
+        vvd(t.aob, b.aob, 1e-12, "topo", "shifted aob");
+        vvd(t.zob, b.zob, 1e-12, "topo", "shifted zob");
+        vvd(t.hob, b.hob, 1e-12, "topo", "shifted hob");
+        vvd(t.dob, b.dob, 1e-12, "topo", "shifted dob");
+        vvd(t.dist, b.dist, 1e-12, "topo", "shifted dist");
+        assert!((t.dob - t0.dob).abs() > 1e-3);
+    }
+
+    #[test]
+    fn test_horizon() {
+        let hz = Horizon::standard();
+        vvd(
+            hz.h0(960.0 * DAS2R, 0.0),
+            -3000.0 * DAS2R,
+            1e-15,
+            "h0",
+            "standard",
+        );
+        vvd(Horizon::dip(100.0), 5.599e-3, 1e-6, "dip", "100m");
+        let hz = Horizon::new(0.0, 0.0, false, true);
+        vvd(hz.h0(0.01, 0.0), 0.0, 0.0, "h0", "sea level");
+        let hz = Horizon::with_weather(&Weather::new(1010.0, 10.0, 0.5, 0.55));
+        vvd(hz.refr, 34.0 * 60.0 * DAS2R, 1e-15, "with_weather", "refr");
+    }
+
+    #[test]
+    fn test_riset() {
+        let obs = greenwich();
+        let eop = Eop::default();
+        let rs = riset(
+            &Epv00,
+            &Body::Solar(Target::Sun),
+            &obs,
+            &eop,
+            &Horizon::standard(),
+            2451544.5,
+            0.0,
+        )
+        .unwrap();
+        let (r1, r2) = rs.rise.unwrap();
+        let (s1, s2) = rs.set.unwrap();
+        let (t1, t2) = rs.transit.unwrap();
+        let (l1, l2) = rs.lower.unwrap();
+        vvd(r2, 0.33720810107722093, 1e-7, "riset", "rise");
+        vvd(s2, 0.6674806560990367, 1e-7, "riset", "set");
+        vvd(t2, 0.5022823531838471, 1e-7, "riset", "transit");
+        vvd(l2, 0.0021166504704967603, 1e-7, "riset", "lower");
+        vvd(r1, 2451544.5, 0.0, "riset", "r1");
+        vvd(s1 + t1 + l1, 3.0 * 2451544.5, 0.0, "riset", "s1");
+        assert_eq!(rs.state, Diurnal::RisesSets);
+
+        let rs = riset(
+            &Moon98,
+            &Body::Solar(Target::Moon),
+            &obs,
+            &eop,
+            &Horizon::standard(),
+            2451544.5,
+            0.0,
+        )
+        .unwrap();
+        vvd(
+            rs.rise.unwrap().1,
+            0.11132414575078058,
+            1e-7,
+            "riset",
+            "moon rise",
+        );
+        vvd(
+            rs.set.unwrap().1,
+            0.5503512715088803,
+            1e-7,
+            "riset",
+            "moon set",
+        );
+        vvd(
+            rs.transit.unwrap().1,
+            0.33398789054385286,
+            1e-7,
+            "riset",
+            "moon transit",
+        );
+        vvd(
+            rs.lower.unwrap().1,
+            0.8491969645208786,
+            1e-7,
+            "riset",
+            "moon lower",
+        );
+    }
+
+    #[test]
+    fn test_riset_polar() {
+        let eop = Eop::default();
+        let hz = Horizon::standard();
+        let obs = greenwich();
+
+        let b = Body::star(37.95 * DD2R, 89.26 * DD2R, 0.0, 0.0, 0.0, 0.0);
+        let rs = riset(&Epv00, &b, &obs, &eop, &hz, 2460000.5, 0.0).unwrap();
+        assert_eq!(rs.state, Diurnal::Circumpolar);
+        assert!(rs.rise.is_none() && rs.set.is_none());
+        assert!(rs.transit.is_some() && rs.lower.is_some());
+
+        let b = Body::star(0.0, -80.0 * DD2R, 0.0, 0.0, 0.0, 0.0);
+        let rs = riset(&Epv00, &b, &obs, &eop, &hz, 2460000.5, 0.0).unwrap();
+        assert_eq!(rs.state, Diurnal::NeverRises);
+
+        let obs = Observer::new(18.96 * DD2R, 69.65 * DD2R, 0.0);
+        let rs = riset(
+            &Epv00,
+            &Body::Solar(Target::Sun),
+            &obs,
+            &eop,
+            &hz,
+            2460117.5,
+            0.0,
+        )
+        .unwrap();
+        assert_eq!(rs.state, Diurnal::Circumpolar);
+        let rs = riset(
+            &Epv00,
+            &Body::Solar(Target::Sun),
+            &obs,
+            &eop,
+            &hz,
+            2460300.5,
+            0.0,
+        )
+        .unwrap();
+        assert_eq!(rs.state, Diurnal::NeverRises);
+    }
+
+    #[test]
+    fn test_night() {
+        let eop = Eop::default();
+        let n = night(&greenwich(), &eop, 2000, 6, 21, 1.0).unwrap();
+        let t = |e: Option<EventTime>| e.unwrap();
+        let e = t(n.sun.start);
+        vvd(e.utc.0, 2400000.5, 0.0, "night", "utc1");
+        vvd(e.utc.1, 51716.84779326315, 1e-7, "night", "sunset");
+        vvd(e.local.1 - e.utc.1, 1.0 / 24.0, 1e-10, "night", "local");
+        vvd(
+            t(n.sun.end).utc.1,
+            51717.1548897622,
+            1e-7,
+            "night",
+            "sunrise",
+        );
+        vvd(
+            t(n.civil.start).utc.1,
+            51716.880942292344,
+            1e-7,
+            "night",
+            "civil",
+        );
+        vvd(
+            t(n.civil.end).utc.1,
+            51717.12174047852,
+            1e-7,
+            "night",
+            "civil",
+        );
+        vvd(
+            t(n.nautical.start).utc.1,
+            51716.932619065825,
+            1e-7,
+            "night",
+            "naut",
+        );
+        vvd(
+            t(n.nautical.end).utc.1,
+            51717.070063490035,
+            1e-7,
+            "night",
+            "naut",
+        );
+        vvd(n.nautical.length, 3.29866618109556, 1e-5, "night", "naut");
+        vvd(n.sun.length, 7.3703159771936715, 1e-5, "night", "sun");
+        assert!(n.astronomical.start.is_none() && n.astronomical.end.is_none());
+        assert_eq!(n.astronomical.state, Diurnal::Circumpolar);
+        vvd(n.astronomical.length, 0.0, 0.0, "night", "astro");
+
+        let obs = Observer::new(18.96 * DD2R, 69.65 * DD2R, 0.0);
+        let n = night(&obs, &eop, 2000, 12, 21, 1.0).unwrap();
+        assert_eq!(n.sun.state, Diurnal::NeverRises);
+        vvd(n.sun.length, 24.0, 0.0, "night", "polar");
+        vvd(
+            n.civil.length,
+            19.64385932881263,
+            1e-5,
+            "night",
+            "polar civil",
+        );
+        vvd(
+            n.astronomical.length,
+            13.546118737392625,
+            1e-5,
+            "night",
+            "polar astro",
+        );
+
+        viv(
+            night(&obs, &eop, 2000, 13, 1, 0.0).unwrap_err(),
+            -2,
+            "night",
+            "j",
+        );
+    }
+
+    #[test]
+    fn test_moonphases() {
+        let ph = moonphases(2451544.5, 0.0, 31.0).unwrap();
+        viv(ph.len() as i32, 4, "moonphases", "n");
+        assert_eq!(
+            ph.iter().map(|p| p.0).collect::<Vec<Phase>>(),
+            [
+                Phase::New,
+                Phase::FirstQuarter,
+                Phase::Full,
+                Phase::LastQuarter
+            ]
+        );
+        vvd(ph[0].1, 2451544.5, 0.0, "moonphases", "tt1");
+        vvd(ph[0].2, 5.760097907043182, 1e-7, "moonphases", "new");
+        vvd(ph[1].2, 13.566130440836794, 1e-7, "moonphases", "first");
+        vvd(ph[2].2, 20.195555246152818, 1e-7, "moonphases", "full");
+        vvd(ph[3].2, 27.331713727135003, 1e-7, "moonphases", "last");
+
+        /* Full moon of 2000 January 21, 04:40 UT. */
+        vvd(
+            ph[2].2,
+            20.0 + (4.0 + 40.0 / 60.0 + 64.2 / 3600.0) / 24.0,
+            1e-3,
+            "moonphases",
+            "full",
+        );
+
+        viv(
+            moonphases(2451544.5, 5.0, 5.0).unwrap().len() as i32,
+            1,
+            "moonphases",
+            "n",
+        );
+    }
+
+    #[test]
+    fn test_moonillum() {
+        let m = moonillum(2451549.5, 0.0).unwrap();
+        vvd(m.elong, 0.1499078266651161, 1e-12, "moonillum", "elong");
+        vvd(m.phase, 2.9912707999699233, 1e-12, "moonillum", "phase");
+        vvd(m.k, 0.005638535240142906, 1e-12, "moonillum", "k");
+        vvd(m.chi, 1.801297118106324, 1e-12, "moonillum", "chi");
+
+        let m = moonillum(2451557.5, 0.0).unwrap();
+        vvd(m.elong, 1.4478851147211769, 1e-12, "moonillum", "elong");
+        vvd(m.phase, 1.6912274323337155, 1e-12, "moonillum", "phase");
+        vvd(m.k, 0.439929899273405, 1e-12, "moonillum", "k");
+        vvd(m.chi, 4.330866399433264, 1e-12, "moonillum", "chi");
+    }
+
+    #[test]
+    fn test_sun() {
+        /* Meeus, Astronomical Algorithms, Example 25.b. */
+        let (ra, dec, dist) = sunapp(2448908.5, 0.0).unwrap();
+        vvd(ra, 198.378112 * DD2R, 1e-6, "sunapp", "ra");
+        vvd(
+            dec,
+            -(7.0 + 47.0 / 60.0 + 1.74 / 3600.0) * DD2R,
+            1e-6,
+            "sunapp",
+            "dec",
+        );
+        vvd(dist, 0.99760775, 1e-5, "sunapp", "dist");
+        vvd(ra, 3.462351634025114, 1e-12, "sunapp", "ra");
+        vvd(dec, -0.13585313534829346, 1e-12, "sunapp", "dec");
+        vvd(dist, 0.9976085436921278, 1e-12, "sunapp", "dist");
+
+        let obs = greenwich();
+        let eop = Eop::default();
+        let wx = Weather::new(1000.0, 10.0, 0.5, 0.55);
+        let p = sunpos(&obs, &eop, &wx, 2451544.5, 0.5).unwrap();
+        vvd(p.ra, 4.909233988176339, 1e-12, "sunpos", "ra");
+        vvd(p.dec, -0.4019917329028203, 1e-12, "sunpos", "dec");
+        vvd(p.dist, 0.9833276195910239, 1e-12, "sunpos", "dist");
+        vvd(p.tra, 4.909235462996227, 1e-12, "sunpos", "tra");
+        vvd(p.tdec, -0.402033386600102, 1e-12, "sunpos", "tdec");
+        vvd(p.tdist, 0.9833161245739777, 1e-12, "sunpos", "tdist");
+        vvd(p.ha, -0.01433613980087476, 1e-12, "sunpos", "ha");
+        vvd(p.az, 3.1279026867883326, 1e-12, "sunpos", "az");
+        vvd(p.el, 0.2712473294454163, 1e-12, "sunpos", "el");
+    }
+
+    #[test]
+    fn test_solartime() {
+        let obs = greenwich();
+        let eop = Eop::default();
+
+        /* Extremes of the equation of time, 2000 February 12 and November 3. */
+        let e = eqtime(&eop, 2451585.5, 0.0).unwrap() / DS2R;
+        vvd(e, -14.236854316650689 * 60.0, 1e-6, "eqtime", "feb");
+        let e = eqtime(&eop, 2451851.5, 0.0).unwrap() / DS2R;
+        vvd(e, 16.431076597213544 * 60.0, 1e-6, "eqtime", "nov");
+
+        let (last, lmst) = solartime(&obs, &eop, 2451544.5, 0.5).unwrap();
+        vvd(last, 3.1272579886088057, 1e-12, "solartime", "last");
+        vvd(lmst, 3.141592653589793, 1e-12, "solartime", "lmst");
+        let e = eqtime(&eop, 2451544.5, 0.5).unwrap();
+        vvd(last - lmst, e, 1e-12, "solartime", "eot");
+
+        /* Solar noon agrees with the transit found by riset. */
+        let n = solarnoon(&obs, &eop, 2000, 1, 1, 0.0).unwrap();
+        vvd(n.utc.1, 51544.50228235318, 1e-8, "solarnoon", "utc");
+        let rs = riset(
+            &Epv00,
+            &Body::Solar(Target::Sun),
+            &obs,
+            &eop,
+            &Horizon::standard(),
+            2451544.5,
+            0.0,
+        )
+        .unwrap();
+        let (t1, t2) = rs.transit.unwrap();
+        vvd(n.utc.0 + n.utc.1, t1 + t2, 1e-7, "solarnoon", "transit");
+        let n = solarnoon(&obs, &eop, 2000, 1, 1, 1.0).unwrap();
+        vvd(n.local.1 - n.utc.1, 1.0 / 24.0, 1e-10, "solarnoon", "local");
+    }
+}
@@ -4,10 +4,12 @@ mod common;
 mod tests {
     use super::common::{viv, vvd};
     use sofars::astro::{
-        ab, apcg, apcg13, apci, apci13, apco, apco13, apcs, apcs13, aper, aper13, atcc13, atci13,
-        atciq, atciqn, atciqz, atco13, atic13, aticq, aticqn, atio13, atoc13, atoi13, ld, ldn,
-        ldsun, pmsafe, pvstar, pvtob, starpv, IauAstrom, IauLdBody,
+        IauAstrom, IauLdBody, ab, apcg, apcg13, apcg13e, apci, apci13, apci13e, apco, apco13,
+        apco13e, apcs, apcs13, apcs13e, aper, aper13, atcc13, atci13, atci13b, atciq, atciqb,
+        atciqn, atciqz, atco13, atco13b, atic13, aticq, aticqn, atio13, atoc13, atoi13, ld, ldn,
+        ldsun, pmsafe, pvstar, pvtob, starpv,
     };
+    use sofars::eph::{Epv00, Moon98, Plan94, Target};
 
     #[test]
     fn test_pmsafe() {
@@ -1415,4 +1417,134 @@ mod tests {
             "starpv: pv[1][2]"
         );
     }
+
+    #[test]
+    fn test_atciqb() {
+        let date1 = 2456165.5;
+        let date2 = 0.401182685;
+        let mut astrom = IauAstrom::default();
+        let mut eo = 0.0;
+        apci13(date1, date2, &mut astrom, &mut eo);
+
+        let (rca, dca, ri, di, dist, lt) = atciqb(&Epv00, Target::Sun, date1, date2, &astrom).unwrap();
+
+        vvd(rca, 2.710590689988662927, 1e-12, "atciqb", "rca");
+        vvd(dca, 0.1791760766592997711, 1e-12, "atciqb", "dca");
+        vvd(ri, 2.710588942138496105, 1e-12, "atciqb", "ri");
+        vvd(di, 0.1780573066357250545, 1e-12, "atciqb", "di");
+        vvd(dist, 1.010465332744381772, 1e-12, "atciqb", "dist");
+        vvd(lt, dist * 499.004782 / 86400.0, 1e-9, "atciqb", "lt");
+
+        assert_eq!(atciqb(&Epv00, Target::Moon, date1, date2, &astrom), Err(-2));
+    }
+
+    #[test]
+    fn test_atci13b() {
+        let date1 = 2456165.5;
+        let date2 = 0.401182685;
+
+        let place = atci13b(&Moon98, Target::Moon, date1, date2).unwrap();
+
+        vvd(place.rca, 4.661254688525232837, 1e-12, "atci13b", "rca");
+        vvd(place.dca, -0.3685763647210665295, 1e-12, "atci13b", "dca");
+        vvd(place.ri, 4.661782730162331134, 1e-12, "atci13b", "ri");
+        vvd(place.di, -0.3686150659336176472, 1e-12, "atci13b", "di");
+        vvd(place.eo, -0.002900618712657376, 1e-14, "atci13b", "eo");
+        vvd(place.dist, 0.002483048019444472, 1e-12, "atci13b", "dist");
+        vvd(place.lt, 0.1434088935413988e-4, 1e-15, "atci13b", "lt");
+        vvd(place.aob, 0.0, 0.0, "atci13b", "aob");
+    }
+
+    #[test]
+    fn test_atco13b() {
+        let utc1 = 2456384.5;
+        let utc2 = 0.969254051;
+        let dut1 = 0.1550675;
+        let elong = -0.527800806;
+        let phi = -1.2345856;
+        let hm = 2738.0;
+        let xp = 2.47230737e-7;
+        let yp = 1.82640464e-6;
+        let phpa = 731.0;
+        let tc = 12.8;
+        let rh = 0.59;
+        let wl = 0.55;
+
+        let place = atco13b(
+            &Moon98,
+            Target::Moon,
+            utc1, utc2, dut1, elong, phi, hm, xp, yp, phpa, tc, rh, wl,
+        )
+        .unwrap();
+
+        vvd(place.rca, 4.905649568967845653, 1e-12, "atco13b", "rca");
+        vvd(place.dca, -0.3130001823546905149, 1e-12, "atco13b", "dca");
+        vvd(place.ri, 4.906067718394021782, 1e-12, "atco13b", "ri");
+        vvd(place.di, -0.3127276153259213598, 1e-12, "atco13b", "di");
+        vvd(place.aob, 2.338728593359595376, 1e-12, "atco13b", "aob");
+        vvd(place.zob, 1.484737965329832043, 1e-12, "atco13b", "zob");
+        vvd(place.hob, -2.287942863506224445, 1e-12, "atco13b", "hob");
+        vvd(place.dob, -0.3146304917586558725, 1e-12, "atco13b", "dob");
+        vvd(place.rob, 4.905551772695876167, 1e-12, "atco13b", "rob");
+        vvd(place.eo, -0.003020548354802412839, 1e-14, "atco13b", "eo");
+        vvd(place.dist, 0.002472505121878675, 1e-12, "atco13b", "dist");
+        vvd(place.lt, 0.1427999865598215e-4, 1e-15, "atco13b", "lt");
+
+    }
+
+    #[test]
+    fn test_apc13e() {
+        let date1 = 2456165.5;
+        let date2 = 0.401182685;
+        let pv = [
+            [-6241497.16, 401346.896, -1251136.04],
+            [-29.264597, -455.021831, 0.0266151194],
+        ];
+
+        let mut a = IauAstrom::default();
+        let mut b = IauAstrom::default();
+        apcg13(date1, date2, &mut a);
+        apcg13e(&Moon98, date1, date2, &mut b).unwrap();
+        assert_eq!(a, b);
+
+        apcs13(date1, date2, &pv, &mut a);
+        apcs13e(&Plan94, date1, date2, &pv, &mut b).unwrap();
+        assert_eq!(a, b);
+
+        let (mut eo1, mut eo2) = (0.0, 0.0);
+        apci13(date1, date2, &mut a, &mut eo1);
+        apci13e(&Epv00, date1, date2, &mut b, &mut eo2).unwrap();
+        assert_eq!(a, b);
+        vvd(eo1, eo2, 0.0, "apci13e", "eo");
+
+        let j = apco13(
+            2456384.5, 0.969254051, 0.1550675, -0.527800806, -1.2345856, 2738.0, 2.47230737e-7,
+            1.82640464e-6, 731.0, 12.8, 0.59, 0.55, &mut a, &mut eo1,
+        );
+        let k = apco13e(
+            &Plan94, 2456384.5, 0.969254051, 0.1550675, -0.527800806, -1.2345856, 2738.0,
+            2.47230737e-7, 1.82640464e-6, 731.0, 12.8, 0.59, 0.55, &mut b, &mut eo2,
+        );
+        assert_eq!(j, k);
+        assert_eq!(a, b);
+        vvd(eo1, eo2, 0.0, "apco13e", "eo");
+
+        let j = apcg13e(&Epv00, 2400000.5, 1e6, &mut b).unwrap_err();
+        viv(j, -1, "apcg13e", "j");
+    }
+
+    #[test]
+    fn test_atci13b_planet() {
+        let place = atci13b(&Plan94, Target::Mars, 2456165.5, 0.401182685).unwrap();
+
+        vvd(place.rca, 3.652480781886813688, 1e-12, "atci13b", "rca");
+        vvd(place.dca, -0.2173367988794258909, 1e-12, "atci13b", "dca");
+        vvd(place.ri, 3.652570570278457218, 1e-12, "atci13b", "ri");
+        vvd(place.di, -0.2184072651256610542, 1e-12, "atci13b", "di");
+        vvd(place.dist, 1.774118294940266294, 1e-12, "atci13b", "dist");
+        vvd(place.lt, 0.1024645273456525549e-1, 1e-14, "atci13b", "lt");
+
+        let j = atci13b(&Moon98, Target::Mars, 2456165.5, 0.401182685).unwrap_err();
+        viv(j, -2, "atci13b", "j");
+    }
 }
@@ -0,0 +1,148 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::common::vvd;
+    use sofars::consts::DD2R;
+    use sofars::eclipse::*;
+    use sofars::eph::Moon98;
+    use sofars::site::Observer;
+
+    /* Total solar eclipse of 2017 August 21, t0 = 18h TT, deltat = 70.3s. */
+    const T0: f64 = 2457987.25;
+    const DT: f64 = 70.3;
+
+    #[test]
+    fn test_bessel() {
+        let b = bessel(T0, 0.0, 0.0, &Moon98).unwrap();
+
+        /* NASA elements (ephemeris hour angle, i.e. deltat = 0). */
+        vvd(b.x, -0.129571, 1e-3, "bessel", "x");
+        vvd(b.y, 0.485416, 1e-3, "bessel", "y");
+        vvd(b.d, 11.86697 * DD2R, 1e-6, "bessel", "d");
+        vvd(b.mu, 89.24545 * DD2R, 1e-6, "bessel", "mu");
+        vvd(b.l1, 0.542093, 1e-4, "bessel", "l1");
+        vvd(b.l2, -0.004025, 1e-4, "bessel", "l2");
+        vvd(b.tanf1, 0.0046222, 1e-7, "bessel", "tanf1");
+        vvd(b.tanf2, 0.0045992, 1e-7, "bessel", "tanf2");
+
+        vvd(b.x, -0.12926281590895294, 1e-12, "bessel", "x");
+        vvd(b.y, 0.48547600072949326, 1e-12, "bessel", "y");
+        vvd(b.mu, 1.5576265954820339, 1e-12, "bessel", "mu");
+        vvd(b.l2, -0.004019409721967571, 1e-12, "bessel", "l2");
+
+        let b = bessel(T0, 0.0, DT, &Moon98).unwrap();
+        vvd(b.mu, 1.5525002385338946, 1e-12, "bessel", "mu");
+    }
+
+    #[test]
+    fn test_besselpoly() {
+        let p = besselpoly(T0, 0.0, DT, &Moon98).unwrap();
+        vvd(p.x[1], 0.5406399372567807, 1e-10, "besselpoly", "x1");
+        vvd(p.x[2], -2.980173366414392e-5, 1e-10, "besselpoly", "x2");
+        vvd(p.x[3], -8.096661745023622e-6, 1e-10, "besselpoly", "x3");
+        vvd(p.y[1], -0.1416336208712997, 1e-10, "besselpoly", "y1");
+        vvd(p.d[1], -0.00023774098992823908, 1e-12, "besselpoly", "d1");
+        vvd(p.mu[1], 0.2618681060602424, 1e-10, "besselpoly", "mu1");
+        vvd(p.l1[2], -1.1779455353651732e-5, 1e-10, "besselpoly", "l12");
+
+        /* The polynomials reproduce the elements between the nodes. */
+        let b = bessel(T0, 2.5 / 24.0, DT, &Moon98).unwrap();
+        let e = p.at(2.5);
+        vvd(e.x, b.x, 1e-6, "besselpoly", "x");
+        vvd(e.y, b.y, 1e-6, "besselpoly", "y");
+        vvd(e.mu, b.mu, 1e-9, "besselpoly", "mu");
+        vvd(e.l1, b.l1, 1e-8, "besselpoly", "l1");
+        vvd(p.rates(0.0).x, p.x[1], 0.0, "besselpoly", "rates");
+    }
+
+    #[test]
+    fn test_solarlocal() {
+        let p = besselpoly(T0, 0.0, DT, &Moon98).unwrap();
+        let h = |t: (f64, f64)| t.1 * 24.0 + 18.0 - DT / 3600.0;
+
+        /* Carbondale, Illinois. */
+        let obs = Observer::new(-89.22 * DD2R, 37.72 * DD2R, 130.0);
+        let l = solarlocal(&p, &obs).unwrap().unwrap();
+        assert_eq!(l.kind, SolarKind::Total);
+        vvd(l.c1.0, T0, 0.0, "solarlocal", "c1");
+        vvd(h(l.c1), 16.872371533428673, 1e-6, "solarlocal", "c1");
+        vvd(
+            h(l.c2.unwrap()),
+            18.333541995891572,
+            1e-6,
+            "solarlocal",
+            "c2",
+        );
+        vvd(h(l.max), 18.35554737498615, 1e-6, "solarlocal", "max");
+        vvd(
+            h(l.c3.unwrap()),
+            18.377516052521319,
+            1e-6,
+            "solarlocal",
+            "c3",
+        );
+        vvd(h(l.c4), 19.789988616619796, 1e-6, "solarlocal", "c4");
+        vvd(l.mag, 1.0131035941856128, 1e-9, "solarlocal", "mag");
+        vvd(l.obsc, 1.0, 0.0, "solarlocal", "obsc");
+        vvd(l.alt, 63.88285392618679 * DD2R, 1e-9, "solarlocal", "alt");
+
+        /* New York. */
+        let obs = Observer::new(-74.0 * DD2R, 40.71 * DD2R, 10.0);
+        let l = solarlocal(&p, &obs).unwrap().unwrap();
+        assert_eq!(l.kind, SolarKind::Partial);
+        assert!(l.c2.is_none() && l.c3.is_none());
+        vvd(l.max.1, 0.03200600693008152, 1e-8, "solarlocal", "max");
+        vvd(l.mag, 0.7698749859548716, 1e-9, "solarlocal", "mag");
+        vvd(l.obsc, 0.715881971099901, 1e-9, "solarlocal", "obsc");
+
+        /* Sydney. */
+        let obs = Observer::new(151.2 * DD2R, -33.87 * DD2R, 10.0);
+        assert!(solarlocal(&p, &obs).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_lunecl() {
+        /* The five lunar eclipses of 2000 and 2001. */
+        let e = lunecl(2451544.5, 0.0, 731.0, Shadow::Danjon, &Moon98).unwrap();
+        assert_eq!(
+            e.iter().map(|x| x.kind).collect::<Vec<LunarKind>>(),
+            [
+                LunarKind::Total,
+                LunarKind::Total,
+                LunarKind::Total,
+                LunarKind::Partial,
+                LunarKind::Penumbral
+            ]
+        );
+        let umag = [1.3226058039532835, 1.7647278194252574, 1.1856475961975745];
+        for (x, u) in e.iter().zip(umag) {
+            vvd(x.umag, u, 1e-9, "lunecl", "umag");
+        }
+        vvd(e[3].umag, 0.49208991722215545, 1e-9, "lunecl", "umag");
+        vvd(e[3].pmag, 1.5448247032104765, 1e-9, "lunecl", "pmag");
+        assert!(e[3].u1.is_some() && e[3].u2.is_none());
+        vvd(e[4].pmag, 0.8911783139447002, 1e-9, "lunecl", "pmag");
+        assert!(e[4].u1.is_none() && e[4].u4.is_none());
+
+        /* Total eclipse of 2000 January 21:  NASA umbral magnitude 1.3246. */
+        vvd(e[0].umag, 1.3246, 5e-3, "lunecl", "umag");
+        vvd(e[0].max.0, 2451544.5, 0.0, "lunecl", "max");
+        vvd(e[0].max.1, 20.197680173732124, 1e-8, "lunecl", "max");
+
+        /* Chauvenet's larger shadow. */
+        let e = lunecl(2451544.5, 0.0, 31.0, Shadow::Chauvenet, &Moon98).unwrap();
+        assert_eq!(e.len(), 1);
+        let x = &e[0];
+        vvd(x.p1.1, 20.08616504497779, 1e-8, "lunecl", "p1");
+        vvd(x.u1.unwrap().1, 20.126825749789468, 1e-8, "lunecl", "u1");
+        vvd(x.u2.unwrap().1, 20.170628438832463, 1e-8, "lunecl", "u2");
+        vvd(x.max.1, 20.197680173732124, 1e-8, "lunecl", "max");
+        vvd(x.u3.unwrap().1, 20.224718588940014, 1e-8, "lunecl", "u3");
+        vvd(x.u4.unwrap().1, 20.268515619618213, 1e-8, "lunecl", "u4");
+        vvd(x.p4.1, 20.30922850486744, 1e-8, "lunecl", "p4");
+        vvd(x.umag, 1.3312006394864295, 1e-9, "lunecl", "umag");
+        vvd(x.pmag, 2.332191410906123, 1e-9, "lunecl", "pmag");
+        vvd(x.sep, 0.0052165367221233975, 1e-12, "lunecl", "sep");
+    }
+}
@@ -70,4 +70,1034 @@ mod tests {
         vvd(pv[1][2], 0.8929809783898904786e-2, 1e-11, "plan94", "zd 4");
         viv(j, 0, "plan94", "j 4");
     }
+
+    #[test]
+    fn test_ephemeris() {
+        use sofars::eph::{Ephemeris, Epv00, Moon98, Plan94, Target};
+
+        let (pvh, pvb) = Epv00.earth(2400000.5, 53411.52501161).unwrap();
+        vvd(pvh[0][0], -0.7757238809297706813, 1e-14, "ephemeris", "ph(x)");
+        vvd(pvb[0][0], -0.7714104440491111971, 1e-14, "ephemeris", "pb(x)");
+
+        let sb = Epv00.pvb(Target::Sun, 2400000.5, 53411.52501161).unwrap();
+        vvd(sb[0][0], 0.4313436880659484200e-2, 1e-14, "ephemeris", "sb(x)");
+        vvd(sb[0][1], 0.3598204608307270e-4, 1e-14, "ephemeris", "sb(y)");
+        vvd(sb[1][2], 0.2796237098807782e-5, 1e-15, "ephemeris", "sbd(z)");
+
+        let eh = Plan94.pvh(Target::Earth, 2400000.5, 53411.52501161).unwrap();
+        vvd(eh[0][0], pvh[0][0], 1e-15, "ephemeris", "eh(x)");
+        vvd(eh[1][1], pvh[1][1], 1e-17, "ephemeris", "ehd(y)");
+
+        let ssb = Moon98.pvb(Target::Ssb, 2400000.5, 53411.52501161).unwrap();
+        vvd(ssb[0][2], 0.0, 0.0, "ephemeris", "ssb(z)");
+
+        let ph = Plan94.pvh(Target::Mercury, 2400000.5, 43999.9).unwrap();
+        vvd(ph[0][0], 0.2945293959257430832, 1e-11, "ephemeris", "x 1");
+        vvd(ph[1][2], 0.8929809783898904786e-2, 1e-11, "ephemeris", "zd 1");
+
+        let pb = Plan94.pvb(Target::Mercury, 2400000.5, 43999.9).unwrap();
+        let sb = Plan94.pvb(Target::Sun, 2400000.5, 43999.9).unwrap();
+        vvd(pb[0][1] - sb[0][1], -0.2452204176601049596, 1e-14, "ephemeris", "y 1");
+
+        let mb = Moon98.pvb(Target::Moon, 2400000.5, 43999.9).unwrap();
+        let eb = Moon98.pvb(Target::Earth, 2400000.5, 43999.9).unwrap();
+        vvd(mb[0][0] - eb[0][0], -0.2601295959971044180e-2, 1e-11, "ephemeris", "x 2");
+        vvd(mb[1][1] - eb[1][1], -0.5219076942678119398e-3, 1e-11, "ephemeris", "yd 2");
+
+        let j = Epv00.pvb(Target::Moon, 2400000.5, 43999.9).unwrap_err();
+        viv(j, -2, "ephemeris", "j 1");
+
+        let j = Plan94.pvb(Target::Pluto, 2400000.5, 43999.9).unwrap_err();
+        viv(j, -2, "ephemeris", "j 2");
+
+        let j = Moon98.pvb(Target::Moon, 2400000.5, 1e6).unwrap_err();
+        viv(j, -1, "ephemeris", "j 3");
+    }
+
+    /* Motion of the synthetic bodies:  p0 + v t + a t^2 (km, s). */
+    const EMB: [[f64; 3]; 3] = [[1e8, -5e7, 2e7], [10.0, 20.0, -5.0], [1e-6, -2e-6, 3e-7]];
+    const GEO: [[f64; 3]; 3] = [[4000.0, -3000.0, 1000.0], [0.01, -0.02, 0.005], [0.0; 3]];
+    const SUN: [[f64; 3]; 3] = [[-1e6, 5e5, 2e5], [0.01, 0.002, -0.001], [0.0; 3]];
+
+    fn motion(m: &[[f64; 3]; 3], t: f64) -> [[f64; 3]; 2] {
+        let mut pv = [[0.0; 3]; 2];
+        for j in 0..3 {
+            pv[0][j] = m[0][j] + m[1][j] * t + m[2][j] * t * t;
+            pv[1][j] = m[1][j] + 2.0 * m[2][j] * t;
+        }
+        pv
+    }
+
+    /* Chebyshev coefficients (c0, c1, c2) of the motion in one record. */
+    fn cheb(m: &[[f64; 3]; 3], j: usize, mid: f64, r: f64) -> [f64; 3] {
+        let (p0, v, a) = (m[0][j], m[1][j], m[2][j]);
+        [
+            p0 + v * mid + a * (mid * mid + r * r / 2.0),
+            v * r + 2.0 * a * mid * r,
+            a * r * r / 2.0,
+        ]
+    }
+
+    /* A small SPK file:  EMB (type 2), Earth (type 3), Sun (type 2). */
+    fn spkfile(big: bool) -> Vec<u8> {
+        let intlen = 32.0 * 86400.0;
+        let init = -intlen;
+
+        let mut data: Vec<f64> = Vec::new();
+        let mut segs = Vec::new();
+        for (target, center, kind, m, n) in [
+            (3, 0, 2, &EMB, 2),
+            (399, 3, 3, &GEO, 2),
+            (10, 0, 2, &SUN, 1),
+        ] {
+            let begin = 385 + data.len();
+            let intl = intlen * 2.0 / n as f64;
+            let rr = intl / 2.0;
+            for i in 0..n {
+                let mid = init + rr + i as f64 * intl;
+                data.extend([mid, rr]);
+                for j in 0..3 {
+                    data.extend(cheb(m, j, mid, rr));
+                }
+                if kind == 3 {
+                    for j in 0..3 {
+                        let c = cheb(m, j, mid, rr);
+                        data.extend([c[1] / rr, 4.0 * c[2] / rr, 0.0]);
+                    }
+                }
+            }
+            let rsize = if kind == 2 { 11.0 } else { 20.0 };
+            data.extend([init, intl, rsize, n as f64]);
+            let end = 384 + data.len();
+            segs.push((target, center, kind, begin as i32, end as i32));
+        }
+
+        let dbl = |x: f64| if big { x.to_be_bytes() } else { x.to_le_bytes() };
+        let int = |i: i32| if big { i.to_be_bytes() } else { i.to_le_bytes() };
+
+        let mut f = vec![0u8; 3 * 1024];
+        f[0..8].copy_from_slice(b"DAF/SPK ");
+        f[8..12].copy_from_slice(&int(2));
+        f[12..16].copy_from_slice(&int(6));
+        f[16..76].copy_from_slice(&[b' '; 60]);
+        f[16..25].copy_from_slice(b"TEST FILE");
+        f[76..80].copy_from_slice(&int(2));
+        f[80..84].copy_from_slice(&int(2));
+        f[84..88].copy_from_slice(&int(385 + data.len() as i32));
+        f[88..96].copy_from_slice(if big { b"BIG-IEEE" } else { b"LTL-IEEE" });
+
+        /* Summary and name records. */
+        f[1024..1032].copy_from_slice(&dbl(0.0));
+        f[1032..1040].copy_from_slice(&dbl(0.0));
+        f[1040..1048].copy_from_slice(&dbl(segs.len() as f64));
+        for (i, (target, center, kind, begin, end)) in segs.into_iter().enumerate() {
+            let o = 1048 + 40 * i;
+            f[o..o + 8].copy_from_slice(&dbl(init));
+            f[o + 8..o + 16].copy_from_slice(&dbl(-init));
+            for (k, x) in [target, center, 1, kind, begin, end].into_iter().enumerate() {
+                f[o + 16 + 4 * k..o + 20 + 4 * k].copy_from_slice(&int(x));
+            }
+            let o = 2048 + 40 * i;
+            f[o..o + 40].copy_from_slice(&[b' '; 40]);
+            f[o..o + 7].copy_from_slice(format!("SEG {:03}", target).as_bytes().split_at(7).0);
+        }
+        for x in data {
+            f.extend(dbl(x));
+        }
+        f
+    }
+
+    #[test]
+    fn test_spk() {
+        use sofars::astro::IauLdBody;
+        use sofars::eph::{Ephemeris, Spk, Target};
+        use std::io::Cursor;
+
+        for big in [false, true] {
+            let spk = Spk::new(Cursor::new(spkfile(big))).unwrap();
+            assert_eq!(spk.segments().len(), 3);
+            assert_eq!(spk.segments()[1].target, 399);
+            assert_eq!(spk.segments()[1].center, 3);
+            assert_eq!(spk.segments()[1].kind, 3);
+            assert_eq!(spk.segments()[2].name, "SEG 010");
+
+            for t in [-20.0 * 86400.0, 0.0, 5.3 * 86400.0, 31.0 * 86400.0] {
+                let date2 = t / 86400.0;
+                let emb = motion(&EMB, t);
+                let geo = motion(&GEO, t);
+                let sun = motion(&SUN, t);
+
+                let pv = spk.pv(399, 0, 2451545.0, date2).unwrap();
+                for j in 0..3 {
+                    vvd(pv[0][j], emb[0][j] + geo[0][j], 1e-6, "spk", "p");
+                    vvd(pv[1][j], emb[1][j] + geo[1][j], 1e-12, "spk", "v");
+                }
+
+                let pv = spk.pv(399, 10, 2451545.0, date2).unwrap();
+                for j in 0..3 {
+                    vvd(pv[0][j], emb[0][j] + geo[0][j] - sun[0][j], 1e-6, "spk", "p");
+                    vvd(pv[1][j], emb[1][j] + geo[1][j] - sun[1][j], 1e-12, "spk", "v");
+                }
+
+                let pv = spk.pv(3, 399, 2451545.0, date2).unwrap();
+                vvd(pv[0][0], -geo[0][0], 1e-6, "spk", "p");
+
+                let au = 149597870.7;
+                let (eh, eb) = spk.earth(2451545.0, date2).unwrap();
+                vvd(eb[0][1], (emb[0][1] + geo[0][1]) / au, 1e-14, "spk", "eb");
+                vvd(eb[1][2], (emb[1][2] + geo[1][2]) * 86400.0 / au, 1e-14, "spk", "ebd");
+                vvd(eh[0][0], (emb[0][0] + geo[0][0] - sun[0][0]) / au, 1e-14, "spk", "eh");
+
+                let b = IauLdBody::from_eph(&spk, Target::Sun, 1.0, 6e-6, 2451545.0, date2).unwrap();
+                vvd(b.pv[0][2], sun[0][2] / au, 1e-14, "spk", "ldbody");
+            }
+
+            let j = spk.pv(399, 0, 2451545.0, 33.0).unwrap_err();
+            viv(j, -1, "spk", "j 1");
+
+            let j = spk.pvb(Target::Jupiter, 2451545.0, 0.0).unwrap_err();
+            viv(j, -2, "spk", "j 2");
+
+            let j = spk.pvb(Target::Mars, 2451545.0, 0.0).unwrap_err();
+            viv(j, -2, "spk", "j 3");
+        }
+
+        assert!(Spk::new(Cursor::new(vec![0u8; 2048])).is_err());
+
+        /* Corrupt summary count, truncated file, and a segment with no
+           records. */
+        let mut f = spkfile(false);
+        f[1040..1048].copy_from_slice(&1e30f64.to_le_bytes());
+        assert!(Spk::new(Cursor::new(f)).is_err());
+        let mut f = spkfile(false);
+        f.truncate(f.len() - 8);
+        assert!(Spk::new(Cursor::new(f)).is_err());
+        let mut f = spkfile(false);
+        let n = f.len();
+        f[n - 8..].copy_from_slice(&0.0f64.to_le_bytes());
+        assert!(Spk::new(Cursor::new(f)).is_err());
+    }
+
+    /* Lunar Euler angles:  phi0 + phidot t + ... (rad, s). */
+    const LIB: [[f64; 3]; 3] = [
+        [0.05, 0.4, 1.2],
+        [1e-9, -2e-9, 2.66e-6],
+        [3e-17, 1e-17, -2e-17],
+    ];
+
+    /* A small binary PCK file:  frame 31008 (type 2), 31006 (type 3). */
+    fn pckfile(big: bool) -> Vec<u8> {
+        let intlen = 32.0 * 86400.0;
+        let init = -intlen;
+
+        let mut data: Vec<f64> = Vec::new();
+        let mut segs = Vec::new();
+        for (body, kind) in [(31008, 2), (31006, 3)] {
+            let begin = 385 + data.len();
+            let rr = intlen / 2.0;
+            for i in 0..2 {
+                let mid = init + rr + i as f64 * intlen;
+                data.extend([mid, rr]);
+                for j in 0..3 {
+                    data.extend(cheb(&LIB, j, mid, rr));
+                }
+                if kind == 3 {
+                    for j in 0..3 {
+                        let c = cheb(&LIB, j, mid, rr);
+                        data.extend([c[1] / rr, 4.0 * c[2] / rr, 0.0]);
+                    }
+                }
+            }
+            let rsize = if kind == 2 { 11.0 } else { 20.0 };
+            data.extend([init, intlen, rsize, 2.0]);
+            let end = 384 + data.len();
+            segs.push((body, kind, begin as i32, end as i32));
+        }
+
+        let dbl = |x: f64| {
+            if big {
+                x.to_be_bytes()
+            } else {
+                x.to_le_bytes()
+            }
+        };
+        let int = |i: i32| {
+            if big {
+                i.to_be_bytes()
+            } else {
+                i.to_le_bytes()
+            }
+        };
+
+        let mut f = vec![0u8; 3 * 1024];
+        f[0..8].copy_from_slice(b"DAF/PCK ");
+        f[8..12].copy_from_slice(&int(2));
+        f[12..16].copy_from_slice(&int(5));
+        f[16..76].copy_from_slice(&[b' '; 60]);
+        f[76..80].copy_from_slice(&int(2));
+        f[80..84].copy_from_slice(&int(2));
+        f[84..88].copy_from_slice(&int(385 + data.len() as i32));
+        f[88..96].copy_from_slice(if big { b"BIG-IEEE" } else { b"LTL-IEEE" });
+
+        f[1024..1032].copy_from_slice(&dbl(0.0));
+        f[1032..1040].copy_from_slice(&dbl(0.0));
+        f[1040..1048].copy_from_slice(&dbl(segs.len() as f64));
+        for (i, (body, kind, begin, end)) in segs.into_iter().enumerate() {
+            let o = 1048 + 40 * i;
+            f[o..o + 8].copy_from_slice(&dbl(init));
+            f[o + 8..o + 16].copy_from_slice(&dbl(-init));
+            for (k, x) in [body, 1, kind, begin, end].into_iter().enumerate() {
+                f[o + 16 + 4 * k..o + 20 + 4 * k].copy_from_slice(&int(x));
+            }
+            let o = 2048 + 40 * i;
+            f[o..o + 40].copy_from_slice(&[b' '; 40]);
+            f[o..o + 8].copy_from_slice(format!("PA {}", body).as_bytes());
+        }
+        for x in data {
+            f.extend(dbl(x));
+        }
+        f
+    }
+
+    #[test]
+    fn test_pck() {
+        use sofars::eph::{Pck, eulrm};
+        use sofars::vm::{rxp, rxr, trxp};
+        use std::io::Cursor;
+
+        for big in [false, true] {
+            let pck = Pck::new(Cursor::new(pckfile(big))).unwrap();
+            assert_eq!(pck.segments().len(), 2);
+            assert_eq!(pck.segments()[0].body, 31008);
+            assert_eq!(pck.segments()[0].frame, 1);
+            assert_eq!(pck.segments()[1].kind, 3);
+            assert_eq!(pck.segments()[1].name, "PA 31006");
+
+            for t in [-20.0 * 86400.0, 0.0, 5.3 * 86400.0, 31.0 * 86400.0] {
+                let ea0 = motion(&LIB, t);
+                for body in [31008, 31006] {
+                    let ea = pck.euler(body, 2451545.0, t / 86400.0).unwrap();
+                    for j in 0..3 {
+                        vvd(ea[0][j], ea0[0][j], 1e-12, "pck", "angle");
+                        vvd(ea[1][j], ea0[1][j], 1e-18, "pck", "rate");
+                    }
+                }
+
+                /* The matrix, and its inverse. */
+                let r = pck.rm(31008, 2451545.0, t / 86400.0).unwrap();
+                let p = [0.3, -0.4, 0.8];
+                let mut q = [0.0; 3];
+                rxp(&r, &p, &mut q);
+                let mut p2 = [0.0; 3];
+                trxp(&r, &q, &mut p2);
+                for j in 0..3 {
+                    vvd(p2[j], p[j], 1e-15, "pck", "trxp");
+                }
+            }
+
+            let j = pck.euler(31008, 2451545.0, 33.0).unwrap_err();
+            viv(j, -1, "pck", "j 1");
+            let j = pck.rm(31007, 2451545.0, 0.0).unwrap_err();
+            viv(j, -2, "pck", "j 2");
+        }
+
+        /* Euler angle convention. */
+        let r = eulrm(0.3, -0.2, 1.1);
+        let mut r1 = [[0.0; 3]; 3];
+        sofars::vm::ir(&mut r1);
+        sofars::vm::rz(0.3, &mut r1);
+        let mut r2 = [[0.0; 3]; 3];
+        sofars::vm::ir(&mut r2);
+        sofars::vm::rx(-0.2, &mut r2);
+        let mut r3 = [[0.0; 3]; 3];
+        sofars::vm::ir(&mut r3);
+        sofars::vm::rz(1.1, &mut r3);
+        let mut r21 = [[0.0; 3]; 3];
+        rxr(&r2, &r1, &mut r21);
+        let mut r321 = [[0.0; 3]; 3];
+        rxr(&r3, &r21, &mut r321);
+        for i in 0..3 {
+            for j in 0..3 {
+                vvd(r[i][j], r321[i][j], 1e-15, "eulrm", "r");
+            }
+        }
+        vvd(r[2][2], (-0.2f64).cos(), 1e-15, "eulrm", "33");
+        vvd(
+            r[0][2],
+            1.1f64.sin() * (-0.2f64).sin(),
+            1e-15,
+            "eulrm",
+            "13",
+        );
+
+        assert!(Pck::new(Cursor::new(spkfile(false))).is_err());
+    }
+
+    /* A VSOP87 file:  (variable, power of T, [(A, B, C)]) blocks. */
+    fn vsopfile(version: char, body: &str, blocks: &[(usize, usize, &[[f64; 3]])]) -> String {
+        let mut f = String::new();
+        for (ic, it, terms) in blocks {
+            f += &format!(
+                " VSOP87 VERSION {}1    {:10}VARIABLE {} (XYZ)       *T**{} {:6} TERMS    TEST\n",
+                version,
+                body,
+                ic,
+                it,
+                terms.len()
+            );
+            for (n, [a, b, c]) in terms.iter().enumerate() {
+                f += &format!(
+                    " 1{}{}{}{:5}  0  0  0  0  0  0  0  0  0  0  0  0 {:15.11}{:18.11}{:18.11}{:14.11}{:20.11}\n",
+                    3,
+                    ic,
+                    it,
+                    n + 1,
+                    0.0,
+                    0.0,
+                    a,
+                    b,
+                    c
+                );
+            }
+        }
+        f
+    }
+
+    /* Series value and rate per millennium. */
+    fn vsopval(terms: &[[f64; 3]], it: i32, t: f64) -> (f64, f64) {
+        let (mut f, mut df) = (0.0, 0.0);
+        for [a, b, c] in terms {
+            f += a * (b + c * t).cos();
+            df -= a * c * (b + c * t).sin();
+        }
+        let dtk = if it > 0 {
+            it as f64 * t.powi(it - 1)
+        } else {
+            0.0
+        };
+        (t.powi(it) * f, t.powi(it) * df + dtk * f)
+    }
+
+    #[test]
+    fn test_vsop87() {
+        use sofars::coords::ecm06;
+        use sofars::eph::{Ephemeris, Epv00, Target, Vsop87, Vsop87Tables, Vsop87Version};
+        use sofars::vm::{s2pv, trxpv};
+
+        const X0: [[f64; 3]; 3] = [
+            [0.99982928844, 1.75348568475, 6283.07584999140],
+            [0.00835257300, 1.71034539450, 12566.15169998280],
+            [0.00000010000, 0.5, 100.0],
+        ];
+        const X1: [[f64; 3]; 1] = [[0.00123403056, 0.0, 0.0]];
+        const Y0: [[f64; 3]; 2] = [
+            [0.99988942981, 0.18265890456, 6283.07584999140],
+            [0.02442699036, 3.14159265359, 0.0],
+        ];
+        const Z1: [[f64; 3]; 1] = [[0.00227822442, 3.41372504278, 6283.07584999140]];
+
+        let text = vsopfile(
+            'A',
+            "EARTH",
+            &[(1, 0, &X0), (1, 1, &X1), (2, 0, &Y0), (3, 1, &Z1)],
+        );
+        let mut vsop = Vsop87::new(Vsop87Version::A);
+        viv(vsop.read(text.as_bytes()).unwrap() as i32, 7, "vsop87", "n");
+        assert!(vsop.has(Target::Earth));
+        assert!(!vsop.has(Target::Mars));
+
+        for d in [-1000.0, 0.0, 3652.5, 200000.0] {
+            let t = d / 365250.0;
+            let v = vsop.variables(Target::Earth, 2451545.0, d).unwrap();
+            let x = [vsopval(&X0, 0, t), vsopval(&X1, 1, t)];
+            let y = vsopval(&Y0, 0, t);
+            let z = vsopval(&Z1, 1, t);
+            vvd(v[0][0], x[0].0 + x[1].0, 1e-15, "vsop87", "x");
+            vvd(v[0][1], y.0, 1e-15, "vsop87", "y");
+            vvd(v[0][2], z.0, 1e-15, "vsop87", "z");
+            vvd(v[1][0], (x[0].1 + x[1].1) / 365250.0, 1e-17, "vsop87", "xd");
+            vvd(v[1][1], y.1 / 365250.0, 1e-17, "vsop87", "yd");
+            vvd(v[1][2], z.1 / 365250.0, 1e-17, "vsop87", "zd");
+
+            /* Equatorial:  the VSOP87 to FK5 rotation. */
+            let (pv, j) = vsop.pveq(Target::Earth, 2451545.0, d).unwrap();
+            viv(j, 0, "vsop87", "j");
+            let (se, ce) = (0.397776982902f64, 0.917482137087f64);
+            vvd(
+                pv[0][2],
+                se * v[0][1] + ce * v[0][2],
+                1e-15,
+                "vsop87",
+                "zeq",
+            );
+            vvd(
+                pv[1][1],
+                -0.000000479966 * v[1][0] + ce * v[1][1] - se * v[1][2],
+                1e-15,
+                "vsop87",
+                "ydeq",
+            );
+
+            let (pe, _) = vsop.pvecl(Target::Earth, 2451545.0, d).unwrap();
+            assert_eq!(pe, v);
+        }
+
+        /* Heliocentric theory:  barycentric via iauEpv00's Sun. */
+        let pb = vsop.pvb(Target::Earth, 2451545.0, 3652.5).unwrap();
+        let sb = Epv00.pvb(Target::Sun, 2451545.0, 3652.5).unwrap();
+        let (ph, _) = vsop.pveq(Target::Earth, 2451545.0, 3652.5).unwrap();
+        vvd(pb[0][0], ph[0][0] + sb[0][0], 1e-15, "vsop87", "pvb");
+
+        /* Truncation. */
+        let v0 = vsop.variables(Target::Earth, 2451545.0, 1000.0).unwrap();
+        vsop.tol = 1e-6;
+        let v1 = vsop.variables(Target::Earth, 2451545.0, 1000.0).unwrap();
+        vvd(
+            v0[0][0] - v1[0][0],
+            1e-7 * (0.5f64 + 100.0 * 1000.0 / 365250.0).cos(),
+            1e-15,
+            "vsop87",
+            "tol",
+        );
+        vsop.tol = 0.0;
+
+        /* The same series as const tables. */
+        const EARTH: Vsop87Tables = [&[&X0, &X1], &[&Y0], &[&[], &Z1]];
+        let mut vc = Vsop87::new(Vsop87Version::A);
+        vc.insert(Target::Earth, &EARTH);
+        assert_eq!(vc.terms(Target::Earth, 0, 1), vsop.terms(Target::Earth, 0, 1));
+        assert_eq!(vc.terms(Target::Earth, 2, 0), Some(&[][..]));
+        assert_eq!(vc.terms(Target::Earth, 1, 1), None);
+        for tol in [0.0, 1e-6] {
+            vsop.tol = tol;
+            vc.tol = tol;
+            assert_eq!(
+                vc.variables(Target::Earth, 2451545.0, 1000.0),
+                vsop.variables(Target::Earth, 2451545.0, 1000.0)
+            );
+        }
+        vsop.tol = 0.0;
+
+        let (_, j) = vsop.pveq(Target::Earth, 2451545.0, 1.5e6).unwrap();
+        viv(j, 1, "vsop87", "warning");
+        let j = vsop.pveq(Target::Mars, 2451545.0, 0.0).unwrap_err();
+        viv(j, -2, "vsop87", "j -2");
+
+        /* Version D:  L, B, R, ecliptic and equinox of date. */
+        const L0: [[f64; 3]; 2] = [
+            [1.75347045673, 0.0, 0.0],
+            [0.03341656456, 4.66925680417, 6283.07584999140],
+        ];
+        const L1: [[f64; 3]; 1] = [[6283.31966747491, 0.0, 0.0]];
+        const B0: [[f64; 3]; 1] = [[0.00000279620, 3.19870156017, 84334.66158130829]];
+        const R0: [[f64; 3]; 2] = [
+            [1.00013988784, 0.0, 0.0],
+            [0.01670699632, 3.09846350258, 6283.07584999140],
+        ];
+        let text = vsopfile(
+            'D',
+            "EARTH",
+            &[(1, 0, &L0), (1, 1, &L1), (2, 0, &B0), (3, 0, &R0)],
+        );
+        let mut vsop = Vsop87::new(Vsop87Version::D);
+        vsop.read(text.as_bytes()).unwrap();
+
+        for d in [-20000.0, 0.0, 9000.25] {
+            let t = d / 365250.0;
+            let l = vsopval(&L0, 0, t).0 + vsopval(&L1, 1, t).0;
+            let b = vsopval(&B0, 0, t).0;
+            let r = vsopval(&R0, 0, t).0;
+            let e = s2pv(l, b, r, 0.0, 0.0, 0.0);
+            let mut pv = [[0.0; 3]; 2];
+            trxpv(&ecm06(2451545.0, d), &e, &mut pv);
+            let (p, _) = vsop.pveq(Target::Earth, 2451545.0, d).unwrap();
+            for j in 0..3 {
+                vvd(p[0][j], pv[0][j], 1e-14, "vsop87", "D p");
+            }
+
+            /* Velocity, by differencing positions in the fixed frame. */
+            let h = 1e-3;
+            let (p1, _) = vsop.pveq(Target::Earth, 2451545.0 + d, h).unwrap();
+            let (p2, _) = vsop.pveq(Target::Earth, 2451545.0 + d, -h).unwrap();
+            for j in 0..3 {
+                vvd(
+                    p[1][j],
+                    (p1[0][j] - p2[0][j]) / (2.0 * h),
+                    1e-10,
+                    "vsop87",
+                    "D v",
+                );
+            }
+
+            let (pe, _) = vsop.pvecl(Target::Earth, 2451545.0, d).unwrap();
+            vvd(
+                (pe[0][0] * pe[0][0] + pe[0][1] * pe[0][1] + pe[0][2] * pe[0][2]).sqrt(),
+                r,
+                1e-14,
+                "vsop87",
+                "D r",
+            );
+        }
+
+        /* Errors. */
+        assert!(
+            vsop.read(vsopfile('A', "MARS", &[(1, 0, &X0)]).as_bytes())
+                .is_err()
+        );
+        let bad = vsopfile('D', "MARS", &[(1, 0, &X0)]).replace("     3 TERMS", "     4 TERMS");
+        assert!(vsop.read(bad.as_bytes()).is_err());
+        assert!(
+            vsop.read(vsopfile('D', "PLUTO", &[(1, 0, &X0)]).as_bytes())
+                .is_err()
+        );
+        assert!(!vsop.has(Target::Mars));
+    }
+
+    /* The IMCCE check values (vsop87.chk), for the A and D series found
+     * in the directory VSOP87_DIR. */
+    #[test]
+    #[ignore = "needs the IMCCE VSOP87 files in VSOP87_DIR"]
+    fn test_vsop87_chk() {
+        use sofars::eph::{Target, Vsop87, Vsop87Version};
+        use std::path::Path;
+
+        let dir = std::env::var("VSOP87_DIR").expect("VSOP87_DIR");
+        let dir = Path::new(&dir);
+        let chk = std::fs::read_to_string(dir.join("vsop87.chk")).unwrap();
+        let bodies = [
+            ("MERCURY", "mer", Target::Mercury),
+            ("VENUS", "ven", Target::Venus),
+            ("EARTH", "ear", Target::Earth),
+            ("EMB", "emb", Target::Emb),
+            ("MARS", "mar", Target::Mars),
+            ("JUPITER", "jup", Target::Jupiter),
+            ("SATURN", "sat", Target::Saturn),
+            ("URANUS", "ura", Target::Uranus),
+            ("NEPTUNE", "nep", Target::Neptune),
+        ];
+
+        let mut n = 0;
+        for (v, version) in [('A', Vsop87Version::A), ('D', Vsop87Version::D)] {
+            let files: Vec<_> = bodies
+                .iter()
+                .map(|(_, ext, _)| dir.join(format!("VSOP87{v}.{ext}")))
+                .filter(|f| f.exists())
+                .collect();
+            let vsop = Vsop87::load(version, &files).unwrap();
+
+            /* A header line, then the variables and their rates. */
+            let mut lines = chk.lines();
+            while let Some(line) = lines.next() {
+                let w: Vec<&str> = line.split_whitespace().collect();
+                if w.len() < 3 || w[0] != format!("VSOP87{v}") {
+                    continue;
+                }
+                let Some(&(_, _, target)) = bodies.iter().find(|b| b.0 == w[1]) else {
+                    continue;
+                };
+                let Some(jd) = w.iter().find_map(|x| x.strip_prefix("JD")) else {
+                    continue;
+                };
+                if !vsop.has(target) {
+                    continue;
+                }
+                let jd: f64 = jd.parse().unwrap();
+                let values = |l: Option<&str>| -> Vec<f64> {
+                    l.unwrap()
+                        .replace('=', " ")
+                        .split_whitespace()
+                        .filter_map(|x| x.parse().ok())
+                        .collect()
+                };
+                let (p, r) = (values(lines.next()), values(lines.next()));
+                let x = vsop.variables(target, jd, 0.0).unwrap();
+                for ic in 0..3 {
+                    vvd(x[0][ic], p[ic], 1e-10, "vsop87 chk", w[1]);
+                    vvd(x[1][ic], r[ic], 1e-10, "vsop87 chk", w[1]);
+                }
+                n += 1;
+            }
+        }
+        assert!(n > 0);
+    }
+
+    /* The largest ELP terms:  multipliers of D, l', l, F and amplitude. */
+    const ELPMAIN: [&[([i32; 4], f64)]; 3] = [
+        &[
+            ([0, 0, 1, 0], 22639.55),
+            ([2, 0, -1, 0], 4586.43),
+            ([2, 0, 0, 0], 2369.91),
+            ([0, 0, 2, 0], 769.02),
+            ([0, 1, 0, 0], -666.42),
+            ([0, 0, 0, 2], -411.60),
+            ([2, 0, -2, 0], 211.65),
+            ([2, -1, -1, 0], 205.44),
+            ([2, 0, 1, 0], 191.96),
+            ([2, -1, 0, 0], 164.73),
+            ([0, 1, -1, 0], -147.32),
+            ([1, 0, 0, 0], -124.99),
+            ([0, 1, 1, 0], -109.38),
+        ],
+        &[
+            ([0, 0, 0, 1], 18461.24),
+            ([0, 0, 1, 1], 1010.17),
+            ([0, 0, 1, -1], 999.70),
+            ([2, 0, 0, -1], 623.65),
+            ([2, 0, -1, 1], 199.49),
+            ([2, 0, -1, -1], 166.58),
+            ([2, 0, 0, 1], 117.26),
+            ([0, 0, 2, 1], 61.91),
+            ([2, 0, 1, -1], 33.36),
+            ([0, 0, 2, -1], 31.76),
+            ([2, -1, 0, -1], 29.58),
+        ],
+        &[
+            ([0, 0, 0, 0], 385000.53),
+            ([0, 0, 1, 0], -20905.36),
+            ([2, 0, -1, 0], -3699.11),
+            ([2, 0, 0, 0], -2955.97),
+            ([0, 0, 2, 0], -569.93),
+        ],
+    ];
+
+    fn elpmain(iv: usize) -> String {
+        let mut f = format!(" MAIN PROBLEM.  VARIABLE {iv}   {:10}\n", ELPMAIN[iv].len());
+        for (ilu, a) in ELPMAIN[iv] {
+            f += &format!(
+                "{:3}{:3}{:3}{:3}  {:13.5}{:12.2}{:12.2}{:12.2}{:12.2}{:12.2}{:12.2}\n",
+                ilu[0], ilu[1], ilu[2], ilu[3], a, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0
+            );
+        }
+        f
+    }
+
+    /* Perturbations:  one term, with no argument, in the T^1 block. */
+    fn elppert(c: f64) -> String {
+        let d = |x: f64| format!("{:20.13e}", x).replace('e', "D");
+        let mut f = String::from(" PERTURBATIONS.  T**0          0         0\n");
+        f += " PERTURBATIONS.  T**1          1         1\n";
+        f += &format!("{:5} {} {}{}\n", 1, d(0.0), d(c), "  0".repeat(16));
+        f
+    }
+
+    #[test]
+    fn test_elpmpp02() {
+        use sofars::eph::{ElpParams, Elpmpp02, Ephemeris, Epv00, Target, moon98};
+        use sofars::vm::{pdp, pm, sepp};
+
+        /* The mean longitude W1, with no series loaded. */
+        let llr = Elpmpp02::new(ElpParams::Llr);
+        let de = Elpmpp02::new(ElpParams::De405);
+        assert_eq!(de.params(), ElpParams::De405);
+        let s1 = llr.spherical(2451545.0, 0.0);
+        let s2 = de.spherical(2451545.0, 0.0);
+        vvd(
+            s1[0][0],
+            (-142.0 + 18.0 / 60.0 + 59.85046 / 3600.0f64).to_radians(),
+            1e-15,
+            "elp",
+            "w1",
+        );
+        vvd(
+            (s2[0][0] - s1[0][0]) / 4.848136811095359935899141e-6,
+            0.03517,
+            1e-9,
+            "elp",
+            "dw1",
+        );
+        vvd(
+            s1[1][0],
+            1732559343.41293 / 36525.0 * 4.848136811095359935899141e-6,
+            1e-12,
+            "elp",
+            "n1",
+        );
+        vvd(s1[0][2], 0.0, 0.0, "elp", "r0");
+
+        /* The principal terms. */
+        let mut elp = Elpmpp02::new(ElpParams::Llr);
+        viv(elp.pv(2451545.0, 0.0).unwrap_err(), -2, "elp", "pv j");
+        viv(
+            elp.pvb(Target::Moon, 2451545.0, 0.0).unwrap_err(),
+            -2,
+            "elp",
+            "pvb j",
+        );
+        for iv in 1..=3 {
+            let n = elp.read_main(iv, elpmain(iv - 1).as_bytes()).unwrap();
+            viv(n as i32, ELPMAIN[iv - 1].len() as i32, "elp", "nmain");
+            viv(
+                elp.read_pert(iv, elppert(0.0).as_bytes()).unwrap() as i32,
+                1,
+                "elp",
+                "npert",
+            );
+        }
+
+        for d in [-3000.0, 0.0, 1234.5, 9000.0] {
+            /* Comparable with iauMoon98. */
+            let pv = elp.pv(2451545.0, d).unwrap();
+            let m = moon98(2451545.0, d);
+            assert!(sepp(&pv[0], &m[0]) < 0.1f64.to_radians());
+            assert!((pm(pv[0]) - pm(m[0])).abs() < 1500.0 / 149597870.7);
+            assert!(pdp(&pv[1], &m[1]) / (pm(pv[1]) * pm(m[1])) > 0.99);
+
+            /* Velocity, by differencing (precession is neglected). */
+            let h = 1e-3;
+            let p1 = elp.pv(2451545.0 + d, h).unwrap();
+            let p2 = elp.pv(2451545.0 + d, -h).unwrap();
+            for j in 0..3 {
+                vvd(
+                    pv[1][j],
+                    (p1[0][j] - p2[0][j]) / (2.0 * h),
+                    5e-9,
+                    "elp",
+                    "v",
+                );
+            }
+
+            /* Barycentric. */
+            if d.abs() < 10000.0 {
+                let pb = elp.pvb(Target::Moon, 2451545.0, d).unwrap();
+                let eb = Epv00.pvb(Target::Earth, 2451545.0, d).unwrap();
+                vvd(pb[0][1], eb[0][1] + pv[0][1], 1e-15, "elp", "pvb");
+            }
+        }
+
+        /* Perturbation term:  C times T in the longitude. */
+        let s0 = elp.spherical(2451545.0, 3652.5);
+        elp.read_pert(1, elppert(2.5).as_bytes()).unwrap();
+        let s1 = elp.spherical(2451545.0, 3652.5);
+        vvd(
+            (s1[0][0] - s0[0][0]) / 4.848136811095359935899141e-6,
+            0.25,
+            1e-7,
+            "elp",
+            "pert",
+        );
+        vvd(
+            (s1[1][0] - s0[1][0]) * 36525.0 / 4.848136811095359935899141e-6,
+            2.5,
+            1e-6,
+            "elp",
+            "dpert",
+        );
+
+        /* Truncation:  only W1 and the mean distance remain. */
+        elp.tol = 1e5;
+        let s2 = elp.spherical(2451545.0, 3652.5);
+        let w1 = llr.spherical(2451545.0, 3652.5);
+        vvd(s2[0][0], w1[0][0], 1e-15, "elp", "tol l");
+        vvd(s2[0][1], 0.0, 0.0, "elp", "tol b");
+        vvd(s2[0][2], 385000.53, 0.1, "elp", "tol r");
+        elp.tol = 0.0;
+
+        /* Errors. */
+        assert!(elp.read_main(4, elpmain(0).as_bytes()).is_err());
+        assert!(
+            elp.read_main(1, "  0  0  1  x  22639.55 0 0 0 0 0 0\n".as_bytes())
+                .is_err()
+        );
+        assert!(elp.read_main(1, "  0  0  1  1\n".as_bytes()).is_err());
+        assert!(elp.read_main(1, "  0  0  1  1é\n".as_bytes()).is_err());
+        assert!(
+            elp.read_pert(1, "    1  0.0D+00  1.0D+00  0  0\n".as_bytes())
+                .is_err()
+        );
+    }
+
+    /* Layout of a small JPL ephemeris:  (coefficients, subintervals). */
+    const LAYOUT: [(usize, usize); 15] = [
+        (6, 2), (6, 2), (6, 2), (6, 2), (6, 2), (6, 2), (6, 2), (6, 2), (6, 2), (6, 2), (6, 2),
+        (4, 2), (4, 2), (4, 1), (4, 1),
+    ];
+    const NCOMP: [usize; 15] = [3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 2, 3, 3, 1];
+    const JD0: f64 = 2451536.5;
+    const STEP: f64 = 32.0;
+
+    fn jplptrs(ncol: usize) -> (Vec<usize>, usize) {
+        let mut off = vec![0; ncol];
+        let mut n = 3;
+        for i in 0..ncol {
+            off[i] = n;
+            n += LAYOUT[i].0 * LAYOUT[i].1 * NCOMP[i];
+        }
+        (off, n - 1)
+    }
+
+    /* Deterministic coefficients, decreasing with order. */
+    fn jplcoef(k: usize, j: usize) -> f64 {
+        let x = ((k * 7919 + j * 104729) % 1000) as f64 / 1000.0 - 0.5;
+        x * 1e6 / (1.0 + (j % 6) as f64).powi(3)
+    }
+
+    fn jplrecord(k: usize, ncoeff: usize) -> Vec<f64> {
+        let t0 = JD0 + k as f64 * STEP;
+        let mut rec = vec![t0, t0 + STEP];
+        rec.extend((2..ncoeff).map(|j| jplcoef(k, j)));
+        rec
+    }
+
+    fn dfmt(x: f64) -> String {
+        format!("{:26.18E}", x).replace('E', "D")
+    }
+
+    fn jplheader(ncol: usize) -> String {
+        let (off, ncoeff) = jplptrs(ncol);
+        let mut h = format!("KSIZE= {:5}    NCOEFF= {:5}\n\n", 2 * ncoeff, ncoeff);
+        h += "GROUP   1010\n\nJPL Planetary Ephemeris DE999/LE999\n";
+        h += "Start Epoch: JED=  2451536.5 1999 DEC 21 00:00:00\n";
+        h += "Final Epoch: JED=  2451632.5 2000 MAR 26 00:00:00\n\n";
+        h += &format!("GROUP   1030\n\n{:12.2} {:12.2} {:12.0}.\n\n", JD0, JD0 + 3.0 * STEP, STEP);
+        h += "GROUP   1040\n\n     4\n  DENUM   AU      EMRAT   GMS\n\n";
+        h += "GROUP   1041\n\n     4\n";
+        h += &format!("{}{}{}\n", dfmt(999.0), dfmt(149597870.7), dfmt(81.3005690741906));
+        h += &format!("{}{}{}\n\n", dfmt(0.2959122082841196e-3), dfmt(0.0), dfmt(0.0));
+        h += "GROUP   1050\n\n";
+        for row in [
+            off.clone(),
+            LAYOUT[..ncol].iter().map(|l| l.0).collect(),
+            LAYOUT[..ncol].iter().map(|l| l.1).collect(),
+        ] {
+            h += &row.iter().map(|x| format!("{:6}", x)).collect::<String>();
+            h += "\n";
+        }
+        h += "\nGROUP   1070\n\n";
+        h
+    }
+
+    fn jpldata(ncol: usize, recs: std::ops::Range<usize>) -> String {
+        let ncoeff = jplptrs(ncol).1;
+        let mut d = String::new();
+        for k in recs {
+            d += &format!("{:6}{:6}\n", k + 1, ncoeff);
+            let mut rec = jplrecord(k, ncoeff);
+            while rec.len() % 3 != 0 {
+                rec.push(0.0);
+            }
+            for c in rec.chunks(3) {
+                d += &format!("{}{}{}\n", dfmt(c[0]), dfmt(c[1]), dfmt(c[2]));
+            }
+        }
+        d
+    }
+
+    /* Independent evaluation of item i, component c, at JD t. */
+    fn jplval(ncol: usize, i: usize, c: usize, t: f64) -> f64 {
+        let (off, ncoeff) = jplptrs(ncol);
+        let k = (((t - JD0) / STEP).floor() as usize).min(2);
+        let rec = jplrecord(k, ncoeff);
+        let (nc, ns) = LAYOUT[i];
+        let dt = STEP / ns as f64;
+        let x = (t - rec[0]) / dt;
+        let l = (x.floor() as usize).min(ns - 1);
+        let tc = 2.0 * (x - l as f64) - 1.0;
+        let a = off[i] - 1 + (l * NCOMP[i] + c) * nc;
+        (0..nc).map(|n| rec[a + n] * (n as f64 * tc.acos()).cos()).sum()
+    }
+
+    #[test]
+    fn test_jpleph() {
+        use sofars::eph::{Ephemeris, JplEph, Target};
+        use std::io::Cursor;
+
+        let mut eph = JplEph::from_header(Cursor::new(jplheader(15))).unwrap();
+        assert_eq!(eph.title.len(), 3);
+        assert_eq!(eph.ncoeff, jplptrs(15).1);
+        assert_eq!(eph.denum(), 999);
+        vvd(eph.au(), 149597870.7, 1e-9, "jpleph", "au");
+        vvd(eph.constant("GMS").unwrap(), 0.2959122082841196e-3, 1e-18, "jpleph", "gms");
+        assert!(eph.constant("XYZ").is_none());
+
+        viv(eph.read_ascii(Cursor::new(jpldata(15, 0..2))).unwrap() as i32, 2, "jpleph", "n1");
+        viv(eph.read_ascii(Cursor::new(jpldata(15, 1..3))).unwrap() as i32, 1, "jpleph", "n2");
+        assert_eq!(eph.loaded(), Some((JD0, JD0 + 3.0 * STEP)));
+
+        for t in [JD0, JD0 + 7.25, JD0 + 40.0, JD0 + 95.9, JD0 + 96.0] {
+            let mars = eph.pv(Target::Mars, Target::Ssb, t, 0.0).unwrap();
+            for c in 0..3 {
+                vvd(mars[0][c], jplval(15, 3, c, t), 1e-6, "jpleph", "mars");
+            }
+
+            /* Velocity against the numerical derivative. */
+            if t > JD0 && t < JD0 + 96.0 {
+                let h = 1e-4;
+                let m1 = eph.pv(Target::Mars, Target::Ssb, t, -h).unwrap();
+                let m2 = eph.pv(Target::Mars, Target::Ssb, t, h).unwrap();
+                let v = (m2[0][0] - m1[0][0]) / (2.0 * h) / 86400.0;
+                vvd(mars[1][0], v, 1e-6, "jpleph", "v");
+            }
+
+            let emrat = eph.emrat();
+            let au = eph.au();
+            let eb = eph.pvb(Target::Earth, t, 0.0).unwrap();
+            let mb = eph.pvb(Target::Moon, t, 0.0).unwrap();
+            for c in 0..3 {
+                let emb = jplval(15, 2, c, t);
+                let moon = jplval(15, 9, c, t);
+                vvd(eb[0][c] * au, emb - moon / (1.0 + emrat), 1e-6, "jpleph", "earth");
+                vvd(mb[0][c] * au, emb + moon * emrat / (1.0 + emrat), 1e-6, "jpleph", "moon");
+            }
+
+            let geo = eph.pv(Target::Moon, Target::Earth, t, 0.0).unwrap();
+            vvd(geo[0][1], jplval(15, 9, 1, t), 1e-6, "jpleph", "geomoon");
+
+            let nut = eph.nutation(t, 0.0).unwrap();
+            vvd(nut[0][1], jplval(15, 11, 1, t), 1e-6, "jpleph", "nut");
+            let lib = eph.libration(t, 0.0).unwrap();
+            vvd(lib[0][2], jplval(15, 12, 2, t), 1e-6, "jpleph", "lib");
+            let man = eph.mantle(t, 0.0).unwrap();
+            vvd(man[0][0], jplval(15, 13, 0, t), 1e-6, "jpleph", "mantle");
+            vvd(eph.ttmtdb(t, 0.0).unwrap(), jplval(15, 14, 0, t), 1e-6, "jpleph", "tt-tdb");
+        }
+
+        let j = eph.pv(Target::Sun, Target::Ssb, JD0 + 96.5, 0.0).unwrap_err();
+        viv(j, -1, "jpleph", "j 1");
+
+        /* Binary export and reload. */
+        let mut bin = Vec::new();
+        eph.write_binary(&mut bin).unwrap();
+        viv(bin.len() as i32, 5 * 8 * eph.ncoeff as i32, "jpleph", "size");
+        let bin = JplEph::read_binary(&mut Cursor::new(bin)).unwrap();
+        assert_eq!(bin.title, eph.title);
+        assert_eq!(bin.consts, eph.consts);
+        assert_eq!(bin.ncoeff, eph.ncoeff);
+        assert_eq!(bin.loaded(), eph.loaded());
+        assert_eq!(
+            bin.pv(Target::Jupiter, Target::Sun, JD0 + 50.0, 0.3),
+            eph.pv(Target::Jupiter, Target::Sun, JD0 + 50.0, 0.3)
+        );
+        assert_eq!(bin.ttmtdb(JD0 + 1.0, 0.0), eph.ttmtdb(JD0 + 1.0, 0.0));
+
+        /* An older layout, without TT-TDB. */
+        let mut old = JplEph::from_header(Cursor::new(jplheader(13))).unwrap();
+        old.read_ascii(Cursor::new(jpldata(13, 0..1))).unwrap();
+        vvd(
+            old.nutation(JD0 + 3.0, 0.0).unwrap()[0][0],
+            jplval(13, 11, 0, JD0 + 3.0),
+            1e-6,
+            "jpleph",
+            "old nut",
+        );
+        viv(old.ttmtdb(JD0, 0.0).unwrap_err(), -2, "jpleph", "j 2");
+
+        /* Gaps prevent export. */
+        let mut gap = JplEph::from_header(Cursor::new(jplheader(15))).unwrap();
+        gap.read_ascii(Cursor::new(jpldata(15, 0..1))).unwrap();
+        gap.read_ascii(Cursor::new(jpldata(15, 2..3))).unwrap();
+        assert!(gap.write_binary(&mut Vec::new()).is_err());
+        viv(gap.pvb(Target::Venus, JD0 + 40.0, 0.0).unwrap_err(), -1, "jpleph", "j 3");
+
+        /* A zero offset with coefficients present is not an item. */
+        let mut off = jplptrs(15).0;
+        let row = |off: &[usize]| off.iter().map(|x| format!("{:6}", x)).collect::<String>();
+        let h = jplheader(15).replace(&row(&off), &{
+            off[14] = 0;
+            row(&off)
+        });
+        let mut bad = JplEph::from_header(Cursor::new(h)).unwrap();
+        bad.read_ascii(Cursor::new(jpldata(15, 0..1))).unwrap();
+        assert!(!bad.has(14));
+        viv(bad.ttmtdb(JD0, 0.0).unwrap_err(), -2, "jpleph", "j 4");
+    }
 }
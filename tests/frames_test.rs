@@ -0,0 +1,288 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::common::vvd;
+    use sofars::astro::{atci13, atco13};
+    use sofars::consts::{DPI, DR2AS};
+    use sofars::frames::{Frame, FrameKind, SkyCoord, frpath};
+    use sofars::site::{Eop, Observer, Weather};
+    use sofars::ts::{taitt, utctai};
+
+    const UTC: (f64, f64) = (2456384.5, 0.969254051);
+
+    fn altaz() -> Frame {
+        Frame::AltAz {
+            obstime: UTC,
+            eop: Eop::new(0.1550675, 2.47230737e-7, 1.82640464e-6),
+            observer: Observer::new(-0.527800806, -1.2345856, 2738.0),
+            weather: Weather::new(731.0, 12.8, 0.59, 0.55),
+        }
+    }
+
+    #[test]
+    fn test_frpath() {
+        let path = frpath(FrameKind::Fk4, FrameKind::AltAz);
+        assert_eq!(
+            path,
+            vec![
+                FrameKind::Fk4,
+                FrameKind::Fk5,
+                FrameKind::Icrs,
+                FrameKind::Gcrs,
+                FrameKind::Cirs,
+                FrameKind::AltAz
+            ]
+        );
+        assert_eq!(
+            frpath(FrameKind::Tete, FrameKind::Tete),
+            vec![FrameKind::Tete]
+        );
+        assert_eq!(frpath(FrameKind::Itrs, FrameKind::Tete).len(), 4);
+    }
+
+    #[test]
+    fn test_icrs_altaz() {
+        let c = SkyCoord::new(Frame::Icrs, 2.71, 0.174)
+            .with_motion(1e-5, 5e-6, 55.0)
+            .with_distance(DR2AS / 0.1);
+        let o = c.transform_to(&altaz()).unwrap();
+
+        let (aob, zob, _, _, _, _) = atco13(
+            2.71,
+            0.174,
+            1e-5,
+            5e-6,
+            0.1,
+            55.0,
+            UTC.0,
+            UTC.1,
+            0.1550675,
+            -0.527800806,
+            -1.2345856,
+            2738.0,
+            2.47230737e-7,
+            1.82640464e-6,
+            731.0,
+            12.8,
+            0.59,
+            0.55,
+        )
+        .unwrap();
+
+        vvd(o.lon, aob, 1e-10, "transform_to", "az");
+        vvd(o.lat, DPI / 2.0 - zob, 1e-10, "transform_to", "alt");
+        assert!(o.pm.is_none());
+
+        /* And back to ICRS (astrometric place, no refraction). */
+        let topo = match altaz() {
+            Frame::AltAz {
+                obstime,
+                eop,
+                observer,
+                ..
+            } => Frame::AltAz {
+                obstime,
+                eop,
+                observer,
+                weather: Weather::vacuum(),
+            },
+            _ => unreachable!(),
+        };
+        let c = SkyCoord::new(Frame::Icrs, 2.71, 0.174).with_distance(2.5);
+        let b = c
+            .transform_to(&topo)
+            .unwrap()
+            .transform_to(&Frame::Icrs)
+            .unwrap();
+        vvd(b.lon, 2.71, 1e-12, "transform_to", "ra");
+        vvd(b.lat, 0.174, 1e-12, "transform_to", "dec");
+        vvd(b.distance.unwrap(), 2.5, 1e-12, "transform_to", "distance");
+    }
+
+    #[test]
+    fn test_icrs_tete() {
+        let (tai1, tai2) = utctai(UTC.0, UTC.1).unwrap();
+        let (tt1, tt2) = taitt(tai1, tai2).unwrap();
+        let (ri, di, eo) = atci13(2.71, 0.174, 1e-5, 5e-6, 0.1, 55.0, tt1, tt2);
+
+        let c = SkyCoord::new(Frame::Icrs, 2.71, 0.174)
+            .with_motion(1e-5, 5e-6, 55.0)
+            .with_distance(DR2AS / 0.1);
+
+        let ci = c.transform_to(&Frame::Cirs { obstime: UTC }).unwrap();
+        vvd(ci.lon, ri, 1e-12, "transform_to", "CIRS ra");
+        vvd(ci.lat, di, 1e-12, "transform_to", "CIRS dec");
+
+        let ct = c.transform_to(&Frame::Tete { obstime: UTC }).unwrap();
+        vvd(ct.lon, ri - eo, 1e-12, "transform_to", "TETE ra");
+        vvd(ct.lat, di, 1e-12, "transform_to", "TETE dec");
+    }
+
+    #[test]
+    fn test_itrs() {
+        let itrs = Frame::Itrs {
+            obstime: UTC,
+            eop: Eop::new(0.1550675, 2.47230737e-7, 1.82640464e-6),
+        };
+        let c = SkyCoord::new(Frame::Icrs, 1.0, -0.3);
+        let t = c.transform_to(&itrs).unwrap();
+        let b = t.transform_to(&Frame::Icrs).unwrap();
+
+        vvd(b.lon, 1.0, 1e-11, "transform_to", "ra");
+        vvd(b.lat, -0.3, 1e-11, "transform_to", "dec");
+    }
+
+    #[test]
+    fn test_galactic() {
+        let c = SkyCoord::new(
+            Frame::Icrs,
+            5.9338074302227188048671,
+            -1.1784870613579944551541,
+        );
+        let g = c.transform_to(&Frame::Galactic).unwrap();
+
+        vvd(g.lon, 5.5850536063818546461558, 1e-14, "transform_to", "l");
+        vvd(g.lat, -0.7853981633974483096157, 1e-14, "transform_to", "b");
+    }
+
+    #[test]
+    fn test_ecliptic() {
+        let c = SkyCoord::new(Frame::Icrs, 1.234, 0.987);
+        let e = c
+            .transform_to(&Frame::Ecliptic {
+                equinox: (1234.5, 2440000.5),
+            })
+            .unwrap();
+
+        vvd(e.lon, 1.342509918994654619, 1e-14, "transform_to", "lambda");
+        vvd(e.lat, 0.5926215259704608132, 1e-14, "transform_to", "beta");
+    }
+
+    #[test]
+    fn test_fk5() {
+        let c = SkyCoord::new(Frame::FK5_J2000, 1.76779433, -0.2917517103)
+            .with_motion(-1.91851572e-7, -5.8468475e-6, -7.6)
+            .with_distance(DR2AS / 0.379210);
+        let h = c.transform_to(&Frame::Icrs).unwrap();
+
+        vvd(h.lon, 1.767794226299947632, 1e-14, "transform_to", "ra");
+        vvd(h.lat, -0.2917516070530391757, 1e-14, "transform_to", "dec");
+        let (pr, pd) = h.pm.unwrap();
+        vvd(pr, -0.1961874125605721270e-6, 1e-19, "transform_to", "drh");
+        vvd(pd, -0.58459905176693911e-5, 1e-19, "transform_to", "ddh");
+        vvd(
+            h.rv.unwrap(),
+            -7.6000000940000254,
+            1e-11,
+            "transform_to",
+            "rv",
+        );
+
+        /* Precession to another equinox and back. */
+        let b = c
+            .transform_to(&Frame::Fk5 { equinox: 1975.0 })
+            .unwrap()
+            .transform_to(&Frame::FK5_J2000)
+            .unwrap();
+        vvd(b.lon, 1.76779433, 1e-12, "transform_to", "ra 2000");
+        vvd(b.lat, -0.2917517103, 1e-12, "transform_to", "dec 2000");
+        let (pr, pd) = b.pm.unwrap();
+        vvd(pr, -1.91851572e-7, 1e-16, "transform_to", "pmra 2000");
+        vvd(pd, -5.8468475e-6, 1e-16, "transform_to", "pmdec 2000");
+    }
+
+    #[test]
+    fn test_fk4() {
+        let c = SkyCoord::new(
+            Frame::FK4_B1950,
+            0.07626899753879587532,
+            -1.137405378399605780,
+        )
+        .with_motion(0.1973749217849087460e-4, 0.5659714913272723189e-5, 8.7)
+        .with_distance(DR2AS / 0.134);
+        let f = c.transform_to(&Frame::FK5_J2000).unwrap();
+
+        vvd(
+            f.lon,
+            0.08757989933556446040,
+            1e-14,
+            "transform_to",
+            "r2000",
+        );
+        vvd(f.lat, -1.132279113042091895, 1e-12, "transform_to", "d2000");
+        let (pr, pd) = f.pm.unwrap();
+        vvd(
+            pr,
+            0.1953670614474396139e-4,
+            1e-17,
+            "transform_to",
+            "dr2000",
+        );
+        vvd(
+            pd,
+            0.5637686678659640164e-5,
+            1e-18,
+            "transform_to",
+            "dd2000",
+        );
+        vvd(
+            DR2AS / f.distance.unwrap(),
+            0.1339919950582767871,
+            1e-13,
+            "transform_to",
+            "p2000",
+        );
+        vvd(
+            f.rv.unwrap(),
+            8.736999669183529069,
+            1e-12,
+            "transform_to",
+            "v2000",
+        );
+
+        /* Without proper motion, via iauFk45z. */
+        let c = SkyCoord::new(
+            Frame::Fk4 {
+                epoch: 1954.677617625256806,
+            },
+            0.01602284975382960982,
+            -0.1164347929099906024,
+        );
+        let f = c.transform_to(&Frame::FK5_J2000).unwrap();
+        let b = f
+            .transform_to(&Frame::Fk4 {
+                epoch: 1954.677617625256806,
+            })
+            .unwrap();
+        vvd(
+            b.lon,
+            0.01602284975382960982,
+            1e-10,
+            "transform_to",
+            "r1950",
+        );
+        vvd(
+            b.lat,
+            -0.1164347929099906024,
+            1e-10,
+            "transform_to",
+            "d1950",
+        );
+    }
+
+    #[test]
+    fn test_separation() {
+        let a = SkyCoord::new(Frame::Icrs, 1.0, 0.1);
+        let b = SkyCoord::new(Frame::Icrs, 1.1, 0.1)
+            .transform_to(&Frame::Galactic)
+            .unwrap();
+        vvd(
+            a.separation(&b).unwrap(),
+            0.09950000291386345,
+            1e-14,
+            "separation",
+            "",
+        );
+    }
+}
@@ -0,0 +1,821 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::common::{viv, vvd};
+    use sofars::consts::{DAS2R, DAU, DD2R, DJ00, GK};
+    use sofars::coords::ecm06;
+    use sofars::eph::{Ephemeris, Plan94, Target, epv00};
+    use sofars::orbit::{
+        Elements, MpcOrbit, NBody, Observation, Sight, cl2el, diffcorr, ecl2000, el2cl, el2pv,
+        gauss, kepler, laplace, packdes, pv2el, pvprop, stumpff, unpackdate, unpackdes,
+    };
+    use sofars::vm::{pdp, pm, rxp, sepp};
+    use std::io::Cursor;
+
+    const GM: f64 = GK * GK;
+
+    /* Energy and angular momentum z-component of a state. */
+    fn integrals(pv: &[[f64; 3]; 2], gm: f64) -> (f64, f64) {
+        (
+            0.5 * pdp(&pv[1], &pv[1]) - gm / pm(pv[0]),
+            pv[0][0] * pv[1][1] - pv[0][1] * pv[1][0],
+        )
+    }
+
+    #[test]
+    fn test_stumpff() {
+        let (c2, c3) = stumpff(0.0);
+        vvd(c2, 0.5, 1e-16, "stumpff", "c2 0");
+        vvd(c3, 1.0 / 6.0, 1e-16, "stumpff", "c3 0");
+
+        let pi2 = std::f64::consts::PI * std::f64::consts::PI;
+        let (c2, c3) = stumpff(pi2);
+        vvd(c2, 2.0 / pi2, 1e-15, "stumpff", "c2 pi^2");
+        vvd(c3, 1.0 / pi2, 1e-15, "stumpff", "c3 pi^2");
+
+        let (c2, c3) = stumpff(-4.0);
+        vvd(c2, (2f64.cosh() - 1.0) / 4.0, 1e-15, "stumpff", "c2 -4");
+        vvd(c3, (2f64.sinh() - 2.0) / 8.0, 1e-15, "stumpff", "c3 -4");
+
+        /* Series and closed forms agree at the changeover. */
+        for z in [0.1, -0.1] {
+            let (c2, c3) = stumpff(z);
+            let (d2, d3) = stumpff(f64::from_bits(z.to_bits() - 1));
+            vvd(c2, d2, 1e-15, "stumpff", "c2 continuity");
+            vvd(c3, d3, 1e-15, "stumpff", "c3 continuity");
+        }
+    }
+
+    #[test]
+    fn test_kepler() {
+        let x = kepler(1.0, 0.5).unwrap();
+        vvd(x, 1.498701133517848, 1e-14, "kepler", "ellipse");
+
+        let x = kepler(-20.0, 0.3).unwrap();
+        vvd(x - 0.3 * x.sin(), -20.0, 1e-12, "kepler", "revolutions");
+
+        for (m, e) in [(1e-4, 0.999), (3.1, 0.9999), (-0.5, 0.0)] {
+            let x = kepler(m, e).unwrap();
+            vvd(x - e * x.sin(), m, 1e-14, "kepler", "difficult ellipse");
+        }
+
+        for (m, e) in [(1.0, 2.0), (-1e-3, 1.001), (1e4, 1.5)] {
+            let x = kepler(m, e).unwrap();
+            vvd((e * x.sinh() - x) / m, 1.0, 1e-13, "kepler", "hyperbola");
+        }
+
+        viv(kepler(1.0, 1.0).unwrap_err(), -1, "kepler", "parabola");
+        viv(kepler(1.0, -0.1).unwrap_err(), -1, "kepler", "negative e");
+    }
+
+    #[test]
+    fn test_pvprop() {
+        /* Vallado, Example 2-4 (km, km/s). */
+        let pv = [
+            [1131.340, -2282.343, 6672.423],
+            [-5.64305, 4.30333, 2.42879],
+        ];
+        let p = pvprop(&pv, 398600.4418, 2400.0).unwrap();
+        vvd(p[0][0], -4219.7527, 1e-3, "pvprop", "x");
+        vvd(p[0][1], 4363.0292, 1e-3, "pvprop", "y");
+        vvd(p[0][2], -3958.7666, 1e-3, "pvprop", "z");
+        vvd(p[1][0], 3.689866, 1e-6, "pvprop", "vx");
+        vvd(p[1][1], -1.916735, 1e-6, "pvprop", "vy");
+        vvd(p[1][2], -6.112511, 1e-6, "pvprop", "vz");
+
+        /* Forward and back, for each kind of conic. */
+        for v in [0.012, 0.8 * (2.0 * GM).sqrt(), (2.0 * GM).sqrt(), 0.03] {
+            let pv = [[0.6, 0.8, 0.1], [-v * 0.8, v * 0.6, 0.0]];
+            let (en0, h0) = integrals(&pv, GM);
+            for dt in [-3000.0, 0.5, 400.0] {
+                let p = pvprop(&pv, GM, dt).unwrap();
+                let (en, h) = integrals(&p, GM);
+                vvd(en, en0, 1e-15, "pvprop", "energy");
+                vvd(h, h0, 1e-15, "pvprop", "momentum");
+                let b = pvprop(&p, GM, -dt).unwrap();
+                for i in 0..2 {
+                    for j in 0..3 {
+                        vvd(b[i][j], pv[i][j], 1e-10, "pvprop", "reversal");
+                    }
+                }
+            }
+        }
+
+        /* Long intervals:  hyperbolic and near-parabolic. */
+        let vp = (2.0 * GM).sqrt();
+        for (v, dt) in [
+            (0.05, 5000.0),
+            (vp * (1.0 + 1e-9), 36500.0),
+            (vp * (1.0 - 1e-9), 36500.0),
+        ] {
+            let pv = [[1.0, 0.0, 0.0], [0.0, v, 0.0]];
+            let p = pvprop(&pv, GM, dt).unwrap();
+            let b = pvprop(&p, GM, -dt).unwrap();
+            for i in 0..2 {
+                for j in 0..3 {
+                    vvd(b[i][j], pv[i][j], 1e-10, "pvprop", "long reversal");
+                }
+            }
+        }
+
+        viv(
+            pvprop(&[[0.0; 3], [0.01, 0.0, 0.0]], GM, 1.0).unwrap_err(),
+            -1,
+            "pvprop",
+            "r = 0",
+        );
+        viv(
+            pvprop(&[[1.0, 0.0, 0.0], [0.0; 3]], 0.0, 1.0).unwrap_err(),
+            -1,
+            "pvprop",
+            "gm = 0",
+        );
+    }
+
+    #[test]
+    fn test_el2pv() {
+        /* An ellipse, against the classical solution. */
+        let el = Elements {
+            q: 2.0,
+            e: 0.2,
+            incl: 0.3,
+            node: 1.2,
+            argp: 4.0,
+            tp: DJ00 - 300.0,
+        };
+        let pv = el2pv(&el, GM, DJ00, 0.0).unwrap();
+        let a = el.q / (1.0 - el.e);
+        let ea = kepler(GK / (a * a.sqrt()) * 300.0, el.e).unwrap();
+        vvd(pm(pv[0]), a * (1.0 - el.e * ea.cos()), 1e-14, "el2pv", "r");
+        vvd(
+            pv[0][2] / pm(pv[0]),
+            0.3f64.sin() * (4.0 + 2.0 * ((1.2f64 / 0.8).sqrt() * (0.5 * ea).tan()).atan()).sin(),
+            1e-14,
+            "el2pv",
+            "z",
+        );
+
+        /* Velocity by central difference. */
+        let pvm = el2pv(&el, GM, DJ00, -0.01).unwrap();
+        let pvp = el2pv(&el, GM, DJ00, 0.01).unwrap();
+        for i in 0..3 {
+            vvd(
+                pv[1][i],
+                (pvp[0][i] - pvm[0][i]) / 0.02,
+                1e-11,
+                "el2pv",
+                "v",
+            );
+        }
+
+        /* Perihelion. */
+        let pv = el2pv(&el, GM, el.tp, 0.0).unwrap();
+        vvd(pm(pv[0]), 2.0, 1e-15, "el2pv", "q");
+        vvd(pdp(&pv[0], &pv[1]), 0.0, 1e-17, "el2pv", "r.v");
+
+        viv(
+            el2pv(&Elements { q: 0.0, ..el }, GM, DJ00, 0.0).unwrap_err(),
+            -1,
+            "el2pv",
+            "q = 0",
+        );
+    }
+
+    #[test]
+    fn test_pv2el() {
+        for (e, incl) in [
+            (0.2, 0.3),
+            (1.0, 2.0),
+            (1.8, 0.7),
+            (0.9999, 0.1),
+            (0.01, 1.0),
+        ] {
+            let el = Elements {
+                q: 0.7,
+                e,
+                incl,
+                node: 5.9,
+                argp: 0.4,
+                tp: 2460000.5,
+            };
+            for dt in [-100.0, 0.0, 20.0] {
+                let pv = el2pv(&el, GM, el.tp, dt).unwrap();
+                let el2 = pv2el(&pv, GM, el.tp, dt).unwrap();
+                vvd(el2.q, el.q, 1e-13, "pv2el", "q");
+                vvd(el2.e, el.e, 1e-13, "pv2el", "e");
+                vvd(el2.incl, el.incl, 1e-13, "pv2el", "incl");
+                vvd(el2.node, el.node, 1e-12, "pv2el", "node");
+                vvd(el2.argp, el.argp, 1e-9, "pv2el", "argp");
+                vvd(el2.tp, el.tp, 1e-7, "pv2el", "tp");
+            }
+        }
+
+        /* Circular, equatorial:  perihelion and node on the x-axis. */
+        let v = GM.sqrt();
+        let el = pv2el(&[[0.0, 1.0, 0.0], [-v, 0.0, 0.0]], GM, DJ00, 0.0).unwrap();
+        vvd(el.e, 0.0, 1e-15, "pv2el", "circular e");
+        vvd(el.node, 0.0, 0.0, "pv2el", "circular node");
+        vvd(el.argp, 0.0, 0.0, "pv2el", "circular argp");
+        vvd(
+            el.tp,
+            DJ00 - 0.25 * 365.2568983,
+            1e-6,
+            "pv2el",
+            "circular tp",
+        );
+
+        viv(
+            pv2el(&[[1.0, 0.0, 0.0], [0.01, 0.0, 0.0]], GM, DJ00, 0.0).unwrap_err(),
+            -1,
+            "pv2el",
+            "rectilinear",
+        );
+    }
+
+    #[test]
+    fn test_cl2el() {
+        let el = cl2el(2.5, 0.1, 0.2, 0.3, 0.4, 1.0, DJ00, 0.0, GM).unwrap();
+        vvd(el.q, 2.25, 1e-15, "cl2el", "q");
+        let n = GK / (2.5f64 * 2.5f64.sqrt());
+        vvd(el.tp, DJ00 - 1.0 / n, 1e-8, "cl2el", "tp");
+
+        let (a, ma) = el2cl(&el, GM, DJ00, 100.0).unwrap();
+        vvd(a, 2.5, 1e-15, "el2cl", "a");
+        vvd(ma, 1.0 + 100.0 * n, 1e-12, "el2cl", "ma");
+
+        let el = cl2el(-3.0, 1.5, 0.2, 0.3, 0.4, -2.0, DJ00, 0.0, GM).unwrap();
+        vvd(el.q, 1.5, 1e-15, "cl2el", "hyperbola q");
+        let (a, ma) = el2cl(&el, GM, DJ00, 0.0).unwrap();
+        vvd(a, -3.0, 1e-15, "el2cl", "hyperbola a");
+        vvd(ma, -2.0, 1e-12, "el2cl", "hyperbola ma");
+
+        viv(
+            cl2el(2.5, 1.5, 0.0, 0.0, 0.0, 0.0, DJ00, 0.0, GM).unwrap_err(),
+            -1,
+            "cl2el",
+            "inconsistent a, e",
+        );
+        viv(
+            el2cl(
+                &Elements {
+                    q: 1.0,
+                    e: 1.0,
+                    ..el
+                },
+                GM,
+                DJ00,
+                0.0,
+            )
+            .unwrap_err(),
+            -1,
+            "el2cl",
+            "parabola",
+        );
+    }
+
+    #[test]
+    fn test_packdes() {
+        let cases = [
+            ("03202", "3202"),
+            ("A0345", "100345"),
+            ("z9999", "619999"),
+            ("~0000", "620000"),
+            ("~AZaz", "3140113"),
+            ("J95X00A", "1995 XA"),
+            ("J95X01L", "1995 XL1"),
+            ("K07Tf8A", "2007 TA418"),
+            ("PLS2040", "2040 P-L"),
+            ("T3S3141", "3141 T-3"),
+            ("0001P", "1P"),
+            ("CJ95O010", "C/1995 O1"),
+            ("PK05F02b", "P/2005 F2-B"),
+            ("J95O010", "1995 O1"),
+        ];
+        for (p, u) in cases {
+            assert_eq!(unpackdes(p).unwrap(), u, "unpackdes {}", p);
+            assert_eq!(packdes(u).unwrap(), p, "packdes {}", u);
+        }
+        assert_eq!(unpackdes("  00001  ").unwrap(), "1");
+
+        for p in ["", "0001", "Z95X00A", "J95x00A", "QJ95O010", "$0001"] {
+            viv(unpackdes(p).unwrap_err(), -1, "unpackdes", p);
+        }
+        for u in [
+            "0",
+            "15396336",
+            "1995 XA620",
+            "Q/1995 O1",
+            "1995 IA",
+            "3141 T-4",
+        ] {
+            viv(packdes(u).unwrap_err(), -1, "packdes", u);
+        }
+    }
+
+    #[test]
+    fn test_unpackdate() {
+        let (d1, d2) = unpackdate("K24AM").unwrap();
+        vvd(d1 + d2, 2460605.5, 0.0, "unpackdate", "K24AM");
+        let (d1, d2) = unpackdate("J9611").unwrap();
+        vvd(d1 + d2, 2450083.5, 0.0, "unpackdate", "J9611");
+        let (d1, d2) = unpackdate("K24AM5").unwrap();
+        vvd(d1 + d2, 2460606.0, 0.0, "unpackdate", "fraction");
+
+        viv(unpackdate("K24D1").unwrap_err(), -1, "unpackdate", "month");
+        viv(unpackdate("K24AW").unwrap_err(), -1, "unpackdate", "day");
+        viv(unpackdate("K24A").unwrap_err(), -1, "unpackdate", "short");
+    }
+
+    const CERES: &str = "00001    3.34  0.12 K205V 162.68631   73.73161   80.28698   \
+        10.58862  0.0775571  0.21406009   2.7676569  0 MPO492748  6751 115 1801-2019 0.60 \
+        M-v 30h Williams   0000      (1) Ceres              20190915";
+
+    #[test]
+    fn test_mpcorb() {
+        let o = MpcOrbit::parse(CERES).unwrap();
+        assert_eq!(o.desig, "1");
+        assert_eq!(o.name, "(1) Ceres");
+        vvd(o.h.unwrap(), 3.34, 0.0, "MpcOrbit", "H");
+        vvd(o.g.unwrap(), 0.12, 0.0, "MpcOrbit", "G");
+        vvd(o.epoch[0] + o.epoch[1], 2459000.5, 0.0, "MpcOrbit", "epoch");
+        vvd(o.ma, 162.68631 * DD2R, 1e-15, "MpcOrbit", "M");
+        vvd(o.incl, 10.58862 * DD2R, 1e-15, "MpcOrbit", "i");
+        vvd(o.e, 0.0775571, 0.0, "MpcOrbit", "e");
+        vvd(o.a, 2.7676569, 0.0, "MpcOrbit", "a");
+        assert_eq!((o.u, o.nobs, o.nopp, o.flags), ('0', 6751, 115, 0));
+        vvd(o.rms.unwrap(), 0.60, 0.0, "MpcOrbit", "rms");
+
+        /* Elements and state. */
+        let el = o.elements();
+        vvd(el.q, 2.7676569 * (1.0 - 0.0775571), 1e-15, "MpcOrbit", "q");
+        vvd(
+            el.tp,
+            2459000.5 - 162.68631 / 0.21406009,
+            1e-8,
+            "MpcOrbit",
+            "tp",
+        );
+        let pv = o.pv(o.epoch[0], o.epoch[1]).unwrap();
+        let (_, ma) = el2cl(&el, GK * GK, o.epoch[0], o.epoch[1]).unwrap();
+        vvd(ma, o.ma, 1e-6, "MpcOrbit", "mean motion");
+        let ea = kepler(o.ma, o.e).unwrap();
+        vvd(
+            pm(pv[0]),
+            o.a * (1.0 - o.e * ea.cos()),
+            1e-6,
+            "MpcOrbit",
+            "r",
+        );
+
+        /* The pole of the orbit, in the ecliptic frame. */
+        let mut h = [0.0; 3];
+        rxp(&ecl2000(), &sofars::vm::pxp(&pv[0], &pv[1]), &mut h);
+        vvd((h[2] / pm(h)).acos(), o.incl, 1e-12, "MpcOrbit", "pole");
+
+        /* A file with header and blank lines. */
+        let file = format!(
+            "MPCORB header\nDes'n     H     G   Epoch\n{}\n{}\n\n{}\n",
+            "-".repeat(160),
+            CERES,
+            CERES
+        );
+        let v = MpcOrbit::read(Cursor::new(file)).unwrap();
+        assert_eq!(v.len(), 2);
+
+        assert!(MpcOrbit::parse(&CERES.replace("K205V", "K20ZV")).is_err());
+        assert!(MpcOrbit::parse(&CERES[..90]).is_err());
+        assert!(MpcOrbit::read(Cursor::new(format!("{}\nrubbish\n", CERES))).is_err());
+    }
+
+    #[test]
+    fn test_ecl2000() {
+        let r = ecl2000();
+        vvd(r[1][2], (84381.448 * DAS2R).sin(), 1e-16, "ecl2000", "12");
+        let e = ecm06(DJ00, 0.0);
+        for i in 0..3 {
+            let mut a = [0.0; 3];
+            let mut b = [0.0; 3];
+            let mut p = [0.0; 3];
+            p[i] = 1.0;
+            rxp(&r, &p, &mut a);
+            rxp(&e, &p, &mut b);
+            assert!(sepp(&a, &b) < 0.1 * DAS2R, "ecl2000 vs ecm06");
+        }
+    }
+
+    const OBS80: [&str; 6] = [
+        "00433         C2004 12 20.12345 01 23 45.678-12 34 56.78         15.2 V      568",
+        "     K04X00A* S2020 12 16.50000 23 59 59.99 +00 00 01.0          19.5 G      C51",
+        "     K04X00A  s2020 12 16.50000 1 - 5634.1734 - 2466.2657 + 3038.3924        C51",
+        "    CJ95O010  V1997 03 01.25    22 50 15.1  +36 40 51                        247",
+        "    CJ95O010  v1997 03 01.25      245.123456 +33.123456   123                247",
+        "01566         R1968 06 14.12345                                              251",
+    ];
+
+    #[test]
+    fn test_obs80() {
+        let file = format!("COD 568\nOBS A. Observer\n{}\n\n", OBS80.join("\n"));
+        let v = Observation::read_obs80(Cursor::new(file)).unwrap();
+        assert_eq!(v.len(), 3);
+
+        let o = &v[0];
+        assert_eq!((o.desig.as_str(), o.kind, o.disc), ("433", 'C', false));
+        vvd(o.utc[0], 2400000.5, 0.0, "read_obs80", "utc1");
+        vvd(o.utc[1], 53359.12345, 1e-10, "read_obs80", "utc2");
+        vvd(
+            o.ra,
+            ((45.678 / 60.0 + 23.0) / 60.0 + 1.0) * 15.0 * DD2R,
+            1e-15,
+            "read_obs80",
+            "ra",
+        );
+        vvd(
+            o.dec,
+            -((56.78 / 60.0 + 34.0) / 60.0 + 12.0) * DD2R,
+            1e-15,
+            "read_obs80",
+            "dec",
+        );
+        vvd(o.mag.unwrap(), 15.2, 0.0, "read_obs80", "mag");
+        assert_eq!((o.band.as_str(), o.code.as_str()), ("V", "568"));
+        assert!(o.pos.is_none() && o.site.is_none() && o.rms.is_none());
+
+        let o = &v[1];
+        assert_eq!((o.desig.as_str(), o.kind, o.disc), ("2004 XA", 'S', true));
+        let p = o.pos.unwrap();
+        vvd(p[0], -5634.1734e3 / DAU, 1e-18, "read_obs80", "x");
+        vvd(p[2], 3038.3924e3 / DAU, 1e-18, "read_obs80", "z");
+
+        let o = &v[2];
+        assert_eq!((o.desig.as_str(), o.kind), ("C/1995 O1", 'V'));
+        assert!(o.mag.is_none());
+        let s = o.site.unwrap();
+        vvd(s[0], 245.123456 * DD2R, 1e-15, "read_obs80", "elong");
+        vvd(s[1], 33.123456 * DD2R, 1e-15, "read_obs80", "phi");
+        vvd(s[2], 123.0, 0.0, "read_obs80", "hm");
+
+        /* Satellite observation without its second line. */
+        assert!(Observation::read_obs80(Cursor::new(OBS80[1])).is_err());
+        assert!(Observation::read_obs80(Cursor::new(OBS80[2])).is_err());
+        assert!(Observation::parse_obs80(&OBS80[0].replace("12 20", "13 20")).is_err());
+    }
+
+    #[test]
+    fn test_ades() {
+        let file = "# version=2017\n\
+            # observatory\n\
+            ! mpcCode 568\n\
+            permID|provID  |trkSub|mode|stn|obsTime                 |ra         |dec        |rmsRA|rmsDec|mag |band\n\
+            433   |        |      |CCD |568|2004-12-20T02:57:46.08Z | 20.9403250|-12.5824389|0.150|0.120 |15.2|V\n\
+            \n\
+            # second block\n\
+            provID|trkSub|mode|stn|sys    |ctr|pos1      |pos2      |pos3     |obsTime             |ra |dec\n\
+            2004 XA|ABC1|CCD|C51|ICRF_KM|399|-5634.1734|-2466.2657|3038.3924|2020-12-16T12:00:00Z|0.0|0.0003\n";
+        let v = Observation::read_ades(Cursor::new(file)).unwrap();
+        assert_eq!(v.len(), 2);
+
+        let o = &v[0];
+        assert_eq!(
+            (o.desig.as_str(), o.kind, o.code.as_str()),
+            ("433", 'C', "568")
+        );
+        vvd(
+            o.utc[0] + o.utc[1],
+            2453359.5 + 0.12345,
+            1e-9,
+            "read_ades",
+            "utc",
+        );
+        vvd(o.ra, 20.9403250 * DD2R, 1e-15, "read_ades", "ra");
+        vvd(o.dec, -12.5824389 * DD2R, 1e-15, "read_ades", "dec");
+        let rms = o.rms.unwrap();
+        vvd(rms[0], 0.150 * DAS2R, 1e-20, "read_ades", "rmsRA");
+        vvd(rms[1], 0.120 * DAS2R, 1e-20, "read_ades", "rmsDec");
+
+        let o = &v[1];
+        assert_eq!((o.desig.as_str(), o.kind), ("2004 XA", 'S'));
+        vvd(
+            o.pos.unwrap()[1],
+            -2466.2657e3 / DAU,
+            1e-18,
+            "read_ades",
+            "y",
+        );
+        vvd(o.utc[1], 59199.5, 0.0, "read_ades", "utc2");
+        assert!(o.mag.is_none() && o.rms.is_none());
+
+        let bad = "stn|obsTime|ra|dec\n568|2004-12-20|1.0|2.0\n";
+        assert!(Observation::read_ades(Cursor::new(bad)).is_err());
+    }
+
+    /* A main-belt orbit, and geocentric sights of it from epoch T0. */
+    const T0: f64 = 2460000.5;
+
+    fn truth(d: f64) -> [[f64; 3]; 2] {
+        let el = Elements {
+            q: 2.2,
+            e: 0.15,
+            incl: 0.2,
+            node: 1.0,
+            argp: 2.0,
+            tp: T0 - 200.0,
+        };
+        let pv = el2pv(&el, GM, T0, d).unwrap();
+        let mut out = [[0.0; 3]; 2];
+        sofars::vm::trxpv(&ecl2000(), &pv, &mut out);
+        out
+    }
+
+    fn sights(days: &[f64]) -> Vec<Sight> {
+        let pv0 = truth(0.0);
+        days.iter()
+            .map(|&d| {
+                let (eh, _) = epv00(T0, d).unwrap();
+                let mut s = Sight {
+                    tdb: [T0, d],
+                    obs: eh,
+                    sigma: [1e-6; 2],
+                    ..Default::default()
+                };
+                (s.ra, s.dec, _) = s.predict(&pv0, T0, 0.0, GM).unwrap();
+                s
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sight() {
+        let s = sights(&[12.0])[0];
+        let (_, _, rho) = s.predict(&truth(0.0), T0, 0.0, GM).unwrap();
+        let p = truth(12.0 - rho / sofars::consts::DC)[0];
+        let d = [p[0] - s.obs[0][0], p[1] - s.obs[0][1], p[2] - s.obs[0][2]];
+        vvd(pm(d), rho, 1e-12, "Sight::predict", "light time");
+        let u = sofars::vm::s2c(s.ra, s.dec);
+        vvd(
+            pdp(&u, &d) / pm(d),
+            1.0,
+            1e-15,
+            "Sight::predict",
+            "direction",
+        );
+
+        /* Observer positions. */
+        let mut ob = Observation {
+            desig: "1".to_string(),
+            disc: false,
+            note1: ' ',
+            kind: 'S',
+            utc: [2460000.5, 0.25],
+            ra: 1.0,
+            dec: 0.5,
+            rms: None,
+            mag: None,
+            band: String::new(),
+            code: "C51".to_string(),
+            pos: Some([4e-5, 0.0, 0.0]),
+            site: None,
+        };
+        let s = Sight::new(&ob, None, 0.0, 0.0, 0.0).unwrap();
+        let (eh, _) = epv00(s.tdb[0], s.tdb[1]).unwrap();
+        vvd(
+            s.obs[0][0],
+            eh[0][0] + 4e-5,
+            1e-15,
+            "Sight::new",
+            "satellite",
+        );
+        vvd(
+            s.tdb[1] - 0.25,
+            (37.0 + 32.184) / 86400.0,
+            1e-7,
+            "Sight::new",
+            "TDB",
+        );
+        vvd(s.sigma[0], DAS2R, 0.0, "Sight::new", "sigma");
+
+        ob.pos = None;
+        viv(
+            Sight::new(&ob, None, 0.0, 0.0, 0.0).unwrap_err(),
+            -2,
+            "Sight::new",
+            "no site",
+        );
+        let s = Sight::new(&ob, Some([0.3, 0.9, 100.0]), 0.1, 0.0, 0.0).unwrap();
+        let d = [
+            s.obs[0][0] - eh[0][0],
+            s.obs[0][1] - eh[0][1],
+            s.obs[0][2] - eh[0][2],
+        ];
+        vvd(pm(d) * DAU, 6.37e6, 2e4, "Sight::new", "site");
+    }
+
+    #[test]
+    fn test_gauss() {
+        let s = sights(&[-6.0, 0.0, 7.0]);
+        let sols = gauss(&[s[0], s[1], s[2]], GM).unwrap();
+        let pv = truth(0.0);
+        let best = sols
+            .iter()
+            .map(|x| pm([x[0][0] - pv[0][0], x[0][1] - pv[0][1], x[0][2] - pv[0][2]]))
+            .fold(f64::MAX, f64::min);
+        vvd(best, 0.0, 1e-9, "gauss", "position");
+        let x = sols
+            .iter()
+            .find(|x| (x[0][0] - pv[0][0]).abs() < 1e-8)
+            .unwrap();
+        for k in 0..3 {
+            vvd(x[1][k], pv[1][k], 1e-11, "gauss", "velocity");
+        }
+
+        viv(
+            gauss(&[s[1], s[0], s[2]], GM).unwrap_err(),
+            -1,
+            "gauss",
+            "order",
+        );
+    }
+
+    #[test]
+    fn test_laplace() {
+        let s = sights(&[-4.0, -2.0, 0.0, 2.0, 4.0]);
+        let sols = laplace(&s, GM).unwrap();
+        let pv = truth(0.0);
+        let best = sols
+            .iter()
+            .map(|x| pm([x[0][0] - pv[0][0], x[0][1] - pv[0][1], x[0][2] - pv[0][2]]))
+            .fold(f64::MAX, f64::min);
+        assert!(best < 0.02, "laplace: position error {} au", best);
+
+        viv(laplace(&s[..2], GM).unwrap_err(), -1, "laplace", "too few");
+    }
+
+    #[test]
+    fn test_diffcorr() {
+        let days: Vec<f64> = (0..11).map(|i| -15.0 + 3.0 * i as f64).collect();
+        let mut s = sights(&days);
+        let pv = truth(0.0);
+        let start = [
+            [pv[0][0] + 1e-3, pv[0][1] - 2e-3, pv[0][2]],
+            [pv[1][0], pv[1][1] + 1e-5, pv[1][2]],
+        ];
+
+        /* Exact observations. */
+        let fit = diffcorr(&s, &start, T0, 0.0, GM).unwrap();
+        for i in 0..2 {
+            for k in 0..3 {
+                vvd(fit.pv[i][k], pv[i][k], 1e-10, "diffcorr", "pv");
+            }
+        }
+        assert!(fit.rms < 1e-4 && fit.niter > 1 && fit.niter < 20);
+        vvd(fit.el.q, 2.2, 1e-9, "diffcorr", "q");
+        vvd(fit.el.e, 0.15, 1e-9, "diffcorr", "e");
+        vvd(fit.el.argp, 2.0, 1e-8, "diffcorr", "argp");
+        for i in 0..6 {
+            assert!(fit.cov[i][i] > 0.0 && fit.elcov[i][i] > 0.0);
+        }
+
+        /* Noisy observations:  within the formal uncertainty. */
+        for (i, o) in s.iter_mut().enumerate() {
+            let w = if i % 2 == 0 { 1.0 } else { -1.0 };
+            o.ra += w * 0.5e-6 / o.dec.cos();
+            o.dec -= w * 0.5e-6 * ((i % 3) as f64 - 1.0);
+        }
+        let fit = diffcorr(&s, &start, T0, 0.0, GM).unwrap();
+        assert!(fit.rms > 0.1 && fit.rms < 0.6, "diffcorr rms {}", fit.rms);
+        for k in 0..3 {
+            assert!((fit.pv[0][k] - pv[0][k]).abs() < 5.0 * fit.cov[k][k].sqrt());
+        }
+        vvd(fit.resid[0][0], 0.5e-6, 0.5e-6, "diffcorr", "residual");
+
+        viv(
+            diffcorr(&s[..2], &start, T0, 0.0, GM).unwrap_err(),
+            -1,
+            "diffcorr",
+            "too few",
+        );
+    }
+
+    /* The Sun fixed at the barycenter. */
+    struct FixedSun;
+
+    impl Ephemeris for FixedSun {
+        fn pvb(&self, _: Target, _: f64, _: f64) -> Result<[[f64; 3]; 2], i32> {
+            Ok([[0.0; 3]; 2])
+        }
+    }
+
+    #[test]
+    fn test_nbody() {
+        let el = Elements {
+            q: 0.9,
+            e: 0.5,
+            incl: 0.4,
+            node: 1.0,
+            argp: 2.0,
+            tp: DJ00 + 30.0,
+        };
+        let pv = el2pv(&el, GM, DJ00, 0.0).unwrap();
+
+        /* Two-body problem:  agrees with pvprop. */
+        let mut nb = NBody::new(FixedSun);
+        nb.bodies = vec![(Target::Sun, GM)];
+        nb.gr = false;
+        let tr = nb.integrate(&pv, DJ00, 0.0, 1000.0).unwrap();
+        let (t1, t2) = tr.span();
+        vvd(t1, DJ00, 0.0, "nbody", "start");
+        vvd(t2, DJ00 + 1000.0, 1e-9, "nbody", "end");
+        assert!(tr.steps() > 10 && tr.steps() < 1000, "steps {}", tr.steps());
+        for dt in [0.0, 0.3, 29.7, 123.456, 517.0, 1000.0] {
+            let p = tr.pv(DJ00, dt).unwrap();
+            let q = pvprop(&pv, GM, dt).unwrap();
+            for i in 0..2 {
+                for k in 0..3 {
+                    vvd(p[i][k], q[i][k], 1e-9, "nbody", "two-body");
+                }
+            }
+        }
+        viv(tr.pv(DJ00, 1000.1).unwrap_err(), -1, "nbody", "after");
+        viv(tr.pv(DJ00, -0.1).unwrap_err(), -1, "nbody", "before");
+
+        /* Backward integration returns to the start. */
+        let q = tr.pv(DJ00, 1000.0).unwrap();
+        let back = nb.integrate(&q, DJ00 + 1000.0, 0.0, -1000.0).unwrap();
+        let p = back.pv(DJ00, 0.0).unwrap();
+        for i in 0..2 {
+            for k in 0..3 {
+                vvd(p[i][k], pv[i][k], 1e-9, "nbody", "reversal");
+            }
+        }
+        vvd(
+            back.pv(DJ00, 600.0).unwrap()[0][1],
+            tr.pv(DJ00, 600.0).unwrap()[0][1],
+            1e-9,
+            "nbody",
+            "back",
+        );
+
+        /* Relativistic perihelion advance of a Mercury-like orbit. */
+        let el = Elements {
+            q: 0.387 * (1.0 - 0.2056),
+            e: 0.2056,
+            tp: DJ00,
+            ..Default::default()
+        };
+        let (a, _) = el2cl(&el, GM, DJ00, 0.0).unwrap();
+        let period = sofars::consts::D2PI * (a * a * a / GM).sqrt();
+        let pv = el2pv(&el, GM, DJ00, 0.0).unwrap();
+        nb.gr = true;
+        let norb = 20.0;
+        let tr = nb.integrate(&pv, DJ00, 0.0, norb * period).unwrap();
+        let end = pv2el(
+            &tr.pv(DJ00, norb * period).unwrap(),
+            GM,
+            DJ00,
+            norb * period,
+        )
+        .unwrap();
+        let c = sofars::consts::DC;
+        let advance = 6.0 * sofars::consts::DPI * GM / (c * c * a * (1.0 - el.e * el.e));
+        vvd(
+            end.argp / norb,
+            advance,
+            0.02 * advance,
+            "nbody",
+            "perihelion advance",
+        );
+
+        /* Planetary perturbations:  a main-belt orbit over a year. */
+        let nb = NBody::new(Plan94);
+        let sun = Plan94.pvb(Target::Sun, DJ00, 0.0).unwrap();
+        let el = Elements {
+            q: 2.2,
+            e: 0.15,
+            incl: 0.2,
+            node: 1.5,
+            argp: 2.0,
+            tp: DJ00 - 200.0,
+        };
+        let gms = 2.9591220828559115e-4;
+        let pvh = el2pv(&el, gms, DJ00, 0.0).unwrap();
+        let pvb: [[f64; 3]; 2] =
+            std::array::from_fn(|i| std::array::from_fn(|k| pvh[i][k] + sun[i][k]));
+        let tr = nb.integrate(&pvb, DJ00, 0.0, 365.0).unwrap();
+        let p = tr.pv(DJ00, 365.0).unwrap();
+        let sun = Plan94.pvb(Target::Sun, DJ00, 365.0).unwrap();
+        let q = pvprop(&pvh, gms, 365.0).unwrap();
+        let d: Vec<f64> = (0..3).map(|k| p[0][k] - sun[0][k] - q[0][k]).collect();
+        let dm = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt();
+        assert!(dm > 1e-5 && dm < 1e-2, "perturbation {dm}");
+
+        /* Dense output agrees with integration to an intermediate date. */
+        let mid = nb.integrate(&pvb, DJ00, 0.0, 123.4).unwrap();
+        let p = mid.pv(DJ00, 123.4).unwrap();
+        let q = tr.pv(DJ00, 123.4).unwrap();
+        for i in 0..2 {
+            for k in 0..3 {
+                vvd(p[i][k], q[i][k], 1e-10, "nbody", "dense");
+            }
+        }
+    }
+}
@@ -0,0 +1,303 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::common::{viv, vvd};
+    use sofars::astro::{IauAstrom, apcg13, atciqb};
+    use sofars::consts::{DAS2R, DAU, DD2R};
+    use sofars::eph::{Moon98, Plan94, Target};
+    use sofars::physical::*;
+    use sofars::vm::{rxp, s2c, seps};
+
+    #[test]
+    fn test_wgccre() {
+        /* Jupiter at J2000.0. */
+        let (a0, d0, w) = wgccre(Target::Jupiter, 2451545.0, 0.0).unwrap();
+        vvd(a0, 268.057 * DD2R, 0.001 * DD2R, "wgccre", "jupiter a0");
+        vvd(d0, 64.495 * DD2R, 0.001 * DD2R, "wgccre", "jupiter d0");
+        vvd(w, 284.95 * DD2R, 1e-12, "wgccre", "jupiter w");
+
+        /* Rotation rates:  Mars direct, Venus retrograde. */
+        let (_, _, w1) = wgccre(Target::Mars, 2451545.0, 0.0).unwrap();
+        let (_, _, w2) = wgccre(Target::Mars, 2451545.0, 0.01).unwrap();
+        vvd(
+            w2 - w1,
+            3.50891982443297 * DD2R,
+            1e-8,
+            "wgccre",
+            "mars rate",
+        );
+        let (_, _, w1) = wgccre(Target::Venus, 2451545.0, 0.0).unwrap();
+        let (_, _, w2) = wgccre(Target::Venus, 2451545.0, 1.0).unwrap();
+        vvd(w2 - w1, -1.4813688 * DD2R, 1e-12, "wgccre", "venus rate");
+
+        /* The Moon's pole, about 1.5 deg from the ecliptic pole. */
+        let (a0, d0, _) = wgccre(Target::Moon, 2460600.5, 0.0).unwrap();
+        vvd(a0, 270.0 * DD2R, 4.0 * DD2R, "wgccre", "moon a0");
+        vvd(d0, 66.56 * DD2R, 1.6 * DD2R, "wgccre", "moon d0");
+
+        viv(
+            wgccre(Target::Emb, 2451545.0, 0.0).unwrap_err(),
+            -1,
+            "wgccre",
+            "j",
+        );
+    }
+
+    #[test]
+    fn test_bodyfix() {
+        let r = bodyfix(Target::Saturn, 2460000.5, 0.0).unwrap();
+        for i in 0..3 {
+            for j in 0..3 {
+                let d: f64 = (0..3).map(|k| r[i][k] * r[j][k]).sum();
+                vvd(
+                    d,
+                    if i == j { 1.0 } else { 0.0 },
+                    1e-15,
+                    "bodyfix",
+                    "orthogonal",
+                );
+            }
+        }
+
+        /* The pole maps to +z. */
+        let (a0, d0, _) = wgccre(Target::Saturn, 2460000.5, 0.0).unwrap();
+        let mut p = [0.0; 3];
+        rxp(&r, &s2c(a0, d0), &mut p);
+        vvd(p[2], 1.0, 1e-15, "bodyfix", "pole");
+
+        let (a, f) = bodyshape(Target::Mars).unwrap();
+        vvd(a, 3396.19, 1e-12, "bodyshape", "mars a");
+        vvd(f, 1.0 - 3376.20 / 3396.19, 1e-15, "bodyshape", "mars f");
+        viv(bodyshape(Target::Ssb).unwrap_err(), -1, "bodyshape", "j");
+    }
+
+    #[test]
+    fn test_physeph() {
+        /* Meeus, Example 42.a:  Mars, 1992 November 9, 0h TD. */
+        let e = physeph(&Plan94, Target::Mars, 2448935.5, 0.0).unwrap();
+        vvd(e.diam, 10.75 * DAS2R, 0.02 * DAS2R, "physeph", "mars diam");
+        vvd(e.k, 0.9012, 1e-3, "physeph", "mars k");
+        vvd(e.p, 347.64 * DD2R, 0.05 * DD2R, "physeph", "mars p");
+        vvd(
+            (e.lat.tan() * (3376.20f64 / 3396.19).powi(2)).atan(),
+            12.44 * DD2R,
+            0.05 * DD2R,
+            "physeph",
+            "mars de",
+        );
+        vvd(e.lon, 111.55 * DD2R, 0.5 * DD2R, "physeph", "mars cm");
+        vvd(e.slat, -2.76 * DD2R, 0.1 * DD2R, "physeph", "mars ds");
+
+        /* Meeus, Example 43.a:  Jupiter, 1992 December 16, 0h TD. */
+        let e = physeph(&Plan94, Target::Jupiter, 2448972.5, 0.0).unwrap();
+        vvd(e.p, 24.80 * DD2R, 0.05 * DD2R, "physeph", "jupiter p");
+        vvd(
+            (e.lat.tan() * (66854f64 / 71492.0).powi(2)).atan(),
+            -2.48 * DD2R,
+            0.05 * DD2R,
+            "physeph",
+            "jupiter de",
+        );
+        vvd(
+            e.lon,
+            349.024906309906 * DD2R,
+            1e-9,
+            "physeph",
+            "jupiter cm",
+        );
+
+        /* Meeus, Example 29.a:  the Sun, 1992 October 13, 0h TD. */
+        let e = physeph(&Plan94, Target::Sun, 2448908.5, 0.0).unwrap();
+        vvd(e.p, 26.27 * DD2R, 0.01 * DD2R, "physeph", "sun p");
+        vvd(e.lat, 5.99 * DD2R, 0.01 * DD2R, "physeph", "sun b0");
+        vvd(e.lon, 238.63 * DD2R, 0.01 * DD2R, "physeph", "sun l0");
+        vvd(e.phase, 0.0, 0.0, "physeph", "sun phase");
+        vvd(e.slat, e.lat, 0.0, "physeph", "sun ds");
+
+        viv(
+            physeph(&Plan94, Target::Earth, 2451545.0, 0.0).unwrap_err(),
+            -2,
+            "physeph",
+            "j",
+        );
+    }
+
+    #[test]
+    fn test_moonlibr() {
+        /* Meeus, Example 53.a:  1992 April 12, 0h TD. */
+        let m = moonlibr(&Moon98, 2448724.5, 0.0).unwrap();
+        vvd(m.lopt, -1.206 * DD2R, 0.003 * DD2R, "moonlibr", "lopt");
+        vvd(m.bopt, 4.194 * DD2R, 0.001 * DD2R, "moonlibr", "bopt");
+        vvd(m.l, -1.23 * DD2R, 0.01 * DD2R, "moonlibr", "l");
+        vvd(m.p, 15.08 * DD2R, 0.01 * DD2R, "moonlibr", "p");
+        vvd(m.l0, 67.89 * DD2R, 0.02 * DD2R, "moonlibr", "l0");
+        vvd(m.b0, 1.46 * DD2R, 0.02 * DD2R, "moonlibr", "b0");
+
+        /* Meeus's physical libration is in a frame a few hundredths of
+        a degree from the IAU mean Earth frame. */
+        vvd(m.b, 4.20 * DD2R, 0.03 * DD2R, "moonlibr", "b");
+        vvd(m.lphys, -0.025 * DD2R, 0.01 * DD2R, "moonlibr", "lphys");
+        vvd(m.bphys, 0.006 * DD2R, 0.03 * DD2R, "moonlibr", "bphys");
+        vvd(m.l, m.lopt + m.lphys, 1e-15, "moonlibr", "sum");
+        vvd(m.b, 0.07283247857275461, 1e-12, "moonlibr", "regression");
+    }
+
+    #[test]
+    fn test_selciq() {
+        let (tt1, tt2) = (2460600.5, 0.3);
+        let mut astrom = IauAstrom::default();
+        apcg13(tt1, tt2, &mut astrom);
+        let m = moonlibr(&Moon98, tt1, tt2).unwrap();
+        let (_, _, ri, di, dist, _) = atciqb(&Moon98, Target::Moon, tt1, tt2, &astrom).unwrap();
+
+        /* The sub-Earth point is in front of the center, displaced by
+        the barycentric motion of the Moon during the 6 ms difference in
+        light time. */
+        let (r, d, s) = selciq(&Moon98, m.l, m.b, 0.0, tt1, tt2, &astrom).unwrap();
+        vvd(r, ri, 1e-6, "selciq", "ri");
+        vvd(d, di, 1e-6, "selciq", "di");
+        vvd(s, dist - 1737.4e3 / DAU, 1e-10, "selciq", "dist");
+
+        /* A point 90 deg away is on the limb. */
+        let (r, d, _) = selciq(&Moon98, m.l + 90.0 * DD2R, 0.0, 0.0, tt1, tt2, &astrom).unwrap();
+        let sd = (1737.4e3 / DAU / dist).asin();
+        let sep = seps(r, d, ri, di);
+        assert!(sep < sd && sep > 0.9 * sd);
+
+        /* Height adds to the distance from the center. */
+        let (r, d, _) = selciq(&Moon98, m.l + 90.0 * DD2R, 0.0, 10.0, tt1, tt2, &astrom).unwrap();
+        assert!(seps(r, d, ri, di) > sep);
+    }
+
+    #[test]
+    fn test_planmag() {
+        /* Magnitudes at unit distances and zero phase. */
+        for (t, v) in [
+            (Target::Mercury, -0.613),
+            (Target::Venus, -4.384),
+            (Target::Earth, -3.99),
+            (Target::Mars, -1.601),
+            (Target::Jupiter, -9.395),
+            (Target::Neptune, -7.00),
+        ] {
+            vvd(
+                planmag(t, 1.0, 1.0, 0.0, 0.0, 0.0).unwrap(),
+                v,
+                1e-12,
+                "planmag",
+                "v10",
+            );
+        }
+        vvd(
+            planmag(Target::Jupiter, 5.0, 4.0, 0.0, 0.0, 0.0).unwrap(),
+            -9.395 + 5.0 * 20f64.log10(),
+            1e-12,
+            "planmag",
+            "distance",
+        );
+
+        /* Saturn's rings:  brighter when open, unlit from the far side. */
+        let b = 20.0 * DD2R;
+        let open = planmag(Target::Saturn, 1.0, 1.0, 0.0, b, b).unwrap();
+        let dark = planmag(Target::Saturn, 1.0, 1.0, 0.0, b, -b).unwrap();
+        assert!(open < -9.5);
+        vvd(dark, -8.95, 1e-12, "planmag", "globe");
+
+        /* Mars changes formula at 50 deg. */
+        let a = 50.0 * DD2R;
+        let v1 = planmag(Target::Mars, 1.0, 1.0, a * (1.0 - 1e-9), 0.0, 0.0).unwrap();
+        let v2 = planmag(Target::Mars, 1.0, 1.0, a * (1.0 + 1e-9), 0.0, 0.0).unwrap();
+        vvd(v1, v2, 0.02, "planmag", "mars");
+
+        viv(
+            planmag(Target::Moon, 1.0, 1.0, 0.0, 0.0, 0.0).unwrap_err(),
+            -1,
+            "planmag",
+            "j",
+        );
+
+        /* Oppositions of Jupiter (2022 September 26) and Saturn (2024
+        September 8). */
+        let v = planmage(&Plan94, Target::Jupiter, 2459848.5, 0.0).unwrap();
+        vvd(v, -2.94, 0.02, "planmage", "jupiter");
+        let v = planmage(&Plan94, Target::Saturn, 2460561.5, 0.0).unwrap();
+        vvd(v, 0.6, 0.1, "planmage", "saturn");
+        vvd(
+            planmage(&Plan94, Target::Mars, 2452879.5, 0.0).unwrap(),
+            -2.9348845160149204,
+            1e-12,
+            "planmage",
+            "regression",
+        );
+        viv(
+            planmage(&Plan94, Target::Sun, 2451545.0, 0.0).unwrap_err(),
+            -1,
+            "planmage",
+            "j",
+        );
+    }
+
+    #[test]
+    fn test_hgmag() {
+        vvd(
+            hgmag(10.0, 0.15, 2.0, 1.5, 0.0),
+            10.0 + 5.0 * 3f64.log10(),
+            1e-12,
+            "hgmag",
+            "zero",
+        );
+        vvd(
+            hgmag(0.0, 0.15, 1.0, 1.0, 20.0 * DD2R),
+            0.99963,
+            1e-4,
+            "hgmag",
+            "20 deg",
+        );
+        assert!(hgmag(0.0, 0.5, 1.0, 1.0, 20.0 * DD2R) < hgmag(0.0, 0.05, 1.0, 1.0, 20.0 * DD2R));
+
+        /* Basis functions at the spline nodes. */
+        let a = |d: f64| d * DD2R;
+        vvd(
+            hg1g2mag(0.0, 1.0, 0.0, 1.0, 1.0, 0.0),
+            0.0,
+            1e-15,
+            "hg1g2mag",
+            "zero",
+        );
+        vvd(
+            hg1g2mag(0.0, 1.0, 0.0, 1.0, 1.0, a(60.0)),
+            -2.5 * 1.3410560e-1f64.log10(),
+            1e-7,
+            "hg1g2mag",
+            "phi1",
+        );
+        vvd(
+            hg1g2mag(0.0, 0.0, 1.0, 1.0, 1.0, a(30.0)),
+            -2.5 * 6.2884169e-1f64.log10(),
+            1e-7,
+            "hg1g2mag",
+            "phi2",
+        );
+        vvd(
+            hg1g2mag(0.0, 0.0, 0.0, 1.0, 1.0, a(8.0)),
+            -2.5 * 1.0348178e-1f64.log10(),
+            1e-7,
+            "hg1g2mag",
+            "phi3",
+        );
+
+        /* Continuity where the basis functions change form. */
+        for d in [7.5, 30.0] {
+            let v1 = hg1g2mag(0.0, 0.26, 0.38, 1.0, 1.0, a(d) * (1.0 - 1e-9));
+            let v2 = hg1g2mag(0.0, 0.26, 0.38, 1.0, 1.0, a(d) * (1.0 + 1e-9));
+            vvd(v1, v2, 1e-8, "hg1g2mag", "continuity");
+        }
+
+        /* Close to H,G with G = 0.15. */
+        for d in [5.0, 20.0] {
+            let v1 = hg1g2mag(0.0, 0.26, 0.38, 1.0, 1.0, a(d));
+            vvd(v1, hgmag(0.0, 0.15, 1.0, 1.0, a(d)), 0.07, "hg1g2mag", "hg");
+        }
+    }
+}
@@ -0,0 +1,165 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::common::{viv, vvd};
+    use sofars::consts::{DAU, DD2R};
+    use sofars::eph::Plan94;
+    use sofars::satellites::*;
+    use sofars::vm::{c2s, pdp, pm};
+
+    /* Meeus, Example 44.b:  1992 December 16, 0h UT (TT - UT = 59s). */
+    const T0: f64 = 2448972.5;
+    const DT: f64 = 59.0 / 86400.0;
+
+    #[test]
+    fn test_galsat() {
+        let p = galsat(T0, DT);
+        for (i, a) in [5.90569, 9.39657, 14.98832, 26.36273].iter().enumerate() {
+            let r = pm(p[i]) * DAU / 71398e3;
+            vvd(r, *a, 0.015 * a, "galsat", "radius");
+        }
+        vvd(p[0][0], 0.00270437936803256, 1e-14, "galsat", "x1");
+        vvd(p[3][2], 0.00265986393288012, 1e-14, "galsat", "z4");
+
+        /* Jupiter's pole (IAU:  268.057, 64.495 deg). */
+        let (a, d) = c2s(&Satellite::Io.pole(2451545.0, 0.0));
+        vvd(
+            a + 360.0 * DD2R,
+            268.057 * DD2R,
+            0.02 * DD2R,
+            "galsat",
+            "pole ra",
+        );
+        vvd(d, 64.495 * DD2R, 0.02 * DD2R, "galsat", "pole dec");
+
+        /* Orbits close to Jupiter's equator. */
+        let n = Satellite::Io.pole(T0, DT);
+        for (q, imax) in p.iter().zip([0.1, 0.6, 0.3, 0.3]) {
+            assert!((pdp(q, &n) / pm(*q)).asin().abs() < imax * DD2R);
+        }
+    }
+
+    #[test]
+    fn test_titan() {
+        let p = titan(T0, DT);
+        vvd(p[0], 0.00740363850767258, 1e-14, "titan", "x");
+        vvd(p[2], -0.000845331402503943, 1e-14, "titan", "z");
+
+        /* Saturn's pole (IAU:  40.589, 83.537 deg). */
+        let (a, d) = c2s(&Satellite::Titan.pole(T0, DT));
+        vvd(a, 40.589 * DD2R, 0.1 * DD2R, "titan", "pole ra");
+        vvd(d, 83.537 * DD2R, 0.05 * DD2R, "titan", "pole dec");
+
+        /* Distance, latitude and period over a year. */
+        let n = Satellite::Titan.pole(T0, DT);
+        let mut prev = p;
+        let mut nodes = Vec::new();
+        for i in 1..=1000 {
+            let t = i as f64 * 0.4;
+            let q = titan(T0, t);
+            let r = pm(q) * DAU / 1e3;
+            assert!((1.18e6..1.26e6).contains(&r), "titan r {r}");
+            assert!((pdp(&q, &n) / pm(q)).asin().abs() < 0.7 * DD2R);
+            if pdp(&prev, &n) < 0.0 && pdp(&q, &n) >= 0.0 {
+                nodes.push(t);
+            }
+            prev = q;
+        }
+        let period = (nodes[nodes.len() - 1] - nodes[0]) / (nodes.len() - 1) as f64;
+        vvd(period, 15.945, 0.05, "titan", "period");
+    }
+
+    #[test]
+    fn test_satxy() {
+        /* Meeus's values, in units of 71398 km. */
+        let k = 71492.0 / 71398.0;
+        let want = [
+            [-3.4502, 0.2137],
+            [7.4418, 0.2753],
+            [1.2011, 0.5900],
+            [7.0720, 1.0291],
+        ];
+        let sats = [
+            Satellite::Io,
+            Satellite::Europa,
+            Satellite::Ganymede,
+            Satellite::Callisto,
+        ];
+        for (s, w) in sats.iter().zip(want) {
+            let xyz = satxy(&Plan94, *s, T0, DT).unwrap();
+            vvd(xyz[0] * k, w[0], 3e-3, "satxy", "X");
+            vvd(xyz[1] * k, w[1], 1e-3, "satxy", "Y");
+            assert!(xyz[2] < 0.0);
+        }
+
+        let xyz = satxy(&Plan94, Satellite::Titan, T0, DT).unwrap();
+        vvd(xyz[0], -17.25626009746914, 1e-10, "satxy", "Titan X");
+        vvd(xyz[1], 2.781790026653745, 1e-10, "satxy", "Titan Y");
+    }
+
+    #[test]
+    fn test_satevents() {
+        let t0 = 2460600.5;
+        let ev = satevents(&Plan94, &Satellite::ALL, t0, 0.0, 5.0).unwrap();
+        viv(ev.len() as i32, 20, "satevents", "count");
+
+        for e in &ev {
+            let (b, f) = (e.begin.unwrap(), e.end.unwrap());
+            assert!(b.1 < f.1 && b.1 > 0.0 && f.1 < 5.1);
+
+            /* Durations. */
+            let hours = (f.1 - b.1) * 24.0;
+            let (lo, hi) = match e.sat {
+                Satellite::Io => (2.0, 2.3),
+                Satellite::Europa => (2.3, 2.7),
+                _ => (1.0, 5.0),
+            };
+            assert!(hours > lo && hours < hi, "{:?} {:?} {hours}", e.sat, e.kind);
+
+            /* Contacts on the limb, and the satellite inside at the middle. */
+            if matches!(e.kind, SatPhenom::Transit | SatPhenom::Occultation) {
+                for t in [b, f] {
+                    let xyz = satxy(&Plan94, e.sat, t.0, t.1).unwrap();
+                    vvd(xyz[0].hypot(xyz[1] / 0.936), 1.0, 2e-3, "satevents", "limb");
+                }
+                let xyz = satxy(&Plan94, e.sat, t0, 0.5 * (b.1 + f.1)).unwrap();
+                assert!(xyz[0].abs() < 0.1);
+                assert_eq!(xyz[2] < 0.0, e.kind == SatPhenom::Transit);
+            }
+        }
+
+        /* Before opposition the shadow precedes the transit. */
+        let io: Vec<&SatEvent> = ev.iter().filter(|e| e.sat == Satellite::Io).collect();
+        assert_eq!(io[0].kind, SatPhenom::Shadow);
+        assert_eq!(io[1].kind, SatPhenom::Transit);
+        assert_eq!(io[2].kind, SatPhenom::Eclipse);
+        assert_eq!(io[3].kind, SatPhenom::Occultation);
+        vvd(
+            io[0].begin.unwrap().1,
+            0.0714210890,
+            1e-6,
+            "satevents",
+            "Io shadow",
+        );
+        vvd(
+            io[1].begin.unwrap().1,
+            0.1181079490,
+            1e-6,
+            "satevents",
+            "Io transit",
+        );
+
+        /* An event in progress at the start. */
+        let ev = satevents(&Plan94, &[Satellite::Io], t0, 0.1, 1.0).unwrap();
+        let e = ev.iter().find(|e| e.begin.is_none()).unwrap();
+        assert_eq!(e.kind, SatPhenom::Shadow);
+        vvd(
+            e.end.unwrap().1,
+            io[0].end.unwrap().1,
+            1e-6,
+            "satevents",
+            "end",
+        );
+    }
+}
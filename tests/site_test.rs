@@ -0,0 +1,121 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::common::{viv, vvd};
+    use sofars::site::{Eop, Observer, Weather};
+
+    fn site() -> (Observer, Eop, Weather) {
+        (
+            Observer::new(-0.527800806, -1.2345856, 2738.0),
+            Eop::new(0.1550675, 2.47230737e-7, 1.82640464e-6),
+            Weather::new(731.0, 12.8, 0.59, 0.55),
+        )
+    }
+
+    #[test]
+    fn test_observer_wgs84() {
+        let obs = Observer::with_ellipsoid(2, -0.527800806, -1.2345856, 2738.0).unwrap();
+        let (elong, phi, hm) = obs.wgs84().unwrap();
+
+        vvd(elong, -0.527800806, 1e-14, "wgs84", "elong");
+        vvd(phi, -1.234585600000000, 1e-9, "wgs84", "phi");
+        vvd(hm, 2737.999906620596, 1e-6, "wgs84", "hm");
+
+        let j = Observer::with_ellipsoid(4, 0.0, 0.0, 0.0).unwrap_err();
+        viv(j, -1, "with_ellipsoid", "j");
+    }
+
+    #[test]
+    fn test_weather_standard() {
+        let wx = Weather::standard(2738.0, 12.8, 0.59, 0.55);
+        vvd(wx.phpa, 744.9152523841028, 1e-9, "standard", "phpa");
+
+        let (refa, refb) = Weather::vacuum().refco();
+        vvd(refa, 0.0, 0.0, "vacuum", "refa");
+        vvd(refb, 0.0, 0.0, "vacuum", "refb");
+    }
+
+    #[test]
+    fn test_observer_atco13() {
+        let (obs, eop, wx) = site();
+        let (aob, zob, hob, dob, rob, eo) = obs
+            .atco13(
+                2.71,
+                0.174,
+                1e-5,
+                5e-6,
+                0.1,
+                55.0,
+                2456384.5,
+                0.969254051,
+                &eop,
+                &wx,
+            )
+            .unwrap();
+
+        vvd(aob, 0.9251774485485515207e-1, 1e-12, "atco13", "aob");
+        vvd(zob, 1.407661405256499357, 1e-12, "atco13", "zob");
+        vvd(hob, -0.9265154431529724692e-1, 1e-12, "atco13", "hob");
+        vvd(dob, 0.1716626560072526200, 1e-12, "atco13", "dob");
+        vvd(rob, 2.710260453504961012, 1e-12, "atco13", "rob");
+        vvd(eo, -0.003020548354802412839, 1e-14, "atco13", "eo");
+    }
+
+    #[test]
+    fn test_observer_atio13() {
+        let (obs, eop, wx) = site();
+        let (_, zob, _, dob, rob) = obs
+            .atio13(
+                2.710121572969038991,
+                0.1729371367218230438,
+                2456384.5,
+                0.969254051,
+                &eop,
+                &wx,
+            )
+            .unwrap();
+
+        vvd(zob, 1.407758704513549991, 1e-12, "atio13", "zob");
+        vvd(dob, 0.1717653435756234676, 1e-12, "atio13", "dob");
+        vvd(rob, 2.710085107988480746, 1e-12, "atio13", "rob");
+    }
+
+    #[test]
+    fn test_observer_atoc13() {
+        let (obs, eop, wx) = site();
+        let (rc, dc) = obs
+            .atoc13(
+                "R",
+                2.710085107986886201,
+                0.1717653435758265198,
+                2456384.5,
+                0.969254051,
+                &eop,
+                &wx,
+            )
+            .unwrap();
+
+        vvd(rc, 2.709956744659136129, 1e-12, "atoc13", "R/rc");
+        vvd(dc, 0.1741696500898471362, 1e-12, "atoc13", "R/dc");
+    }
+
+    #[test]
+    fn test_observer_atoi13() {
+        let (obs, eop, wx) = site();
+        let (ri, di) = obs
+            .atoi13(
+                "R",
+                2.710085107986886201,
+                0.1717653435758265198,
+                2456384.5,
+                0.969254051,
+                &eop,
+                &wx,
+            )
+            .unwrap();
+
+        vvd(ri, 2.710121574447540810, 1e-12, "atoi13", "R/ri");
+        vvd(di, 0.1729371839116608778, 1e-12, "atoi13", "R/di");
+    }
+}
@@ -0,0 +1,92 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::common::{viv, vvd};
+    use sofars::consts::{DAU, DD2R};
+    use sofars::eph::Plan94;
+    use sofars::solar::*;
+
+    #[test]
+    fn test_sunpbl() {
+        /* Meeus, Example 29.a:  1992 October 13, 0h TD. */
+        let (p, b0, l0, dist) = sunpbl(&Plan94, 2448908.5, 0.0).unwrap();
+        vvd(p, 26.27 * DD2R, 0.01 * DD2R, "sunpbl", "p");
+        vvd(b0, 5.99 * DD2R, 0.01 * DD2R, "sunpbl", "b0");
+        vvd(l0, 238.63 * DD2R, 0.01 * DD2R, "sunpbl", "l0");
+        vvd(dist, 0.99761, 1e-4, "sunpbl", "dist");
+    }
+
+    #[test]
+    fn test_carrington() {
+        /* Meeus, Example 29.b:  rotation 1699. */
+        let (tt1, tt2) = carrstart(&Plan94, 1699).unwrap();
+        vvd(tt1 + tt2, 2444480.7230, 0.002, "carrstart", "1699");
+        vvd(
+            carrington(&Plan94, tt1, tt2 + 0.01).unwrap(),
+            1699.0,
+            1e-3,
+            "carrington",
+            "start",
+        );
+
+        /* Rotation 2290 began on 2024 October 19. */
+        let (tt1, tt2) = carrstart(&Plan94, 2290).unwrap();
+        vvd(tt1 + tt2, 2460600.37871878, 1e-6, "carrstart", "2290");
+
+        let c = carrington(&Plan94, 2448908.5, 0.0).unwrap();
+        viv(c.floor() as i32, 1861, "carrington", "n");
+        vvd(
+            c.fract(),
+            1.0 - 238.63 / 360.0,
+            1e-4,
+            "carrington",
+            "fraction",
+        );
+    }
+
+    #[test]
+    fn test_hgs2hpc() {
+        let dsun = DAU / 695700e3;
+        let b0 = 5.0 * DD2R;
+
+        /* The sub-observer point is at the center of the disk. */
+        let (tx, ty, d) = hgs2hpc(0.0, b0, 1.0, b0, dsun);
+        vvd(tx, 0.0, 1e-15, "hgs2hpc", "tx");
+        vvd(ty, 0.0, 1e-15, "hgs2hpc", "ty");
+        vvd(d, dsun - 1.0, 1e-12, "hgs2hpc", "d");
+
+        /* Western hemisphere and north pole. */
+        let (tx, _, _) = hgs2hpc(30.0 * DD2R, 0.0, 1.0, b0, dsun);
+        assert!(tx > 0.0);
+        let (tx, ty, _) = hgs2hpc(0.0, 90.0 * DD2R, 1.0, b0, dsun);
+        vvd(tx, 0.0, 1e-15, "hgs2hpc", "pole tx");
+        vvd(
+            ty,
+            (b0.cos() / (dsun - b0.sin())).atan(),
+            1e-15,
+            "hgs2hpc",
+            "pole ty",
+        );
+
+        /* Round trip. */
+        let (lon, lat) = (-40.0 * DD2R, 25.0 * DD2R);
+        let (tx, ty, d) = hgs2hpc(lon, lat, 1.0, b0, dsun);
+        let (lon2, lat2, d2) = hpc2hgs(tx, ty, b0, dsun, 1.0).unwrap();
+        vvd(lon2, lon, 1e-10, "hpc2hgs", "lon");
+        vvd(lat2, lat, 1e-10, "hpc2hgs", "lat");
+        vvd(d2, d, 1e-9, "hpc2hgs", "d");
+
+        /* Tangent point on the limb, and beyond it. */
+        let rho = (1.0 / dsun).asin();
+        let (lon, lat, _) = hpc2hgs(rho * (1.0 - 1e-8), 0.0, 0.0, dsun, 1.0).unwrap();
+        vvd(lon, (1.0 / dsun).acos(), 1e-3, "hpc2hgs", "limb");
+        vvd(lat, 0.0, 1e-15, "hpc2hgs", "limb lat");
+        viv(
+            hpc2hgs(rho * 1.001, 0.0, 0.0, dsun, 1.0).unwrap_err(),
+            -1,
+            "hpc2hgs",
+            "j",
+        );
+    }
+}
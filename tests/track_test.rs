@@ -0,0 +1,159 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::common::{viv, vvd};
+    use sofars::consts::{CMPS, DAYSEC, DC};
+    use sofars::track::{dop2w, dop3w, lt1w, lt2w, lt3w, shapiro, stapvb};
+    use sofars::vm::{pm, pmp};
+
+    /* Spacecraft in uniform motion, about 1.2 au from the Earth. */
+    fn sc(d1: f64, d2: f64) -> [[f64; 3]; 2] {
+        let t = (d1 - 2456384.5) + d2;
+        [
+            [0.4 + 0.01 * t, -1.6 - 0.002 * t, -0.3 + 0.001 * t],
+            [0.01, -0.002, 0.001],
+        ]
+    }
+
+    const UTC1: f64 = 2456384.5;
+    const UTC2: f64 = 0.969254051;
+    const DUT1: f64 = 0.1550675;
+    const ELONG: f64 = -0.527800806;
+    const PHI: f64 = -1.2345856;
+    const HM: f64 = 2738.0;
+    const XP: f64 = 2.47230737e-7;
+    const YP: f64 = 1.82640464e-6;
+
+    #[test]
+    fn test_shapiro() {
+        let ps = [0.0, 0.0, 0.0];
+
+        let dt = shapiro(&[1.0, 0.0, 0.0], &[-1.0, 0.01, 0.0], &ps);
+        vvd(dt * DAYSEC, 1.180442332626e-4, 1e-15, "shapiro", "grazing");
+
+        let dt = shapiro(&[1.0, 0.0, 0.0], &[0.0, 1.0, 0.0], &ps);
+        vvd(
+            dt * DAYSEC,
+            1.736479050275e-5,
+            1e-15,
+            "shapiro",
+            "quadrature",
+        );
+
+        let dt = shapiro(&[1.0, 0.0, 0.0], &[-1.0, 0.0, 0.0], &ps);
+        vvd(dt, 0.0, 0.0, "shapiro", "through Sun");
+    }
+
+    #[test]
+    fn test_stapvb() {
+        let (tdb1, tdb2, pv) = stapvb(UTC1, UTC2, DUT1, ELONG, PHI, HM, XP, YP).unwrap();
+
+        vvd(tdb1, 2456384.5, 0.0, "stapvb", "tdb1");
+        vvd(tdb2, 0.970031662558530, 1e-12, "stapvb", "tdb2");
+        vvd(pv[0][0], -0.9741827106629827, 1e-12, "stapvb", "x");
+        vvd(pv[0][1], -0.2115130193415925, 1e-12, "stapvb", "y");
+        vvd(pv[0][2], -0.0917984020218351, 1e-12, "stapvb", "z");
+        vvd(pv[1][0], 0.0035991665938183, 1e-12, "stapvb", "xd");
+        vvd(pv[1][1], -0.0155057253419565, 1e-12, "stapvb", "yd");
+        vvd(pv[1][2], -0.0066891642378299, 1e-12, "stapvb", "zd");
+    }
+
+    #[test]
+    fn test_lt1w() {
+        let (tdb1, tdb2, lt, pv) = lt1w(UTC1, UTC2, DUT1, ELONG, PHI, HM, XP, YP, sc).unwrap();
+
+        vvd(tdb1, 2456384.5, 0.0, "lt1w", "tdb1");
+        vvd(tdb2, 0.958639109387163, 1e-12, "lt1w", "tdb2");
+        vvd(lt, 984.316594006113, 1e-7, "lt1w", "lt");
+        vvd(pv[0][0], 0.4095863910938716, 1e-12, "lt1w", "x");
+
+        /* Light time is consistent with the epochs and the geometry. */
+        let (r1, r2, pvr) = stapvb(UTC1, UTC2, DUT1, ELONG, PHI, HM, XP, YP).unwrap();
+        vvd(
+            ((r1 - tdb1) + (r2 - tdb2)) * DAYSEC,
+            lt,
+            1e-8,
+            "lt1w",
+            "epochs",
+        );
+        vvd(
+            pm(pmp(&pv[0], &pvr[0])) / DC * DAYSEC,
+            lt,
+            2e-5,
+            "lt1w",
+            "geometric",
+        );
+
+        /* Outside the range of the Earth ephemeris. */
+        viv(
+            lt1w(2506331.5, 0.5, 0.0, ELONG, PHI, HM, XP, YP, sc).unwrap_err(),
+            -1,
+            "lt1w",
+            "j",
+        );
+    }
+
+    #[test]
+    fn test_lt2w() {
+        let (u1, u2, s1, s2, rtlt) = lt2w(UTC1, UTC2, DUT1, ELONG, PHI, HM, XP, YP, sc).unwrap();
+
+        vvd(u1, 2456384.5, 0.0, "lt2w", "utc1");
+        vvd(u2, 0.94646708245436, 1e-12, "lt2w", "utc2");
+        vvd(s1, 2456384.5, 0.0, "lt2w", "tdb1");
+        vvd(s2, 0.958639109387163, 1e-12, "lt2w", "tdb2");
+        vvd(rtlt, 1968.794082414316, 1e-7, "lt2w", "rtlt");
+    }
+
+    #[test]
+    fn test_lt3w() {
+        let (u1, u2, s1, s2, rtlt) = lt3w(
+            UTC1, UTC2, DUT1, ELONG, PHI, HM, 0.3, 0.5, 100.0, XP, YP, sc,
+        )
+        .unwrap();
+
+        vvd(u1, 2456384.5, 0.0, "lt3w", "utc1");
+        vvd(u2, 0.946467001117579, 1e-12, "lt3w", "utc2");
+        vvd(s1, 2456384.5, 0.0, "lt3w", "tdb1");
+        vvd(s2, 0.958639109387163, 1e-12, "lt3w", "tdb2");
+        vvd(rtlt, 1968.801110694848, 1e-7, "lt3w", "rtlt");
+    }
+
+    #[test]
+    fn test_dop2w() {
+        let m2 = 880.0 / 749.0;
+        let (fd, rr) = dop2w(
+            UTC1, UTC2, 60.0, DUT1, ELONG, PHI, HM, XP, YP, 7.2e9, m2, sc,
+        )
+        .unwrap();
+
+        vvd(fd, 569718.229960803, 1e-3, "dop2w", "fd");
+        vvd(rr, -10095.259167218386, 1e-5, "dop2w", "rr");
+        vvd(fd, -2.0 * rr / CMPS * 7.2e9 * m2, 1e-6, "dop2w", "fd/rr");
+    }
+
+    #[test]
+    fn test_dop3w() {
+        let (fd, rr) = dop3w(
+            UTC1,
+            UTC2,
+            60.0,
+            DUT1,
+            ELONG,
+            PHI,
+            HM,
+            0.3,
+            0.5,
+            100.0,
+            XP,
+            YP,
+            7.2e9,
+            880.0 / 749.0,
+            sc,
+        )
+        .unwrap();
+
+        vvd(fd, 578513.2266371388, 1e-3, "dop3w", "fd");
+        vvd(rr, -10251.104225623034, 1e-5, "dop3w", "rr");
+    }
+}
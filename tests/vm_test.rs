@@ -80,6 +80,12 @@ mod tests {
         vvd(a, -0.7893115794313644842, 1e-12, "af2a", "a");
     }
 
+    #[test]
+    fn test_anpm() {
+        vvd(vm::anpm(-4.0), 2.283185307179586477, 1e-12, "anpm", "");
+        vvd(vm::anpm(4.0), -2.283185307179586477, 1e-12, "anpm", "");
+    }
+
     #[test]
     fn test_pv2s() {
         let pv = [